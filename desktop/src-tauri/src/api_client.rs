@@ -0,0 +1,529 @@
+//! Authenticated HTTP client for the time tracking server.
+//!
+//! All server traffic of the app goes through here: the webview via the
+//! `api_request` and `session_fetch` commands, and the outbox replay in
+//! `sync.rs`. The client
+//!
+//! - only talks to the server of the active profile (`server_profile`),
+//!   trusting just its pinned certificate if one is set,
+//! - attaches the session token (only for the server that issued it),
+//! - tags every request with an `X-Request-ID`,
+//! - applies connect and request timeouts,
+//! - retries idempotent requests with backoff on transient failures,
+//! - maps failures to `ApiError`,
+//! - emits `auth:expired` on 401 and `api:online` when reachability changes.
+//!
+//! The server profile commands live here as well, since switching the
+//! profile replaces the HTTP client.
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_http::reqwest::{self, Method, StatusCode};
+
+use crate::api_error::{self, ApiError, ApiErrorKind};
+use crate::auth::{self, AuthState};
+use crate::server_profile::{self, ProfileSettings, ServerProfile};
+use crate::sync::SyncState;
+use crate::tray;
+
+/// Emitted with `bool` whenever the server becomes reachable or unreachable
+pub const EVENT_API_ONLINE: &str = "api:online";
+/// Emitted with the new active `ServerProfile` after a switch
+pub const EVENT_PROFILE_CHANGED: &str = "server-profile:changed";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const REQUEST_ID_HEADER: &str = "X-Request-ID";
+
+pub struct ApiClient {
+    dir: PathBuf,
+    /// Client and profile settings are replaced together on a switch
+    active: RwLock<Active>,
+    online: AtomicBool,
+}
+
+struct Active {
+    http: reqwest::Client,
+    settings: ProfileSettings,
+}
+
+/// A response with any HTTP status; transport failures are `ApiError`s
+pub struct RawResponse {
+    pub status: StatusCode,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+    pub request_id: String,
+}
+
+impl RawResponse {
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap_or(Value::Null)
+    }
+
+    /// Parsed body of a 2xx response, `ApiError` otherwise
+    pub fn into_json(self) -> Result<Value, ApiError> {
+        let body = self.json();
+        if self.status.is_success() {
+            Ok(body)
+        } else {
+            Err(ApiError::from_response(
+                self.status.as_u16(),
+                &body,
+                &self.request_id,
+            ))
+        }
+    }
+}
+
+/// HTTP client for the profile; a pinned certificate replaces the system
+/// roots
+fn build_http(profile: &ServerProfile) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT);
+    if let Some(pem) = &profile.pinned_certificate {
+        let certificate = reqwest::Certificate::from_pem(pem.as_bytes())
+            .map_err(|e| format!("Zertifikat: {}", e))?;
+        builder = builder
+            .tls_built_in_root_certs(false)
+            .add_root_certificate(certificate);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+impl ApiClient {
+    pub fn open(dir: PathBuf) -> Result<Self, String> {
+        let mut settings = server_profile::load(&dir).unwrap_or_else(|error| {
+            log::warn!("Failed to load server profiles, using defaults: {}", error);
+            ProfileSettings::default()
+        });
+        let http = match build_http(settings.active_profile()) {
+            Ok(http) => http,
+            Err(error) => {
+                // Fall back to the defaults rather than starting without client
+                log::warn!("Active server profile unusable: {}", error);
+                settings = ProfileSettings::default();
+                build_http(settings.active_profile())?
+            }
+        };
+        Ok(Self {
+            dir,
+            active: RwLock::new(Active { http, settings }),
+            online: AtomicBool::new(true),
+        })
+    }
+
+    pub fn profiles(&self) -> ProfileSettings {
+        self.active
+            .read()
+            .map(|active| active.settings.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn profile(&self) -> ServerProfile {
+        self.profiles().active_profile().clone()
+    }
+
+    /// Applies `change` to a copy of the settings; the copy is saved and
+    /// takes effect only if it succeeds and the client can be built
+    fn update_profiles<T>(
+        &self,
+        change: impl FnOnce(&mut ProfileSettings) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let mut active = self.active.write().map_err(|e| e.to_string())?;
+        let mut settings = active.settings.clone();
+        let result = change(&mut settings)?;
+
+        let http = if settings.active_profile() == active.settings.active_profile() {
+            active.http.clone()
+        } else {
+            build_http(settings.active_profile())?
+        };
+        server_profile::save(&self.dir, &settings).map_err(|e| e.to_string())?;
+        *active = Active { http, settings };
+        Ok(result)
+    }
+
+    /// Client for `url`, or an error if it is outside the active profile
+    fn http_for(&self, url: &str, request_id: &str) -> Result<reqwest::Client, ApiError> {
+        let active = self.active.read().map_err(|e| ApiError {
+            message: e.to_string(),
+            ..ApiError::new(ApiErrorKind::Unexpected, request_id)
+        })?;
+        let profile = active.settings.active_profile();
+        if !profile.allows(url) {
+            return Err(ApiError {
+                message: format!(
+                    "{} gehört nicht zum aktiven Serverprofil \"{}\"",
+                    url, profile.name
+                ),
+                ..ApiError::new(ApiErrorKind::Forbidden, request_id)
+            });
+        }
+        Ok(active.http.clone())
+    }
+
+    /// Sends the request, retrying idempotent methods on transient
+    /// failures. Every HTTP status counts as a response here.
+    pub async fn execute(
+        &self,
+        app: &AppHandle,
+        method: Method,
+        url: &str,
+        headers: &HashMap<String, String>,
+        body: Option<String>,
+    ) -> Result<RawResponse, ApiError> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let http = self.http_for(url, &request_id).inspect_err(|error| {
+            log::warn!("{} {} blocked [{}]: {}", method, url, request_id, error);
+        })?;
+        let token = app.state::<AuthState>().token_for(url);
+        let idempotent = matches!(method, Method::GET | Method::HEAD | Method::OPTIONS);
+
+        let mut retry = 0;
+        loop {
+            let started = Instant::now();
+            let result = Self::attempt(
+                &http,
+                &method,
+                url,
+                headers,
+                body.clone(),
+                token.as_deref(),
+                &request_id,
+            )
+            .await;
+
+            let transient = match &result {
+                Ok(response) => {
+                    ApiErrorKind::from_status(response.status.as_u16()).is_transient()
+                        && !response.status.is_success()
+                }
+                Err(error) => error.is_transient(),
+            };
+            match &result {
+                Ok(response) => {
+                    log_response(&method, url, &request_id, response.status.as_u16(), started)
+                }
+                Err(error) => log::warn!(
+                    "{} {} failed [{}]: {:?}",
+                    method,
+                    url,
+                    request_id,
+                    error.kind
+                ),
+            }
+
+            match (transient && idempotent, api_error::backoff(retry)) {
+                (true, Some(delay)) => {
+                    retry += 1;
+                    tokio::time::sleep(delay).await;
+                }
+                _ => {
+                    self.set_online(
+                        app,
+                        !matches!(&result, Err(e) if e.kind == ApiErrorKind::Offline),
+                    );
+                    if let Ok(response) = &result {
+                        if response.status == StatusCode::UNAUTHORIZED && token.is_some() {
+                            auth::expire(app);
+                        }
+                    }
+                    return result;
+                }
+            }
+        }
+    }
+
+    async fn attempt(
+        http: &reqwest::Client,
+        method: &Method,
+        url: &str,
+        headers: &HashMap<String, String>,
+        body: Option<String>,
+        token: Option<&str>,
+        request_id: &str,
+    ) -> Result<RawResponse, ApiError> {
+        let mut request = http
+            .request(method.clone(), url)
+            .header(REQUEST_ID_HEADER, request_id);
+        for (name, value) in headers {
+            // Never let callers choose the credentials
+            if !name.eq_ignore_ascii_case("authorization") {
+                request = request.header(name, value);
+            }
+        }
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| transport_error(&e, request_id))?;
+        let status = response.status();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| transport_error(&e, request_id))?
+            .to_vec();
+
+        Ok(RawResponse {
+            status,
+            headers,
+            body,
+            request_id: request_id.to_string(),
+        })
+    }
+
+    /// JSON request; a non-2xx status becomes an `ApiError`
+    pub async fn json(
+        &self,
+        app: &AppHandle,
+        method: Method,
+        url: &str,
+        body: Option<&Value>,
+    ) -> Result<Value, ApiError> {
+        let headers = HashMap::from([("Content-Type".to_string(), "application/json".to_string())]);
+        self.execute(app, method, url, &headers, body.map(Value::to_string))
+            .await?
+            .into_json()
+    }
+
+    /// Whether the last request reached the server
+    pub fn is_online(&self) -> bool {
+        self.online.load(Ordering::SeqCst)
+    }
+
+    fn set_online(&self, app: &AppHandle, online: bool) {
+        if self.online.swap(online, Ordering::SeqCst) != online {
+            let _ = app.emit(EVENT_API_ONLINE, online);
+            tray::refresh(app);
+        }
+    }
+}
+
+fn transport_error(error: &reqwest::Error, request_id: &str) -> ApiError {
+    let kind = if error.is_timeout() {
+        ApiErrorKind::Timeout
+    } else if error.is_connect() || error.is_request() {
+        ApiErrorKind::Offline
+    } else {
+        ApiErrorKind::Unexpected
+    };
+    ApiError::new(kind, request_id)
+}
+
+fn log_response(method: &Method, url: &str, request_id: &str, status: u16, started: Instant) {
+    let millis = started.elapsed().as_millis();
+    let level = if status >= 400 {
+        log::Level::Warn
+    } else {
+        log::Level::Debug
+    };
+    log::log!(
+        level,
+        "{} {} → {} [{}] {}ms",
+        method,
+        url,
+        status,
+        request_id,
+        millis
+    );
+}
+
+/// `{base}{path}` for a collection path like `/time-entries/5`
+pub fn url(base: &str, path: &str) -> String {
+    format!("{}{}", base.trim_end_matches('/'), path)
+}
+
+pub fn setup(app: &AppHandle) {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."));
+    match ApiClient::open(dir) {
+        Ok(client) => {
+            app.manage(client);
+        }
+        Err(error) => log::warn!("HTTP client unavailable: {}", error),
+    }
+}
+
+fn parse_method(method: Option<&str>) -> Result<Method, ApiError> {
+    method
+        .unwrap_or("GET")
+        .to_ascii_uppercase()
+        .parse::<Method>()
+        .map_err(|_| ApiError {
+            message: format!("Ungültige HTTP-Methode: {}", method.unwrap_or_default()),
+            ..ApiError::new(ApiErrorKind::Unexpected, "")
+        })
+}
+
+// ========================================
+// Commands
+// ========================================
+
+/// JSON request from the webview
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRequest {
+    /// Absolute URL, e.g. `http://localhost:3000/api/time-entries`
+    pub url: String,
+    pub method: Option<String>,
+    pub body: Option<Value>,
+}
+
+/// Sends a JSON request and returns the server's response envelope
+/// (`{ success, data, … }`), or an `ApiError` the UI can switch on
+#[tauri::command]
+pub async fn api_request(
+    app: AppHandle,
+    client: State<'_, ApiClient>,
+    request: ApiRequest,
+) -> Result<Value, ApiError> {
+    let method = parse_method(request.method.as_deref())?;
+    client
+        .json(&app, method, &request.url, request.body.as_ref())
+        .await
+}
+
+/// Raw request from the webview (downloads, uploads, non-JSON bodies)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchRequest {
+    pub url: String,
+    pub method: Option<String>,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub headers: HashMap<String, String>,
+    /// Base64, so binary downloads (exports, backups) survive the IPC
+    pub body: String,
+    pub request_id: String,
+}
+
+/// `fetch` replacement for the webview; any HTTP status is returned as
+/// response, only transport failures are errors
+#[tauri::command]
+pub async fn session_fetch(
+    app: AppHandle,
+    client: State<'_, ApiClient>,
+    request: FetchRequest,
+) -> Result<FetchResponse, ApiError> {
+    let method = parse_method(request.method.as_deref())?;
+    let response = client
+        .execute(&app, method, &request.url, &request.headers, request.body)
+        .await?;
+
+    Ok(FetchResponse {
+        status: response.status.as_u16(),
+        status_text: response
+            .status
+            .canonical_reason()
+            .unwrap_or_default()
+            .to_string(),
+        headers: response.headers,
+        body: STANDARD.encode(&response.body),
+        request_id: response.request_id,
+    })
+}
+
+/// All profiles and the id of the active one
+#[tauri::command]
+pub fn server_profiles(client: State<'_, ApiClient>) -> ProfileSettings {
+    client.profiles()
+}
+
+/// Adds or replaces a profile; editing the active one applies immediately
+#[tauri::command]
+pub fn save_server_profile(
+    app: AppHandle,
+    client: State<'_, ApiClient>,
+    profile: ServerProfile,
+) -> Result<ProfileSettings, String> {
+    let active_before = client.profile();
+    client.update_profiles(|settings| {
+        settings
+            .upsert(profile)
+            .map(drop)
+            .map_err(|e| e.to_string())
+    })?;
+    profile_changed(&app, &client, &active_before);
+    Ok(client.profiles())
+}
+
+#[tauri::command]
+pub fn delete_server_profile(
+    client: State<'_, ApiClient>,
+    id: String,
+) -> Result<ProfileSettings, String> {
+    client.update_profiles(|settings| settings.remove(&id).map_err(|e| e.to_string()))?;
+    Ok(client.profiles())
+}
+
+/// Switches the server; a session of the previous server ends
+#[tauri::command]
+pub fn activate_server_profile(
+    app: AppHandle,
+    client: State<'_, ApiClient>,
+    id: String,
+) -> Result<ServerProfile, String> {
+    let active_before = client.profile();
+    if id != active_before.id {
+        // Queued changes carry ids of the current server
+        let pending = app
+            .state::<SyncState>()
+            .with_store(|s| s.pending_count())
+            .unwrap_or(0);
+        if pending > 0 {
+            return Err(format!(
+                "Es gibt noch {} nicht synchronisierte Änderungen. Bitte synchronisieren oder verwerfen Sie diese vor dem Serverwechsel.",
+                pending
+            ));
+        }
+    }
+    client
+        .update_profiles(|settings| settings.activate(&id).map(drop).map_err(|e| e.to_string()))?;
+    profile_changed(&app, &client, &active_before);
+    Ok(client.profile())
+}
+
+fn profile_changed(app: &AppHandle, client: &ApiClient, before: &ServerProfile) {
+    let profile = client.profile();
+    if &profile == before {
+        return;
+    }
+    if !profile.allows(&before.api_base_url) {
+        auth::end_session(app);
+    }
+    let _ = app.emit(EVENT_PROFILE_CHANGED, &profile);
+}
@@ -0,0 +1,190 @@
+//! Error type and retry policy of the API client.
+//!
+//! Every failed request ends up as an `ApiError` with a kind the frontend
+//! can switch on, the server's message (or a German fallback) and the
+//! request id that was sent as `X-Request-ID`.
+
+use std::{fmt, time::Duration};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Attempts for idempotent requests (first try + retries)
+pub const MAX_ATTEMPTS: u32 = 3;
+const BACKOFF_BASE: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiErrorKind {
+    /// Server not reachable (DNS, refused, TLS, …)
+    Offline,
+    Timeout,
+    /// 401 – token missing, expired or revoked
+    Unauthorized,
+    /// 403
+    Forbidden,
+    /// 404
+    NotFound,
+    /// 409 – e.g. overlapping entries or a concurrent change
+    Conflict,
+    /// 400 / 422
+    Validation,
+    /// 429
+    RateLimited,
+    /// 5xx
+    Server,
+    /// Anything else, including unparsable responses
+    Unexpected,
+}
+
+impl ApiErrorKind {
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            400 | 422 => ApiErrorKind::Validation,
+            401 => ApiErrorKind::Unauthorized,
+            403 => ApiErrorKind::Forbidden,
+            404 => ApiErrorKind::NotFound,
+            408 => ApiErrorKind::Timeout,
+            409 => ApiErrorKind::Conflict,
+            429 => ApiErrorKind::RateLimited,
+            500..=599 => ApiErrorKind::Server,
+            _ => ApiErrorKind::Unexpected,
+        }
+    }
+
+    /// Worth another attempt for idempotent requests
+    pub fn is_transient(self) -> bool {
+        matches!(
+            self,
+            ApiErrorKind::Offline
+                | ApiErrorKind::Timeout
+                | ApiErrorKind::RateLimited
+                | ApiErrorKind::Server
+        )
+    }
+
+    fn fallback_message(self) -> &'static str {
+        match self {
+            ApiErrorKind::Offline => "Server nicht erreichbar",
+            ApiErrorKind::Timeout => "Zeitüberschreitung bei der Anfrage an den Server",
+            ApiErrorKind::Unauthorized => {
+                "Ihre Anmeldung ist abgelaufen. Bitte melden Sie sich erneut an."
+            }
+            ApiErrorKind::Forbidden => "Sie haben keine Berechtigung für diese Aktion",
+            ApiErrorKind::NotFound => "Eintrag nicht gefunden",
+            ApiErrorKind::Conflict => "Die Änderung steht im Konflikt mit vorhandenen Daten",
+            ApiErrorKind::Validation => "Ungültige Eingabe",
+            ApiErrorKind::RateLimited => "Zu viele Anfragen. Bitte versuchen Sie es gleich erneut.",
+            ApiErrorKind::Server => "Serverfehler. Bitte versuchen Sie es später erneut.",
+            ApiErrorKind::Unexpected => "Unerwartete Antwort vom Server",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiError {
+    pub kind: ApiErrorKind,
+    /// HTTP status, `None` if no response arrived
+    pub status: Option<u16>,
+    pub message: String,
+    pub request_id: String,
+}
+
+impl ApiError {
+    pub fn new(kind: ApiErrorKind, request_id: &str) -> Self {
+        Self {
+            kind,
+            status: None,
+            message: kind.fallback_message().to_string(),
+            request_id: request_id.to_string(),
+        }
+    }
+
+    /// Error for a non-2xx response with the server's `{ error }` body
+    pub fn from_response(status: u16, body: &Value, request_id: &str) -> Self {
+        let kind = ApiErrorKind::from_status(status);
+        let server_message = body
+            .get("error")
+            .or_else(|| body.get("message"))
+            .and_then(Value::as_str)
+            .filter(|m| !m.is_empty());
+        let message = match (kind, server_message) {
+            // Internal details of 5xx responses are no help to the user
+            (ApiErrorKind::Server, _) | (_, None) => kind.fallback_message().to_string(),
+            (_, Some(message)) => message.to_string(),
+        };
+        Self {
+            kind,
+            status: Some(status),
+            message,
+            request_id: request_id.to_string(),
+        }
+    }
+
+    pub fn is_transient(&self) -> bool {
+        self.kind.is_transient()
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Delay before retry number `retry` (0-based), `None` once all attempts
+/// are used up. Doubles per retry: 300 ms, 600 ms.
+pub fn backoff(retry: u32) -> Option<Duration> {
+    (retry + 1 < MAX_ATTEMPTS).then(|| BACKOFF_BASE * 2u32.pow(retry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn maps_statuses_to_kinds_and_keeps_server_messages() {
+        let conflict = ApiError::from_response(
+            409,
+            &json!({ "success": false, "error": "Time entry overlaps with existing entry on this date" }),
+            "req-1",
+        );
+        assert_eq!(conflict.kind, ApiErrorKind::Conflict);
+        assert_eq!(conflict.status, Some(409));
+        assert_eq!(
+            conflict.message,
+            "Time entry overlaps with existing entry on this date"
+        );
+        assert_eq!(conflict.request_id, "req-1");
+
+        let forbidden = ApiError::from_response(403, &Value::Null, "req-2");
+        assert_eq!(forbidden.kind, ApiErrorKind::Forbidden);
+        assert_eq!(
+            forbidden.message,
+            "Sie haben keine Berechtigung für diese Aktion"
+        );
+
+        let server = ApiError::from_response(500, &json!({ "error": "SQLITE_BUSY" }), "req-3");
+        assert_eq!(server.kind, ApiErrorKind::Server);
+        assert!(!server.message.contains("SQLITE"));
+
+        assert_eq!(ApiErrorKind::from_status(401), ApiErrorKind::Unauthorized);
+        assert_eq!(ApiErrorKind::from_status(418), ApiErrorKind::Unexpected);
+    }
+
+    #[test]
+    fn only_transient_failures_are_retried_with_growing_backoff() {
+        assert!(ApiError::new(ApiErrorKind::Offline, "r").is_transient());
+        assert!(ApiError::from_response(503, &Value::Null, "r").is_transient());
+        assert!(!ApiError::from_response(409, &Value::Null, "r").is_transient());
+        assert!(!ApiError::from_response(401, &Value::Null, "r").is_transient());
+
+        assert_eq!(backoff(0), Some(Duration::from_millis(300)));
+        assert_eq!(backoff(1), Some(Duration::from_millis(600)));
+        assert_eq!(backoff(2), None);
+    }
+}
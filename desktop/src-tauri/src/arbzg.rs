@@ -0,0 +1,527 @@
+//! Arbeitszeitgesetz (ArbZG) rule engine
+//!
+//! Native port of `server/src/services/arbeitszeitgesetzService.ts`, so an
+//! entry can be checked while it is typed or while the tray timer runs,
+//! before anything is sent to the server. Like the server, all rules only
+//! produce warnings – nothing here blocks an entry.
+//!
+//! Key Rules:
+//! - Max 24h per day (absolute), 10h recommended, 8h standard (§3 ArbZG)
+//! - Max 48h per week (§3 ArbZG)
+//! - Min 30 Min break after 6h, 45 Min after 9h (§4 ArbZG)
+//! - Min 11h rest period between shifts (§5 ArbZG)
+//!
+//! The weekly rule uses the Monday–Sunday week of the entry. The server
+//! compares a JS week number against SQLite's `%W`, which can select the
+//! neighbouring week; the intended Monday–Sunday week is used here.
+
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+pub const STANDARD_DAILY_HOURS: f64 = 8.0;
+pub const RECOMMENDED_MAX_DAILY_HOURS: f64 = 10.0;
+pub const ABSOLUTE_MAX_DAILY_HOURS: f64 = 24.0;
+pub const MAX_WEEKLY_HOURS: f64 = 48.0;
+pub const MIN_REST_HOURS: f64 = 11.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArbzgRule {
+    MaxDailyHours,
+    BreakTime,
+    RestPeriod,
+    MaxWeeklyHours,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// A single rule violation.
+///
+/// `required_minutes` is the legal limit (maximum for daily/weekly hours,
+/// minimum for break and rest period), `actual_minutes` the value found.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Violation {
+    pub rule: ArbzgRule,
+    pub severity: Severity,
+    pub required_minutes: i64,
+    pub actual_minutes: i64,
+    pub message: String,
+}
+
+/// The entry to check (new or edited)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryCandidate {
+    pub date: String,       // YYYY-MM-DD
+    pub start_time: String, // HH:MM
+    pub end_time: String,   // HH:MM
+    #[serde(default)]
+    pub break_minutes: i64,
+    /// Id of the entry being edited, so it is not counted twice
+    #[serde(default)]
+    pub exclude_entry_id: Option<i64>,
+}
+
+/// An entry that is already booked for the same user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookedEntry {
+    pub id: i64,
+    pub date: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub hours: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArbzgReport {
+    pub hours: f64,
+    pub violations: Vec<Violation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArbzgError {
+    InvalidDate(String),
+    InvalidTime(String),
+}
+
+impl fmt::Display for ArbzgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArbzgError::InvalidDate(value) => write!(f, "Ungültiges Datum: {}", value),
+            ArbzgError::InvalidTime(value) => write!(f, "Ungültige Uhrzeit: {}", value),
+        }
+    }
+}
+
+impl std::error::Error for ArbzgError {}
+
+pub fn parse_date(value: &str) -> Result<NaiveDate, ArbzgError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| ArbzgError::InvalidDate(value.to_string()))
+}
+
+pub fn parse_time(value: &str) -> Result<NaiveTime, ArbzgError> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map_err(|_| ArbzgError::InvalidTime(value.to_string()))
+}
+
+/// Net hours of an entry, same as `timeEntryService.calculateHours`
+/// (overnight shifts wrap around midnight, rounded to 2 decimals)
+pub fn calculate_hours(
+    start_time: &str,
+    end_time: &str,
+    break_minutes: i64,
+) -> Result<f64, ArbzgError> {
+    let start = parse_time(start_time)?;
+    let end = parse_time(end_time)?;
+
+    let mut gross_minutes = (end - start).num_minutes();
+    if gross_minutes < 0 {
+        gross_minutes += 24 * 60;
+    }
+    let net_minutes = gross_minutes - break_minutes;
+    Ok(((net_minutes as f64 / 60.0) * 100.0).round() / 100.0)
+}
+
+/// ArbZG §3 – max daily hours (8h info, 10h warning, 24h critical)
+pub fn check_max_daily_hours(
+    date: &str,
+    hours: f64,
+    booked: &[BookedEntry],
+    exclude_entry_id: Option<i64>,
+) -> Option<Violation> {
+    let existing_hours: f64 = booked
+        .iter()
+        .filter(|e| e.date == date && Some(e.id) != exclude_entry_id)
+        .map(|e| e.hours)
+        .sum();
+    let total_hours = existing_hours + hours;
+
+    let (severity, limit, message) = if total_hours > ABSOLUTE_MAX_DAILY_HOURS {
+        (
+            Severity::Critical,
+            ABSOLUTE_MAX_DAILY_HOURS,
+            format!(
+                "⚠️ Achtung: {}h pro Tag überschreitet das absolute Maximum von {}h! (Bereits {}h gebucht)",
+                to_fixed_1(total_hours),
+                ABSOLUTE_MAX_DAILY_HOURS,
+                to_fixed_1(existing_hours),
+            ),
+        )
+    } else if total_hours > RECOMMENDED_MAX_DAILY_HOURS {
+        (
+            Severity::Warning,
+            RECOMMENDED_MAX_DAILY_HOURS,
+            format!(
+                "⚠️ Arbeitszeitgesetz-Hinweis: {}h überschreitet die empfohlene Arbeitszeit von {}h pro Tag! (Bereits {}h gebucht, gesamt wird {}h)",
+                to_fixed_1(total_hours),
+                RECOMMENDED_MAX_DAILY_HOURS,
+                to_fixed_1(existing_hours),
+                to_fixed_1(total_hours),
+            ),
+        )
+    } else if total_hours > STANDARD_DAILY_HOURS {
+        (
+            Severity::Info,
+            STANDARD_DAILY_HOURS,
+            format!(
+                "ℹ️ Hinweis: {}h überschreitet die Standard-Arbeitszeit von {}h pro Tag.",
+                to_fixed_1(total_hours),
+                STANDARD_DAILY_HOURS,
+            ),
+        )
+    } else {
+        return None;
+    };
+
+    Some(Violation {
+        rule: ArbzgRule::MaxDailyHours,
+        severity,
+        required_minutes: hours_to_minutes(limit),
+        actual_minutes: hours_to_minutes(total_hours),
+        message,
+    })
+}
+
+/// Minimum break for the given working hours (ArbZG §4)
+pub fn required_break_minutes(working_hours: f64) -> i64 {
+    if working_hours > 9.0 {
+        45
+    } else if working_hours > 6.0 {
+        30
+    } else {
+        0
+    }
+}
+
+/// ArbZG §4 – 30 Min break after 6h, 45 Min after 9h
+pub fn check_break_time(working_hours: f64, break_minutes: i64) -> Option<Violation> {
+    let required_break = required_break_minutes(working_hours);
+    if required_break == 0 || break_minutes >= required_break {
+        return None;
+    }
+
+    Some(Violation {
+        rule: ArbzgRule::BreakTime,
+        severity: Severity::Warning,
+        required_minutes: required_break,
+        actual_minutes: break_minutes,
+        message: format!(
+            "⚠️ Arbeitszeitgesetz-Hinweis: Bei {}h Arbeit werden mindestens {} Minuten Pause empfohlen! (Aktuell: {} Min)",
+            to_fixed_1(working_hours),
+            required_break,
+            break_minutes,
+        ),
+    })
+}
+
+/// ArbZG §5 – min 11h between the end of the previous shift and `start_time`
+pub fn check_rest_period(
+    date: &str,
+    start_time: &str,
+    booked: &[BookedEntry],
+    exclude_entry_id: Option<i64>,
+) -> Result<Option<Violation>, ArbzgError> {
+    // Most recent entry that ended before the new start (same ordering as
+    // the server query: date DESC, endTime DESC)
+    let last_entry = booked
+        .iter()
+        .filter(|e| Some(e.id) != exclude_entry_id)
+        .filter(|e| e.date.as_str() < date || (e.date == date && e.end_time.as_str() < start_time))
+        .max_by(|a, b| (&a.date, &a.end_time).cmp(&(&b.date, &b.end_time)));
+
+    let Some(last_entry) = last_entry else {
+        return Ok(None);
+    };
+
+    let last_end = NaiveDateTime::new(
+        parse_date(&last_entry.date)?,
+        parse_time(&last_entry.end_time)?,
+    );
+    let new_start = NaiveDateTime::new(parse_date(date)?, parse_time(start_time)?);
+    Ok(check_rest_between(last_end, new_start))
+}
+
+/// Rest period check between two points in time (also used by the timer)
+pub fn check_rest_between(last_end: NaiveDateTime, new_start: NaiveDateTime) -> Option<Violation> {
+    let minutes_between = (new_start - last_end).num_minutes();
+    let hours_between = minutes_between as f64 / 60.0;
+    if hours_between >= MIN_REST_HOURS {
+        return None;
+    }
+
+    let earliest_start = last_end + Duration::minutes(hours_to_minutes(MIN_REST_HOURS));
+
+    Some(Violation {
+        rule: ArbzgRule::RestPeriod,
+        severity: Severity::Warning,
+        required_minutes: hours_to_minutes(MIN_REST_HOURS),
+        actual_minutes: minutes_between,
+        message: format!(
+            "⚠️ Arbeitszeitgesetz-Hinweis: Zwischen Schichten sollten mindestens {}h Ruhezeit liegen! Letzte Schicht endete am {} um {}. Empfohlener frühester Start: {} (Aktuell: {}h Ruhezeit)",
+            MIN_REST_HOURS,
+            last_end.format("%Y-%m-%d"),
+            last_end.format("%H:%M"),
+            earliest_start.format("%Y-%m-%d %H:%M"),
+            to_fixed_1(hours_between),
+        ),
+    })
+}
+
+/// ArbZG §3 – max 48h in the Monday–Sunday week of `date`
+pub fn check_max_weekly_hours(
+    date: &str,
+    hours: f64,
+    booked: &[BookedEntry],
+    exclude_entry_id: Option<i64>,
+) -> Result<Option<Violation>, ArbzgError> {
+    let week_start = week_start(parse_date(date)?);
+    let week_end = week_start + Duration::days(6);
+
+    let mut existing_week_hours = 0.0;
+    for entry in booked.iter().filter(|e| Some(e.id) != exclude_entry_id) {
+        let entry_date = parse_date(&entry.date)?;
+        if entry_date >= week_start && entry_date <= week_end {
+            existing_week_hours += entry.hours;
+        }
+    }
+    let total_week_hours = existing_week_hours + hours;
+
+    if total_week_hours <= MAX_WEEKLY_HOURS {
+        return Ok(None);
+    }
+
+    Ok(Some(Violation {
+        rule: ArbzgRule::MaxWeeklyHours,
+        severity: Severity::Warning,
+        required_minutes: hours_to_minutes(MAX_WEEKLY_HOURS),
+        actual_minutes: hours_to_minutes(total_week_hours),
+        message: format!(
+            "⚠️ Hinweis: Diese Woche bereits {}h gearbeitet. Mit dieser Buchung: {}h (über dem Richtwert von {}h/Woche).",
+            to_fixed_1(existing_week_hours),
+            to_fixed_1(total_week_hours),
+            MAX_WEEKLY_HOURS,
+        ),
+    }))
+}
+
+/// Comprehensive check, same order as `validateTimeEntryArbZG`
+pub fn validate_entry(
+    entry: &EntryCandidate,
+    booked: &[BookedEntry],
+) -> Result<ArbzgReport, ArbzgError> {
+    parse_date(&entry.date)?;
+    let hours = calculate_hours(&entry.start_time, &entry.end_time, entry.break_minutes)?;
+    let exclude = entry.exclude_entry_id;
+
+    let violations = [
+        check_max_daily_hours(&entry.date, hours, booked, exclude),
+        check_break_time(hours, entry.break_minutes),
+        check_rest_period(&entry.date, &entry.start_time, booked, exclude)?,
+        check_max_weekly_hours(&entry.date, hours, booked, exclude)?,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    Ok(ArbzgReport { hours, violations })
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn hours_to_minutes(hours: f64) -> i64 {
+    (hours * 60.0).round() as i64
+}
+
+/// JS `Number.toFixed(1)` (rounds halves up instead of to even)
+fn to_fixed_1(value: f64) -> String {
+    format!("{:.1}", (value * 10.0).round() / 10.0)
+}
+
+#[tauri::command]
+pub fn validate_arbzg(
+    entry: EntryCandidate,
+    booked: Vec<BookedEntry>,
+) -> Result<ArbzgReport, String> {
+    validate_entry(&entry, &booked).map_err(|e| e.to_string())
+}
+
+/// Parity tests: thresholds and messages mirror arbeitszeitgesetzService.ts
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn booked(id: i64, date: &str, start: &str, end: &str, hours: f64) -> BookedEntry {
+        BookedEntry {
+            id,
+            date: date.into(),
+            start_time: start.into(),
+            end_time: end.into(),
+            hours,
+        }
+    }
+
+    fn entry(date: &str, start: &str, end: &str, break_minutes: i64) -> EntryCandidate {
+        EntryCandidate {
+            date: date.into(),
+            start_time: start.into(),
+            end_time: end.into(),
+            break_minutes,
+            exclude_entry_id: None,
+        }
+    }
+
+    #[test]
+    fn calculate_hours_matches_time_entry_service() {
+        assert_eq!(calculate_hours("08:00", "16:30", 30).unwrap(), 8.0);
+        assert_eq!(calculate_hours("22:00", "06:00", 0).unwrap(), 8.0);
+        assert_eq!(calculate_hours("08:00", "08:20", 0).unwrap(), 0.33);
+        assert!(calculate_hours("8 Uhr", "16:00", 0).is_err());
+    }
+
+    #[test]
+    fn daily_hours_thresholds() {
+        assert_eq!(check_max_daily_hours("2026-03-02", 8.0, &[], None), None);
+
+        let info = check_max_daily_hours("2026-03-02", 8.5, &[], None).unwrap();
+        assert_eq!(info.severity, Severity::Info);
+        assert_eq!(
+            info.message,
+            "ℹ️ Hinweis: 8.5h überschreitet die Standard-Arbeitszeit von 8h pro Tag."
+        );
+
+        let existing = [booked(1, "2026-03-02", "06:00", "10:00", 4.0)];
+        let warning = check_max_daily_hours("2026-03-02", 7.0, &existing, None).unwrap();
+        assert_eq!(warning.severity, Severity::Warning);
+        assert_eq!(warning.required_minutes, 600);
+        assert_eq!(warning.actual_minutes, 660);
+        assert_eq!(
+            warning.message,
+            "⚠️ Arbeitszeitgesetz-Hinweis: 11.0h überschreitet die empfohlene Arbeitszeit von 10h pro Tag! (Bereits 4.0h gebucht, gesamt wird 11.0h)"
+        );
+
+        let critical = check_max_daily_hours("2026-03-02", 21.0, &existing, None).unwrap();
+        assert_eq!(critical.severity, Severity::Critical);
+        assert_eq!(
+            critical.message,
+            "⚠️ Achtung: 25.0h pro Tag überschreitet das absolute Maximum von 24h! (Bereits 4.0h gebucht)"
+        );
+
+        // Editing entry 1 must not count it twice
+        assert_eq!(
+            check_max_daily_hours("2026-03-02", 7.0, &existing, Some(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn break_time_thresholds() {
+        assert_eq!(check_break_time(6.0, 0), None);
+        assert_eq!(check_break_time(6.5, 30), None);
+        assert_eq!(check_break_time(9.5, 45), None);
+
+        let short = check_break_time(6.5, 15).unwrap();
+        assert_eq!(short.required_minutes, 30);
+        assert_eq!(short.actual_minutes, 15);
+        assert_eq!(
+            short.message,
+            "⚠️ Arbeitszeitgesetz-Hinweis: Bei 6.5h Arbeit werden mindestens 30 Minuten Pause empfohlen! (Aktuell: 15 Min)"
+        );
+
+        assert_eq!(check_break_time(9.25, 30).unwrap().required_minutes, 45);
+    }
+
+    #[test]
+    fn rest_period_uses_most_recent_previous_shift() {
+        let existing = [
+            booked(1, "2026-03-01", "08:00", "16:00", 8.0),
+            booked(2, "2026-03-01", "18:00", "22:00", 4.0),
+            booked(3, "2026-03-03", "08:00", "16:00", 8.0),
+        ];
+
+        let violation = check_rest_period("2026-03-02", "07:00", &existing, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(violation.required_minutes, 660);
+        assert_eq!(violation.actual_minutes, 540);
+        assert_eq!(
+            violation.message,
+            "⚠️ Arbeitszeitgesetz-Hinweis: Zwischen Schichten sollten mindestens 11h Ruhezeit liegen! Letzte Schicht endete am 2026-03-01 um 22:00. Empfohlener frühester Start: 2026-03-02 09:00 (Aktuell: 9.0h Ruhezeit)"
+        );
+
+        assert_eq!(
+            check_rest_period("2026-03-02", "09:00", &existing, None).unwrap(),
+            None
+        );
+        assert_eq!(
+            check_rest_period("2026-03-02", "07:00", &existing, Some(2)).unwrap(),
+            None
+        );
+        assert_eq!(
+            check_rest_period("2026-02-28", "07:00", &existing, None).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn weekly_hours_use_monday_to_sunday_week() {
+        // Mon 2026-03-02 .. Fri 2026-03-06 with 10h each, previous Sunday excluded
+        let mut existing: Vec<BookedEntry> = (2..=6)
+            .map(|day| booked(day, &format!("2026-03-{:02}", day), "07:00", "17:30", 10.0))
+            .collect();
+        existing.push(booked(99, "2026-03-01", "08:00", "16:00", 8.0));
+
+        assert_eq!(
+            check_max_weekly_hours("2026-03-07", 0.0, &existing[..4], None).unwrap(),
+            None
+        );
+
+        let violation = check_max_weekly_hours("2026-03-07", 4.0, &existing, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(violation.actual_minutes, 54 * 60);
+        assert_eq!(
+            violation.message,
+            "⚠️ Hinweis: Diese Woche bereits 50.0h gearbeitet. Mit dieser Buchung: 54.0h (über dem Richtwert von 48h/Woche)."
+        );
+    }
+
+    #[test]
+    fn validate_entry_collects_all_rules_in_server_order() {
+        let existing = [booked(1, "2026-03-01", "14:00", "23:00", 9.0)];
+        let report = validate_entry(&entry("2026-03-02", "06:00", "17:00", 15), &existing).unwrap();
+
+        assert_eq!(report.hours, 10.75);
+        let rules: Vec<ArbzgRule> = report.violations.iter().map(|v| v.rule).collect();
+        assert_eq!(
+            rules,
+            vec![
+                ArbzgRule::MaxDailyHours,
+                ArbzgRule::BreakTime,
+                ArbzgRule::RestPeriod
+            ]
+        );
+
+        let clean = validate_entry(&entry("2026-03-04", "08:00", "16:30", 30), &existing).unwrap();
+        assert!(clean.violations.is_empty());
+    }
+
+    #[test]
+    fn to_fixed_rounds_like_javascript() {
+        assert_eq!(to_fixed_1(0.25), "0.3");
+        assert_eq!(to_fixed_1(10.0), "10.0");
+    }
+}
@@ -0,0 +1,305 @@
+//! Login session owned by the Rust side.
+//!
+//! `auth_login` posts the credentials to `/auth/login` of the active server
+//! profile and keeps the
+//! returned JWT in `secure_store`; the webview only learns who is logged in
+//! and until when. `api_client` asks `token_for` before every request, so
+//! the `Authorization` header is only ever sent to the server that issued
+//! the token. A 401 from that server ends the session (`auth:expired`).
+//! A token older versions kept in the webview's localStorage is handed over
+//! once with `auth_import_token` and checked against `/auth/me`.
+
+use std::{collections::HashMap, path::PathBuf, sync::Mutex};
+
+use chrono::{Local, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_http::reqwest::{Method, Url};
+
+use crate::api_client::{self, ApiClient};
+use crate::api_error::{ApiError, ApiErrorKind};
+use crate::secure_store::{self, SessionKey, StoredSession};
+
+/// Emitted with `Option<SessionInfo>` after login, logout or expiry
+pub const EVENT_AUTH_CHANGED: &str = "auth:changed";
+/// Emitted when the server rejected the token; the UI asks for a new login
+pub const EVENT_AUTH_EXPIRED: &str = "auth:expired";
+
+pub struct AuthState {
+    dir: PathBuf,
+    key: Option<SessionKey>,
+    session: Mutex<Option<StoredSession>>,
+}
+
+impl AuthState {
+    pub fn open(dir: PathBuf) -> Self {
+        let key = match SessionKey::load_or_create(&dir) {
+            Ok(key) => Some(key),
+            Err(error) => {
+                log::warn!("Session key unavailable, login will not persist: {}", error);
+                None
+            }
+        };
+        let session = key
+            .as_ref()
+            .and_then(|key| match secure_store::load(&dir, key) {
+                Ok(session) => session,
+                Err(error) => {
+                    log::warn!("Failed to load session: {}", error);
+                    None
+                }
+            });
+        Self {
+            dir,
+            key,
+            session: Mutex::new(session),
+        }
+    }
+
+    /// Current session; an expired one is dropped
+    fn current(&self) -> Option<StoredSession> {
+        let mut guard = self.session.lock().ok()?;
+        if guard
+            .as_ref()
+            .is_some_and(|s| s.is_expired(Utc::now().naive_utc()))
+        {
+            *guard = None;
+            let _ = secure_store::clear(&self.dir);
+        }
+        guard.clone()
+    }
+
+    /// Bearer token for `url`, if it belongs to the server that issued it
+    pub fn token_for(&self, url: &str) -> Option<String> {
+        let session = self.current()?;
+        same_origin(url, &session.api_base_url).then_some(session.token)
+    }
+
+    /// Id of the logged-in user (`id` of the server's `SessionUser`)
+    pub fn user_id(&self) -> Option<i64> {
+        self.current()?.user.get("id")?.as_i64()
+    }
+
+    fn store(&self, session: StoredSession) -> Result<(), String> {
+        if let Some(key) = &self.key {
+            secure_store::save(&self.dir, key, &session).map_err(|e| e.to_string())?;
+        }
+        *self.session.lock().map_err(|e| e.to_string())? = Some(session);
+        Ok(())
+    }
+
+    /// Uses `session` for requests without saving it (token not verified yet)
+    fn try_session(&self, session: StoredSession) -> Result<(), String> {
+        *self.session.lock().map_err(|e| e.to_string())? = Some(session);
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), String> {
+        *self.session.lock().map_err(|e| e.to_string())? = None;
+        secure_store::clear(&self.dir).map_err(|e| e.to_string())
+    }
+}
+
+/// What the webview may know about the session (no token)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionInfo {
+    pub user: Value,
+    pub api_base_url: String,
+    pub logged_in_at: NaiveDateTime,
+    /// UTC, from the token's `exp` claim
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl From<&StoredSession> for SessionInfo {
+    fn from(session: &StoredSession) -> Self {
+        Self {
+            user: session.user.clone(),
+            api_base_url: session.api_base_url.clone(),
+            logged_in_at: session.logged_in_at,
+            expires_at: session.expires_at(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginResult {
+    pub session: SessionInfo,
+    /// Set after an admin password reset
+    pub force_password_change: bool,
+}
+
+/// Response of `POST /auth/login`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoginResponse {
+    #[serde(default)]
+    success: bool,
+    data: Option<Value>,
+    token: Option<String>,
+    error: Option<String>,
+    #[serde(default)]
+    force_password_change: bool,
+}
+
+pub fn setup(app: &AppHandle) {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."));
+    app.manage(AuthState::open(dir));
+}
+
+fn same_origin(url: &str, base: &str) -> bool {
+    match (Url::parse(url), Url::parse(base)) {
+        (Ok(url), Ok(base)) => url.origin() == base.origin(),
+        _ => false,
+    }
+}
+
+fn emit_changed(app: &AppHandle, session: Option<&StoredSession>) {
+    let _ = app.emit(EVENT_AUTH_CHANGED, session.map(SessionInfo::from));
+}
+
+/// Forgets the session without contacting the server (e.g. after
+/// switching to another server profile)
+pub fn end_session(app: &AppHandle) {
+    let state = app.state::<AuthState>();
+    if state.current().is_none() {
+        return;
+    }
+    if let Err(error) = state.clear() {
+        log::warn!("Failed to clear session: {}", error);
+    }
+    emit_changed(app, None);
+}
+
+/// Drops the session after the server answered 401
+pub fn expire(app: &AppHandle) {
+    end_session(app);
+    let _ = app.emit(EVENT_AUTH_EXPIRED, ());
+}
+
+// ========================================
+// Commands
+// ========================================
+
+#[tauri::command]
+pub async fn auth_login(
+    app: AppHandle,
+    client: State<'_, ApiClient>,
+    username: String,
+    password: String,
+) -> Result<LoginResult, ApiError> {
+    let api_base_url = client.profile().api_base_url;
+    let credentials = json!({ "username": username, "password": password });
+    let response = client
+        .json(
+            &app,
+            Method::POST,
+            &api_client::url(&api_base_url, "/auth/login"),
+            Some(&credentials),
+        )
+        .await?;
+
+    let body: LoginResponse = serde_json::from_value(response)
+        .map_err(|_| ApiError::new(ApiErrorKind::Unexpected, ""))?;
+    let (true, Some(user), Some(token)) = (body.success, body.data, body.token) else {
+        return Err(ApiError {
+            message: body
+                .error
+                .unwrap_or_else(|| "Login fehlgeschlagen".to_string()),
+            ..ApiError::new(ApiErrorKind::Unauthorized, "")
+        });
+    };
+
+    let session = StoredSession {
+        token,
+        user,
+        api_base_url,
+        logged_in_at: Local::now().naive_local(),
+    };
+    app.state::<AuthState>()
+        .store(session.clone())
+        .map_err(|message| ApiError {
+            message,
+            ..ApiError::new(ApiErrorKind::Unexpected, "")
+        })?;
+    emit_changed(&app, Some(&session));
+
+    Ok(LoginResult {
+        session: SessionInfo::from(&session),
+        force_password_change: body.force_password_change,
+    })
+}
+
+/// Ends the session on the server (best effort) and forgets the token
+#[tauri::command]
+pub async fn auth_logout(app: AppHandle, client: State<'_, ApiClient>) -> Result<(), String> {
+    let state = app.state::<AuthState>();
+    if let Some(session) = state.current() {
+        let url = api_client::url(&session.api_base_url, "/auth/logout");
+        if let Err(error) = client
+            .execute(&app, Method::POST, &url, &HashMap::new(), None)
+            .await
+        {
+            log::warn!("Logout request failed: {}", error);
+        }
+    }
+    state.clear()?;
+    emit_changed(&app, None);
+    Ok(())
+}
+
+/// Takes over the JWT of the legacy `timetracking_jwt_token` localStorage
+/// key if the active server still accepts it. `None` when it was expired or
+/// rejected, or when a session exists already.
+#[tauri::command]
+pub async fn auth_import_token(
+    app: AppHandle,
+    client: State<'_, ApiClient>,
+    token: String,
+) -> Result<Option<SessionInfo>, String> {
+    let state = app.state::<AuthState>();
+    if state.current().is_some() {
+        return Ok(None);
+    }
+    let api_base_url = client.profile().api_base_url;
+    let mut session = StoredSession {
+        token,
+        user: Value::Null,
+        api_base_url,
+        logged_in_at: Local::now().naive_local(),
+    };
+    if session.is_expired(Utc::now().naive_utc()) {
+        return Ok(None);
+    }
+
+    state.try_session(session.clone())?;
+    let user = client
+        .json(
+            &app,
+            Method::GET,
+            &api_client::url(&session.api_base_url, "/auth/me"),
+            None,
+        )
+        .await
+        .ok()
+        .and_then(|body| body.get("data")?.get("user").cloned());
+    let Some(user) = user else {
+        state.clear()?;
+        return Ok(None);
+    };
+
+    session.user = user;
+    state.store(session.clone())?;
+    emit_changed(&app, Some(&session));
+    Ok(Some(SessionInfo::from(&session)))
+}
+
+#[tauri::command]
+pub fn auth_session(state: State<'_, AuthState>) -> Option<SessionInfo> {
+    state.current().as_ref().map(SessionInfo::from)
+}
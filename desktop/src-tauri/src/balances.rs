@@ -0,0 +1,260 @@
+//! Overtime balance, remaining vacation and open absence requests of the
+//! logged-in user, shown as info items in the tray menu.
+//!
+//! Fetched from the server on login, every `REFRESH_INTERVAL` and whenever
+//! the WebSocket reports `overtime:updated` or an `absence:*` event. A
+//! value that cannot be fetched (e.g. offline) keeps its last known value.
+
+use std::{sync::Mutex, time::Duration};
+
+use chrono::{Datelike, Local};
+use serde_json::Value;
+use tauri::{AppHandle, Listener, Manager};
+use tauri_plugin_http::reqwest::Method;
+
+use crate::api_client::{self, ApiClient};
+use crate::auth::{self, AuthState};
+use crate::server_events::EventKind;
+use crate::tray;
+use crate::tray_status::format_balance;
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Server events after which the figures are fetched again
+const REFRESH_EVENTS: [EventKind; 4] = [
+    EventKind::OvertimeUpdated,
+    EventKind::AbsenceCreated,
+    EventKind::AbsenceApproved,
+    EventKind::AbsenceRejected,
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Balances {
+    /// Overtime in hours, including the carryover from last year
+    pub overtime: Option<f64>,
+    /// Vacation days left this year
+    pub vacation_remaining: Option<f64>,
+    /// Absence requests awaiting approval (all users' for admins)
+    pub pending_requests: Option<i64>,
+}
+
+impl Balances {
+    /// Fills values missing here from `previous`
+    pub fn or(self, previous: Balances) -> Balances {
+        Balances {
+            overtime: self.overtime.or(previous.overtime),
+            vacation_remaining: self.vacation_remaining.or(previous.vacation_remaining),
+            pending_requests: self.pending_requests.or(previous.pending_requests),
+        }
+    }
+
+    /// e.g. "Überstunden: +12:30h"
+    pub fn overtime_label(&self) -> String {
+        match self.overtime {
+            Some(hours) => format!("Überstunden: {}", format_balance(hours)),
+            None => "Überstunden: –".to_string(),
+        }
+    }
+
+    /// e.g. "Resturlaub 2026: 12,5 Tage"
+    pub fn vacation_label(&self, year: i32) -> String {
+        match self.vacation_remaining {
+            Some(1.0) => format!("Resturlaub {}: 1 Tag", year),
+            Some(days) => format!("Resturlaub {}: {} Tage", year, format_days(days)),
+            None => format!("Resturlaub {}: –", year),
+        }
+    }
+
+    /// e.g. "Offene Anträge: 2"
+    pub fn pending_label(&self) -> String {
+        match self.pending_requests {
+            Some(0) => "Keine offenen Anträge".to_string(),
+            Some(count) => format!("Offene Anträge: {}", count),
+            None => "Offene Anträge: –".to_string(),
+        }
+    }
+}
+
+/// Last figures and the user they belong to
+#[derive(Default)]
+pub struct BalancesState(Mutex<Option<(i64, Balances)>>);
+
+impl BalancesState {
+    /// Figures of `user_id`; empty for another or no user
+    pub fn get(&self, user_id: Option<i64>) -> Balances {
+        let Ok(current) = self.0.lock() else {
+            return Balances::default();
+        };
+        match (*current, user_id) {
+            (Some((id, balances)), Some(user_id)) if id == user_id => balances,
+            _ => Balances::default(),
+        }
+    }
+
+    fn set(&self, value: Option<(i64, Balances)>) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = value;
+        }
+    }
+}
+
+/// Days with up to two decimals and a German decimal comma
+fn format_days(days: f64) -> String {
+    let rounded = (days * 100.0).round() / 100.0;
+    rounded.to_string().replace('.', ",")
+}
+
+/// `data.overtime` of `GET /overtime/balance/:userId/year/:year`
+pub fn parse_overtime(body: &Value) -> Option<f64> {
+    body.get("data")?.get("overtime")?.as_f64()
+}
+
+/// `data.remaining` of `GET /absences/vacation-balance/:year`
+pub fn parse_vacation_remaining(body: &Value) -> Option<f64> {
+    body.get("data")?.get("remaining")?.as_f64()
+}
+
+/// `pagination.total` of `GET /absences?status=pending`
+pub fn parse_pending_requests(body: &Value) -> Option<i64> {
+    body.get("pagination")?.get("total")?.as_i64()
+}
+
+pub fn setup(app: &AppHandle) {
+    app.manage(BalancesState::default());
+
+    let events = REFRESH_EVENTS.iter().map(|kind| kind.event_name());
+    for event in std::iter::once(auth::EVENT_AUTH_CHANGED).chain(events) {
+        let handle = app.clone();
+        app.listen(event, move |_| trigger_refresh(&handle));
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            refresh(&app).await;
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+        }
+    });
+}
+
+fn trigger_refresh(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { refresh(&app).await });
+}
+
+/// Fetches the figures of the logged-in user and updates the tray menu
+pub async fn refresh(app: &AppHandle) {
+    let state = app.state::<BalancesState>();
+    let Some(user_id) = app.state::<AuthState>().user_id() else {
+        state.set(None);
+        tray::set_balances(app, &Balances::default());
+        return;
+    };
+
+    let year = Local::now().year();
+    let base = app.state::<ApiClient>().profile().api_base_url;
+    let overtime_path = format!("/overtime/balance/{}/year/{}", user_id, year);
+    let (overtime, vacation, pending) = tokio::join!(
+        fetch(app, &base, &overtime_path),
+        fetch(app, &base, &format!("/absences/vacation-balance/{}", year)),
+        fetch(app, &base, "/absences?status=pending&limit=1"),
+    );
+    let fetched = Balances {
+        overtime: overtime.as_ref().and_then(parse_overtime),
+        vacation_remaining: vacation.as_ref().and_then(parse_vacation_remaining),
+        pending_requests: pending.as_ref().and_then(parse_pending_requests),
+    };
+
+    // Logged out or switched user while the requests were running
+    if app.state::<AuthState>().user_id() != Some(user_id) {
+        return;
+    }
+    let balances = fetched.or(state.get(Some(user_id)));
+    state.set(Some((user_id, balances)));
+    if let Some(overtime) = balances.overtime {
+        tray::set_balance(app, user_id, overtime);
+    }
+    tray::set_balances(app, &balances);
+}
+
+async fn fetch(app: &AppHandle, base: &str, path: &str) -> Option<Value> {
+    let client = app.try_state::<ApiClient>()?;
+    match client
+        .json(app, Method::GET, &api_client::url(base, path), None)
+        .await
+    {
+        Ok(body) => Some(body),
+        Err(error) => {
+            log::warn!("Failed to fetch {}: {}", path, error.message);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_server_responses() {
+        let overtime = json!({ "data": { "overtime": 12.5, "carryoverFromPreviousYear": 4 } });
+        let vacation = json!({ "data": { "entitlement": 30, "remaining": 12.5, "pending": 3 } });
+        let pending = json!({ "data": [], "pagination": { "total": 2, "page": 1 } });
+
+        assert_eq!(parse_overtime(&overtime), Some(12.5));
+        assert_eq!(parse_vacation_remaining(&vacation), Some(12.5));
+        assert_eq!(parse_pending_requests(&pending), Some(2));
+        assert_eq!(
+            parse_overtime(&json!({ "success": false, "error": "x" })),
+            None
+        );
+    }
+
+    #[test]
+    fn labels() {
+        let balances = Balances {
+            overtime: Some(-1.25),
+            vacation_remaining: Some(12.5),
+            pending_requests: Some(2),
+        };
+        assert_eq!(balances.overtime_label(), "Überstunden: -1:15h");
+        assert_eq!(balances.vacation_label(2026), "Resturlaub 2026: 12,5 Tage");
+        assert_eq!(balances.pending_label(), "Offene Anträge: 2");
+
+        let one = Balances {
+            vacation_remaining: Some(1.0),
+            pending_requests: Some(0),
+            ..Balances::default()
+        };
+        assert_eq!(one.vacation_label(2026), "Resturlaub 2026: 1 Tag");
+        assert_eq!(one.pending_label(), "Keine offenen Anträge");
+
+        let unknown = Balances::default();
+        assert_eq!(unknown.overtime_label(), "Überstunden: –");
+        assert_eq!(unknown.vacation_label(2026), "Resturlaub 2026: –");
+    }
+
+    #[test]
+    fn failed_fetch_keeps_previous_value_of_same_user() {
+        let state = BalancesState::default();
+        let first = Balances {
+            overtime: Some(3.0),
+            vacation_remaining: Some(20.0),
+            pending_requests: Some(1),
+        };
+        state.set(Some((7, first)));
+
+        let offline = Balances {
+            pending_requests: Some(0),
+            ..Balances::default()
+        };
+        let merged = offline.or(state.get(Some(7)));
+        assert_eq!(merged.overtime, Some(3.0));
+        assert_eq!(merged.vacation_remaining, Some(20.0));
+        assert_eq!(merged.pending_requests, Some(0));
+
+        assert_eq!(state.get(Some(8)), Balances::default());
+        assert_eq!(state.get(None), Balances::default());
+    }
+}
@@ -0,0 +1,696 @@
+//! iCalendar (RFC 5545) export of absences and holidays
+//!
+//! Approved absences of the logged-in user (from the offline store, so it
+//! works without the server; an empty cache is pulled first) and the
+//! holidays of the configured state become all-day VEVENTs. UIDs only
+//! depend on the absence id or the holiday's state and date, so re-imports
+//! and feed refreshes update events instead of duplicating them.
+//!
+//! The calendar can be saved as `.ics` file or served read-only at
+//! `http://127.0.0.1:<port>/<token>/calendar.ics` for clients that
+//! subscribe (Thunderbird, Outlook). The token is a random secret created
+//! once per installation (kept in the OS keyring like the session key), so
+//! other local users and programs cannot guess the URL. The feed only
+//! listens on the loopback interface and rejects requests whose `Host` is
+//! not the loopback address, so web pages cannot read it through DNS
+//! rebinding. Every request is answered on its own thread; the calendar is
+//! built each time and follows whoever is logged in; without a session it
+//! only contains the holidays.
+
+use std::{
+    fmt, fs,
+    io::{self, Read, Write},
+    net::{Ipv4Addr, SocketAddrV4, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::auth::AuthState;
+use crate::holidays::{self, Holiday, HolidayError, RegionalOptions};
+use crate::overtime::{Absence, AbsenceKind};
+use crate::secure_store::SessionKey;
+use crate::session_store::write_atomic;
+use crate::sync;
+
+const FILE_NAME: &str = "calendar_settings.json";
+pub const DEFAULT_FEED_PORT: u16 = 47_321;
+const FEED_FILE_NAME: &str = "calendar.ics";
+const TOKEN_KEYRING_USER: &str = "calendar-feed-token";
+const TOKEN_FILE_NAME: &str = "calendar-feed.key";
+const PRODID: &str = "-//TimeTracker//Desktop//DE";
+/// RFC 5545 §3.1: lines longer than 75 octets are folded
+const MAX_LINE_OCTETS: usize = 75;
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum CalendarError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Holiday(HolidayError),
+    /// Offline store not available
+    Store(String),
+}
+
+impl fmt::Display for CalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalendarError::Io(e) => write!(f, "Kalender: {}", e),
+            CalendarError::Json(e) => write!(f, "Ungültige Kalender-Einstellungen: {}", e),
+            CalendarError::Holiday(e) => e.fmt(f),
+            CalendarError::Store(e) => write!(f, "Abwesenheiten nicht verfügbar: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CalendarError {}
+
+impl From<io::Error> for CalendarError {
+    fn from(e: io::Error) -> Self {
+        CalendarError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for CalendarError {
+    fn from(e: serde_json::Error) -> Self {
+        CalendarError::Json(e)
+    }
+}
+
+impl From<HolidayError> for CalendarError {
+    fn from(e: HolidayError) -> Self {
+        CalendarError::Holiday(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarSettings {
+    /// Holidays of this state; `None` = no holidays
+    #[serde(default)]
+    pub state: Option<holidays::State>,
+    #[serde(default)]
+    pub options: RegionalOptions,
+    /// Also show pending requests (as tentative)
+    #[serde(default)]
+    pub include_pending: bool,
+    #[serde(default)]
+    pub feed_enabled: bool,
+    #[serde(default = "default_feed_port")]
+    pub feed_port: u16,
+}
+
+fn default_feed_port() -> u16 {
+    DEFAULT_FEED_PORT
+}
+
+impl Default for CalendarSettings {
+    fn default() -> Self {
+        CalendarSettings {
+            state: None,
+            options: RegionalOptions::default(),
+            include_pending: false,
+            feed_enabled: false,
+            feed_port: DEFAULT_FEED_PORT,
+        }
+    }
+}
+
+/// One all-day event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub start_date: NaiveDate,
+    /// Last day (inclusive)
+    pub end_date: NaiveDate,
+    pub summary: String,
+    pub category: &'static str,
+    /// Holidays don't block time in the client's free/busy view
+    pub transparent: bool,
+    pub tentative: bool,
+}
+
+fn absence_title(kind: AbsenceKind) -> &'static str {
+    match kind {
+        AbsenceKind::Vacation => "Urlaub",
+        AbsenceKind::Sick => "Krank",
+        AbsenceKind::OvertimeComp => "Überstundenausgleich",
+        AbsenceKind::Special => "Sonderurlaub",
+        AbsenceKind::Unpaid => "Unbezahlter Urlaub",
+        AbsenceKind::Other => "Abwesenheit",
+    }
+}
+
+/// Approved (and optionally pending) absences; rejected ones are skipped
+pub fn absence_events(absences: &[Absence], include_pending: bool) -> Vec<CalendarEvent> {
+    absences
+        .iter()
+        .filter(|a| a.start_date <= a.end_date)
+        .filter_map(|a| {
+            let tentative = match a.status.as_str() {
+                "approved" => false,
+                "pending" if include_pending => true,
+                _ => return None,
+            };
+            let uid = match a.id {
+                Some(id) => format!("absence-{}@timetracker", id),
+                None => format!(
+                    "absence-{}-{}@timetracker",
+                    a.start_date.format("%Y%m%d"),
+                    a.end_date.format("%Y%m%d")
+                ),
+            };
+            let title = absence_title(a.kind);
+            Some(CalendarEvent {
+                uid,
+                start_date: a.start_date,
+                end_date: a.end_date,
+                summary: if tentative {
+                    format!("{} (beantragt)", title)
+                } else {
+                    title.to_string()
+                },
+                category: "Abwesenheit",
+                transparent: false,
+                tentative,
+            })
+        })
+        .collect()
+}
+
+pub fn holiday_events(state: holidays::State, holidays: &[Holiday]) -> Vec<CalendarEvent> {
+    holidays
+        .iter()
+        .map(|h| CalendarEvent {
+            uid: format!(
+                "holiday-{}-{}@timetracker",
+                state.code(),
+                h.date.format("%Y%m%d")
+            ),
+            start_date: h.date,
+            end_date: h.date,
+            summary: if h.half_day {
+                format!("{} (halber Tag)", h.name)
+            } else {
+                h.name.clone()
+            },
+            category: "Feiertag",
+            transparent: true,
+            tentative: false,
+        })
+        .collect()
+}
+
+/// TEXT value escaping (RFC 5545 §3.3.11)
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Appends `line` with CRLF, folded after 75 octets without splitting a
+/// UTF-8 character
+fn push_line(out: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // The leading space counts towards the next line
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// The complete VCALENDAR; `stamp` is used as DTSTAMP of all events
+pub fn to_ics(name: &str, events: &[CalendarEvent], stamp: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    let stamp = stamp.format("%Y%m%dT%H%M%SZ").to_string();
+    for line in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        &format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN",
+        "METHOD:PUBLISH",
+        &format!("X-WR-CALNAME:{}", escape_text(name)),
+        "REFRESH-INTERVAL;VALUE=DURATION:PT1H",
+        "X-PUBLISHED-TTL:PT1H",
+    ] {
+        push_line(&mut out, line);
+    }
+    for event in events {
+        // DTEND of all-day events is exclusive
+        let end = event.end_date.succ_opt().unwrap_or(event.end_date);
+        for line in [
+            "BEGIN:VEVENT",
+            &format!("UID:{}", event.uid),
+            &format!("DTSTAMP:{}", stamp),
+            &format!("DTSTART;VALUE=DATE:{}", event.start_date.format("%Y%m%d")),
+            &format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")),
+            &format!("SUMMARY:{}", escape_text(&event.summary)),
+            &format!("CATEGORIES:{}", escape_text(event.category)),
+            if event.tentative {
+                "STATUS:TENTATIVE"
+            } else {
+                "STATUS:CONFIRMED"
+            },
+            if event.transparent {
+                "TRANSP:TRANSPARENT"
+            } else {
+                "TRANSP:OPAQUE"
+            },
+            "END:VEVENT",
+        ] {
+            push_line(&mut out, line);
+        }
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+pub fn file_path(dir: &Path) -> PathBuf {
+    dir.join(FILE_NAME)
+}
+
+pub fn load(dir: &Path) -> Result<CalendarSettings, CalendarError> {
+    match fs::read_to_string(file_path(dir)) {
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(CalendarSettings::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn save(dir: &Path, settings: &CalendarSettings) -> Result<(), CalendarError> {
+    write_atomic(
+        &file_path(dir),
+        serde_json::to_string_pretty(settings)?.as_bytes(),
+    )?;
+    Ok(())
+}
+
+/// Calendar of the logged-in user: absences plus holidays of last, this
+/// and next year
+fn build(app: &AppHandle, settings: &CalendarSettings) -> Result<String, CalendarError> {
+    let mut events = Vec::new();
+    if let Some(user_id) = app.state::<AuthState>().user_id() {
+        let absences = sync::cached_absences(app, user_id).map_err(CalendarError::Store)?;
+        events.extend(absence_events(&absences, settings.include_pending));
+    }
+    if let Some(state) = settings.state {
+        let year = Local::now().year();
+        for year in year - 1..=year + 1 {
+            let holidays = holidays::holidays(state, year, &settings.options)?;
+            events.extend(holiday_events(state, &holidays));
+        }
+    }
+    events.sort_by(|a, b| (a.start_date, &a.uid).cmp(&(b.start_date, &b.uid)));
+    Ok(to_ics("TimeTracker", &events, Utc::now()))
+}
+
+fn feed_path(token: &str) -> String {
+    format!("/{}/{}", token, FEED_FILE_NAME)
+}
+
+/// Compares without an early exit, so the response time does not reveal
+/// how much of the token was right
+fn same_secret(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+/// HTTP response to a request head (request line and headers)
+fn respond(
+    head: &str,
+    port: u16,
+    token: &str,
+    body: impl FnOnce() -> Result<String, CalendarError>,
+) -> Vec<u8> {
+    let response = |status: &str, content_type: &str, body: &str, send_body: bool| {
+        let mut bytes = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+            status,
+            content_type,
+            body.len()
+        )
+        .into_bytes();
+        if send_body {
+            bytes.extend_from_slice(body.as_bytes());
+        }
+        bytes
+    };
+    let text = |status: &str, body: &str| response(status, "text/plain; charset=utf-8", body, true);
+
+    let mut lines = head.lines();
+    let mut request = lines.next().unwrap_or_default().split(' ');
+    let (method, target) = (request.next().unwrap_or_default(), request.next());
+    let host = lines
+        .filter_map(|l| l.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("host"))
+        .map(|(_, value)| value.trim().to_ascii_lowercase());
+    let allowed_hosts = [format!("127.0.0.1:{}", port), format!("localhost:{}", port)];
+    if !host.is_some_and(|h| allowed_hosts.contains(&h)) {
+        return text("403 Forbidden", "Nur lokaler Zugriff erlaubt");
+    }
+    let path = target.map(|t| t.split('?').next().unwrap_or(t));
+    if !path.is_some_and(|p| same_secret(p, &feed_path(token))) {
+        return text("404 Not Found", "Nicht gefunden");
+    }
+    if method != "GET" && method != "HEAD" {
+        return text("405 Method Not Allowed", "Nur GET");
+    }
+    match body() {
+        Ok(ics) => response(
+            "200 OK",
+            "text/calendar; charset=utf-8",
+            &ics,
+            method == "GET",
+        ),
+        Err(e) => text("500 Internal Server Error", &e.to_string()),
+    }
+}
+
+fn handle(mut stream: TcpStream, port: u16, token: &str, app: &AppHandle) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut head = Vec::new();
+    let mut buffer = [0; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_BYTES {
+        let read = stream.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    let settings = *app
+        .state::<CalendarState>()
+        .settings
+        .lock()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    let response = respond(&String::from_utf8_lossy(&head), port, token, || {
+        tauri::async_runtime::block_on(sync::ensure_pulled(app));
+        build(app, &settings)
+    });
+    stream.write_all(&response)
+}
+
+struct Feed {
+    port: u16,
+    token: Arc<str>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Feed {
+    fn start(app: &AppHandle, port: u16, token: String) -> io::Result<Self> {
+        let listener = TcpListener::bind(SocketAddrV4::new(Ipv4Addr::LOCALHOST, port))?;
+        let port = listener.local_addr()?.port();
+        let token: Arc<str> = token.into();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (stopped, feed_token) = (stop.clone(), token.clone());
+        let app = app.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                // Building the calendar may wait for a pull, which must
+                // not hold up other requests
+                let (app, token) = (app.clone(), feed_token.clone());
+                thread::spawn(move || {
+                    if let Err(error) = handle(stream, port, &token, &app) {
+                        log::warn!("Calendar feed request failed: {}", error);
+                    }
+                });
+            }
+        });
+        Ok(Feed { port, token, stop })
+    }
+
+    fn stop(&self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake up the blocking accept
+        let _ = TcpStream::connect(SocketAddrV4::new(Ipv4Addr::LOCALHOST, self.port));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarFeedStatus {
+    pub running: bool,
+    pub url: Option<String>,
+}
+
+#[derive(Default)]
+pub struct CalendarState {
+    settings: Mutex<CalendarSettings>,
+    feed: Mutex<Option<Feed>>,
+}
+
+impl CalendarState {
+    fn status(&self) -> CalendarFeedStatus {
+        let url = self.feed.lock().ok().and_then(|f| {
+            f.as_ref()
+                .map(|f| format!("http://127.0.0.1:{}{}", f.port, feed_path(&f.token)))
+        });
+        CalendarFeedStatus {
+            running: url.is_some(),
+            url,
+        }
+    }
+}
+
+fn settings_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+}
+
+/// Secret path segment of the feed, created on first use
+fn feed_token(app: &AppHandle) -> Result<String, String> {
+    let key =
+        SessionKey::load_or_create_as(&settings_dir(app), TOKEN_KEYRING_USER, TOKEN_FILE_NAME)
+            .map_err(|e| format!("Kalender-Feed: {}", e))?;
+    Ok(URL_SAFE_NO_PAD.encode(key.as_bytes()))
+}
+
+/// Starts or stops the feed to match `settings`
+fn apply(app: &AppHandle, settings: CalendarSettings) -> Result<CalendarFeedStatus, String> {
+    let state = app.state::<CalendarState>();
+    *state.settings.lock().map_err(|e| e.to_string())? = settings;
+    let mut feed = state.feed.lock().map_err(|e| e.to_string())?;
+    let wanted = settings.feed_enabled.then_some(settings.feed_port);
+    if feed.as_ref().map(|f| f.port) != wanted {
+        if let Some(old) = feed.take() {
+            old.stop();
+        }
+        if let Some(port) = wanted {
+            let started = Feed::start(app, port, feed_token(app)?).map_err(|e| {
+                format!(
+                    "Kalender-Feed konnte nicht auf Port {} gestartet werden: {}",
+                    port, e
+                )
+            })?;
+            *feed = Some(started);
+        }
+    }
+    drop(feed);
+    Ok(state.status())
+}
+
+pub fn setup(app: &AppHandle) {
+    app.manage(CalendarState::default());
+    let settings = match load(&settings_dir(app)) {
+        Ok(settings) => settings,
+        Err(error) => {
+            log::warn!("Failed to load calendar settings: {}", error);
+            CalendarSettings::default()
+        }
+    };
+    if let Err(error) = apply(app, settings) {
+        log::warn!("{}", error);
+    }
+}
+
+#[tauri::command]
+pub fn calendar_settings(state: State<'_, CalendarState>) -> Result<CalendarSettings, String> {
+    Ok(*state.settings.lock().map_err(|e| e.to_string())?)
+}
+
+/// Saves the settings and starts, moves or stops the feed
+#[tauri::command]
+pub fn save_calendar_settings(
+    app: AppHandle,
+    settings: CalendarSettings,
+) -> Result<CalendarFeedStatus, String> {
+    save(&settings_dir(&app), &settings).map_err(|e| e.to_string())?;
+    apply(&app, settings)
+}
+
+#[tauri::command]
+pub fn calendar_feed_status(state: State<'_, CalendarState>) -> CalendarFeedStatus {
+    state.status()
+}
+
+/// Writes the calendar to the location chosen with the save dialog. Returns
+/// the saved path, `None` when the dialog was cancelled.
+#[tauri::command]
+pub async fn export_calendar_ics(app: AppHandle) -> Result<Option<String>, String> {
+    let settings = *app
+        .state::<CalendarState>()
+        .settings
+        .lock()
+        .map_err(|e| e.to_string())?;
+    sync::ensure_pulled(&app).await;
+    let ics = build(&app, &settings).map_err(|e| e.to_string())?;
+    let chosen = app
+        .dialog()
+        .file()
+        .set_file_name("Abwesenheiten.ics")
+        .add_filter("iCalendar", &["ics"])
+        .blocking_save_file();
+    let path = match chosen {
+        Some(chosen) => chosen.into_path().map_err(|e| e.to_string())?,
+        None => return Ok(None),
+    };
+    write_atomic(&path, ics.as_bytes())
+        .map_err(CalendarError::from)
+        .map_err(|e| e.to_string())?;
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn absence(id: i64, kind: AbsenceKind, status: &str, start: &str, end: &str) -> Absence {
+        Absence {
+            id: Some(id),
+            kind,
+            start_date: date(start),
+            end_date: date(end),
+            status: status.into(),
+        }
+    }
+
+    #[test]
+    fn calendar_has_all_day_events_with_stable_uids() {
+        let absences = [
+            absence(
+                7,
+                AbsenceKind::Vacation,
+                "approved",
+                "2026-08-03",
+                "2026-08-14",
+            ),
+            absence(8, AbsenceKind::Sick, "pending", "2026-09-01", "2026-09-01"),
+            absence(
+                9,
+                AbsenceKind::Vacation,
+                "rejected",
+                "2026-10-01",
+                "2026-10-02",
+            ),
+        ];
+        let mut events = absence_events(&absences, false);
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            absence_events(&absences, true)[1].summary,
+            "Krank (beantragt)"
+        );
+
+        let holidays =
+            holidays::holidays(holidays::State::BY, 2026, &RegionalOptions::default()).unwrap();
+        events.extend(holiday_events(holidays::State::BY, &holidays[..1]));
+        let stamp = Utc.with_ymd_and_hms(2026, 10, 16, 8, 30, 0).unwrap();
+        let ics = to_ics("TimeTracker", &events, stamp);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert!(ics.contains(
+            "BEGIN:VEVENT\r\nUID:absence-7@timetracker\r\nDTSTAMP:20261016T083000Z\r\n\
+             DTSTART;VALUE=DATE:20260803\r\nDTEND;VALUE=DATE:20260815\r\nSUMMARY:Urlaub\r\n\
+             CATEGORIES:Abwesenheit\r\nSTATUS:CONFIRMED\r\nTRANSP:OPAQUE\r\nEND:VEVENT\r\n"
+        ));
+        assert!(ics.contains(
+            "UID:holiday-BY-20260101@timetracker\r\nDTSTAMP:20261016T083000Z\r\n\
+             DTSTART;VALUE=DATE:20260101\r\nDTEND;VALUE=DATE:20260102\r\nSUMMARY:Neujahr\r\n"
+        ));
+        // Same input, same calendar
+        assert_eq!(ics, to_ics("TimeTracker", &events, stamp));
+    }
+
+    #[test]
+    fn text_is_escaped_and_long_lines_are_folded() {
+        assert_eq!(escape_text("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+        let mut out = String::new();
+        let line = format!("SUMMARY:{}", "Überstundenausgleich ".repeat(5));
+        push_line(&mut out, &line);
+        let physical: Vec<&str> = out.trim_end_matches("\r\n").split("\r\n").collect();
+        assert!(physical.len() > 1);
+        assert!(physical.iter().all(|l| l.len() <= MAX_LINE_OCTETS));
+        assert!(physical[1..].iter().all(|l| l.starts_with(' ')));
+        let unfolded: String = physical
+            .iter()
+            .enumerate()
+            .map(|(i, l)| if i == 0 { *l } else { &l[1..] })
+            .collect();
+        assert_eq!(unfolded, line);
+    }
+
+    #[test]
+    fn feed_only_answers_local_calendar_requests_with_the_token() {
+        let ok = || Ok("BEGIN:VCALENDAR\r\n".to_string());
+        let get = |target: &str, host: &str| {
+            let head = format!("GET {} HTTP/1.1\r\nHost: {}\r\n\r\n", target, host);
+            String::from_utf8(respond(&head, 47321, "s3cr3t", ok)).unwrap()
+        };
+
+        let response = get("/s3cr3t/calendar.ics", "127.0.0.1:47321");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/calendar; charset=utf-8\r\n"));
+        assert!(response.ends_with("\r\n\r\nBEGIN:VCALENDAR\r\n"));
+        assert!(get("/s3cr3t/calendar.ics?x=1", "localhost:47321").starts_with("HTTP/1.1 200"));
+
+        assert!(get("/s3cr3t/calendar.ics", "evil.example:47321").starts_with("HTTP/1.1 403"));
+        assert!(get("/calendar.ics", "127.0.0.1:47321").starts_with("HTTP/1.1 404"));
+        assert!(get("/s3cr3x/calendar.ics", "127.0.0.1:47321").starts_with("HTTP/1.1 404"));
+        assert!(get("/", "127.0.0.1:47321").starts_with("HTTP/1.1 404"));
+        let post = "POST /s3cr3t/calendar.ics HTTP/1.1\r\nHost: 127.0.0.1:47321\r\n\r\n";
+        let response = String::from_utf8(respond(post, 47321, "s3cr3t", ok)).unwrap();
+        assert!(response.starts_with("HTTP/1.1 405"));
+        let head = "HEAD /s3cr3t/calendar.ics HTTP/1.1\r\nhost: 127.0.0.1:47321\r\n\r\n";
+        let response = String::from_utf8(respond(head, 47321, "s3cr3t", ok)).unwrap();
+        assert!(response.contains("Content-Length: 17\r\n") && response.ends_with("\r\n\r\n"));
+    }
+}
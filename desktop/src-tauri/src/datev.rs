@@ -0,0 +1,548 @@
+//! DATEV LODAS ASCII import file
+//!
+//! `generateDATEVExport` (`server/src/services/exportService.ts`) writes a
+//! plain CSV that payroll has to retype. This module turns the same rows
+//! into a LODAS import file ("ASCII-Import", Windows-1252, CRLF):
+//!
+//! ```text
+//! [Allgemein]               Ziel, Berater- and Mandantennummer, formats
+//! [Satzbeschreibung]        one record type: booking per Lohnart
+//! [Bewegungsdaten]          Personalnummer;Zeitraum;Lohnart;Schlüssel;Wert
+//! ```
+//!
+//! Worked hours and overtime (positive only) are booked in hours, absences
+//! in days. Which Lohnart is used for what is set per client in
+//! `datev_settings.json` (Settings → DATEV); rows without a configured
+//! Lohnart are skipped and reported as warnings. Absence days are the days
+//! of the month with a target (`daily_targets`), or Monday–Friday when no
+//! targets are given.
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::overtime::AbsenceKind;
+use crate::session_store::write_atomic;
+use crate::timesheet::{absence_kind, format_hours, win_ansi, DatevRow};
+use crate::work_schedule::{self, DailyTarget};
+
+const FILE_NAME: &str = "datev_settings.json";
+const RECORD_TYPE: u32 = 10;
+/// Bearbeitungsschlüssel of a booking
+const KEY_HOURS: u32 = 1;
+const KEY_DAYS: u32 = 2;
+const MAX_CONSULTANT_NUMBER: u32 = 9_999_999;
+const MAX_CLIENT_NUMBER: u32 = 99_999;
+const MAX_PERSONNEL_NUMBER: i64 = 99_999;
+const MAX_WAGE_TYPE: u32 = 9_999;
+
+#[derive(Debug)]
+pub enum DatevError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// Field name and reason
+    Invalid(&'static str, String),
+    InvalidMonth(i32, u32),
+}
+
+impl fmt::Display for DatevError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatevError::Io(e) => write!(f, "DATEV-Export: {}", e),
+            DatevError::Json(e) => write!(f, "Ungültige DATEV-Einstellungen: {}", e),
+            DatevError::Invalid(field, reason) => write!(f, "{}: {}", field, reason),
+            DatevError::InvalidMonth(year, month) => {
+                write!(f, "Ungültiger Abrechnungsmonat: {:02}/{}", month, year)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DatevError {}
+
+impl From<io::Error> for DatevError {
+    fn from(e: io::Error) -> Self {
+        DatevError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for DatevError {
+    fn from(e: serde_json::Error) -> Self {
+        DatevError::Json(e)
+    }
+}
+
+pub type DatevResult<T> = Result<T, DatevError>;
+
+/// Lohnarten of the client; `None` = not exported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WageTypes {
+    #[serde(default)]
+    pub worked_hours: Option<u32>,
+    #[serde(default)]
+    pub overtime: Option<u32>,
+    #[serde(default)]
+    pub vacation: Option<u32>,
+    #[serde(default)]
+    pub sick: Option<u32>,
+    #[serde(default)]
+    pub unpaid: Option<u32>,
+    #[serde(default)]
+    pub overtime_comp: Option<u32>,
+}
+
+impl WageTypes {
+    fn for_absence(&self, kind: AbsenceKind) -> Option<u32> {
+        match kind {
+            AbsenceKind::Vacation => self.vacation,
+            AbsenceKind::Sick => self.sick,
+            AbsenceKind::Unpaid => self.unpaid,
+            AbsenceKind::OvertimeComp => self.overtime_comp,
+            AbsenceKind::Special | AbsenceKind::Other => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatevSettings {
+    /// Beraternummer
+    pub consultant_number: u32,
+    /// Mandantennummer
+    pub client_number: u32,
+    #[serde(default)]
+    pub wage_types: WageTypes,
+}
+
+impl DatevSettings {
+    pub fn validate(&self) -> DatevResult<()> {
+        if !(1..=MAX_CONSULTANT_NUMBER).contains(&self.consultant_number) {
+            return Err(DatevError::Invalid(
+                "Beraternummer",
+                format!("muss zwischen 1 und {} liegen", MAX_CONSULTANT_NUMBER),
+            ));
+        }
+        if !(1..=MAX_CLIENT_NUMBER).contains(&self.client_number) {
+            return Err(DatevError::Invalid(
+                "Mandantennummer",
+                format!("muss zwischen 1 und {} liegen", MAX_CLIENT_NUMBER),
+            ));
+        }
+        let w = &self.wage_types;
+        for wage_type in [
+            w.worked_hours,
+            w.overtime,
+            w.vacation,
+            w.sick,
+            w.unpaid,
+            w.overtime_comp,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if !(1..=MAX_WAGE_TYPE).contains(&wage_type) {
+                return Err(DatevError::Invalid(
+                    "Lohnart",
+                    format!(
+                        "{} ist keine gültige Lohnart (1–{})",
+                        wage_type, MAX_WAGE_TYPE
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rows of one employee, as in the DATEV export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LodasEmployee {
+    pub personnel_number: i64,
+    pub rows: Vec<DatevRow>,
+    /// Targets of the month, used to count absence days
+    #[serde(default)]
+    pub daily_targets: Vec<DailyTarget>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LodasInput {
+    pub year: i32,
+    pub month: u32,
+    pub employees: Vec<LodasEmployee>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LodasExport {
+    /// File content (Windows-1252)
+    #[serde(skip)]
+    pub content: Vec<u8>,
+    pub bookings: usize,
+    pub warnings: Vec<String>,
+}
+
+fn month_range(year: i32, month: u32) -> DatevResult<(NaiveDate, NaiveDate)> {
+    work_schedule::month_range(year, month).ok_or(DatevError::InvalidMonth(year, month))
+}
+
+/// Sums per (Personalnummer, Lohnart, Schlüssel), in file order
+type Bookings = BTreeMap<(i64, u32, u32), f64>;
+
+fn collect(
+    employee: &LodasEmployee,
+    wage_types: &WageTypes,
+    (first, last): (NaiveDate, NaiveDate),
+    bookings: &mut Bookings,
+    warnings: &mut Vec<String>,
+) {
+    let pnr = employee.personnel_number;
+    let is_workday = |date: NaiveDate| {
+        if employee.daily_targets.is_empty() {
+            return date.weekday().num_days_from_monday() < 5;
+        }
+        employee
+            .daily_targets
+            .iter()
+            .any(|t| t.date == date && t.target_hours > 0.0)
+    };
+
+    // Every entry row carries the full target of its day, so a day with
+    // several entries counts its target once (as in the timesheet)
+    let mut entry_days: BTreeMap<NaiveDate, (f64, f64)> = BTreeMap::new();
+    for row in &employee.rows {
+        let Some(label) = &row.absence_type else {
+            if (first..=last).contains(&row.date) {
+                let day = entry_days
+                    .entry(row.date)
+                    .or_insert((0.0, row.target_hours));
+                day.0 += row.actual_hours;
+            }
+            continue;
+        };
+        let start = row.date.max(first);
+        let end = row.end_date.unwrap_or(row.date).min(last);
+        let days = start
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .filter(|d| is_workday(*d))
+            .count() as f64;
+        if days == 0.0 {
+            continue;
+        }
+        match wage_types.for_absence(absence_kind(label)) {
+            Some(wage_type) => *bookings.entry((pnr, wage_type, KEY_DAYS)).or_default() += days,
+            None => warnings.push(format!(
+                "Personalnummer {}: keine Lohnart für \"{}\", {} Tag(e) nicht exportiert",
+                pnr, label, days
+            )),
+        }
+    }
+    let worked: f64 = entry_days.values().map(|(actual, _)| actual).sum();
+    let overtime: f64 = entry_days
+        .values()
+        .map(|(actual, target)| actual - target)
+        .sum();
+    for (hours, wage_type, label) in [
+        (worked, wage_types.worked_hours, "Arbeitsstunden"),
+        (overtime, wage_types.overtime, "Überstunden"),
+    ] {
+        // Minus hours are settled in the overtime account, not paid
+        let hours = (hours * 100.0).round() / 100.0;
+        if hours <= 0.0 {
+            continue;
+        }
+        match wage_type {
+            Some(wage_type) => *bookings.entry((pnr, wage_type, KEY_HOURS)).or_default() += hours,
+            None => warnings.push(format!(
+                "Personalnummer {}: keine Lohnart für {}, {} h nicht exportiert",
+                pnr,
+                label,
+                format_hours(hours)
+            )),
+        }
+    }
+}
+
+/// Builds the LODAS file for the month
+pub fn lodas_export(settings: &DatevSettings, input: &LodasInput) -> DatevResult<LodasExport> {
+    settings.validate()?;
+    let range = month_range(input.year, input.month)?;
+    let mut bookings = Bookings::new();
+    let mut warnings = Vec::new();
+    for employee in &input.employees {
+        if !(1..=MAX_PERSONNEL_NUMBER).contains(&employee.personnel_number) {
+            return Err(DatevError::Invalid(
+                "Personalnummer",
+                format!(
+                    "{} ist in LODAS nicht möglich (1–{})",
+                    employee.personnel_number, MAX_PERSONNEL_NUMBER
+                ),
+            ));
+        }
+        collect(
+            employee,
+            &settings.wage_types,
+            range,
+            &mut bookings,
+            &mut warnings,
+        );
+    }
+
+    let period = range.0.format("%d.%m.%Y");
+    let mut lines = vec![
+        "[Allgemein]".to_string(),
+        "Ziel=LODAS".to_string(),
+        "Version_SST=1.0".to_string(),
+        format!("BeraterNr={}", settings.consultant_number),
+        format!("MandantenNr={}", settings.client_number),
+        "Feldtrennzeichen=;".to_string(),
+        "Zahlenkomma=,".to_string(),
+        "Datumsangaben=TT.MM.JJJJ".to_string(),
+        "Kommentarzeichen=*".to_string(),
+        String::new(),
+        "[Satzbeschreibung]".to_string(),
+        format!(
+            "{};u_lod_bwd_buchung_standard;pnr#bwd;abrechnung_zeitraum#bwd;la_eigene#bwd;bs_nr#bwd;bs_wert_butab#bwd;",
+            RECORD_TYPE
+        ),
+        String::new(),
+        "[Bewegungsdaten]".to_string(),
+        format!("* Abrechnungsmonat {:02}/{}", input.month, input.year),
+    ];
+    for ((pnr, wage_type, key), value) in &bookings {
+        let value = format_hours(*value).replace('.', "");
+        lines.push(format!(
+            "{};{:05};{};{};{};{};",
+            RECORD_TYPE, pnr, period, wage_type, key, value
+        ));
+    }
+
+    let mut content = Vec::new();
+    for line in &lines {
+        content.extend(win_ansi(line));
+        content.extend_from_slice(b"\r\n");
+    }
+    Ok(LodasExport {
+        content,
+        bookings: bookings.len(),
+        warnings,
+    })
+}
+
+pub fn file_path(dir: &Path) -> PathBuf {
+    dir.join(FILE_NAME)
+}
+
+/// Saved settings; `None` until Berater- and Mandantennummer are set
+pub fn load(dir: &Path) -> DatevResult<Option<DatevSettings>> {
+    match fs::read_to_string(file_path(dir)) {
+        Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn save(dir: &Path, settings: &DatevSettings) -> DatevResult<()> {
+    settings.validate()?;
+    write_atomic(
+        &file_path(dir),
+        serde_json::to_string_pretty(settings)?.as_bytes(),
+    )?;
+    Ok(())
+}
+
+fn settings_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+}
+
+#[tauri::command]
+pub fn datev_settings(app: AppHandle) -> Result<Option<DatevSettings>, String> {
+    load(&settings_dir(&app)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn save_datev_settings(app: AppHandle, settings: DatevSettings) -> Result<(), String> {
+    save(&settings_dir(&app), &settings).map_err(|e| e.to_string())
+}
+
+/// Writes the LODAS file to the location chosen with the save dialog. The
+/// returned export has no content; `None` when the dialog was cancelled.
+#[tauri::command]
+pub async fn export_datev_lodas(
+    app: AppHandle,
+    input: LodasInput,
+) -> Result<Option<LodasExport>, String> {
+    let run = || -> DatevResult<Option<LodasExport>> {
+        let settings = load(&settings_dir(&app))?.ok_or(DatevError::Invalid(
+            "DATEV",
+            "Berater- und Mandantennummer sind nicht eingerichtet".to_string(),
+        ))?;
+        let export = lodas_export(&settings, &input)?;
+        let chosen = app
+            .dialog()
+            .file()
+            .set_file_name(format!("LODAS_{}-{:02}.txt", input.year, input.month))
+            .add_filter("DATEV ASCII", &["txt"])
+            .blocking_save_file();
+        let path = match chosen {
+            Some(chosen) => chosen
+                .into_path()
+                .map_err(|e| DatevError::Invalid("Speicherort", e.to_string()))?,
+            None => return Ok(None),
+        };
+        write_atomic(&path, &export.content)?;
+        Ok(Some(export))
+    };
+    run().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn settings() -> DatevSettings {
+        DatevSettings {
+            consultant_number: 1234567,
+            client_number: 99,
+            wage_types: WageTypes {
+                worked_hours: Some(100),
+                overtime: Some(220),
+                vacation: Some(300),
+                sick: Some(310),
+                unpaid: Some(320),
+                overtime_comp: None,
+            },
+        }
+    }
+
+    fn entry(pnr: i64, day: &str, target: f64, actual: f64) -> DatevRow {
+        DatevRow {
+            personnel_number: pnr,
+            date: date(day),
+            end_date: None,
+            target_hours: target,
+            actual_hours: actual,
+            break_minutes: 30,
+            absence_type: None,
+            start_time: Some("08:00".into()),
+            end_time: None,
+            note: None,
+        }
+    }
+
+    fn absence(pnr: i64, label: &str, start: &str, end: &str) -> DatevRow {
+        DatevRow {
+            end_date: Some(date(end)),
+            absence_type: Some(label.into()),
+            ..entry(pnr, start, 0.0, 0.0)
+        }
+    }
+
+    /// March 2026: two employees, a day with two entries, vacation into
+    /// April, sick leave and an overtime compensation day without Lohnart
+    fn march() -> LodasInput {
+        LodasInput {
+            year: 2026,
+            month: 3,
+            employees: vec![
+                LodasEmployee {
+                    personnel_number: 48,
+                    rows: vec![
+                        entry(48, "2026-03-02", 8.0, 9.5),
+                        entry(48, "2026-03-03", 8.0, 8.25),
+                        // Split day: the target counts once
+                        entry(48, "2026-03-04", 8.0, 4.5),
+                        entry(48, "2026-03-04", 8.0, 5.0),
+                        absence(48, "Urlaub", "2026-03-30", "2026-04-03"),
+                        absence(48, "Überstundenausgleich", "2026-03-06", "2026-03-06"),
+                    ],
+                    daily_targets: Vec::new(),
+                },
+                LodasEmployee {
+                    personnel_number: 1201,
+                    rows: vec![
+                        entry(1201, "2026-03-02", 4.0, 3.5),
+                        // Mon + Tue schedule: only 09.03. and 10.03. count
+                        absence(1201, "sick", "2026-03-09", "2026-03-13"),
+                    ],
+                    daily_targets: ["2026-03-02", "2026-03-03", "2026-03-09", "2026-03-10"]
+                        .iter()
+                        .map(|d| DailyTarget {
+                            date: date(d),
+                            target_hours: 4.0,
+                            is_holiday: false,
+                        })
+                        .collect(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn export_matches_fixture() {
+        let export = lodas_export(&settings(), &march()).unwrap();
+        let fixture = include_bytes!("../tests/fixtures/lodas_2026-03.txt");
+        assert_eq!(
+            String::from_utf8_lossy(&export.content),
+            String::from_utf8_lossy(fixture)
+        );
+        assert_eq!(export.content, fixture);
+        assert_eq!(export.bookings, 5);
+        assert_eq!(
+            export.warnings,
+            vec!["Personalnummer 48: keine Lohnart für \"Überstundenausgleich\", 1 Tag(e) nicht exportiert"]
+        );
+    }
+
+    #[test]
+    fn settings_are_validated_and_saved() {
+        let mut invalid = settings();
+        invalid.client_number = 100_000;
+        assert!(matches!(
+            lodas_export(&invalid, &march()),
+            Err(DatevError::Invalid("Mandantennummer", _))
+        ));
+        invalid = settings();
+        invalid.wage_types.sick = Some(0);
+        assert!(matches!(
+            invalid.validate(),
+            Err(DatevError::Invalid("Lohnart", _))
+        ));
+
+        let mut input = march();
+        input.employees[0].personnel_number = 123_456;
+        assert!(matches!(
+            lodas_export(&settings(), &input),
+            Err(DatevError::Invalid("Personalnummer", _))
+        ));
+        input.month = 0;
+        assert!(matches!(
+            lodas_export(&settings(), &input),
+            Err(DatevError::InvalidMonth(2026, 0))
+        ));
+
+        let dir = std::env::temp_dir().join(format!("timetracker-datev-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(load(&dir).unwrap(), None);
+        save(&dir, &settings()).unwrap();
+        assert_eq!(load(&dir).unwrap(), Some(settings()));
+        assert!(save(&dir, &DatevSettings::default()).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,394 @@
+//! German public holidays, computed offline
+//!
+//! Replaces the spiketime.de lookup of `server/src/services/holidayService.ts`
+//! on the desktop: all federal and state holidays of the 16 Bundesländer are
+//! derived from the fixed dates and Easter Sunday (Gauss's Easter formula),
+//! so the calendar and the overtime engine work without a connection and
+//! outside Bavaria.
+//!
+//! Some holidays depend on the municipality, see [`RegionalOptions`]:
+//! - Augsburger Friedensfest (08.08.) only in the city of Augsburg
+//! - Mariä Himmelfahrt (15.08.) in Bavaria only in predominantly Catholic
+//!   municipalities (the default), in Saarland everywhere
+//! - Fronleichnam in Saxony and Thuringia only in some Catholic
+//!   municipalities (Sorbian area, Eichsfeld)
+//!
+//! Heiligabend and Silvester are no public holidays but usually half
+//! working days; they are only listed (as `halfDay`) when requested.
+//! Rules follow today's law, including changes since 2017 (Reformationstag
+//! in the north, Frauentag in Berlin and Mecklenburg-Vorpommern,
+//! Weltkindertag in Thuringia).
+
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// First full year after reunification
+pub const FIRST_YEAR: i32 = 1991;
+pub const LAST_YEAR: i32 = 9999;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum State {
+    BW,
+    BY,
+    BE,
+    BB,
+    HB,
+    HH,
+    HE,
+    MV,
+    NI,
+    NW,
+    RP,
+    SL,
+    SN,
+    ST,
+    SH,
+    TH,
+}
+
+impl State {
+    pub const ALL: [State; 16] = [
+        State::BW,
+        State::BY,
+        State::BE,
+        State::BB,
+        State::HB,
+        State::HH,
+        State::HE,
+        State::MV,
+        State::NI,
+        State::NW,
+        State::RP,
+        State::SL,
+        State::SN,
+        State::ST,
+        State::SH,
+        State::TH,
+    ];
+
+    /// Two-letter code, e.g. `BY`
+    pub fn code(self) -> &'static str {
+        match self {
+            State::BW => "BW",
+            State::BY => "BY",
+            State::BE => "BE",
+            State::BB => "BB",
+            State::HB => "HB",
+            State::HH => "HH",
+            State::HE => "HE",
+            State::MV => "MV",
+            State::NI => "NI",
+            State::NW => "NW",
+            State::RP => "RP",
+            State::SL => "SL",
+            State::SN => "SN",
+            State::ST => "ST",
+            State::SH => "SH",
+            State::TH => "TH",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            State::BW => "Baden-Württemberg",
+            State::BY => "Bayern",
+            State::BE => "Berlin",
+            State::BB => "Brandenburg",
+            State::HB => "Bremen",
+            State::HH => "Hamburg",
+            State::HE => "Hessen",
+            State::MV => "Mecklenburg-Vorpommern",
+            State::NI => "Niedersachsen",
+            State::NW => "Nordrhein-Westfalen",
+            State::RP => "Rheinland-Pfalz",
+            State::SL => "Saarland",
+            State::SN => "Sachsen",
+            State::ST => "Sachsen-Anhalt",
+            State::SH => "Schleswig-Holstein",
+            State::TH => "Thüringen",
+        }
+    }
+}
+
+/// Where the user works within the state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionalOptions {
+    /// City of Augsburg (Augsburger Friedensfest)
+    #[serde(default)]
+    pub augsburg: bool,
+    /// Predominantly Catholic municipality; `None` uses the state's
+    /// default (Bavaria: yes, Saxony/Thuringia: no)
+    #[serde(default)]
+    pub catholic_community: Option<bool>,
+    /// Include Heiligabend and Silvester as half days
+    #[serde(default)]
+    pub half_days: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+    /// Nationwide holiday (`federal = 1` in the server's `holidays` table)
+    pub federal: bool,
+    pub half_day: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HolidayError {
+    UnsupportedYear(i32),
+}
+
+impl fmt::Display for HolidayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HolidayError::UnsupportedYear(year) => write!(
+                f,
+                "Feiertage können nur für {}–{} berechnet werden ({})",
+                FIRST_YEAR, LAST_YEAR, year
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HolidayError {}
+
+/// Easter Sunday (Gregorian), Gauss's formula with the corrections by
+/// Lichtenberg for April 25/26
+pub fn easter_sunday(year: i32) -> NaiveDate {
+    let k = year / 100;
+    let m = 15 + (3 * k + 3) / 4 - (8 * k + 13) / 25;
+    let s = 2 - (3 * k + 3) / 4;
+    let a = year % 19;
+    let d = (19 * a + m) % 30;
+    let r = (d + a / 11) / 29;
+    let full_moon = 21 + d - r;
+    let first_sunday = 7 - (year + year / 4 + s) % 7;
+    let offset = 7 - (full_moon - first_sunday) % 7;
+    // Day of March, may run into April
+    let day = full_moon + offset;
+    NaiveDate::from_ymd_opt(year, 3, 1).unwrap() + Duration::days(i64::from(day - 1))
+}
+
+/// Buß- und Bettag: the Wednesday before November 23
+fn repentance_day(year: i32) -> NaiveDate {
+    let nov22 = NaiveDate::from_ymd_opt(year, 11, 22).unwrap();
+    let back =
+        (nov22.weekday().num_days_from_monday() + 7 - Weekday::Wed.num_days_from_monday()) % 7;
+    nov22 - Duration::days(i64::from(back))
+}
+
+/// All holidays of `year` in `state`, sorted by date
+pub fn holidays(
+    state: State,
+    year: i32,
+    options: &RegionalOptions,
+) -> Result<Vec<Holiday>, HolidayError> {
+    use State::*;
+
+    if !(FIRST_YEAR..=LAST_YEAR).contains(&year) {
+        return Err(HolidayError::UnsupportedYear(year));
+    }
+    let easter = easter_sunday(year);
+    let fixed = |month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    let moveable = |days| easter + Duration::days(days);
+    let is = |states: &[State]| states.contains(&state);
+    let catholic = |default| options.catholic_community.unwrap_or(default);
+
+    let mut list = Vec::new();
+    let mut add = |date, name: &str, federal, half_day| {
+        list.push(Holiday {
+            date,
+            name: name.to_string(),
+            federal,
+            half_day,
+        })
+    };
+
+    add(fixed(1, 1), "Neujahr", true, false);
+    if is(&[BW, BY, ST]) {
+        add(fixed(1, 6), "Heilige Drei Könige", false, false);
+    }
+    if (state == BE && year >= 2019) || (state == MV && year >= 2023) {
+        add(fixed(3, 8), "Internationaler Frauentag", false, false);
+    }
+    add(moveable(-2), "Karfreitag", true, false);
+    if state == BB {
+        add(easter, "Ostersonntag", false, false);
+    }
+    add(moveable(1), "Ostermontag", true, false);
+    add(fixed(5, 1), "Tag der Arbeit", true, false);
+    if state == BE && (year == 2020 || year == 2025) {
+        add(fixed(5, 8), "Tag der Befreiung", false, false);
+    }
+    add(moveable(39), "Christi Himmelfahrt", true, false);
+    if state == BB {
+        add(moveable(49), "Pfingstsonntag", false, false);
+    }
+    add(moveable(50), "Pfingstmontag", true, false);
+    if is(&[BW, BY, HE, NW, RP, SL]) || (is(&[SN, TH]) && catholic(false)) {
+        add(moveable(60), "Fronleichnam", false, false);
+    }
+    if state == BY && options.augsburg {
+        add(fixed(8, 8), "Augsburger Friedensfest", false, false);
+    }
+    if state == SL || (state == BY && catholic(true)) {
+        add(fixed(8, 15), "Mariä Himmelfahrt", false, false);
+    }
+    if state == TH && year >= 2019 {
+        add(fixed(9, 20), "Weltkindertag", false, false);
+    }
+    add(fixed(10, 3), "Tag der Deutschen Einheit", true, false);
+    if year == 2017 {
+        add(fixed(10, 31), "Reformationstag", true, false);
+    } else if is(&[BB, MV, SN, ST, TH]) || (is(&[HB, HH, NI, SH]) && year >= 2018) {
+        add(fixed(10, 31), "Reformationstag", false, false);
+    }
+    if is(&[BW, BY, NW, RP, SL]) {
+        add(fixed(11, 1), "Allerheiligen", false, false);
+    }
+    if state == SN {
+        add(repentance_day(year), "Buß- und Bettag", false, false);
+    }
+    if options.half_days {
+        add(fixed(12, 24), "Heiligabend", false, true);
+    }
+    add(fixed(12, 25), "1. Weihnachtstag", true, false);
+    add(fixed(12, 26), "2. Weihnachtstag", true, false);
+    if options.half_days {
+        add(fixed(12, 31), "Silvester", false, true);
+    }
+
+    list.sort_by_key(|h| h.date);
+    Ok(list)
+}
+
+#[tauri::command]
+pub fn holidays_for_year(
+    state: State,
+    year: i32,
+    options: Option<RegionalOptions>,
+) -> Result<Vec<Holiday>, String> {
+    holidays(state, year, &options.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn names(state: State, year: i32, options: RegionalOptions) -> Vec<String> {
+        holidays(state, year, &options)
+            .unwrap()
+            .into_iter()
+            .map(|h| h.name)
+            .collect()
+    }
+
+    #[test]
+    fn easter_matches_known_dates() {
+        for (year, expected) in [
+            (1991, "1991-03-31"),
+            (2008, "2008-03-23"),
+            (2019, "2019-04-21"),
+            (2025, "2025-04-20"),
+            (2026, "2026-04-05"),
+            (2038, "2038-04-25"),
+            (2049, "2049-04-18"),
+            (2285, "2285-03-22"),
+        ] {
+            assert_eq!(easter_sunday(year), date(expected), "{}", year);
+        }
+    }
+
+    #[test]
+    fn bavaria_matches_the_server_list() {
+        let list = holidays(State::BY, 2026, &RegionalOptions::default()).unwrap();
+        let dates: Vec<_> = list.iter().map(|h| h.date.to_string()).collect();
+        assert_eq!(
+            dates,
+            [
+                "2026-01-01",
+                "2026-01-06",
+                "2026-04-03",
+                "2026-04-06",
+                "2026-05-01",
+                "2026-05-14",
+                "2026-05-25",
+                "2026-06-04",
+                "2026-08-15",
+                "2026-10-03",
+                "2026-11-01",
+                "2026-12-25",
+                "2026-12-26",
+            ]
+        );
+        assert_eq!(list.iter().filter(|h| h.federal).count(), 9);
+
+        let augsburg = RegionalOptions {
+            augsburg: true,
+            ..Default::default()
+        };
+        assert!(names(State::BY, 2026, augsburg).contains(&"Augsburger Friedensfest".into()));
+        let protestant = RegionalOptions {
+            catholic_community: Some(false),
+            ..Default::default()
+        };
+        assert!(!names(State::BY, 2026, protestant).contains(&"Mariä Himmelfahrt".into()));
+    }
+
+    #[test]
+    fn state_rules() {
+        let default = RegionalOptions::default();
+        let count = |state, year| names(state, year, default).len();
+        assert_eq!(count(State::HB, 2017), 10);
+        assert_eq!(count(State::HB, 2018), 10);
+        assert_eq!(count(State::HB, 2016), 9);
+        assert_eq!(count(State::BE, 2018), 9);
+        assert_eq!(count(State::BE, 2025), 11);
+        assert_eq!(count(State::MV, 2026), 11);
+        assert_eq!(count(State::BB, 2026), 12);
+        assert_eq!(count(State::SL, 2026), 12);
+        assert_eq!(count(State::TH, 2026), 11);
+        for state in State::ALL {
+            assert!(count(state, 2026) >= 10, "{}", state.name());
+        }
+
+        let saxony = holidays(State::SN, 2025, &default).unwrap();
+        assert!(saxony
+            .iter()
+            .any(|h| h.name == "Buß- und Bettag" && h.date == date("2025-11-19")));
+        assert!(!saxony.iter().any(|h| h.name == "Fronleichnam"));
+        let sorbian = RegionalOptions {
+            catholic_community: Some(true),
+            ..default
+        };
+        assert!(names(State::SN, 2025, sorbian).contains(&"Fronleichnam".into()));
+        assert_eq!(repentance_day(2026), date("2026-11-18"));
+        assert_eq!(repentance_day(2028), date("2028-11-22"));
+    }
+
+    #[test]
+    fn half_days_are_opt_in() {
+        let options = RegionalOptions {
+            half_days: true,
+            ..Default::default()
+        };
+        let list = holidays(State::NW, 2026, &options).unwrap();
+        let half: Vec<_> = list.iter().filter(|h| h.half_day).map(|h| h.date).collect();
+        assert_eq!(half, vec![date("2026-12-24"), date("2026-12-31")]);
+        assert_eq!(list.last().unwrap().name, "Silvester");
+
+        assert_eq!(
+            holidays(State::NW, 1990, &options),
+            Err(HolidayError::UnsupportedYear(1990))
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Single instance and launch commands.
+//!
+//! Starting the app while it is already running does not open a second
+//! process (with its own tray icon and WebSocket connection): the
+//! single-instance plugin hands the arguments to the running instance,
+//! which focuses the main window and runs the commands they name. The same
+//! commands are accepted as CLI flags and as `timetracker://` deep links,
+//! on the first start as well. Any web page can open a deep link, so timer
+//! actions from a link only run after the user confirmed them.
+//!
+//! | Flag              | Deep link                     |
+//! |-------------------|-------------------------------|
+//! | `--start-timer`   | `timetracker://timer/start`   |
+//! | `--stop-timer`    | `timetracker://timer/stop`    |
+//! | `--start-break`   | `timetracker://break/start`   |
+//! | `--end-break`     | `timetracker://break/end`     |
+//! | `--quick-entry`   | `timetracker://quick-entry`   |
+//! | `--new-absence`   | `timetracker://absences/new`  |
+
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
+use url::Url;
+
+use crate::quick_entry;
+use crate::tracking::{self, TimerAction};
+use crate::tray;
+
+/// URL scheme registered in `tauri.conf.json`
+pub const SCHEME: &str = "timetracker";
+
+/// Emitted when a launch command wants the main window to open a view;
+/// the frontend fetches it with `take_launch_view`
+pub const EVENT_LAUNCH_VIEW: &str = "launch:view";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchCommand {
+    Timer(TimerAction),
+    QuickEntry,
+    Open(LaunchView),
+}
+
+/// Where a command came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchOrigin {
+    /// CLI flag, typed or set up by the user
+    Flag,
+    /// `timetracker://` link, possibly opened by a web page
+    DeepLink,
+}
+
+/// View of the main window a launch command opens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LaunchView {
+    AbsenceRequest,
+}
+
+/// View requested but not yet taken by the frontend
+#[derive(Default)]
+pub struct LaunchState(Mutex<Option<LaunchView>>);
+
+/// Command named by a CLI flag or deep link; `None` for anything else
+pub fn parse_arg(arg: &str) -> Option<LaunchCommand> {
+    let name = match arg.strip_prefix("--") {
+        Some(flag) => flag.to_string(),
+        None => deep_link_path(arg)?,
+    };
+    match name.as_str() {
+        "start-timer" | "timer/start" => Some(LaunchCommand::Timer(TimerAction::ClockIn)),
+        "stop-timer" | "timer/stop" => Some(LaunchCommand::Timer(TimerAction::ClockOut)),
+        "start-break" | "break/start" => Some(LaunchCommand::Timer(TimerAction::StartBreak)),
+        "end-break" | "break/end" => Some(LaunchCommand::Timer(TimerAction::EndBreak)),
+        "quick-entry" => Some(LaunchCommand::QuickEntry),
+        "new-absence" | "absences/new" => Some(LaunchCommand::Open(LaunchView::AbsenceRequest)),
+        _ => None,
+    }
+}
+
+/// "timer/start" for `timetracker://timer/start/`
+fn deep_link_path(arg: &str) -> Option<String> {
+    let url = Url::parse(arg).ok()?;
+    if url.scheme() != SCHEME {
+        return None;
+    }
+    let path = format!("{}{}", url.host_str().unwrap_or(""), url.path());
+    Some(path.trim_matches('/').to_string())
+}
+
+fn origin(arg: &str) -> LaunchOrigin {
+    if arg.starts_with("--") {
+        LaunchOrigin::Flag
+    } else {
+        LaunchOrigin::DeepLink
+    }
+}
+
+/// Commands in `args`; the first argument is the executable
+pub fn parse_args(args: &[String]) -> Vec<(LaunchCommand, LaunchOrigin)> {
+    args.iter()
+        .skip(1)
+        .filter_map(|arg| {
+            let command = parse_arg(arg);
+            if command.is_none() {
+                log::warn!("Ignoring unknown launch argument: {}", arg);
+            }
+            Some((command?, origin(arg)))
+        })
+        .collect()
+}
+
+/// Arguments of a second launch, forwarded by the single-instance plugin.
+/// Deep links among them arrive through `on_open_url` as well (the
+/// plugin's `deep-link` feature), so only the flags are run here.
+pub fn handle_second_instance(app: &AppHandle, args: Vec<String>, _cwd: String) {
+    tray::show_main_window(app);
+    let flags: Vec<_> = parse_args(&args)
+        .into_iter()
+        .filter(|(_, origin)| *origin == LaunchOrigin::Flag)
+        .collect();
+    run(app, &flags);
+}
+
+/// Runs the commands of the first launch and listens for deep links
+pub fn setup(app: &AppHandle) {
+    app.manage(LaunchState::default());
+
+    // Installers register the scheme; dev builds and AppImages do not
+    #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+    if let Err(error) = app.deep_link().register_all() {
+        log::warn!("Failed to register deep links: {}", error);
+    }
+
+    // Links opened while running; on Linux and Windows the single-instance
+    // plugin hands them over from the second launch
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        let commands: Vec<_> = event
+            .urls()
+            .iter()
+            .filter_map(|url| parse_arg(url.as_str()))
+            .map(|command| (command, LaunchOrigin::DeepLink))
+            .collect();
+        tray::show_main_window(&handle);
+        run(&handle, &commands);
+    });
+
+    let args: Vec<String> = std::env::args().collect();
+    run(app, &parse_args(&args));
+}
+
+/// Tray label of a timer action
+fn action_label(action: TimerAction) -> &'static str {
+    match action {
+        TimerAction::ClockIn => "Kommen",
+        TimerAction::ClockOut => "Gehen",
+        TimerAction::StartBreak => "Pause starten",
+        TimerAction::EndBreak => "Pause beenden",
+    }
+}
+
+fn perform_timer_action(app: &AppHandle, action: TimerAction) {
+    if let Err(error) = tracking::perform(app, action) {
+        log::warn!("Launch action {:?} failed: {}", action, error);
+    }
+    if action == TimerAction::ClockOut {
+        // Show the draft in the main window for confirmation
+        tray::show_main_window(app);
+    }
+}
+
+/// Asks before running a timer action requested by a link
+fn confirm_timer_action(app: &AppHandle, action: TimerAction) {
+    let handle = app.clone();
+    app.dialog()
+        .message(format!(
+            "Ein Link möchte die Zeiterfassung steuern: „{}“.\n\nAusführen?",
+            action_label(action)
+        ))
+        .title("Zeiterfassung")
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            action_label(action).to_string(),
+            "Abbrechen".to_string(),
+        ))
+        .show(move |confirmed| {
+            if confirmed {
+                perform_timer_action(&handle, action);
+            }
+        });
+}
+
+fn run(app: &AppHandle, commands: &[(LaunchCommand, LaunchOrigin)]) {
+    for &(command, origin) in commands {
+        match command {
+            LaunchCommand::Timer(action) => match origin {
+                LaunchOrigin::Flag => perform_timer_action(app, action),
+                LaunchOrigin::DeepLink => confirm_timer_action(app, action),
+            },
+            LaunchCommand::QuickEntry => {
+                if let Err(error) = quick_entry::open(app) {
+                    log::warn!("Failed to open quick entry: {}", error);
+                }
+            }
+            LaunchCommand::Open(view) => {
+                tray::show_main_window(app);
+                if let Ok(mut pending) = app.state::<LaunchState>().0.lock() {
+                    *pending = Some(view);
+                }
+                let _ = app.emit(EVENT_LAUNCH_VIEW, view);
+            }
+        }
+    }
+}
+
+/// Hands the requested view to the frontend and clears it
+#[tauri::command]
+pub fn take_launch_view(state: State<'_, LaunchState>) -> Result<Option<LaunchView>, String> {
+    let mut pending = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(pending.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_and_deep_links_name_the_same_commands() {
+        let clock_in = Some(LaunchCommand::Timer(TimerAction::ClockIn));
+        assert_eq!(parse_arg("--start-timer"), clock_in);
+        assert_eq!(parse_arg("timetracker://timer/start"), clock_in);
+        assert_eq!(parse_arg("timetracker://timer/start/"), clock_in);
+
+        let absence = Some(LaunchCommand::Open(LaunchView::AbsenceRequest));
+        assert_eq!(parse_arg("--new-absence"), absence);
+        assert_eq!(parse_arg("timetracker://absences/new"), absence);
+
+        assert_eq!(
+            parse_arg("timetracker://quick-entry"),
+            Some(LaunchCommand::QuickEntry)
+        );
+    }
+
+    #[test]
+    fn deep_links_are_marked_for_confirmation() {
+        let args = [
+            "/usr/bin/timetracker".to_string(),
+            "timetracker://timer/stop".to_string(),
+            "--stop-timer".to_string(),
+        ];
+        let clock_out = LaunchCommand::Timer(TimerAction::ClockOut);
+        assert_eq!(
+            parse_args(&args),
+            vec![
+                (clock_out, LaunchOrigin::DeepLink),
+                (clock_out, LaunchOrigin::Flag)
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_arguments_are_ignored() {
+        assert_eq!(parse_arg("--verbose"), None);
+        assert_eq!(parse_arg("https://timer/start"), None);
+        assert_eq!(parse_arg("timetracker://timer/pause"), None);
+        assert_eq!(parse_arg("start-timer"), None);
+
+        let args = [
+            "/usr/bin/timetracker".to_string(),
+            "--start-timer".to_string(),
+            "-psn_0_12345".to_string(),
+        ];
+        assert_eq!(
+            parse_args(&args),
+            vec![(
+                LaunchCommand::Timer(TimerAction::ClockIn),
+                LaunchOrigin::Flag
+            )]
+        );
+    }
+}
@@ -1,8 +1,33 @@
-use tauri::{
-    menu::{Menu, MenuItem},
-    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager,
-};
+mod api_client;
+mod api_error;
+mod arbzg;
+mod auth;
+mod balances;
+mod calendar;
+mod datev;
+mod holidays;
+mod launch;
+mod offline_store;
+mod overtime;
+mod quick_entry;
+mod realtime;
+mod reminders;
+mod rollover;
+mod secure_store;
+mod server_events;
+mod server_profile;
+mod session_store;
+mod shortcuts;
+mod sync;
+mod time_import;
+mod timer;
+mod timesheet;
+mod tracking;
+mod tray;
+mod tray_status;
+mod vacation;
+mod work_schedule;
+mod xlsx_report;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -13,6 +38,20 @@ fn greet(name: &str) -> String {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must be registered first, so a second launch exits right away
+        .plugin(tauri_plugin_single_instance::init(
+            launch::handle_second_instance,
+        ))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(
+            tauri_plugin_log::Builder::new()
+                .level(if cfg!(debug_assertions) {
+                    log::LevelFilter::Debug
+                } else {
+                    log::LevelFilter::Info
+                })
+                .build(),
+        )
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_notification::init())
@@ -20,63 +59,98 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(shortcuts::handle)
+                .build(),
+        )
+        .manage(tracking::TrackingState::default())
         .setup(|app| {
-            // System Tray Menü erstellen
-            let show_item = MenuItem::with_id(app, "show", "Anzeigen", true, None::<&str>)?;
-            let hide_item = MenuItem::with_id(app, "hide", "Verstecken", true, None::<&str>)?;
-            let quit_item = MenuItem::with_id(app, "quit", "Beenden", true, None::<&str>)?;
-
-            let menu = Menu::with_items(
-                app,
-                &[&show_item, &hide_item, &quit_item],
-            )?;
-
-            // System Tray Icon erstellen
-            // Load icon from embedded resources
-            let icon = app.default_window_icon().cloned().unwrap();
-
-            let _tray = TrayIconBuilder::new()
-                .icon(icon)
-                .menu(&menu)
-                .show_menu_on_left_click(false)
-                .on_menu_event(|app, event| match event.id.as_ref() {
-                    "show" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                    "hide" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.hide();
-                        }
-                    }
-                    "quit" => {
-                        app.exit(0);
-                    }
-                    _ => {}
-                })
-                .on_tray_icon_event(|tray, event| {
-                    match event {
-                        TrayIconEvent::Click {
-                            button: MouseButton::Left,
-                            button_state: MouseButtonState::Up,
-                            ..
-                        } => {
-                            // Bei Linksklick: Fenster anzeigen/fokussieren
-                            if let Some(window) = tray.app_handle().get_webview_window("main") {
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                            }
-                        }
-                        _ => {}
-                    }
-                })
-                .build(app)?;
-
+            api_client::setup(app.handle());
+            auth::setup(app.handle());
+            sync::setup(app.handle());
+            realtime::setup(app.handle());
+            calendar::setup(app.handle());
+            tray::create(app)?;
+            balances::setup(app.handle());
+            shortcuts::setup(app.handle());
+            tracking::restore(app.handle());
+            tray::refresh(app.handle());
+            launch::setup(app.handle());
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![greet])
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            arbzg::validate_arbzg,
+            auth::auth_login,
+            auth::auth_logout,
+            auth::auth_session,
+            auth::auth_import_token,
+            holidays::holidays_for_year,
+            launch::take_launch_view,
+            calendar::calendar_settings,
+            calendar::save_calendar_settings,
+            calendar::calendar_feed_status,
+            calendar::export_calendar_ics,
+            overtime::calculate_overtime,
+            work_schedule::validate_work_schedule,
+            work_schedule::resolve_daily_targets,
+            vacation::project_vacation,
+            api_client::api_request,
+            api_client::session_fetch,
+            api_client::server_profiles,
+            api_client::save_server_profile,
+            api_client::delete_server_profile,
+            api_client::activate_server_profile,
+            realtime::realtime_status,
+            rollover::load_rollover_snapshot,
+            rollover::simulate_rollover,
+            rollover::save_rollover_summary,
+            rollover::verify_rollover_summary,
+            timesheet::export_timesheet_pdf,
+            time_import::preview_time_import,
+            time_import::validate_time_import,
+            xlsx_report::export_report_xlsx,
+            datev::datev_settings,
+            datev::save_datev_settings,
+            datev::export_datev_lodas,
+            tracking::timer_status,
+            tracking::timer_clock_in,
+            tracking::timer_clock_out,
+            tracking::timer_clock_out_at,
+            tracking::timer_set_details,
+            tracking::timer_check_arbzg,
+            tracking::timer_start_break,
+            tracking::timer_end_break,
+            tracking::time_entry_drafts,
+            tracking::resolve_time_entry_draft,
+            quick_entry::open_quick_entry,
+            quick_entry::close_quick_entry,
+            quick_entry::quick_entry_prefill,
+            quick_entry::quick_entry_submit,
+            shortcuts::shortcut_settings,
+            shortcuts::shortcut_status,
+            shortcuts::save_shortcut_settings,
+            sync::sync_now,
+            sync::outbox_list,
+            sync::outbox_retry,
+            sync::outbox_discard,
+            sync::list_conflicts,
+            sync::resolve_conflict,
+            sync::offline_cache_time_entries,
+            sync::offline_list_time_entries,
+            sync::offline_create_time_entry,
+            sync::offline_import_time_entries,
+            sync::offline_update_time_entry,
+            sync::offline_delete_time_entry,
+            sync::offline_cache_absence_requests,
+            sync::offline_list_absence_requests,
+            sync::offline_create_absence_request,
+            sync::offline_update_absence_request,
+            sync::offline_delete_absence_request,
+            sync::offline_overtime_ledger,
+            sync::offline_vacation_projection
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,1930 @@
+//! Offline-first local store for time entries and absence requests.
+//!
+//! An embedded SQLite database (`offline.db` in the app data directory)
+//! mirrors the `time_entries` and `absence_requests` tables of
+//! `server/src/database/schema.ts`. Changes made while the server is
+//! unreachable are applied locally right away and queued in the `outbox`
+//! table, which `sync.rs` replays in order once the connection is back.
+//!
+//! Entries that only exist locally carry negative ids. When their create
+//! operation is replayed, the row and all later outbox items are rewritten
+//! to the id assigned by the server.
+//!
+//! Updates and deletes of server records remember the version they were
+//! based on (`baseVersion`). If the server copy changed in the meantime,
+//! the entry overlaps another one or the server rejects the change, the
+//! item is parked and a row in `sync_conflicts` keeps both versions until
+//! the user decides how to resolve it.
+
+use std::{collections::HashSet, fmt, path::Path};
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::arbzg;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS time_entries (
+      id INTEGER PRIMARY KEY,
+      userId INTEGER NOT NULL,
+      date TEXT NOT NULL,
+      startTime TEXT NOT NULL,
+      endTime TEXT NOT NULL,
+      breakMinutes INTEGER DEFAULT 0,
+      hours REAL NOT NULL,
+      activity TEXT,
+      project TEXT,
+      location TEXT NOT NULL CHECK(location IN ('office', 'homeoffice', 'field')),
+      notes TEXT,
+      createdAt TEXT DEFAULT (datetime('now')),
+      updatedAt TEXT,
+      pendingSync INTEGER NOT NULL DEFAULT 0
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_time_entries_user_date ON time_entries(userId, date);
+
+    CREATE TABLE IF NOT EXISTS absence_requests (
+      id INTEGER PRIMARY KEY,
+      userId INTEGER NOT NULL,
+      type TEXT NOT NULL CHECK(type IN ('vacation', 'sick', 'unpaid', 'overtime_comp')),
+      startDate TEXT NOT NULL,
+      endDate TEXT NOT NULL,
+      days REAL NOT NULL,
+      status TEXT DEFAULT 'pending' CHECK(status IN ('pending', 'approved', 'rejected')),
+      reason TEXT,
+      adminNote TEXT,
+      approvedBy INTEGER,
+      approvedAt TEXT,
+      createdAt TEXT DEFAULT (datetime('now')),
+      pendingSync INTEGER NOT NULL DEFAULT 0
+    );
+
+    CREATE TABLE IF NOT EXISTS outbox (
+      seq INTEGER PRIMARY KEY AUTOINCREMENT,
+      entity TEXT NOT NULL CHECK(entity IN ('time_entry', 'absence_request')),
+      operation TEXT NOT NULL CHECK(operation IN ('create', 'update', 'delete')),
+      targetId INTEGER NOT NULL,
+      payload TEXT NOT NULL,
+      status TEXT NOT NULL DEFAULT 'pending' CHECK(status IN ('pending', 'failed')),
+      attempts INTEGER NOT NULL DEFAULT 0,
+      lastError TEXT,
+      createdAt TEXT NOT NULL DEFAULT (datetime('now')),
+      baseVersion TEXT,
+      forced INTEGER NOT NULL DEFAULT 0
+    );
+
+    CREATE TABLE IF NOT EXISTS sync_conflicts (
+      id INTEGER PRIMARY KEY AUTOINCREMENT,
+      entity TEXT NOT NULL CHECK(entity IN ('time_entry', 'absence_request')),
+      recordId INTEGER NOT NULL,
+      kind TEXT NOT NULL CHECK(kind IN ('modified', 'deleted', 'overlap', 'rejected')),
+      localVersion TEXT,
+      serverVersion TEXT,
+      conflictingEntry TEXT,
+      outboxSeq INTEGER NOT NULL,
+      message TEXT NOT NULL,
+      createdAt TEXT NOT NULL DEFAULT (datetime('now'))
+    );
+";
+
+/// Fields compared when the server copy has no `updatedAt`
+const TIME_ENTRY_FINGERPRINT: &[&str] = &[
+    "date",
+    "startTime",
+    "endTime",
+    "breakMinutes",
+    "activity",
+    "project",
+    "location",
+    "notes",
+];
+const ABSENCE_FINGERPRINT: &[&str] = &[
+    "type",
+    "startDate",
+    "endDate",
+    "days",
+    "status",
+    "adminNote",
+];
+
+#[derive(Debug)]
+pub enum StoreError {
+    Sqlite(rusqlite::Error),
+    Json(serde_json::Error),
+    NotFound(&'static str, i64),
+    Invalid(String),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Sqlite(e) => write!(f, "Lokale Datenbank: {}", e),
+            StoreError::Json(e) => write!(f, "Ungültige Daten: {}", e),
+            StoreError::NotFound(what, id) => write!(f, "{} {} nicht gefunden", what, id),
+            StoreError::Invalid(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(e: rusqlite::Error) -> Self {
+        StoreError::Sqlite(e)
+    }
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(e: serde_json::Error) -> Self {
+        StoreError::Json(e)
+    }
+}
+
+pub type StoreResult<T> = Result<T, StoreError>;
+
+/// Row of `time_entries` (same shape as the server's `TimeEntry`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntryRecord {
+    pub id: i64,
+    pub user_id: i64,
+    pub date: String,
+    pub start_time: String,
+    pub end_time: String,
+    #[serde(default)]
+    pub break_minutes: i64,
+    pub hours: f64,
+    pub activity: Option<String>,
+    pub project: Option<String>,
+    pub location: String,
+    pub notes: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    /// Local change not yet accepted by the server
+    #[serde(default)]
+    pub pending_sync: bool,
+}
+
+/// Row of `absence_requests` (same shape as the server's `AbsenceRequest`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbsenceRequestRecord {
+    pub id: i64,
+    pub user_id: i64,
+    #[serde(rename = "type")]
+    pub absence_type: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub days: f64,
+    pub status: String,
+    pub reason: Option<String>,
+    pub admin_note: Option<String>,
+    pub approved_by: Option<i64>,
+    pub approved_at: Option<String>,
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub pending_sync: bool,
+}
+
+/// Body of `POST /api/time-entries`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntryInput {
+    pub user_id: i64,
+    pub date: String,
+    pub start_time: String,
+    pub end_time: String,
+    #[serde(default)]
+    pub break_minutes: i64,
+    pub activity: Option<String>,
+    pub project: Option<String>,
+    pub location: String,
+    pub notes: Option<String>,
+}
+
+/// Body of `POST /api/absences`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbsenceRequestInput {
+    pub user_id: i64,
+    #[serde(rename = "type")]
+    pub absence_type: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxEntity {
+    TimeEntry,
+    AbsenceRequest,
+}
+
+impl OutboxEntity {
+    fn as_str(self) -> &'static str {
+        match self {
+            OutboxEntity::TimeEntry => "time_entry",
+            OutboxEntity::AbsenceRequest => "absence_request",
+        }
+    }
+
+    fn table(self) -> &'static str {
+        match self {
+            OutboxEntity::TimeEntry => "time_entries",
+            OutboxEntity::AbsenceRequest => "absence_requests",
+        }
+    }
+
+    /// REST collection on the server
+    pub fn endpoint(self) -> &'static str {
+        match self {
+            OutboxEntity::TimeEntry => "/time-entries",
+            OutboxEntity::AbsenceRequest => "/absences",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "time_entry" => Some(OutboxEntity::TimeEntry),
+            "absence_request" => Some(OutboxEntity::AbsenceRequest),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutboxOperation {
+    Create,
+    Update,
+    Delete,
+}
+
+impl OutboxOperation {
+    fn as_str(self) -> &'static str {
+        match self {
+            OutboxOperation::Create => "create",
+            OutboxOperation::Update => "update",
+            OutboxOperation::Delete => "delete",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "create" => Some(OutboxOperation::Create),
+            "update" => Some(OutboxOperation::Update),
+            "delete" => Some(OutboxOperation::Delete),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutboxStatus {
+    Pending,
+    Failed,
+}
+
+/// A queued change waiting to be replayed against the server
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxItem {
+    pub seq: i64,
+    pub entity: OutboxEntity,
+    pub operation: OutboxOperation,
+    pub target_id: i64,
+    pub payload: Value,
+    pub status: OutboxStatus,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    /// Server version the local change was based on (updates and deletes)
+    pub base_version: Option<Value>,
+    /// Set once the user resolved a conflict; replayed without checks
+    pub forced: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictKind {
+    /// Changed on the server since the local edit
+    Modified,
+    /// Deleted on the server while edited locally
+    Deleted,
+    /// Overlaps another entry on the same date
+    Overlap,
+    /// Rejected by the server for another reason
+    Rejected,
+}
+
+impl ConflictKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ConflictKind::Modified => "modified",
+            ConflictKind::Deleted => "deleted",
+            ConflictKind::Overlap => "overlap",
+            ConflictKind::Rejected => "rejected",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "modified" => Some(ConflictKind::Modified),
+            "deleted" => Some(ConflictKind::Deleted),
+            "overlap" => Some(ConflictKind::Overlap),
+            "rejected" => Some(ConflictKind::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// A parked outbox item together with both versions of the record
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    pub id: i64,
+    pub entity: OutboxEntity,
+    pub record_id: i64,
+    pub kind: ConflictKind,
+    /// Local row (`None` if deleted locally)
+    pub local_version: Option<Value>,
+    /// Current server copy (`None` if deleted there or unknown)
+    pub server_version: Option<Value>,
+    /// The entry the local change overlaps with
+    pub conflicting_entry: Option<Value>,
+    pub outbox_seq: i64,
+    pub message: String,
+    pub created_at: String,
+}
+
+/// What the sync layer found out about a queued change
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedConflict {
+    pub kind: ConflictKind,
+    pub server_version: Option<Value>,
+    pub conflicting_entry: Option<Value>,
+    pub message: String,
+}
+
+/// How the user resolves a conflict
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "strategy", rename_all = "kebab-case")]
+pub enum Resolution {
+    /// Send the local version anyway
+    KeepMine,
+    /// Drop the local change and take the server copy
+    KeepServer,
+    /// Apply `fields` on top of the server copy and send the result
+    Merge { fields: Value },
+}
+
+pub struct OfflineStore {
+    conn: Connection,
+}
+
+impl OfflineStore {
+    pub fn open(path: &Path) -> StoreResult<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| StoreError::Invalid(e.to_string()))?;
+        }
+        Self::init(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> StoreResult<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> StoreResult<Self> {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.execute_batch(SCHEMA)?;
+        // Databases created before conflict detection
+        ensure_column(&conn, "outbox", "baseVersion", "TEXT")?;
+        ensure_column(&conn, "outbox", "forced", "INTEGER NOT NULL DEFAULT 0")?;
+        Ok(Self { conn })
+    }
+
+    // ========================================
+    // Time entries
+    // ========================================
+
+    /// Mirrors entries fetched from the server. Rows with unsynced local
+    /// changes are left untouched.
+    pub fn cache_time_entries(&mut self, records: &[TimeEntryRecord]) -> StoreResult<()> {
+        let tx = self.conn.transaction()?;
+        for r in records {
+            upsert_time_entry(&tx, r, false)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Replaces the synced entries of `user_id` from `from` to `to` with a
+    /// fresh copy from the server, so entries deleted there disappear.
+    /// Records with unsynced changes or queued items are kept as they are.
+    pub fn replace_time_entries(
+        &mut self,
+        user_id: i64,
+        from: &str,
+        to: &str,
+        records: &[TimeEntryRecord],
+    ) -> StoreResult<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "DELETE FROM time_entries
+             WHERE userId = ?1 AND date >= ?2 AND date <= ?3 AND pendingSync = 0
+               AND id NOT IN (SELECT targetId FROM outbox WHERE entity = 'time_entry')",
+            params![user_id, from, to],
+        )?;
+        let queued = queued_ids(&tx, OutboxEntity::TimeEntry)?;
+        for r in records.iter().filter(|r| !queued.contains(&r.id)) {
+            upsert_time_entry(&tx, r, false)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn time_entry(&self, id: i64) -> StoreResult<Option<TimeEntryRecord>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT * FROM time_entries WHERE id = ?1",
+                params![id],
+                time_entry_from_row,
+            )
+            .optional()?)
+    }
+
+    pub fn list_time_entries(
+        &self,
+        user_id: i64,
+        from: &str,
+        to: &str,
+    ) -> StoreResult<Vec<TimeEntryRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT * FROM time_entries
+             WHERE userId = ?1 AND date >= ?2 AND date <= ?3
+             ORDER BY date DESC, startTime DESC",
+        )?;
+        let rows = stmt.query_map(params![user_id, from, to], time_entry_from_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Creates the entry locally (negative id) and queues the POST
+    pub fn create_time_entry(&mut self, input: &TimeEntryInput) -> StoreResult<TimeEntryRecord> {
+        let tx = self.conn.transaction()?;
+        let id = insert_time_entry(&tx, input)?;
+        tx.commit()?;
+
+        self.time_entry(id)?
+            .ok_or(StoreError::NotFound("Zeiteintrag", id))
+    }
+
+    /// Creates all entries or none (bulk import); returns their local ids
+    pub fn create_time_entries(&mut self, inputs: &[TimeEntryInput]) -> StoreResult<Vec<i64>> {
+        let tx = self.conn.transaction()?;
+        let ids = inputs
+            .iter()
+            .map(|input| insert_time_entry(&tx, input))
+            .collect::<StoreResult<Vec<_>>>()?;
+        tx.commit()?;
+        Ok(ids)
+    }
+
+    /// Applies a partial update (camelCase fields of `TimeEntryInput`)
+    /// locally and queues the PUT
+    pub fn update_time_entry(&mut self, id: i64, patch: &Value) -> StoreResult<TimeEntryRecord> {
+        let current = self
+            .time_entry(id)?
+            .ok_or(StoreError::NotFound("Zeiteintrag", id))?;
+
+        let before = serde_json::to_value(&current)?;
+        let mut merged = before.clone();
+        merge_object(&mut merged, patch)?;
+        let updated: TimeEntryRecord = serde_json::from_value(merged)?;
+        validate_location(&updated.location)?;
+        let hours = arbzg::calculate_hours(
+            &updated.start_time,
+            &updated.end_time,
+            updated.break_minutes,
+        )
+        .map_err(|e| StoreError::Invalid(e.to_string()))?;
+
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "UPDATE time_entries SET date = ?2, startTime = ?3, endTime = ?4, breakMinutes = ?5,
+               hours = ?6, activity = ?7, project = ?8, location = ?9, notes = ?10, pendingSync = 1
+             WHERE id = ?1",
+            params![
+                id,
+                updated.date,
+                updated.start_time,
+                updated.end_time,
+                updated.break_minutes,
+                hours,
+                updated.activity,
+                updated.project,
+                updated.location,
+                updated.notes
+            ],
+        )?;
+        queue_update(&tx, OutboxEntity::TimeEntry, id, patch, before)?;
+        tx.commit()?;
+
+        self.time_entry(id)?
+            .ok_or(StoreError::NotFound("Zeiteintrag", id))
+    }
+
+    pub fn delete_time_entry(&mut self, id: i64) -> StoreResult<()> {
+        self.delete_record(OutboxEntity::TimeEntry, id)
+    }
+
+    // ========================================
+    // Absence requests
+    // ========================================
+
+    pub fn cache_absence_requests(&mut self, records: &[AbsenceRequestRecord]) -> StoreResult<()> {
+        let tx = self.conn.transaction()?;
+        for r in records {
+            upsert_absence_request(&tx, r, false)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Replaces the synced requests of `user_id` with a fresh copy from the
+    /// server, like `replace_time_entries`
+    pub fn replace_absence_requests(
+        &mut self,
+        user_id: i64,
+        records: &[AbsenceRequestRecord],
+    ) -> StoreResult<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "DELETE FROM absence_requests
+             WHERE userId = ?1 AND pendingSync = 0
+               AND id NOT IN (SELECT targetId FROM outbox WHERE entity = 'absence_request')",
+            params![user_id],
+        )?;
+        let queued = queued_ids(&tx, OutboxEntity::AbsenceRequest)?;
+        for r in records.iter().filter(|r| !queued.contains(&r.id)) {
+            upsert_absence_request(&tx, r, false)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn absence_request(&self, id: i64) -> StoreResult<Option<AbsenceRequestRecord>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT * FROM absence_requests WHERE id = ?1",
+                params![id],
+                absence_from_row,
+            )
+            .optional()?)
+    }
+
+    pub fn list_absence_requests(&self, user_id: i64) -> StoreResult<Vec<AbsenceRequestRecord>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM absence_requests WHERE userId = ?1 ORDER BY startDate DESC")?;
+        let rows = stmt.query_map(params![user_id], absence_from_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Creates the request locally as `pending` and queues the POST.
+    /// `days` stays 0 until the server has calculated it.
+    pub fn create_absence_request(
+        &mut self,
+        input: &AbsenceRequestInput,
+    ) -> StoreResult<AbsenceRequestRecord> {
+        validate_absence_type(&input.absence_type)?;
+        if input.end_date < input.start_date {
+            return Err(StoreError::Invalid(
+                "Das Enddatum liegt vor dem Startdatum".to_string(),
+            ));
+        }
+
+        let tx = self.conn.transaction()?;
+        let id = next_local_id(&tx, OutboxEntity::AbsenceRequest)?;
+        tx.execute(
+            "INSERT INTO absence_requests (id, userId, type, startDate, endDate, days, status,
+               reason, pendingSync)
+             VALUES (?1, ?2, ?3, ?4, ?5, 0, 'pending', ?6, 1)",
+            params![
+                id,
+                input.user_id,
+                input.absence_type,
+                input.start_date,
+                input.end_date,
+                input.reason
+            ],
+        )?;
+        enqueue(
+            &tx,
+            OutboxEntity::AbsenceRequest,
+            OutboxOperation::Create,
+            id,
+            &serde_json::to_value(input)?,
+            None,
+        )?;
+        tx.commit()?;
+
+        self.absence_request(id)?
+            .ok_or(StoreError::NotFound("Abwesenheitsantrag", id))
+    }
+
+    pub fn update_absence_request(
+        &mut self,
+        id: i64,
+        patch: &Value,
+    ) -> StoreResult<AbsenceRequestRecord> {
+        let current = self
+            .absence_request(id)?
+            .ok_or(StoreError::NotFound("Abwesenheitsantrag", id))?;
+
+        let before = serde_json::to_value(&current)?;
+        let mut merged = before.clone();
+        merge_object(&mut merged, patch)?;
+        let updated: AbsenceRequestRecord = serde_json::from_value(merged)?;
+        validate_absence_type(&updated.absence_type)?;
+
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "UPDATE absence_requests SET type = ?2, startDate = ?3, endDate = ?4, reason = ?5,
+               pendingSync = 1
+             WHERE id = ?1",
+            params![
+                id,
+                updated.absence_type,
+                updated.start_date,
+                updated.end_date,
+                updated.reason
+            ],
+        )?;
+        queue_update(&tx, OutboxEntity::AbsenceRequest, id, patch, before)?;
+        tx.commit()?;
+
+        self.absence_request(id)?
+            .ok_or(StoreError::NotFound("Abwesenheitsantrag", id))
+    }
+
+    pub fn delete_absence_request(&mut self, id: i64) -> StoreResult<()> {
+        self.delete_record(OutboxEntity::AbsenceRequest, id)
+    }
+
+    // ========================================
+    // Outbox
+    // ========================================
+
+    /// All queued items in replay order
+    pub fn outbox(&self) -> StoreResult<Vec<OutboxItem>> {
+        let mut stmt = self.conn.prepare("SELECT * FROM outbox ORDER BY seq")?;
+        let rows = stmt.query_map([], outbox_from_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    pub fn outbox_item(&self, seq: i64) -> StoreResult<Option<OutboxItem>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT * FROM outbox WHERE seq = ?1",
+                params![seq],
+                outbox_from_row,
+            )
+            .optional()?)
+    }
+
+    pub fn pending_count(&self) -> StoreResult<i64> {
+        Ok(self
+            .conn
+            .query_row("SELECT COUNT(*) FROM outbox", [], |row| row.get(0))?)
+    }
+
+    /// Removes a replayed item. For creates, the local row and all later
+    /// outbox items move from the local id to `server_id`; `server_record`
+    /// (the `data` of the server response) replaces the local row.
+    pub fn complete(
+        &mut self,
+        item: &OutboxItem,
+        server_id: Option<i64>,
+        server_record: Option<&Value>,
+    ) -> StoreResult<()> {
+        let table = item.entity.table();
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM outbox WHERE seq = ?1", params![item.seq])?;
+
+        let mut id = item.target_id;
+        if let (OutboxOperation::Create, Some(server_id)) = (item.operation, server_id) {
+            tx.execute(
+                &format!("UPDATE {} SET id = ?2 WHERE id = ?1", table),
+                params![item.target_id, server_id],
+            )?;
+            tx.execute(
+                "UPDATE outbox SET targetId = ?3 WHERE entity = ?1 AND targetId = ?2",
+                params![item.entity.as_str(), item.target_id, server_id],
+            )?;
+            tx.execute(
+                "UPDATE sync_conflicts SET recordId = ?3 WHERE entity = ?1 AND recordId = ?2",
+                params![item.entity.as_str(), item.target_id, server_id],
+            )?;
+            id = server_id;
+        }
+
+        // Later items for the record are now based on what the server stored
+        if let Some(record) = server_record {
+            tx.execute(
+                "UPDATE outbox SET baseVersion = ?3 WHERE entity = ?1 AND targetId = ?2",
+                params![item.entity.as_str(), id, record.to_string()],
+            )?;
+        }
+
+        let still_queued: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM outbox WHERE entity = ?1 AND targetId = ?2)",
+            params![item.entity.as_str(), id],
+            |row| row.get(0),
+        )?;
+        if !still_queued {
+            tx.execute(
+                &format!("UPDATE {} SET pendingSync = 0 WHERE id = ?1", table),
+                params![id],
+            )?;
+        }
+        drop_orphaned_conflicts(&tx)?;
+        tx.commit()?;
+
+        if let (Some(record), false) = (server_record, still_queued) {
+            match item.entity {
+                OutboxEntity::TimeEntry => {
+                    if let Ok(record) = serde_json::from_value::<TimeEntryRecord>(record.clone()) {
+                        self.cache_time_entries(&[record])?;
+                    }
+                }
+                OutboxEntity::AbsenceRequest => {
+                    if let Ok(record) =
+                        serde_json::from_value::<AbsenceRequestRecord>(record.clone())
+                    {
+                        self.cache_absence_requests(&[record])?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Records a failed attempt. `permanent` failures (server rejected the
+    /// change) are parked until the user retries or discards them.
+    pub fn record_failure(&mut self, seq: i64, error: &str, permanent: bool) -> StoreResult<()> {
+        let status = if permanent { "failed" } else { "pending" };
+        self.conn.execute(
+            "UPDATE outbox SET attempts = attempts + 1, lastError = ?2, status = ?3 WHERE seq = ?1",
+            params![seq, error, status],
+        )?;
+        Ok(())
+    }
+
+    pub fn retry(&mut self, seq: i64) -> StoreResult<()> {
+        let changed = self.conn.execute(
+            "UPDATE outbox SET status = 'pending', lastError = NULL WHERE seq = ?1",
+            params![seq],
+        )?;
+        if changed == 0 {
+            return Err(StoreError::NotFound("Warteschlangeneintrag", seq));
+        }
+        Ok(())
+    }
+
+    /// Drops a queued change. A discarded create also removes the local
+    /// row and everything queued for it; otherwise the row stays marked as
+    /// unsynced until the next refresh from the server overwrites it.
+    pub fn discard(&mut self, seq: i64) -> StoreResult<()> {
+        let item = self
+            .outbox_item(seq)?
+            .ok_or(StoreError::NotFound("Warteschlangeneintrag", seq))?;
+
+        let tx = self.conn.transaction()?;
+        if item.operation == OutboxOperation::Create {
+            tx.execute(
+                "DELETE FROM outbox WHERE entity = ?1 AND targetId = ?2",
+                params![item.entity.as_str(), item.target_id],
+            )?;
+            tx.execute(
+                &format!("DELETE FROM {} WHERE id = ?1", item.entity.table()),
+                params![item.target_id],
+            )?;
+        } else {
+            tx.execute("DELETE FROM outbox WHERE seq = ?1", params![seq])?;
+            tx.execute(
+                &format!(
+                    "UPDATE {} SET pendingSync = 0 WHERE id = ?1",
+                    item.entity.table()
+                ),
+                params![item.target_id],
+            )?;
+        }
+        drop_orphaned_conflicts(&tx)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // ========================================
+    // Conflicts
+    // ========================================
+
+    /// Parks the item and stores both versions of the record
+    pub fn record_conflict(
+        &mut self,
+        item: &OutboxItem,
+        detected: &DetectedConflict,
+    ) -> StoreResult<SyncConflict> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "UPDATE outbox SET attempts = attempts + 1, lastError = ?2, status = 'failed'
+             WHERE seq = ?1",
+            params![item.seq, detected.message],
+        )?;
+        tx.execute(
+            "DELETE FROM sync_conflicts WHERE outboxSeq = ?1",
+            params![item.seq],
+        )?;
+        let local = load_record(&tx, item.entity, item.target_id)?;
+        tx.execute(
+            "INSERT INTO sync_conflicts (entity, recordId, kind, localVersion, serverVersion,
+               conflictingEntry, outboxSeq, message)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                item.entity.as_str(),
+                item.target_id,
+                detected.kind.as_str(),
+                local.as_ref().map(Value::to_string),
+                detected.server_version.as_ref().map(Value::to_string),
+                detected.conflicting_entry.as_ref().map(Value::to_string),
+                item.seq,
+                detected.message
+            ],
+        )?;
+        let id = tx.last_insert_rowid();
+        tx.commit()?;
+
+        self.conflict(id)?
+            .ok_or(StoreError::NotFound("Konflikt", id))
+    }
+
+    pub fn conflicts(&self) -> StoreResult<Vec<SyncConflict>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM sync_conflicts ORDER BY id")?;
+        let rows = stmt.query_map([], conflict_from_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    fn conflict(&self, id: i64) -> StoreResult<Option<SyncConflict>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT * FROM sync_conflicts WHERE id = ?1",
+                params![id],
+                conflict_from_row,
+            )
+            .optional()?)
+    }
+
+    /// Synced entry of the same user and date that the local entry `id`
+    /// overlaps with
+    pub fn find_overlap(&self, id: i64) -> StoreResult<Option<TimeEntryRecord>> {
+        let Some(entry) = self.time_entry(id)? else {
+            return Ok(None);
+        };
+        let mut stmt = self.conn.prepare(
+            "SELECT * FROM time_entries
+             WHERE userId = ?1 AND date = ?2 AND id != ?3 AND id > 0 AND pendingSync = 0",
+        )?;
+        let others = stmt
+            .query_map(params![entry.user_id, entry.date, id], time_entry_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(others
+            .into_iter()
+            .find(|other| times_overlap(&entry, other)))
+    }
+
+    /// Applies the user's decision. The parked item is either dropped
+    /// (keep-server) or re-queued to be sent without further checks.
+    pub fn resolve_conflict(&mut self, id: i64, resolution: &Resolution) -> StoreResult<()> {
+        let conflict = self
+            .conflict(id)?
+            .ok_or(StoreError::NotFound("Konflikt", id))?;
+        let item = self.outbox_item(conflict.outbox_seq)?;
+        let entity = conflict.entity;
+        let record_id = conflict.record_id;
+
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "DELETE FROM sync_conflicts WHERE entity = ?1 AND recordId = ?2",
+            params![entity.as_str(), record_id],
+        )?;
+
+        match (resolution, item) {
+            // Discarded in the meantime, nothing left to send
+            (_, None) => {}
+            (Resolution::KeepMine, Some(item)) => {
+                if conflict.kind == ConflictKind::Deleted
+                    && item.operation == OutboxOperation::Update
+                {
+                    // Gone on the server: recreate it from the local row
+                    let local = load_record(&tx, entity, record_id)?
+                        .ok_or(StoreError::NotFound("Eintrag", record_id))?;
+                    requeue(
+                        &tx,
+                        item.seq,
+                        OutboxOperation::Create,
+                        &input_payload(entity, &local, false)?,
+                    )?;
+                } else {
+                    requeue(&tx, item.seq, item.operation, &item.payload)?;
+                }
+            }
+            (Resolution::KeepServer, Some(item)) => {
+                tx.execute(
+                    "DELETE FROM outbox WHERE entity = ?1 AND targetId = ?2",
+                    params![entity.as_str(), record_id],
+                )?;
+                let server = conflict
+                    .server_version
+                    .as_ref()
+                    .or(item.base_version.as_ref());
+                match server {
+                    _ if record_id < 0 || conflict.kind == ConflictKind::Deleted => {
+                        tx.execute(
+                            &format!("DELETE FROM {} WHERE id = ?1", entity.table()),
+                            params![record_id],
+                        )?;
+                    }
+                    Some(server) => overwrite_record(&tx, entity, record_id, server, false)?,
+                    None => {
+                        tx.execute(
+                            &format!(
+                                "UPDATE {} SET pendingSync = 0 WHERE id = ?1",
+                                entity.table()
+                            ),
+                            params![record_id],
+                        )?;
+                    }
+                }
+            }
+            (Resolution::Merge { fields }, Some(item)) => {
+                if item.operation == OutboxOperation::Delete {
+                    return Err(StoreError::Invalid(
+                        "Eine Löschung kann nicht zusammengeführt werden".to_string(),
+                    ));
+                }
+                let mut merged = conflict
+                    .server_version
+                    .or(conflict.local_version)
+                    .ok_or(StoreError::NotFound("Eintrag", record_id))?;
+                merge_object(&mut merged, fields)?;
+                overwrite_record(&tx, entity, record_id, &merged, true)?;
+
+                let recreate = item.operation == OutboxOperation::Create
+                    || conflict.kind == ConflictKind::Deleted;
+                let operation = if recreate {
+                    OutboxOperation::Create
+                } else {
+                    OutboxOperation::Update
+                };
+                requeue(
+                    &tx,
+                    item.seq,
+                    operation,
+                    &input_payload(entity, &merged, !recreate)?,
+                )?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn delete_record(&mut self, entity: OutboxEntity, id: i64) -> StoreResult<()> {
+        let tx = self.conn.transaction()?;
+        let current = load_record(&tx, entity, id)?;
+        let deleted = tx.execute(
+            &format!("DELETE FROM {} WHERE id = ?1", entity.table()),
+            params![id],
+        )?;
+        if deleted == 0 {
+            return Err(StoreError::NotFound("Eintrag", id));
+        }
+
+        if id < 0 {
+            // Never reached the server: drop the queued create/updates instead
+            tx.execute(
+                "DELETE FROM outbox WHERE entity = ?1 AND targetId = ?2",
+                params![entity.as_str(), id],
+            )?;
+        } else {
+            let base = base_version(&tx, entity, id, current)?;
+            tx.execute(
+                "DELETE FROM outbox
+                 WHERE entity = ?1 AND targetId = ?2 AND operation = 'update' AND status = 'pending'",
+                params![entity.as_str(), id],
+            )?;
+            enqueue(
+                &tx,
+                entity,
+                OutboxOperation::Delete,
+                id,
+                &Value::Object(Map::new()),
+                base.as_ref(),
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// Inserts a local entry and queues its POST
+fn insert_time_entry(conn: &Connection, input: &TimeEntryInput) -> StoreResult<i64> {
+    validate_location(&input.location)?;
+    let hours = arbzg::calculate_hours(&input.start_time, &input.end_time, input.break_minutes)
+        .map_err(|e| StoreError::Invalid(e.to_string()))?;
+
+    let id = next_local_id(conn, OutboxEntity::TimeEntry)?;
+    conn.execute(
+        "INSERT INTO time_entries (id, userId, date, startTime, endTime, breakMinutes, hours,
+           activity, project, location, notes, pendingSync)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 1)",
+        params![
+            id,
+            input.user_id,
+            input.date,
+            input.start_time,
+            input.end_time,
+            input.break_minutes,
+            hours,
+            input.activity,
+            input.project,
+            input.location,
+            input.notes
+        ],
+    )?;
+    enqueue(
+        conn,
+        OutboxEntity::TimeEntry,
+        OutboxOperation::Create,
+        id,
+        &serde_json::to_value(input)?,
+        None,
+    )?;
+    Ok(id)
+}
+
+fn enqueue(
+    conn: &Connection,
+    entity: OutboxEntity,
+    operation: OutboxOperation,
+    target_id: i64,
+    payload: &Value,
+    base: Option<&Value>,
+) -> StoreResult<()> {
+    conn.execute(
+        "INSERT INTO outbox (entity, operation, targetId, payload, baseVersion)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            entity.as_str(),
+            operation.as_str(),
+            target_id,
+            payload.to_string(),
+            base.map(Value::to_string)
+        ],
+    )?;
+    Ok(())
+}
+
+/// Folds an update into a still-pending create or update for the same
+/// record, so a record edited several times offline is sent only once.
+/// `current` is the row before this change.
+fn queue_update(
+    conn: &Connection,
+    entity: OutboxEntity,
+    id: i64,
+    patch: &Value,
+    current: Value,
+) -> StoreResult<()> {
+    let existing: Option<(i64, String)> = conn
+        .query_row(
+            "SELECT seq, payload FROM outbox
+             WHERE entity = ?1 AND targetId = ?2 AND status = 'pending'
+               AND operation IN ('create', 'update')
+             ORDER BY seq DESC LIMIT 1",
+            params![entity.as_str(), id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+
+    match existing {
+        Some((seq, payload)) => {
+            let mut payload: Value = serde_json::from_str(&payload)?;
+            merge_object(&mut payload, patch)?;
+            conn.execute(
+                "UPDATE outbox SET payload = ?2 WHERE seq = ?1",
+                params![seq, payload.to_string()],
+            )?;
+            Ok(())
+        }
+        None => {
+            let base = base_version(conn, entity, id, Some(current))?;
+            enqueue(
+                conn,
+                entity,
+                OutboxOperation::Update,
+                id,
+                patch,
+                base.as_ref(),
+            )
+        }
+    }
+}
+
+/// Records with queued outbox items (e.g. deleted locally, not yet synced)
+fn queued_ids(conn: &Connection, entity: OutboxEntity) -> StoreResult<HashSet<i64>> {
+    let mut stmt = conn.prepare("SELECT DISTINCT targetId FROM outbox WHERE entity = ?1")?;
+    let rows = stmt.query_map(params![entity.as_str()], |row| row.get(0))?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+/// Inserts or refreshes a row. Rows with unsynced local changes are only
+/// replaced after they have been deleted by the caller.
+fn upsert_time_entry(conn: &Connection, r: &TimeEntryRecord, pending: bool) -> StoreResult<()> {
+    conn.execute(
+        "INSERT INTO time_entries (id, userId, date, startTime, endTime, breakMinutes, hours,
+           activity, project, location, notes, createdAt, updatedAt, pendingSync)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+         ON CONFLICT(id) DO UPDATE SET
+           userId = excluded.userId, date = excluded.date, startTime = excluded.startTime,
+           endTime = excluded.endTime, breakMinutes = excluded.breakMinutes,
+           hours = excluded.hours, activity = excluded.activity, project = excluded.project,
+           location = excluded.location, notes = excluded.notes,
+           createdAt = excluded.createdAt, updatedAt = excluded.updatedAt,
+           pendingSync = excluded.pendingSync
+         WHERE time_entries.pendingSync = 0",
+        params![
+            r.id,
+            r.user_id,
+            r.date,
+            r.start_time,
+            r.end_time,
+            r.break_minutes,
+            r.hours,
+            r.activity,
+            r.project,
+            r.location,
+            r.notes,
+            r.created_at,
+            r.updated_at,
+            pending
+        ],
+    )?;
+    Ok(())
+}
+
+fn upsert_absence_request(
+    conn: &Connection,
+    r: &AbsenceRequestRecord,
+    pending: bool,
+) -> StoreResult<()> {
+    conn.execute(
+        "INSERT INTO absence_requests (id, userId, type, startDate, endDate, days, status,
+           reason, adminNote, approvedBy, approvedAt, createdAt, pendingSync)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+         ON CONFLICT(id) DO UPDATE SET
+           userId = excluded.userId, type = excluded.type, startDate = excluded.startDate,
+           endDate = excluded.endDate, days = excluded.days, status = excluded.status,
+           reason = excluded.reason, adminNote = excluded.adminNote,
+           approvedBy = excluded.approvedBy, approvedAt = excluded.approvedAt,
+           createdAt = excluded.createdAt, pendingSync = excluded.pendingSync
+         WHERE absence_requests.pendingSync = 0",
+        params![
+            r.id,
+            r.user_id,
+            r.absence_type,
+            r.start_date,
+            r.end_date,
+            r.days,
+            r.status,
+            r.reason,
+            r.admin_note,
+            r.approved_by,
+            r.approved_at,
+            r.created_at,
+            pending
+        ],
+    )?;
+    Ok(())
+}
+
+/// Replaces the local row with `record` (a server or merged version)
+fn overwrite_record(
+    conn: &Connection,
+    entity: OutboxEntity,
+    id: i64,
+    record: &Value,
+    pending: bool,
+) -> StoreResult<()> {
+    conn.execute(
+        &format!("DELETE FROM {} WHERE id = ?1", entity.table()),
+        params![id],
+    )?;
+    match entity {
+        OutboxEntity::TimeEntry => {
+            let mut record: TimeEntryRecord = serde_json::from_value(record.clone())?;
+            validate_location(&record.location)?;
+            record.hours =
+                arbzg::calculate_hours(&record.start_time, &record.end_time, record.break_minutes)
+                    .map_err(|e| StoreError::Invalid(e.to_string()))?;
+            record.id = id;
+            upsert_time_entry(conn, &record, pending)
+        }
+        OutboxEntity::AbsenceRequest => {
+            let mut record: AbsenceRequestRecord = serde_json::from_value(record.clone())?;
+            validate_absence_type(&record.absence_type)?;
+            record.id = id;
+            upsert_absence_request(conn, &record, pending)
+        }
+    }
+}
+
+/// Current local row as JSON
+fn load_record(conn: &Connection, entity: OutboxEntity, id: i64) -> StoreResult<Option<Value>> {
+    let sql = format!("SELECT * FROM {} WHERE id = ?1", entity.table());
+    let record = match entity {
+        OutboxEntity::TimeEntry => conn
+            .query_row(&sql, params![id], time_entry_from_row)
+            .optional()?
+            .map(serde_json::to_value),
+        OutboxEntity::AbsenceRequest => conn
+            .query_row(&sql, params![id], absence_from_row)
+            .optional()?
+            .map(serde_json::to_value),
+    };
+    Ok(record.transpose()?)
+}
+
+/// Body for `POST`/`PUT` built from a full record
+fn input_payload(entity: OutboxEntity, record: &Value, for_update: bool) -> StoreResult<Value> {
+    let mut payload = match entity {
+        OutboxEntity::TimeEntry => {
+            serde_json::to_value(serde_json::from_value::<TimeEntryInput>(record.clone())?)?
+        }
+        OutboxEntity::AbsenceRequest => serde_json::to_value(serde_json::from_value::<
+            AbsenceRequestInput,
+        >(record.clone())?)?,
+    };
+    if for_update {
+        if let Some(fields) = payload.as_object_mut() {
+            fields.remove("userId");
+        }
+    }
+    Ok(payload)
+}
+
+/// Version an update/delete of `id` is based on: the base of an earlier
+/// queued item, otherwise the row as it is before the local change
+fn base_version(
+    conn: &Connection,
+    entity: OutboxEntity,
+    id: i64,
+    current: Option<Value>,
+) -> StoreResult<Option<Value>> {
+    if id < 0 {
+        return Ok(None);
+    }
+    let earlier: Option<String> = conn
+        .query_row(
+            "SELECT baseVersion FROM outbox
+             WHERE entity = ?1 AND targetId = ?2 AND baseVersion IS NOT NULL
+             ORDER BY seq LIMIT 1",
+            params![entity.as_str(), id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match earlier {
+        Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+        None => Ok(current),
+    }
+}
+
+/// Whether the server copy differs from the version a local change was
+/// based on. `updatedAt` decides if both sides have it; otherwise (and for
+/// absence requests, which have no `updatedAt`) the content is compared.
+pub fn server_changed(entity: OutboxEntity, base: &Value, server: &Value) -> bool {
+    if entity == OutboxEntity::TimeEntry {
+        let base_stamp = base.get("updatedAt").and_then(Value::as_str);
+        let server_stamp = server.get("updatedAt").and_then(Value::as_str);
+        if let (Some(base_stamp), Some(server_stamp)) = (base_stamp, server_stamp) {
+            return base_stamp != server_stamp;
+        }
+    }
+    let fields = match entity {
+        OutboxEntity::TimeEntry => TIME_ENTRY_FINGERPRINT,
+        OutboxEntity::AbsenceRequest => ABSENCE_FINGERPRINT,
+    };
+    fields.iter().any(|field| {
+        let base = base.get(*field).unwrap_or(&Value::Null);
+        let server = server.get(*field).unwrap_or(&Value::Null);
+        match (base.as_f64(), server.as_f64()) {
+            // `1` and `1.0` are the same number of days
+            (Some(a), Some(b)) => a != b,
+            _ => base != server,
+        }
+    })
+}
+
+/// Same rule as `checkOverlap` in `timeEntryService.ts`, including
+/// entries that cross midnight
+fn times_overlap(a: &TimeEntryRecord, b: &TimeEntryRecord) -> bool {
+    let span = |entry: &TimeEntryRecord| -> Option<(i64, i64)> {
+        let minutes = |value: &str| {
+            let (h, m) = value.split_once(':')?;
+            Some(h.parse::<i64>().ok()? * 60 + m.parse::<i64>().ok()?)
+        };
+        let start = minutes(&entry.start_time)?;
+        let mut end = minutes(&entry.end_time)?;
+        if end < start {
+            end += 1440;
+        }
+        Some((start, end))
+    };
+    match (span(a), span(b)) {
+        (Some((a_start, a_end)), Some((b_start, b_end))) => a_start < b_end && a_end > b_start,
+        _ => false,
+    }
+}
+
+fn ensure_column(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> StoreResult<()> {
+    let exists: bool = conn.query_row(
+        &format!(
+            "SELECT EXISTS(SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1)",
+            table
+        ),
+        params![column],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))?;
+    }
+    Ok(())
+}
+
+/// Puts a parked item back into the queue, marked to skip conflict checks
+fn requeue(
+    conn: &Connection,
+    seq: i64,
+    operation: OutboxOperation,
+    payload: &Value,
+) -> StoreResult<()> {
+    conn.execute(
+        "UPDATE outbox SET operation = ?2, payload = ?3, status = 'pending', lastError = NULL,
+           forced = 1
+         WHERE seq = ?1",
+        params![seq, operation.as_str(), payload.to_string()],
+    )?;
+    Ok(())
+}
+
+fn drop_orphaned_conflicts(conn: &Connection) -> StoreResult<()> {
+    conn.execute(
+        "DELETE FROM sync_conflicts WHERE outboxSeq NOT IN (SELECT seq FROM outbox)",
+        [],
+    )?;
+    Ok(())
+}
+
+fn next_local_id(conn: &Connection, entity: OutboxEntity) -> StoreResult<i64> {
+    let min: i64 = conn.query_row(
+        &format!("SELECT COALESCE(MIN(id), 0) FROM {}", entity.table()),
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(min.min(0) - 1)
+}
+
+fn merge_object(target: &mut Value, patch: &Value) -> StoreResult<()> {
+    let (Some(target), Some(patch)) = (target.as_object_mut(), patch.as_object()) else {
+        return Err(StoreError::Invalid(
+            "Änderungen müssen als Objekt übergeben werden".to_string(),
+        ));
+    };
+    for (key, value) in patch {
+        target.insert(key.clone(), value.clone());
+    }
+    Ok(())
+}
+
+fn validate_location(location: &str) -> StoreResult<()> {
+    match location {
+        "office" | "homeoffice" | "field" => Ok(()),
+        other => Err(StoreError::Invalid(format!(
+            "Ungültiger Arbeitsort: {}",
+            other
+        ))),
+    }
+}
+
+fn validate_absence_type(absence_type: &str) -> StoreResult<()> {
+    match absence_type {
+        "vacation" | "sick" | "unpaid" | "overtime_comp" => Ok(()),
+        other => Err(StoreError::Invalid(format!(
+            "Ungültige Abwesenheitsart: {}",
+            other
+        ))),
+    }
+}
+
+fn time_entry_from_row(row: &Row<'_>) -> rusqlite::Result<TimeEntryRecord> {
+    Ok(TimeEntryRecord {
+        id: row.get("id")?,
+        user_id: row.get("userId")?,
+        date: row.get("date")?,
+        start_time: row.get("startTime")?,
+        end_time: row.get("endTime")?,
+        break_minutes: row.get::<_, Option<i64>>("breakMinutes")?.unwrap_or(0),
+        hours: row.get("hours")?,
+        activity: row.get("activity")?,
+        project: row.get("project")?,
+        location: row.get("location")?,
+        notes: row.get("notes")?,
+        created_at: row.get("createdAt")?,
+        updated_at: row.get("updatedAt")?,
+        pending_sync: row.get("pendingSync")?,
+    })
+}
+
+fn absence_from_row(row: &Row<'_>) -> rusqlite::Result<AbsenceRequestRecord> {
+    Ok(AbsenceRequestRecord {
+        id: row.get("id")?,
+        user_id: row.get("userId")?,
+        absence_type: row.get("type")?,
+        start_date: row.get("startDate")?,
+        end_date: row.get("endDate")?,
+        days: row.get("days")?,
+        status: row
+            .get::<_, Option<String>>("status")?
+            .unwrap_or_else(|| "pending".to_string()),
+        reason: row.get("reason")?,
+        admin_note: row.get("adminNote")?,
+        approved_by: row.get("approvedBy")?,
+        approved_at: row.get("approvedAt")?,
+        created_at: row.get("createdAt")?,
+        pending_sync: row.get("pendingSync")?,
+    })
+}
+
+fn outbox_from_row(row: &Row<'_>) -> rusqlite::Result<OutboxItem> {
+    let entity: String = row.get("entity")?;
+    let operation: String = row.get("operation")?;
+    let status: String = row.get("status")?;
+    let payload: String = row.get("payload")?;
+    Ok(OutboxItem {
+        seq: row.get("seq")?,
+        entity: OutboxEntity::parse(&entity).unwrap_or(OutboxEntity::TimeEntry),
+        operation: OutboxOperation::parse(&operation).unwrap_or(OutboxOperation::Update),
+        target_id: row.get("targetId")?,
+        payload: serde_json::from_str(&payload).unwrap_or(Value::Null),
+        status: if status == "failed" {
+            OutboxStatus::Failed
+        } else {
+            OutboxStatus::Pending
+        },
+        attempts: row.get("attempts")?,
+        last_error: row.get("lastError")?,
+        created_at: row.get("createdAt")?,
+        base_version: json_column(row, "baseVersion")?,
+        forced: row.get("forced")?,
+    })
+}
+
+fn conflict_from_row(row: &Row<'_>) -> rusqlite::Result<SyncConflict> {
+    let entity: String = row.get("entity")?;
+    let kind: String = row.get("kind")?;
+    Ok(SyncConflict {
+        id: row.get("id")?,
+        entity: OutboxEntity::parse(&entity).unwrap_or(OutboxEntity::TimeEntry),
+        record_id: row.get("recordId")?,
+        kind: ConflictKind::parse(&kind).unwrap_or(ConflictKind::Rejected),
+        local_version: json_column(row, "localVersion")?,
+        server_version: json_column(row, "serverVersion")?,
+        conflicting_entry: json_column(row, "conflictingEntry")?,
+        outbox_seq: row.get("outboxSeq")?,
+        message: row.get("message")?,
+        created_at: row.get("createdAt")?,
+    })
+}
+
+fn json_column(row: &Row<'_>, column: &str) -> rusqlite::Result<Option<Value>> {
+    let json: Option<String> = row.get(column)?;
+    Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry_input(date: &str) -> TimeEntryInput {
+        TimeEntryInput {
+            user_id: 7,
+            date: date.into(),
+            start_time: "08:00".into(),
+            end_time: "16:30".into(),
+            break_minutes: 30,
+            activity: None,
+            project: Some("Verwaltung".into()),
+            location: "office".into(),
+            notes: None,
+        }
+    }
+
+    fn server_entry(id: i64, date: &str) -> TimeEntryRecord {
+        TimeEntryRecord {
+            id,
+            user_id: 7,
+            date: date.into(),
+            start_time: "09:00".into(),
+            end_time: "17:00".into(),
+            break_minutes: 30,
+            hours: 7.5,
+            activity: None,
+            project: None,
+            location: "office".into(),
+            notes: None,
+            created_at: Some("2026-03-01 10:00:00".into()),
+            updated_at: None,
+            pending_sync: false,
+        }
+    }
+
+    #[test]
+    fn offline_create_gets_local_id_and_outbox_item() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        let first = store.create_time_entry(&entry_input("2026-03-02")).unwrap();
+        let second = store.create_time_entry(&entry_input("2026-03-03")).unwrap();
+
+        assert_eq!((first.id, second.id), (-1, -2));
+        assert_eq!(first.hours, 8.0);
+        assert!(first.pending_sync);
+
+        let outbox = store.outbox().unwrap();
+        assert_eq!(outbox.len(), 2);
+        assert_eq!(outbox[0].operation, OutboxOperation::Create);
+        assert_eq!(outbox[0].payload["startTime"], "08:00");
+    }
+
+    #[test]
+    fn bulk_create_is_all_or_nothing() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        let mut invalid = entry_input("2026-03-04");
+        invalid.location = "beach".into();
+        let mut inputs = [
+            entry_input("2026-03-02"),
+            invalid,
+            entry_input("2026-03-03"),
+        ];
+        assert!(store.create_time_entries(&inputs).is_err());
+        assert!(store.outbox().unwrap().is_empty());
+
+        inputs.swap(1, 2);
+        let ids = store.create_time_entries(&inputs[..2]).unwrap();
+        assert_eq!(ids, vec![-1, -2]);
+        assert_eq!(store.outbox().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn completing_create_rewrites_ids_of_later_items() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        store
+            .cache_time_entries(&[server_entry(40, "2026-03-01")])
+            .unwrap();
+        let local = store.create_time_entry(&entry_input("2026-03-02")).unwrap();
+        store
+            .update_time_entry(40, &json!({ "notes": "korrigiert" }))
+            .unwrap();
+
+        let create = store.outbox().unwrap().remove(0);
+        let mut saved = server_entry(41, "2026-03-02");
+        saved.start_time = "08:00".into();
+        store
+            .complete(
+                &create,
+                Some(41),
+                Some(&serde_json::to_value(&saved).unwrap()),
+            )
+            .unwrap();
+
+        assert!(store.time_entry(local.id).unwrap().is_none());
+        let synced = store.time_entry(41).unwrap().unwrap();
+        assert!(!synced.pending_sync);
+        assert_eq!(synced.start_time, "08:00");
+
+        let remaining = store.outbox().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].target_id, 40);
+    }
+
+    #[test]
+    fn updates_of_unsynced_entries_are_folded_into_create() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        let local = store.create_time_entry(&entry_input("2026-03-02")).unwrap();
+        let updated = store
+            .update_time_entry(local.id, &json!({ "endTime": "17:00" }))
+            .unwrap();
+
+        assert_eq!(updated.hours, 8.5);
+        let outbox = store.outbox().unwrap();
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].payload["endTime"], "17:00");
+    }
+
+    #[test]
+    fn deleting_local_only_entry_drops_queued_items() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        let local = store.create_time_entry(&entry_input("2026-03-02")).unwrap();
+        store.delete_time_entry(local.id).unwrap();
+        assert_eq!(store.pending_count().unwrap(), 0);
+
+        store
+            .cache_time_entries(&[server_entry(40, "2026-03-01")])
+            .unwrap();
+        store.delete_time_entry(40).unwrap();
+        let outbox = store.outbox().unwrap();
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].operation, OutboxOperation::Delete);
+    }
+
+    #[test]
+    fn server_refresh_does_not_overwrite_pending_changes() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        store
+            .cache_time_entries(&[server_entry(40, "2026-03-01")])
+            .unwrap();
+        store
+            .update_time_entry(40, &json!({ "endTime": "18:00" }))
+            .unwrap();
+
+        store
+            .cache_time_entries(&[server_entry(40, "2026-03-01")])
+            .unwrap();
+        assert_eq!(store.time_entry(40).unwrap().unwrap().end_time, "18:00");
+    }
+
+    #[test]
+    fn pull_drops_entries_deleted_on_server_but_keeps_local_changes() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        store
+            .cache_time_entries(&[
+                server_entry(40, "2026-03-01"),
+                server_entry(41, "2026-03-02"),
+                server_entry(42, "2026-03-03"),
+                server_entry(43, "2025-12-01"),
+            ])
+            .unwrap();
+        store
+            .update_time_entry(40, &json!({ "endTime": "18:00" }))
+            .unwrap();
+        store.delete_time_entry(41).unwrap();
+        let local = store.create_time_entry(&entry_input("2026-03-04")).unwrap();
+
+        // 42 was deleted on the server, 44 is new
+        let server = [
+            server_entry(40, "2026-03-01"),
+            server_entry(41, "2026-03-02"),
+            server_entry(44, "2026-03-05"),
+        ];
+        store
+            .replace_time_entries(7, "2026-01-01", "2026-12-31", &server)
+            .unwrap();
+
+        let ids: Vec<i64> = store
+            .list_time_entries(7, "2025-01-01", "2026-12-31")
+            .unwrap()
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids, vec![44, local.id, 40, 43]);
+        assert_eq!(store.time_entry(40).unwrap().unwrap().end_time, "18:00");
+    }
+
+    #[test]
+    fn rejected_items_are_parked_until_retried() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        store
+            .create_absence_request(&AbsenceRequestInput {
+                user_id: 7,
+                absence_type: "vacation".into(),
+                start_date: "2026-08-03".into(),
+                end_date: "2026-08-14".into(),
+                reason: None,
+            })
+            .unwrap();
+        let seq = store.outbox().unwrap()[0].seq;
+
+        store
+            .record_failure(seq, "Nicht genügend Urlaubstage", true)
+            .unwrap();
+        let item = &store.outbox().unwrap()[0];
+        assert_eq!(item.status, OutboxStatus::Failed);
+        assert_eq!(item.attempts, 1);
+
+        store.retry(seq).unwrap();
+        assert_eq!(store.outbox().unwrap()[0].status, OutboxStatus::Pending);
+
+        store.discard(seq).unwrap();
+        assert!(store.list_absence_requests(7).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_values_outside_server_constraints() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        let mut input = entry_input("2026-03-02");
+        input.location = "beach".into();
+        assert!(matches!(
+            store.create_time_entry(&input),
+            Err(StoreError::Invalid(_))
+        ));
+    }
+
+    fn modified_on_server(store: &mut OfflineStore) -> SyncConflict {
+        let mut cached = server_entry(40, "2026-03-02");
+        cached.updated_at = Some("2026-03-02 18:00:00".into());
+        store.cache_time_entries(&[cached.clone()]).unwrap();
+        store
+            .update_time_entry(40, &json!({ "endTime": "18:00" }))
+            .unwrap();
+
+        let mut server = cached;
+        server.notes = Some("Admin-Korrektur".into());
+        server.updated_at = Some("2026-03-03 09:00:00".into());
+        let item = store.outbox().unwrap().remove(0);
+        store
+            .record_conflict(
+                &item,
+                &DetectedConflict {
+                    kind: ConflictKind::Modified,
+                    server_version: Some(serde_json::to_value(&server).unwrap()),
+                    conflicting_entry: None,
+                    message: "geändert".into(),
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn server_change_is_detected_by_updated_at_or_content() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        let mut cached = server_entry(40, "2026-03-02");
+        cached.updated_at = Some("2026-03-02 18:00:00".into());
+        store.cache_time_entries(&[cached]).unwrap();
+        store
+            .update_time_entry(40, &json!({ "notes": "lokal" }))
+            .unwrap();
+
+        let base = store.outbox().unwrap()[0].base_version.clone().unwrap();
+        assert_eq!(base["updatedAt"], "2026-03-02 18:00:00");
+        assert_eq!(base["notes"], Value::Null);
+
+        let mut server = base.clone();
+        assert!(!server_changed(OutboxEntity::TimeEntry, &base, &server));
+        server["updatedAt"] = json!("2026-03-03 09:00:00");
+        assert!(server_changed(OutboxEntity::TimeEntry, &base, &server));
+
+        let absence = json!({ "type": "vacation", "days": 5.0, "status": "pending" });
+        let mut server = json!({ "type": "vacation", "days": 5, "status": "pending" });
+        assert!(!server_changed(
+            OutboxEntity::AbsenceRequest,
+            &absence,
+            &server
+        ));
+        server["status"] = json!("approved");
+        assert!(server_changed(
+            OutboxEntity::AbsenceRequest,
+            &absence,
+            &server
+        ));
+    }
+
+    #[test]
+    fn finds_overlap_with_synced_entries_of_the_same_day() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        store
+            .cache_time_entries(&[server_entry(40, "2026-03-02")])
+            .unwrap();
+        let overlapping = store.create_time_entry(&entry_input("2026-03-02")).unwrap();
+        let other_day = store.create_time_entry(&entry_input("2026-03-03")).unwrap();
+
+        assert_eq!(
+            store.find_overlap(overlapping.id).unwrap().map(|e| e.id),
+            Some(40)
+        );
+        assert_eq!(store.find_overlap(other_day.id).unwrap(), None);
+    }
+
+    #[test]
+    fn conflict_keeps_both_versions_until_resolved_with_keep_server() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        let conflict = modified_on_server(&mut store);
+
+        assert_eq!(store.conflicts().unwrap(), vec![conflict.clone()]);
+        assert_eq!(conflict.local_version.unwrap()["endTime"], "18:00");
+        assert_eq!(conflict.server_version.unwrap()["notes"], "Admin-Korrektur");
+        assert_eq!(store.outbox().unwrap()[0].status, OutboxStatus::Failed);
+        assert_eq!(store.time_entry(40).unwrap().unwrap().end_time, "18:00");
+
+        store
+            .resolve_conflict(conflict.id, &Resolution::KeepServer)
+            .unwrap();
+        let entry = store.time_entry(40).unwrap().unwrap();
+        assert_eq!(entry.end_time, "17:00");
+        assert_eq!(entry.notes.as_deref(), Some("Admin-Korrektur"));
+        assert!(!entry.pending_sync);
+        assert_eq!(store.pending_count().unwrap(), 0);
+        assert!(store.conflicts().unwrap().is_empty());
+    }
+
+    #[test]
+    fn keep_mine_and_merge_requeue_the_item_as_forced() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        let conflict = modified_on_server(&mut store);
+        store
+            .resolve_conflict(conflict.id, &Resolution::KeepMine)
+            .unwrap();
+        let item = store.outbox().unwrap().remove(0);
+        assert_eq!(item.status, OutboxStatus::Pending);
+        assert!(item.forced);
+        assert_eq!(item.payload, json!({ "endTime": "18:00" }));
+
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        let conflict = modified_on_server(&mut store);
+        store
+            .resolve_conflict(
+                conflict.id,
+                &Resolution::Merge {
+                    fields: json!({ "endTime": "18:00" }),
+                },
+            )
+            .unwrap();
+        let entry = store.time_entry(40).unwrap().unwrap();
+        assert_eq!(
+            (entry.end_time.as_str(), entry.notes.as_deref()),
+            ("18:00", Some("Admin-Korrektur"))
+        );
+        assert_eq!(entry.hours, 8.5);
+        assert!(entry.pending_sync);
+
+        let item = store.outbox().unwrap().remove(0);
+        assert!(item.forced);
+        assert_eq!(item.operation, OutboxOperation::Update);
+        assert_eq!(item.payload["notes"], "Admin-Korrektur");
+        assert_eq!(item.payload.get("userId"), None);
+    }
+
+    #[test]
+    fn keep_mine_recreates_entry_deleted_on_server() {
+        let mut store = OfflineStore::open_in_memory().unwrap();
+        store
+            .cache_time_entries(&[server_entry(40, "2026-03-02")])
+            .unwrap();
+        store
+            .update_time_entry(40, &json!({ "notes": "lokal" }))
+            .unwrap();
+        let item = store.outbox().unwrap().remove(0);
+        let conflict = store
+            .record_conflict(
+                &item,
+                &DetectedConflict {
+                    kind: ConflictKind::Deleted,
+                    server_version: None,
+                    conflicting_entry: None,
+                    message: "gelöscht".into(),
+                },
+            )
+            .unwrap();
+
+        store
+            .resolve_conflict(conflict.id, &Resolution::KeepMine)
+            .unwrap();
+        let item = store.outbox().unwrap().remove(0);
+        assert_eq!(item.operation, OutboxOperation::Create);
+        assert_eq!(item.payload["notes"], "lokal");
+        assert_eq!(item.payload["userId"], 7);
+    }
+}
@@ -0,0 +1,678 @@
+//! Overtime engine (Soll/Ist per day and running balance)
+//!
+//! Native port of `server/src/services/overtimeTransactionRebuildService.ts`
+//! (daily targets via [`crate::work_schedule`]), so the desktop can show a
+//! balance computed from local data while offline. The result is the same
+//! transaction ledger the server writes to `overtime_transactions`:
+//!
+//! - Regular day: `time_entry` = worked − target
+//! - Absence day: `time_entry` = worked − target, plus a credit of the
+//!   day's target (`vacation_credit`, `sick_credit`, …), so a paid absence
+//!   is neutral
+//! - Unpaid leave: `unpaid_deduction` of +target instead of a credit, which
+//!   effectively removes the day from the target
+//!
+//! Only approved absences count. The period is limited to the employment
+//! (hire date … end date). Differences to the server:
+//! - Days without any change (weekends, holidays) produce no transaction.
+//! - Corrections are booked as separate `correction` transactions. The
+//!   server folds them into `time_entry` on regular days and drops them on
+//!   absence days; the balance is identical except for the latter.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::work_schedule::{ScheduleError, ScheduleHistory, ScheduleVersion, TargetRule};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Employee {
+    /// `weeklyHours` and `workSchedule` as stored on the user
+    #[serde(flatten)]
+    pub rule: TargetRule,
+    pub hire_date: NaiveDate,
+    #[serde(default)]
+    pub end_date: Option<NaiveDate>,
+    /// Contract changes; when given, they replace `rule`
+    #[serde(default)]
+    pub schedule_history: Vec<ScheduleVersion>,
+}
+
+impl Employee {
+    /// Versioned contract hours; without history, `rule` from the hire date
+    pub fn schedule(&self) -> Result<ScheduleHistory, ScheduleError> {
+        if self.schedule_history.is_empty() {
+            return ScheduleHistory::new(vec![ScheduleVersion {
+                valid_from: self.hire_date,
+                rule: self.rule,
+            }]);
+        }
+        ScheduleHistory::new(self.schedule_history.clone())
+    }
+
+    fn is_employed(&self, date: NaiveDate) -> bool {
+        date >= self.hire_date && self.end_date.is_none_or(|end| date <= end)
+    }
+}
+
+/// Hours of one time entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkedHours {
+    pub date: NaiveDate,
+    pub hours: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AbsenceKind {
+    Vacation,
+    Sick,
+    OvertimeComp,
+    Special,
+    Unpaid,
+    #[serde(other)]
+    Other,
+}
+
+impl AbsenceKind {
+    pub fn parse(value: &str) -> Self {
+        serde_json::from_value(serde_json::Value::from(value)).unwrap_or(AbsenceKind::Other)
+    }
+
+    fn credit(self) -> (TransactionType, &'static str) {
+        match self {
+            AbsenceKind::Vacation => (TransactionType::VacationCredit, "Urlaubs-Gutschrift"),
+            AbsenceKind::Sick => (TransactionType::SickCredit, "Krankheits-Gutschrift"),
+            AbsenceKind::OvertimeComp => (
+                TransactionType::OvertimeCompCredit,
+                "Überstunden-Ausgleich Gutschrift",
+            ),
+            AbsenceKind::Special => (TransactionType::SpecialCredit, "Sonderurlaub-Gutschrift"),
+            AbsenceKind::Unpaid => (
+                TransactionType::UnpaidDeduction,
+                "Unbezahlter Urlaub Anpassung",
+            ),
+            AbsenceKind::Other => (TransactionType::TimeEntry, "Gutschrift"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Absence {
+    #[serde(default)]
+    pub id: Option<i64>,
+    #[serde(rename = "type")]
+    pub kind: AbsenceKind,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Only `approved` absences count
+    pub status: String,
+}
+
+impl Absence {
+    fn covers(&self, date: NaiveDate) -> bool {
+        self.status == "approved" && self.start_date <= date && date <= self.end_date
+    }
+}
+
+/// Entry of `overtime_corrections`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Correction {
+    pub date: NaiveDate,
+    pub hours: f64,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OvertimeInput {
+    pub employee: Employee,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Balance brought forward (e.g. from the previous year), booked as a
+    /// `carryover` transaction on `from`
+    #[serde(default)]
+    pub carryover: f64,
+    #[serde(default)]
+    pub time_entries: Vec<WorkedHours>,
+    #[serde(default)]
+    pub absences: Vec<Absence>,
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
+    #[serde(default)]
+    pub corrections: Vec<Correction>,
+}
+
+/// Transaction types as stored in `overtime_transactions.type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionType {
+    TimeEntry,
+    VacationCredit,
+    SickCredit,
+    OvertimeCompCredit,
+    SpecialCredit,
+    UnpaidDeduction,
+    Correction,
+    Carryover,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    pub date: NaiveDate,
+    #[serde(rename = "type")]
+    pub kind: TransactionType,
+    pub hours: f64,
+    pub balance_before: f64,
+    pub balance_after: f64,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absence_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayResult {
+    pub date: NaiveDate,
+    /// Target after unpaid leave, i.e. 0 on unpaid days
+    pub target_hours: f64,
+    pub worked_hours: f64,
+    pub absence: Option<AbsenceKind>,
+    /// Absence credit (0 for unpaid leave)
+    pub credit_hours: f64,
+    pub correction_hours: f64,
+    pub overtime: f64,
+    pub balance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthSummary {
+    /// `YYYY-MM`
+    pub month: String,
+    pub target_hours: f64,
+    /// Worked hours + absence credits + corrections
+    pub actual_hours: f64,
+    pub overtime: f64,
+    /// Running balance at the end of the month
+    pub balance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OvertimeLedger {
+    pub carryover: f64,
+    pub target_hours: f64,
+    pub actual_hours: f64,
+    pub overtime: f64,
+    /// Carryover + overtime
+    pub balance: f64,
+    pub months: Vec<MonthSummary>,
+    pub days: Vec<DayResult>,
+    pub transactions: Vec<Transaction>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OvertimeError {
+    InvalidPeriod(NaiveDate, NaiveDate),
+    Schedule(ScheduleError),
+}
+
+impl fmt::Display for OvertimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OvertimeError::InvalidPeriod(from, to) => write!(
+                f,
+                "Ungültiger Zeitraum: {} liegt nach {}",
+                from.format("%d.%m.%Y"),
+                to.format("%d.%m.%Y")
+            ),
+            OvertimeError::Schedule(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for OvertimeError {}
+
+impl From<ScheduleError> for OvertimeError {
+    fn from(e: ScheduleError) -> Self {
+        OvertimeError::Schedule(e)
+    }
+}
+
+struct Ledger {
+    balance: f64,
+    transactions: Vec<Transaction>,
+}
+
+impl Ledger {
+    fn book(
+        &mut self,
+        date: NaiveDate,
+        kind: TransactionType,
+        hours: f64,
+        description: String,
+        absence_id: Option<i64>,
+    ) {
+        let hours = round2(hours);
+        if hours == 0.0 {
+            return;
+        }
+        let balance_before = self.balance;
+        self.balance = round2(balance_before + hours);
+        self.transactions.push(Transaction {
+            date,
+            kind,
+            hours,
+            balance_before,
+            balance_after: self.balance,
+            description,
+            absence_id,
+        });
+    }
+}
+
+pub fn calculate(input: &OvertimeInput) -> Result<OvertimeLedger, OvertimeError> {
+    if input.from > input.to {
+        return Err(OvertimeError::InvalidPeriod(input.from, input.to));
+    }
+    let employee = &input.employee;
+    let schedule = employee.schedule()?;
+    let holidays: HashSet<NaiveDate> = input.holidays.iter().copied().collect();
+    let mut worked: HashMap<NaiveDate, f64> = HashMap::new();
+    for entry in &input.time_entries {
+        *worked.entry(entry.date).or_default() += entry.hours;
+    }
+    let mut corrections: HashMap<NaiveDate, f64> = HashMap::new();
+    for correction in &input.corrections {
+        *corrections.entry(correction.date).or_default() += correction.hours;
+    }
+
+    let mut ledger = Ledger {
+        balance: 0.0,
+        transactions: Vec::new(),
+    };
+    ledger.book(
+        input.from,
+        TransactionType::Carryover,
+        input.carryover,
+        "Übertrag".into(),
+        None,
+    );
+
+    let mut days = Vec::new();
+    let mut months: BTreeMap<String, MonthSummary> = BTreeMap::new();
+    for date in input.from.iter_days().take_while(|d| *d <= input.to) {
+        if !employee.is_employed(date) {
+            continue;
+        }
+        let target = schedule.daily_target(date, holidays.contains(&date));
+        let worked_hours = round2(worked.get(&date).copied().unwrap_or(0.0));
+        let correction_hours = round2(corrections.get(&date).copied().unwrap_or(0.0));
+        let absence = input.absences.iter().find(|a| a.covers(date));
+        let day = date.format("%d.%m.%Y");
+
+        let balance_before = ledger.balance;
+        let mut day_target = target;
+        let mut credit_hours = 0.0;
+        match absence {
+            Some(absence) => {
+                ledger.book(
+                    date,
+                    TransactionType::TimeEntry,
+                    worked_hours - target,
+                    "Abwesenheit: Soll/Ist-Differenz".into(),
+                    absence.id,
+                );
+                let (kind, description) = absence.kind.credit();
+                ledger.book(
+                    date,
+                    kind,
+                    target,
+                    format!("{} {}", description, day),
+                    absence.id,
+                );
+                if absence.kind == AbsenceKind::Unpaid {
+                    day_target = 0.0;
+                } else {
+                    credit_hours = target;
+                }
+            }
+            None => ledger.book(
+                date,
+                TransactionType::TimeEntry,
+                worked_hours - target,
+                format!("Differenz Soll/Ist {}", day),
+                None,
+            ),
+        }
+        ledger.book(
+            date,
+            TransactionType::Correction,
+            correction_hours,
+            format!("Korrektur {}", day),
+            None,
+        );
+
+        let actual = worked_hours + credit_hours + correction_hours;
+        let result = DayResult {
+            date,
+            target_hours: day_target,
+            worked_hours,
+            absence: absence.map(|a| a.kind),
+            credit_hours,
+            correction_hours,
+            overtime: round2(ledger.balance - balance_before),
+            balance: ledger.balance,
+        };
+        let key = date.format("%Y-%m").to_string();
+        let month = months.entry(key.clone()).or_insert_with(|| MonthSummary {
+            month: key,
+            target_hours: 0.0,
+            actual_hours: 0.0,
+            overtime: 0.0,
+            balance: 0.0,
+        });
+        month.target_hours = round2(month.target_hours + day_target);
+        month.actual_hours = round2(month.actual_hours + actual);
+        month.overtime = round2(month.overtime + result.overtime);
+        month.balance = ledger.balance;
+        days.push(result);
+    }
+
+    let months: Vec<MonthSummary> = months.into_values().collect();
+    let carryover = round2(input.carryover);
+    let overtime = round2(months.iter().map(|m| m.overtime).sum());
+    Ok(OvertimeLedger {
+        carryover,
+        target_hours: round2(months.iter().map(|m| m.target_hours).sum()),
+        actual_hours: round2(months.iter().map(|m| m.actual_hours).sum()),
+        overtime,
+        balance: ledger.balance,
+        months,
+        days,
+        transactions: ledger.transactions,
+    })
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[tauri::command]
+pub fn calculate_overtime(input: OvertimeInput) -> Result<OvertimeLedger, String> {
+    calculate(&input).map_err(|e| e.to_string())
+}
+
+/// Fixtures from `server/TEST_USERS_EXPECTED_VALUES.md` (validated on
+/// 2026-01-18, holidays 01.01. and 06.01. as in Bavaria)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::work_schedule::WorkSchedule;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn employee(weekly_hours: f64, schedule: &[(&str, f64)], hire_date: &str) -> Employee {
+        let work_schedule = (!schedule.is_empty()).then(|| {
+            let object: serde_json::Map<_, _> = schedule
+                .iter()
+                .map(|(day, hours)| (day.to_string(), serde_json::Value::from(*hours)))
+                .collect();
+            WorkSchedule::try_from(serde_json::Value::Object(object)).unwrap()
+        });
+        Employee {
+            rule: TargetRule {
+                weekly_hours,
+                work_schedule,
+            },
+            hire_date: date(hire_date),
+            end_date: None,
+            schedule_history: Vec::new(),
+        }
+    }
+
+    fn january(employee: Employee) -> OvertimeInput {
+        OvertimeInput {
+            employee,
+            from: date("2026-01-01"),
+            to: date("2026-01-18"),
+            carryover: 0.0,
+            time_entries: Vec::new(),
+            absences: Vec::new(),
+            holidays: vec![date("2026-01-01"), date("2026-01-06")],
+            corrections: Vec::new(),
+        }
+    }
+
+    fn worked(day: &str, hours: f64) -> WorkedHours {
+        WorkedHours {
+            date: date(day),
+            hours,
+        }
+    }
+
+    fn absence(kind: AbsenceKind, start: &str, end: &str) -> Absence {
+        Absence {
+            id: Some(1),
+            kind,
+            start_date: date(start),
+            end_date: date(end),
+            status: "approved".into(),
+        }
+    }
+
+    fn totals(ledger: &OvertimeLedger) -> (f64, f64, f64) {
+        (ledger.target_hours, ledger.actual_hours, ledger.overtime)
+    }
+
+    #[test]
+    fn full_time_with_compensation_day_and_carryover() {
+        // User 48: worked Saturday 03.01., overtime compensation on 02.01.
+        let mut input = january(employee(40.0, &[], "2024-01-01"));
+        input.time_entries = vec![worked("2026-01-03", 8.0)];
+        input.absences = vec![absence(
+            AbsenceKind::OvertimeComp,
+            "2026-01-02",
+            "2026-01-02",
+        )];
+        let ledger = calculate(&input).unwrap();
+        assert_eq!(totals(&ledger), (80.0, 16.0, -64.0));
+        assert_eq!(ledger.balance, -64.0);
+
+        let comp_day: Vec<_> = ledger
+            .transactions
+            .iter()
+            .filter(|t| t.date == date("2026-01-02"))
+            .map(|t| (t.kind, t.hours, t.balance_after))
+            .collect();
+        assert_eq!(
+            comp_day,
+            vec![
+                (TransactionType::TimeEntry, -8.0, -8.0),
+                (TransactionType::OvertimeCompCredit, 8.0, 0.0),
+            ]
+        );
+        // Holidays and weekends without work leave no transaction
+        assert!(ledger
+            .transactions
+            .iter()
+            .all(|t| t.date != date("2026-01-06") && t.date != date("2026-01-04")));
+
+        // Users 50 and 51: same January, different balance from 2025
+        input.carryover = 60.0;
+        assert_eq!(calculate(&input).unwrap().balance, -4.0);
+        input.carryover = -400.0;
+        let ledger = calculate(&input).unwrap();
+        assert_eq!(ledger.balance, -464.0);
+        assert_eq!(ledger.transactions[0].kind, TransactionType::Carryover);
+        assert_eq!(ledger.months[0].balance, -464.0);
+    }
+
+    #[test]
+    fn work_schedule_overrides_weekly_hours() {
+        // User 49: Mon + Tue 4 h, vacation 01.–25.01., holiday on Tue 06.01.
+        // The holiday has no target (getDailyTargetHours), so 12 h target
+        // and 12 h credit.
+        let mut christine = january(employee(
+            8.0,
+            &[("monday", 4.0), ("tuesday", 4.0)],
+            "2025-01-01",
+        ));
+        christine.absences = vec![absence(AbsenceKind::Vacation, "2026-01-01", "2026-01-25")];
+        let ledger = calculate(&christine).unwrap();
+        assert_eq!(totals(&ledger), (12.0, 12.0, 0.0));
+        let credits: f64 = ledger.days.iter().map(|d| d.credit_hours).sum();
+        assert_eq!(credits, 12.0);
+
+        // User 53: Mon–Thu 10 h
+        let tom = january(employee(
+            40.0,
+            &[
+                ("monday", 10.0),
+                ("tuesday", 10.0),
+                ("wednesday", 10.0),
+                ("thursday", 10.0),
+            ],
+            "2025-01-01",
+        ));
+        assert_eq!(totals(&calculate(&tom).unwrap()), (70.0, 0.0, -70.0));
+
+        // User 57: Sat + Sun 8 h, +72 h from 2025; the 3./4., 10./11. and
+        // 17./18.01.2026 are six weekend days.
+        let mut emma = january(employee(
+            16.0,
+            &[("saturday", 8.0), ("sunday", 8.0)],
+            "2025-01-01",
+        ));
+        emma.carryover = 72.0;
+        let ledger = calculate(&emma).unwrap();
+        assert_eq!(totals(&ledger), (48.0, 0.0, -48.0));
+        assert_eq!(ledger.balance, 24.0);
+    }
+
+    #[test]
+    fn unpaid_leave_reduces_the_target_without_credit() {
+        // User 52, August 2025 (21 workdays): unpaid leave 11.–22.08.
+        // (10 workdays).
+        let mut input = OvertimeInput {
+            from: date("2025-08-01"),
+            to: date("2025-08-31"),
+            holidays: Vec::new(),
+            ..january(employee(40.0, &[], "2024-01-01"))
+        };
+        input.absences = vec![absence(AbsenceKind::Unpaid, "2025-08-11", "2025-08-22")];
+        input.time_entries = [
+            "2025-08-04",
+            "2025-08-05",
+            "2025-08-06",
+            "2025-08-25",
+            "2025-08-26",
+        ]
+        .iter()
+        .map(|day| worked(day, 8.0))
+        .collect();
+        let ledger = calculate(&input).unwrap();
+        assert_eq!(totals(&ledger), (88.0, 40.0, -48.0));
+
+        let unpaid_day: Vec<_> = ledger
+            .transactions
+            .iter()
+            .filter(|t| t.date == date("2025-08-11"))
+            .map(|t| (t.kind, t.hours))
+            .collect();
+        assert_eq!(
+            unpaid_day,
+            vec![
+                (TransactionType::TimeEntry, -8.0),
+                (TransactionType::UnpaidDeduction, 8.0),
+            ]
+        );
+        assert!(ledger.days.iter().all(|d| d.credit_hours == 0.0));
+
+        // Pending absences do not count
+        input.absences[0].status = "pending".into();
+        assert_eq!(calculate(&input).unwrap().overtime, -128.0);
+    }
+
+    #[test]
+    fn period_is_limited_to_the_employment() {
+        // User 55, hired Thu 15.01.2026; the 17th is a Saturday
+        let mut nina = january(employee(40.0, &[], "2026-01-15"));
+        nina.time_entries = vec![worked("2026-01-15", 8.0), worked("2026-01-16", 8.0)];
+        let ledger = calculate(&nina).unwrap();
+        assert_eq!(totals(&ledger), (16.0, 16.0, 0.0));
+        assert_eq!(ledger.days.first().unwrap().date, date("2026-01-15"));
+
+        // User 56, left on 31.12.2025: nothing in January
+        let mut klaus = january(employee(40.0, &[], "2024-01-01"));
+        klaus.employee.end_date = Some(date("2025-12-31"));
+        let ledger = calculate(&klaus).unwrap();
+        assert_eq!(totals(&ledger), (0.0, 0.0, 0.0));
+        assert!(ledger.transactions.is_empty());
+
+        // Corrections are booked on their own
+        let mut corrected = january(employee(40.0, &[], "2024-01-01"));
+        corrected.corrections = vec![Correction {
+            date: date("2026-01-10"),
+            hours: 2.5,
+            reason: None,
+        }];
+        let ledger = calculate(&corrected).unwrap();
+        assert_eq!(ledger.overtime, -77.5);
+        assert!(ledger
+            .transactions
+            .iter()
+            .any(|t| t.kind == TransactionType::Correction
+                && t.date == date("2026-01-10")
+                && t.hours == 2.5));
+
+        assert!(calculate(&OvertimeInput {
+            from: date("2026-02-01"),
+            ..corrected
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn contract_change_keeps_earlier_targets() {
+        // 40 h until 11.01., 30 h from Mon 12.01.
+        let mut input = january(employee(40.0, &[], "2024-01-01"));
+        input.employee.schedule_history = vec![
+            ScheduleVersion {
+                valid_from: date("2024-01-01"),
+                rule: input.employee.rule,
+            },
+            ScheduleVersion {
+                valid_from: date("2026-01-12"),
+                rule: TargetRule {
+                    weekly_hours: 30.0,
+                    work_schedule: None,
+                },
+            },
+        ];
+        let ledger = calculate(&input).unwrap();
+        // 02.01. and 05.–09.01. without the holiday on 06.01.
+        assert_eq!(totals(&ledger), (5.0 * 8.0 + 5.0 * 6.0, 0.0, -70.0));
+
+        input.employee.schedule_history[1].valid_from = date("2024-01-01");
+        assert!(matches!(
+            calculate(&input),
+            Err(OvertimeError::Schedule(ScheduleError::DuplicateValidFrom(
+                _
+            )))
+        ));
+    }
+}
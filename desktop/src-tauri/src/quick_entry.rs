@@ -0,0 +1,286 @@
+//! Small always-on-top window for logging a single time entry.
+//!
+//! Created on demand from the tray or the global shortcut. It loads the
+//! same frontend, which renders only the quick-entry form for this window
+//! label. Its capability (`capabilities/quick-entry.json`) is limited to
+//! the commands of this module, so the window cannot reach the rest of the
+//! app. Entries are queued in the offline store
+//! (`sync::offline_create_time_entry`), the same command the main window's
+//! writes are routed to; its `outbox:changed` event makes the main window
+//! refetch the affected queries.
+//!
+//! An entry saved from the running timer's values also stops the timer, so
+//! the time is not booked twice. Any other entry overlapping the running
+//! session is rejected.
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::auth::AuthState;
+use crate::offline_store::{TimeEntryInput, TimeEntryRecord};
+use crate::sync;
+use crate::timer::WorkTimer;
+use crate::tracking::{self, TrackingState};
+
+pub const WINDOW_LABEL: &str = "quick-entry";
+const WIDTH: f64 = 420.0;
+const HEIGHT: f64 = 460.0;
+
+/// Initial values of the form
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickEntryPrefill {
+    pub date: String,               // YYYY-MM-DD
+    pub start_time: Option<String>, // HH:MM
+    pub end_time: String,           // HH:MM
+    pub break_minutes: i64,
+    pub project: Option<String>,
+    pub activity: Option<String>,
+    pub location: Option<String>,
+    /// Values come from the running timer
+    pub from_timer: bool,
+}
+
+/// Form values; the user comes from the session, not from the window
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickEntryInput {
+    pub date: String,
+    pub start_time: String,
+    pub end_time: String,
+    #[serde(default)]
+    pub break_minutes: i64,
+    pub activity: Option<String>,
+    pub project: Option<String>,
+    pub location: String,
+    pub notes: Option<String>,
+    /// The form was prefilled from the running timer
+    #[serde(default)]
+    pub from_timer: bool,
+}
+
+impl QuickEntryInput {
+    /// Start and end of the entry
+    fn range(&self) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        let at = |time: &str| {
+            NaiveDateTime::parse_from_str(&format!("{} {}", self.date, time), "%Y-%m-%d %H:%M").ok()
+        };
+        Some((at(&self.start_time)?, at(&self.end_time)?))
+    }
+
+    fn into_time_entry(self, user_id: i64) -> TimeEntryInput {
+        TimeEntryInput {
+            user_id,
+            date: self.date,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            break_minutes: self.break_minutes,
+            activity: self.activity,
+            project: self.project,
+            location: self.location,
+            notes: self.notes,
+        }
+    }
+}
+
+/// Whether saving the entry has to stop the timer running since
+/// `session_start`; an error when the entry would overlap the session
+fn stops_timer(
+    input: &QuickEntryInput,
+    session_start: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+) -> Result<bool, String> {
+    let Some(session_start) = session_start else {
+        return Ok(false);
+    };
+    let (start, end) = input.range().ok_or("Ungültiges Datum oder Uhrzeit")?;
+    if input.from_timer {
+        if end < session_start {
+            return Err("Das Ende liegt vor dem Beginn der laufenden Zeiterfassung".into());
+        }
+        return Ok(true);
+    }
+    if start < now && session_start < end {
+        return Err(
+            "Der Zeitraum überschneidet sich mit der laufenden Zeiterfassung – bitte zuerst „Gehen“ buchen"
+                .into(),
+        );
+    }
+    Ok(false)
+}
+
+/// Today's part of the running session up to `now`; without a session
+/// only date and end time are known
+pub fn prefill(timer: &WorkTimer, now: NaiveDateTime) -> QuickEntryPrefill {
+    match timer.preview(now).pop() {
+        Some(draft) => QuickEntryPrefill {
+            date: draft.date,
+            start_time: Some(draft.start_time),
+            end_time: draft.end_time,
+            break_minutes: draft.break_minutes,
+            project: draft.project,
+            activity: draft.activity,
+            location: draft.location,
+            from_timer: true,
+        },
+        None => QuickEntryPrefill {
+            date: now.format("%Y-%m-%d").to_string(),
+            start_time: None,
+            end_time: now.format("%H:%M").to_string(),
+            break_minutes: 0,
+            project: None,
+            activity: None,
+            location: None,
+            from_timer: false,
+        },
+    }
+}
+
+/// Shows the window, creating it on first use
+pub fn open(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        window.show()?;
+        return window.set_focus();
+    }
+    WebviewWindowBuilder::new(app, WINDOW_LABEL, WebviewUrl::App("index.html".into()))
+        .title("Schnellerfassung")
+        .inner_size(WIDTH, HEIGHT)
+        .resizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .center()
+        .focused(true)
+        .build()?;
+    Ok(())
+}
+
+fn close(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        let _ = window.close();
+    }
+}
+
+#[tauri::command]
+pub fn open_quick_entry(app: AppHandle) -> Result<(), String> {
+    open(&app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn close_quick_entry(app: AppHandle) {
+    close(&app);
+}
+
+#[tauri::command]
+pub fn quick_entry_prefill(app: AppHandle) -> Result<QuickEntryPrefill, String> {
+    let state = app.state::<TrackingState>();
+    let timer = state.timer.lock().map_err(|e| e.to_string())?;
+    Ok(prefill(&timer, tracking::now()))
+}
+
+/// Saves the entry for the logged-in user, stops the timer when the entry
+/// was taken from it and closes the window
+#[tauri::command]
+pub fn quick_entry_submit(
+    app: AppHandle,
+    input: QuickEntryInput,
+) -> Result<TimeEntryRecord, String> {
+    let user_id = app
+        .state::<AuthState>()
+        .user_id()
+        .ok_or("Nicht angemeldet – bitte im Hauptfenster anmelden")?;
+    let now = tracking::now();
+    let state = app.state::<TrackingState>();
+    let session_start = state
+        .timer
+        .lock()
+        .map_err(|e| e.to_string())?
+        .snapshot(now)
+        .started_at;
+    let stop_timer = stops_timer(&input, session_start, now)?;
+    let (date, end) = (input.date.clone(), input.range().map(|(_, end)| end));
+
+    let record = sync::offline_create_time_entry(app.clone(), input.into_time_entry(user_id))?;
+    if let (true, Some(end)) = (stop_timer, end) {
+        if let Err(error) = tracking::clock_out_for_entry(&app, end, &date) {
+            log::warn!("Failed to stop the timer after a quick entry: {}", error);
+        }
+    }
+    close(&app);
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn prefill_uses_todays_part_of_running_session() {
+        let mut timer = WorkTimer::default();
+        timer.clock_in(at("2026-03-02 22:00")).unwrap();
+        timer.start_break(at("2026-03-03 00:10")).unwrap();
+        timer.end_break(at("2026-03-03 00:25")).unwrap();
+
+        let prefill = prefill(&timer, at("2026-03-03 01:30"));
+        assert!(prefill.from_timer);
+        assert_eq!(prefill.date, "2026-03-03");
+        assert_eq!(prefill.start_time.as_deref(), Some("00:00"));
+        assert_eq!(prefill.end_time, "01:30");
+        assert_eq!(prefill.break_minutes, 15);
+        // Preview must not stop the timer
+        assert_eq!(timer.phase(), crate::timer::TimerPhase::Running);
+    }
+
+    fn input(start: &str, end: &str, from_timer: bool) -> QuickEntryInput {
+        QuickEntryInput {
+            date: "2026-03-03".into(),
+            start_time: start.into(),
+            end_time: end.into(),
+            break_minutes: 0,
+            activity: None,
+            project: None,
+            location: "office".into(),
+            notes: None,
+            from_timer,
+        }
+    }
+
+    #[test]
+    fn entries_from_the_timer_stop_it_and_others_must_not_overlap() {
+        let (started, now) = (Some(at("2026-03-03 08:00")), at("2026-03-03 12:00"));
+        assert_eq!(
+            stops_timer(&input("08:00", "12:00", true), started, now),
+            Ok(true)
+        );
+        assert!(stops_timer(&input("06:00", "07:30", true), started, now).is_err());
+        // Overlaps the running session
+        assert!(stops_timer(&input("07:00", "09:00", false), started, now).is_err());
+        // Before the session, after now, or no session at all
+        assert_eq!(
+            stops_timer(&input("06:00", "08:00", false), started, now),
+            Ok(false)
+        );
+        assert_eq!(
+            stops_timer(&input("12:00", "13:00", false), started, now),
+            Ok(false)
+        );
+        assert_eq!(
+            stops_timer(&input("08:00", "12:00", true), None, now),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn prefill_without_session_ends_now() {
+        let prefill = prefill(&WorkTimer::default(), at("2026-03-02 16:45"));
+        assert!(!prefill.from_timer);
+        assert_eq!(prefill.date, "2026-03-02");
+        assert_eq!(prefill.start_time, None);
+        assert_eq!(prefill.end_time, "16:45");
+    }
+}
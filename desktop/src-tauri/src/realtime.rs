@@ -0,0 +1,228 @@
+//! Native WebSocket connection to the server.
+//!
+//! The webview's own socket is throttled while the window is hidden in the
+//! tray, so the Rust side keeps the connection instead: it connects to the
+//! `wsUrl` of the active server profile as soon as someone is logged in,
+//! sends the `auth` handshake, pings every `HEARTBEAT_INTERVAL` and
+//! reconnects with backoff. Server events are re-emitted as Tauri events
+//! of the same name (see `server_events`); approvals and rejections of the
+//! user's own absences also raise a native notification.
+//!
+//! Login, logout and profile switches restart the connection.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Listener, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::Notify;
+use tokio_tungstenite::{
+    connect_async_tls_with_config,
+    tungstenite::{
+        client::IntoClientRequest,
+        http::{header::USER_AGENT, HeaderValue},
+        Message,
+    },
+    Connector,
+};
+
+use crate::api_client::{self, ApiClient};
+use crate::auth::{self, AuthState};
+use crate::server_events::{self, Incoming, ServerEvent, HEARTBEAT_INTERVAL, HEARTBEAT_TIMEOUT};
+use crate::server_profile::ServerProfile;
+
+/// Emitted with a `RealtimeStatus` whenever the connection state changes
+pub const EVENT_REALTIME_STATUS: &str = "realtime:status";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RealtimeStatus {
+    pub connected: bool,
+    pub reconnecting: bool,
+}
+
+#[derive(Default)]
+pub struct RealtimeState {
+    /// Wakes the supervisor after login, logout or a profile switch
+    restart: Notify,
+    status: Mutex<RealtimeStatus>,
+}
+
+pub fn setup(app: &AppHandle) {
+    app.manage(RealtimeState::default());
+    for event in [auth::EVENT_AUTH_CHANGED, api_client::EVENT_PROFILE_CHANGED] {
+        let handle = app.clone();
+        app.listen(event, move |_| {
+            handle.state::<RealtimeState>().restart.notify_one();
+        });
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { supervise(app).await });
+}
+
+fn set_status(app: &AppHandle, connected: bool, reconnecting: bool) {
+    let status = RealtimeStatus {
+        connected,
+        reconnecting,
+    };
+    let state = app.state::<RealtimeState>();
+    let Ok(mut current) = state.status.lock() else {
+        return;
+    };
+    if *current != status {
+        *current = status;
+        let _ = app.emit(EVENT_REALTIME_STATUS, status);
+    }
+}
+
+/// Keeps one connection per login alive for the lifetime of the app
+async fn supervise(app: AppHandle) {
+    let mut attempt = 0;
+    loop {
+        let state = app.state::<RealtimeState>();
+        let Some(user_id) = app.state::<AuthState>().user_id() else {
+            set_status(&app, false, false);
+            state.restart.notified().await;
+            attempt = 0;
+            continue;
+        };
+        let profile = app.state::<ApiClient>().profile();
+
+        tokio::select! {
+            result = run(&app, &profile, user_id, &mut attempt) => {
+                if let Err(error) = result {
+                    log::warn!("WebSocket {}: {}", profile.ws_url, error);
+                }
+            }
+            _ = state.restart.notified() => {
+                attempt = 0;
+                continue;
+            }
+        }
+
+        set_status(&app, false, true);
+        let delay = server_events::reconnect_delay(attempt);
+        attempt = attempt.saturating_add(1);
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = state.restart.notified() => attempt = 0,
+        }
+    }
+}
+
+/// One connection: handshake, then events and heartbeat until it drops.
+/// Resets `attempt` once the server confirmed the handshake.
+async fn run(
+    app: &AppHandle,
+    profile: &ServerProfile,
+    user_id: i64,
+    attempt: &mut u32,
+) -> Result<(), String> {
+    let mut request = profile
+        .ws_url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| e.to_string())?;
+    request.headers_mut().insert(
+        USER_AGENT,
+        HeaderValue::from_static(concat!("TimeTracker-Desktop/", env!("CARGO_PKG_VERSION"))),
+    );
+    let connector = match &profile.pinned_certificate {
+        Some(pem) => Some(Connector::Rustls(Arc::new(pinned_tls(pem)?))),
+        None => None,
+    };
+
+    let (mut socket, _) = tokio::time::timeout(
+        CONNECT_TIMEOUT,
+        connect_async_tls_with_config(request, None, false, connector),
+    )
+    .await
+    .map_err(|_| "Zeitüberschreitung beim Verbindungsaufbau".to_string())?
+    .map_err(|e| e.to_string())?;
+
+    socket
+        .send(Message::text(server_events::auth_message(user_id)))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    let mut last_seen = Instant::now();
+    loop {
+        tokio::select! {
+            frame = socket.next() => {
+                last_seen = Instant::now();
+                match frame {
+                    Some(Ok(Message::Text(text))) => match server_events::parse(&text) {
+                        Some(Incoming::AuthSuccess) => {
+                            *attempt = 0;
+                            set_status(app, true, false);
+                        }
+                        Some(Incoming::Event(event)) => dispatch(app, &event, user_id),
+                        None => log::warn!("Ignoring WebSocket message: {}", text.as_str()),
+                    },
+                    // Pings are answered by tungstenite itself
+                    Some(Ok(Message::Close(_))) | None => return Ok(()),
+                    Some(Ok(_)) => {}
+                    Some(Err(error)) => return Err(error.to_string()),
+                }
+            }
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() > HEARTBEAT_TIMEOUT {
+                    return Err("keine Antwort vom Server (Heartbeat)".to_string());
+                }
+                socket
+                    .send(Message::Ping(Default::default()))
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+}
+
+/// TLS config that trusts only the profile's pinned certificate
+fn pinned_tls(pem: &str) -> Result<rustls::ClientConfig, String> {
+    use rustls::pki_types::{pem::PemObject, CertificateDer};
+
+    let mut roots = rustls::RootCertStore::empty();
+    for certificate in CertificateDer::pem_slice_iter(pem.as_bytes()) {
+        let certificate = certificate.map_err(|e| format!("Zertifikat: {}", e))?;
+        roots
+            .add(certificate)
+            .map_err(|e| format!("Zertifikat: {}", e))?;
+    }
+    Ok(rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| e.to_string())?
+    .with_root_certificates(roots)
+    .with_no_client_auth())
+}
+
+fn dispatch(app: &AppHandle, event: &ServerEvent, user_id: i64) {
+    let _ = app.emit(event.kind.event_name(), event);
+
+    if let Some(notification) = server_events::notification(event, user_id) {
+        if let Err(error) = app
+            .notification()
+            .builder()
+            .title(&notification.title)
+            .body(&notification.body)
+            .show()
+        {
+            log::warn!("Failed to show notification: {}", error);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn realtime_status(state: State<'_, RealtimeState>) -> RealtimeStatus {
+    state.status.lock().map(|s| *s).unwrap_or_default()
+}
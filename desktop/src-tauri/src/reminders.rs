@@ -0,0 +1,285 @@
+//! Proactive ArbZG reminders for the current workday.
+//!
+//! Tracks what the user has worked today (synced entries + running timer)
+//! and when they last clocked out, and decides which notification is due.
+//! Each reminder fires at most once per workday.
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::Serialize;
+
+use crate::arbzg::{self, BookedEntry};
+use crate::timer::{TimerPhase, WorkTimer};
+
+const FIRST_BREAK_AFTER_HOURS: i64 = 6;
+const SECOND_BREAK_AFTER_HOURS: i64 = 9;
+const MAX_DAILY_HOURS: i64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReminderKind {
+    BreakAfterSixHours,
+    BreakAfterNineHours,
+    MaxDailyHours,
+    RestPeriod,
+}
+
+/// A reminder that should be shown as native notification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reminder {
+    pub kind: ReminderKind,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorkdayTracker {
+    /// Entries from the last sync with the server
+    booked: Vec<BookedEntry>,
+    /// Clock-out recorded locally by the timer (may not be synced yet)
+    last_local_clock_out: Option<NaiveDateTime>,
+    day: Option<NaiveDate>,
+    fired: Vec<ReminderKind>,
+}
+
+impl WorkdayTracker {
+    pub fn set_booked(&mut self, entries: Vec<BookedEntry>) {
+        self.booked = entries;
+    }
+
+    pub fn record_clock_out(&mut self, at: NaiveDateTime) {
+        self.last_local_clock_out = Some(at);
+    }
+
+    /// Latest end of a shift before `now`, from synced entries or the timer
+    pub fn last_clock_out(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.booked
+            .iter()
+            .filter_map(|entry| {
+                let date = arbzg::parse_date(&entry.date).ok()?;
+                let end = arbzg::parse_time(&entry.end_time).ok()?;
+                let start = arbzg::parse_time(&entry.start_time).ok()?;
+                // Overnight entries end on the following day
+                let end_date = if end < start { date.succ_opt()? } else { date };
+                Some(NaiveDateTime::new(end_date, end))
+            })
+            .chain(self.last_local_clock_out)
+            .filter(|end| *end <= now)
+            .max()
+    }
+
+    /// Rest-period check (§5 ArbZG) when clocking in
+    pub fn on_clock_in(&mut self, now: NaiveDateTime) -> Option<Reminder> {
+        self.reset_if_new_day(now.date());
+        let last_end = self.last_clock_out(now)?;
+        arbzg::check_rest_between(last_end, now)?;
+
+        let rest = now - last_end;
+        let earliest = last_end + Duration::hours(arbzg::MIN_REST_HOURS as i64);
+        self.fired.push(ReminderKind::RestPeriod);
+        Some(Reminder {
+            kind: ReminderKind::RestPeriod,
+            title: "Ruhezeit unterschritten".to_string(),
+            body: format!(
+                "Seit Arbeitsende am {} sind erst {}h vergangen. Vorgeschrieben sind {}h Ruhezeit (frühester Beginn {} Uhr).",
+                last_end.format("%d.%m. um %H:%M"),
+                crate::timer::format_hours_minutes(rest),
+                arbzg::MIN_REST_HOURS,
+                earliest.format("%H:%M"),
+            ),
+        })
+    }
+
+    /// Limits exceeded right now by today's work, most severe first
+    pub fn violations(&self, timer: &WorkTimer, now: NaiveDateTime) -> Vec<ReminderKind> {
+        if timer.phase() == TimerPhase::Idle {
+            return Vec::new();
+        }
+
+        let worked = self.booked_today(now.date()) + timer.worked(now);
+        let break_minutes = timer.break_taken(now).num_minutes();
+
+        let mut kinds = Vec::new();
+        if worked >= Duration::hours(MAX_DAILY_HOURS) {
+            kinds.push(ReminderKind::MaxDailyHours);
+        }
+        if worked >= Duration::hours(SECOND_BREAK_AFTER_HOURS) && break_minutes < 45 {
+            kinds.push(ReminderKind::BreakAfterNineHours);
+        }
+        if worked >= Duration::hours(FIRST_BREAK_AFTER_HOURS) && break_minutes < 30 {
+            kinds.push(ReminderKind::BreakAfterSixHours);
+        }
+        kinds
+    }
+
+    /// Reminders that became due since the last evaluation
+    pub fn evaluate(&mut self, timer: &WorkTimer, now: NaiveDateTime) -> Vec<Reminder> {
+        self.reset_if_new_day(now.date());
+        let break_minutes = timer.break_taken(now).num_minutes();
+
+        let mut due: Vec<Reminder> = self
+            .violations(timer, now)
+            .into_iter()
+            .map(|kind| match kind {
+                ReminderKind::MaxDailyHours => Reminder {
+                    kind,
+                    title: "10 Stunden Höchstarbeitszeit erreicht".to_string(),
+                    body: "Die tägliche Höchstarbeitszeit nach §3 ArbZG ist erreicht. Bitte beenden Sie Ihren Arbeitstag.".to_string(),
+                },
+                ReminderKind::BreakAfterNineHours => Reminder {
+                    kind,
+                    title: "9 Stunden – 45 Min Pause".to_string(),
+                    body: format!(
+                        "Nach 9 Stunden Arbeit sind insgesamt 45 Minuten Pause vorgeschrieben (§4 ArbZG). Bisher: {} Min.",
+                        break_minutes
+                    ),
+                },
+                _ => Reminder {
+                    kind,
+                    title: "6 Stunden erreicht – 30 Min Pause fällig".to_string(),
+                    body: format!(
+                        "Nach 6 Stunden Arbeit sind 30 Minuten Pause vorgeschrieben (§4 ArbZG). Bisher: {} Min.",
+                        break_minutes
+                    ),
+                },
+            })
+            .collect();
+
+        // The 9h break reminder supersedes the 6h one if both are due at once
+        if due
+            .iter()
+            .any(|r| r.kind == ReminderKind::BreakAfterNineHours)
+        {
+            self.fired.push(ReminderKind::BreakAfterSixHours);
+        }
+
+        due.retain(|r| !self.fired.contains(&r.kind));
+        self.fired.extend(due.iter().map(|r| r.kind));
+        due
+    }
+
+    fn booked_today(&self, today: NaiveDate) -> Duration {
+        let today = today.format("%Y-%m-%d").to_string();
+        let hours: f64 = self
+            .booked
+            .iter()
+            .filter(|e| e.date == today)
+            .map(|e| e.hours)
+            .sum();
+        Duration::minutes((hours * 60.0).round() as i64)
+    }
+
+    fn reset_if_new_day(&mut self, today: NaiveDate) {
+        if self.day != Some(today) {
+            self.day = Some(today);
+            self.fired.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn kinds(reminders: &[Reminder]) -> Vec<ReminderKind> {
+        reminders.iter().map(|r| r.kind).collect()
+    }
+
+    #[test]
+    fn break_reminders_fire_once_per_day() {
+        let mut tracker = WorkdayTracker::default();
+        let mut timer = WorkTimer::default();
+        timer.clock_in(at("2026-03-02 07:00")).unwrap();
+
+        assert!(tracker.evaluate(&timer, at("2026-03-02 12:59")).is_empty());
+        assert_eq!(
+            kinds(&tracker.evaluate(&timer, at("2026-03-02 13:00"))),
+            vec![ReminderKind::BreakAfterSixHours]
+        );
+        assert!(tracker.evaluate(&timer, at("2026-03-02 13:10")).is_empty());
+
+        timer.start_break(at("2026-03-02 13:10")).unwrap();
+        timer.end_break(at("2026-03-02 13:40")).unwrap();
+        assert_eq!(
+            kinds(&tracker.evaluate(&timer, at("2026-03-02 16:30"))),
+            vec![ReminderKind::BreakAfterNineHours]
+        );
+        assert_eq!(
+            kinds(&tracker.evaluate(&timer, at("2026-03-02 17:30"))),
+            vec![ReminderKind::MaxDailyHours]
+        );
+    }
+
+    #[test]
+    fn violations_last_until_resolved() {
+        let tracker = WorkdayTracker::default();
+        let mut timer = WorkTimer::default();
+        timer.clock_in(at("2026-03-02 07:00")).unwrap();
+        assert!(tracker
+            .violations(&timer, at("2026-03-02 12:00"))
+            .is_empty());
+        assert_eq!(
+            tracker.violations(&timer, at("2026-03-02 16:30")),
+            vec![
+                ReminderKind::BreakAfterNineHours,
+                ReminderKind::BreakAfterSixHours
+            ]
+        );
+
+        timer.start_break(at("2026-03-02 16:30")).unwrap();
+        timer.end_break(at("2026-03-02 17:00")).unwrap();
+        assert_eq!(
+            tracker.violations(&timer, at("2026-03-02 17:00")),
+            vec![ReminderKind::BreakAfterNineHours]
+        );
+    }
+
+    #[test]
+    fn booked_hours_of_today_count_towards_limits() {
+        let mut tracker = WorkdayTracker::default();
+        tracker.set_booked(vec![BookedEntry {
+            id: 1,
+            date: "2026-03-02".into(),
+            start_time: "06:00".into(),
+            end_time: "10:00".into(),
+            hours: 4.0,
+        }]);
+        let mut timer = WorkTimer::default();
+        timer.clock_in(at("2026-03-02 11:00")).unwrap();
+
+        assert_eq!(
+            kinds(&tracker.evaluate(&timer, at("2026-03-02 13:00"))),
+            vec![ReminderKind::BreakAfterSixHours]
+        );
+    }
+
+    #[test]
+    fn clock_in_warns_about_short_rest_period() {
+        let mut tracker = WorkdayTracker::default();
+        tracker.set_booked(vec![BookedEntry {
+            id: 1,
+            date: "2026-03-01".into(),
+            start_time: "14:00".into(),
+            end_time: "22:30".into(),
+            hours: 8.0,
+        }]);
+
+        let reminder = tracker.on_clock_in(at("2026-03-02 07:00")).unwrap();
+        assert_eq!(reminder.kind, ReminderKind::RestPeriod);
+        assert!(reminder.body.contains("8:30h"));
+        assert!(reminder.body.contains("09:30"));
+
+        assert_eq!(tracker.on_clock_in(at("2026-03-02 09:30")), None);
+    }
+
+    #[test]
+    fn local_clock_out_counts_as_last_shift() {
+        let mut tracker = WorkdayTracker::default();
+        tracker.record_clock_out(at("2026-03-02 23:00"));
+        assert!(tracker.on_clock_in(at("2026-03-03 06:00")).is_some());
+    }
+}
@@ -0,0 +1,611 @@
+//! Year-end rollover dry run
+//!
+//! Runs the rules of `server/src/services/yearEndRolloverService.ts` on an
+//! exported snapshot of balances instead of the live database, so HR can
+//! review the result before the cron job (`cronService.ts`) performs the
+//! real rollover on January 1st:
+//!
+//! - Vacation: remaining days (entitlement + carryover − taken) of the
+//!   previous year are carried over, optionally capped
+//! - Overtime: the year-end balance is carried over; a cap only applies to
+//!   positive balances, deficits are always carried in full
+//! - Expiry: with `vacation_expires`, carried-over days not booked by
+//!   31.03. of the new year are reported as expiring (§7 Abs. 3 BUrlG)
+//!
+//! The default policy is the server's (no caps, no expiry). The report can
+//! be saved as a summary file signed with HMAC-SHA256; the key is kept in
+//! the OS keyring like the session key, so only this installation can
+//! confirm that an archived summary is unchanged.
+
+use std::{collections::HashSet, fmt, fs, io, path::PathBuf};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::secure_store::{SecureStoreError, SessionKey};
+use crate::session_store::write_atomic;
+
+const KEYRING_USER: &str = "rollover-signing-key";
+const KEY_FILE_NAME: &str = "rollover-signing.key";
+const FORMAT_VERSION: u8 = 1;
+
+#[derive(Debug)]
+pub enum RolloverError {
+    InvalidYear(i32),
+    DuplicateUser(i64),
+    Io(io::Error),
+    Json(serde_json::Error),
+    Key(SecureStoreError),
+}
+
+impl fmt::Display for RolloverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RolloverError::InvalidYear(year) => {
+                write!(f, "Ungültiges Jahr: {} (erlaubt 2000–2100)", year)
+            }
+            RolloverError::DuplicateUser(id) => {
+                write!(f, "Mitarbeiter #{} ist mehrfach im Export enthalten", id)
+            }
+            RolloverError::Io(e) => write!(f, "Zusammenfassung: {}", e),
+            RolloverError::Json(e) => write!(f, "Ungültige Zusammenfassung: {}", e),
+            RolloverError::Key(e) => write!(f, "Signaturschlüssel: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RolloverError {}
+
+impl From<io::Error> for RolloverError {
+    fn from(e: io::Error) -> Self {
+        RolloverError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for RolloverError {
+    fn from(e: serde_json::Error) -> Self {
+        RolloverError::Json(e)
+    }
+}
+
+impl From<SecureStoreError> for RolloverError {
+    fn from(e: SecureStoreError) -> Self {
+        RolloverError::Key(e)
+    }
+}
+
+/// `vacation_balance` row of the previous year
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VacationBalanceRow {
+    pub entitlement: f64,
+    pub carryover: f64,
+    pub taken: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Carryover {
+    pub vacation_days: f64,
+    pub overtime_hours: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserSnapshot {
+    pub user_id: i64,
+    pub first_name: String,
+    pub last_name: String,
+    #[serde(default)]
+    pub hire_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub end_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub vacation: Option<VacationBalanceRow>,
+    /// Overtime balance on 31.12. of the previous year
+    #[serde(default)]
+    pub overtime_balance: Option<f64>,
+    /// Carryover already stored for the new year (earlier run or manual)
+    #[serde(default)]
+    pub current: Option<Carryover>,
+    /// Approved vacation days in the new year up to 31.03.
+    #[serde(default)]
+    pub vacation_booked_until_expiry: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloverSnapshot {
+    /// New year, e.g. 2027
+    pub year: i32,
+    pub users: Vec<UserSnapshot>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloverPolicy {
+    /// Max vacation days carried over; `None` = unlimited
+    #[serde(default)]
+    pub max_vacation_carryover: Option<f64>,
+    /// Carried-over vacation expires after 31.03. of the new year
+    #[serde(default)]
+    pub vacation_expires: bool,
+    /// Max positive overtime hours carried over; `None` = unlimited
+    #[serde(default)]
+    pub max_overtime_carryover: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserRollover {
+    pub user_id: i64,
+    pub name: String,
+    /// Remaining vacation of the previous year
+    pub vacation_remaining: f64,
+    pub vacation_carryover: f64,
+    /// Cut off by `max_vacation_carryover`
+    pub vacation_capped: f64,
+    /// Carried-over days not booked by the expiry date
+    pub vacation_expiring: f64,
+    pub overtime_balance: f64,
+    pub overtime_carryover: f64,
+    /// Cut off by `max_overtime_carryover`
+    pub overtime_capped: f64,
+    pub current: Option<Carryover>,
+    /// The rollover would change the stored carryover
+    pub changed: bool,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloverTotals {
+    pub vacation_carryover: f64,
+    pub vacation_capped: f64,
+    pub vacation_expiring: f64,
+    pub overtime_carryover: f64,
+    pub overtime_capped: f64,
+    pub users_changed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloverReport {
+    pub year: i32,
+    pub previous_year: i32,
+    pub policy: RolloverPolicy,
+    pub expiry_date: Option<NaiveDate>,
+    pub users: Vec<UserRollover>,
+    pub totals: RolloverTotals,
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn simulate_user(user: &UserSnapshot, year: i32, policy: &RolloverPolicy) -> UserRollover {
+    let mut warnings = Vec::new();
+    match user.hire_date {
+        None => warnings.push("Eintrittsdatum fehlt".to_string()),
+        Some(hire) if hire.year() == year => {
+            warnings.push(format!("Eintritt {} – kein Übertrag erwartet", year))
+        }
+        Some(_) => {}
+    }
+
+    let vacation_remaining = match user.vacation {
+        Some(row) => round2(row.entitlement + row.carryover - row.taken),
+        None => {
+            warnings.push(format!("Kein Urlaubskonto für {}", year - 1));
+            0.0
+        }
+    };
+    if vacation_remaining < 0.0 {
+        warnings.push(format!(
+            "Urlaubskonto überzogen ({} Tage), es wird nichts übertragen",
+            vacation_remaining
+        ));
+    }
+    let uncapped = vacation_remaining.max(0.0);
+    let vacation_carryover = policy
+        .max_vacation_carryover
+        .map_or(uncapped, |max| uncapped.min(max));
+    let vacation_expiring = if policy.vacation_expires {
+        round2((vacation_carryover - user.vacation_booked_until_expiry).max(0.0))
+    } else {
+        0.0
+    };
+
+    let overtime_balance = match user.overtime_balance {
+        Some(hours) => round2(hours),
+        None => {
+            warnings.push(format!("Kein Überstundensaldo für {}", year - 1));
+            0.0
+        }
+    };
+    let overtime_carryover = policy
+        .max_overtime_carryover
+        .map_or(overtime_balance, |max| overtime_balance.min(max));
+
+    let changed = user.current.is_none_or(|current| {
+        current.vacation_days != vacation_carryover || current.overtime_hours != overtime_carryover
+    });
+    UserRollover {
+        user_id: user.user_id,
+        name: format!("{} {}", user.first_name, user.last_name),
+        vacation_remaining,
+        vacation_carryover,
+        vacation_capped: round2(uncapped - vacation_carryover),
+        vacation_expiring,
+        overtime_balance,
+        overtime_carryover,
+        overtime_capped: round2(overtime_balance - overtime_carryover),
+        current: user.current,
+        changed,
+        warnings,
+    }
+}
+
+/// Dry run of the rollover into `snapshot.year`. Users who left before
+/// January 1st of the new year are skipped, like inactive users on the
+/// server.
+pub fn simulate(
+    snapshot: &RolloverSnapshot,
+    policy: &RolloverPolicy,
+) -> Result<RolloverReport, RolloverError> {
+    let year = snapshot.year;
+    if !(2000..=2100).contains(&year) {
+        return Err(RolloverError::InvalidYear(year));
+    }
+    let new_year = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+    let mut seen = HashSet::new();
+    let mut users = Vec::new();
+    for user in &snapshot.users {
+        if !seen.insert(user.user_id) {
+            return Err(RolloverError::DuplicateUser(user.user_id));
+        }
+        if user.end_date.is_some_and(|end| end < new_year) {
+            continue;
+        }
+        users.push(simulate_user(user, year, policy));
+    }
+
+    let totals = users
+        .iter()
+        .fold(RolloverTotals::default(), |t, u| RolloverTotals {
+            vacation_carryover: round2(t.vacation_carryover + u.vacation_carryover),
+            vacation_capped: round2(t.vacation_capped + u.vacation_capped),
+            vacation_expiring: round2(t.vacation_expiring + u.vacation_expiring),
+            overtime_carryover: round2(t.overtime_carryover + u.overtime_carryover),
+            overtime_capped: round2(t.overtime_capped + u.overtime_capped),
+            users_changed: t.users_changed + usize::from(u.changed),
+        });
+    Ok(RolloverReport {
+        year,
+        previous_year: year - 1,
+        policy: *policy,
+        expiry_date: policy
+            .vacation_expires
+            .then(|| NaiveDate::from_ymd_opt(year, 3, 31).unwrap()),
+        users,
+        totals,
+    })
+}
+
+/// Archived summary file. `version`, `createdAt` and `report` are kept as
+/// the exact JSON text that was signed, so verifying never depends on
+/// serializing the report again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedSummary {
+    pub version: Box<RawValue>,
+    pub created_at: Box<RawValue>,
+    pub report: Box<RawValue>,
+    /// Hex SHA-256 of the report JSON
+    pub sha256: String,
+    /// HMAC-SHA256 of version, creation time and report (base64url)
+    pub signature: String,
+}
+
+/// Length-prefixed parts, so no part can absorb bytes of its neighbour
+fn signed_message(summary: &SignedSummary) -> Vec<u8> {
+    let mut message = Vec::new();
+    for part in [&summary.version, &summary.created_at, &summary.report] {
+        let bytes = part.get().as_bytes();
+        message.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+        message.extend_from_slice(bytes);
+    }
+    message
+}
+
+fn hex_sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn mac(key: &SessionKey, bytes: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("any key length");
+    mac.update(bytes);
+    mac
+}
+
+pub fn sign(
+    key: &SessionKey,
+    report: &RolloverReport,
+    created_at: NaiveDateTime,
+) -> Result<SignedSummary, RolloverError> {
+    let report = RawValue::from_string(serde_json::to_string(report)?)?;
+    let mut summary = SignedSummary {
+        version: RawValue::from_string(FORMAT_VERSION.to_string())?,
+        created_at: RawValue::from_string(serde_json::to_string(&created_at)?)?,
+        sha256: hex_sha256(report.get().as_bytes()),
+        report,
+        signature: String::new(),
+    };
+    summary.signature =
+        URL_SAFE_NO_PAD.encode(mac(key, &signed_message(&summary)).finalize().into_bytes());
+    Ok(summary)
+}
+
+/// `false` if the summary was changed or signed by another installation
+pub fn verify(key: &SessionKey, summary: &SignedSummary) -> bool {
+    let Ok(signature) = URL_SAFE_NO_PAD.decode(&summary.signature) else {
+        return false;
+    };
+    summary.version.get() == FORMAT_VERSION.to_string()
+        && summary.sha256 == hex_sha256(summary.report.get().as_bytes())
+        && mac(key, &signed_message(summary))
+            .verify_slice(&signature)
+            .is_ok()
+}
+
+fn signing_key(app: &AppHandle) -> Result<SessionKey, RolloverError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."));
+    Ok(SessionKey::load_or_create_as(
+        &dir,
+        KEYRING_USER,
+        KEY_FILE_NAME,
+    )?)
+}
+
+/// Reads an exported snapshot picked with the open dialog; `None` when the
+/// dialog was cancelled
+#[tauri::command]
+pub async fn load_rollover_snapshot(app: AppHandle) -> Result<Option<RolloverSnapshot>, String> {
+    let chosen = app
+        .dialog()
+        .file()
+        .add_filter("Export", &["json"])
+        .blocking_pick_file();
+    let path = match chosen {
+        Some(chosen) => chosen.into_path().map_err(|e| e.to_string())?,
+        None => return Ok(None),
+    };
+    let run = || -> Result<RolloverSnapshot, RolloverError> {
+        Ok(serde_json::from_slice(&fs::read(&path)?)?)
+    };
+    run().map(Some).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn simulate_rollover(
+    snapshot: RolloverSnapshot,
+    policy: Option<RolloverPolicy>,
+) -> Result<RolloverReport, String> {
+    simulate(&snapshot, &policy.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+/// Signs the report and writes it to the location chosen with the save
+/// dialog; `None` when the dialog was cancelled
+#[tauri::command]
+pub async fn save_rollover_summary(
+    app: AppHandle,
+    report: RolloverReport,
+) -> Result<Option<SignedSummary>, String> {
+    let chosen = app
+        .dialog()
+        .file()
+        .set_file_name(format!("Jahreswechsel_{}.json", report.year))
+        .add_filter("Zusammenfassung", &["json"])
+        .blocking_save_file();
+    let path = match chosen {
+        Some(chosen) => chosen.into_path().map_err(|e| e.to_string())?,
+        None => return Ok(None),
+    };
+    let run = || -> Result<SignedSummary, RolloverError> {
+        let summary = sign(&signing_key(&app)?, &report, Local::now().naive_local())?;
+        write_atomic(&path, &serde_json::to_vec_pretty(&summary)?)?;
+        Ok(summary)
+    };
+    run().map(Some).map_err(|e| e.to_string())
+}
+
+/// Checks a summary picked with the open dialog; `None` when the dialog was
+/// cancelled
+#[tauri::command]
+pub async fn verify_rollover_summary(app: AppHandle) -> Result<Option<bool>, String> {
+    let chosen = app
+        .dialog()
+        .file()
+        .add_filter("Zusammenfassung", &["json"])
+        .blocking_pick_file();
+    let path = match chosen {
+        Some(chosen) => chosen.into_path().map_err(|e| e.to_string())?,
+        None => return Ok(None),
+    };
+    let run = || -> Result<bool, RolloverError> {
+        let summary: SignedSummary = serde_json::from_slice(&fs::read(&path)?)?;
+        Ok(verify(&signing_key(&app)?, &summary))
+    };
+    run().map(Some).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn user(id: i64, remaining: f64, overtime: f64) -> UserSnapshot {
+        UserSnapshot {
+            user_id: id,
+            first_name: "Max".into(),
+            last_name: format!("Muster{}", id),
+            hire_date: Some(date("2020-01-01")),
+            end_date: None,
+            vacation: Some(VacationBalanceRow {
+                entitlement: 30.0,
+                carryover: 2.0,
+                taken: 32.0 - remaining,
+            }),
+            overtime_balance: Some(overtime),
+            current: None,
+            vacation_booked_until_expiry: 0.0,
+        }
+    }
+
+    fn snapshot(users: Vec<UserSnapshot>) -> RolloverSnapshot {
+        RolloverSnapshot { year: 2027, users }
+    }
+
+    #[test]
+    fn default_policy_carries_everything_over() {
+        let mut unchanged = user(2, 3.0, -12.5);
+        unchanged.current = Some(Carryover {
+            vacation_days: 3.0,
+            overtime_hours: -12.5,
+        });
+        let mut left = user(3, 5.0, 0.0);
+        left.end_date = Some(date("2026-12-31"));
+        let report = simulate(
+            &snapshot(vec![user(1, 12.5, 40.0), unchanged, left]),
+            &RolloverPolicy::default(),
+        )
+        .unwrap();
+
+        assert_eq!(report.previous_year, 2026);
+        assert_eq!(report.expiry_date, None);
+        assert_eq!(report.users.len(), 2);
+        assert_eq!(report.users[0].vacation_carryover, 12.5);
+        assert_eq!(report.users[0].overtime_carryover, 40.0);
+        assert!(report.users[0].changed);
+        assert!(!report.users[1].changed);
+        assert_eq!(
+            report.totals,
+            RolloverTotals {
+                vacation_carryover: 15.5,
+                overtime_carryover: 27.5,
+                users_changed: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn caps_and_expiry() {
+        let mut booked = user(1, 8.0, 120.0);
+        booked.vacation_booked_until_expiry = 3.0;
+        let mut overdrawn = user(2, -2.0, -30.0);
+        overdrawn.vacation = None;
+        let mut new_hire = user(3, -1.0, 0.0);
+        new_hire.hire_date = Some(date("2027-02-01"));
+        let policy = RolloverPolicy {
+            max_vacation_carryover: Some(5.0),
+            vacation_expires: true,
+            max_overtime_carryover: Some(100.0),
+        };
+        let report = simulate(&snapshot(vec![booked, overdrawn, new_hire]), &policy).unwrap();
+        assert_eq!(report.expiry_date, Some(date("2027-03-31")));
+
+        let first = &report.users[0];
+        assert_eq!(
+            (
+                first.vacation_carryover,
+                first.vacation_capped,
+                first.vacation_expiring
+            ),
+            (5.0, 3.0, 2.0)
+        );
+        assert_eq!(
+            (first.overtime_carryover, first.overtime_capped),
+            (100.0, 20.0)
+        );
+
+        // Deficits are carried in full
+        assert_eq!(report.users[1].overtime_carryover, -30.0);
+        assert_eq!(report.users[1].warnings, vec!["Kein Urlaubskonto für 2026"]);
+        assert_eq!(report.users[2].vacation_carryover, 0.0);
+        assert_eq!(report.users[2].warnings.len(), 2);
+
+        assert!(matches!(
+            simulate(
+                &snapshot(vec![user(1, 0.0, 0.0), user(1, 0.0, 0.0)]),
+                &policy
+            ),
+            Err(RolloverError::DuplicateUser(1))
+        ));
+        assert!(matches!(
+            simulate(
+                &RolloverSnapshot {
+                    year: 1999,
+                    users: Vec::new()
+                },
+                &policy
+            ),
+            Err(RolloverError::InvalidYear(1999))
+        ));
+    }
+
+    #[test]
+    fn signed_summary_detects_changes() {
+        let key = SessionKey::generate();
+        let report = simulate(
+            &snapshot(vec![user(1, 4.0, 8.0)]),
+            &RolloverPolicy::default(),
+        )
+        .unwrap();
+        let created = NaiveDateTime::parse_from_str("2026-12-15 10:00", "%Y-%m-%d %H:%M").unwrap();
+        let summary = sign(&key, &report, created).unwrap();
+        assert_eq!(summary.sha256.len(), 64);
+
+        // Round trip through the file format
+        let json = serde_json::to_vec_pretty(&summary).unwrap();
+        let read: SignedSummary = serde_json::from_slice(&json).unwrap();
+        assert!(verify(&key, &read));
+        let stored: RolloverReport = serde_json::from_str(read.report.get()).unwrap();
+        assert_eq!(stored, report);
+
+        let mut tampered = read.clone();
+        tampered.report = RawValue::from_string(
+            read.report
+                .get()
+                .replace("\"vacationCarryover\":4.0", "\"vacationCarryover\":40.0"),
+        )
+        .unwrap();
+        assert_ne!(tampered.report.get(), read.report.get());
+        assert!(!verify(&key, &tampered));
+
+        let mut backdated = read.clone();
+        backdated.created_at = RawValue::from_string("\"2025-12-15T10:00:00\"".into()).unwrap();
+        assert!(!verify(&key, &backdated));
+
+        let mut wrong_hash = read.clone();
+        wrong_hash.sha256 = "0".repeat(64);
+        assert!(!verify(&key, &wrong_hash));
+
+        assert!(!verify(&SessionKey::generate(), &read));
+    }
+}
@@ -0,0 +1,314 @@
+//! Encrypted storage of the login session.
+//!
+//! The JWT and the logged-in user are sealed with ChaCha20-Poly1305 into
+//! `session.bin` in the app data directory. The 256-bit key is kept in the
+//! OS keyring (Keychain, Credential Manager, Secret Service). Where no
+//! keyring is available it falls back to `session.key` next to the
+//! session file, readable only by the current user.
+//!
+//! The token itself never leaves the Rust side; the webview only gets a
+//! `SessionInfo` without it.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use chrono::{DateTime, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::session_store::write_private;
+
+const FILE_NAME: &str = "session.bin";
+const KEY_FILE_NAME: &str = "session.key";
+const KEYRING_SERVICE: &str = "com.dpolg-stiftung.timetracker";
+const KEYRING_USER: &str = "session-key";
+const FORMAT_VERSION: u8 = 1;
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum SecureStoreError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// Wrong key or tampered file
+    Crypto,
+}
+
+impl fmt::Display for SecureStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecureStoreError::Io(e) => write!(f, "Sitzungsdatei: {}", e),
+            SecureStoreError::Json(e) => write!(f, "Ungültige Sitzungsdaten: {}", e),
+            SecureStoreError::Crypto => f.write_str("Sitzung konnte nicht entschlüsselt werden"),
+        }
+    }
+}
+
+impl std::error::Error for SecureStoreError {}
+
+impl From<io::Error> for SecureStoreError {
+    fn from(e: io::Error) -> Self {
+        SecureStoreError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SecureStoreError {
+    fn from(e: serde_json::Error) -> Self {
+        SecureStoreError::Json(e)
+    }
+}
+
+/// Everything needed to make authenticated requests after a restart
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredSession {
+    pub token: String,
+    /// `data` of the login response (the server's `SessionUser`)
+    pub user: Value,
+    /// Server the token was issued by, e.g. `http://localhost:3000/api`
+    pub api_base_url: String,
+    pub logged_in_at: NaiveDateTime,
+}
+
+impl fmt::Debug for StoredSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoredSession")
+            .field("token", &"***")
+            .field("user", &self.user)
+            .field("api_base_url", &self.api_base_url)
+            .field("logged_in_at", &self.logged_in_at)
+            .finish()
+    }
+}
+
+impl StoredSession {
+    /// Expiry from the token's `exp` claim
+    pub fn expires_at(&self) -> Option<NaiveDateTime> {
+        let payload = self.token.split('.').nth(1)?;
+        let claims: Value = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).ok()?).ok()?;
+        let exp = claims.get("exp")?.as_i64()?;
+        Some(DateTime::from_timestamp(exp, 0)?.naive_utc())
+    }
+
+    /// `now` is UTC, like the `exp` claim
+    pub fn is_expired(&self, now: NaiveDateTime) -> bool {
+        self.expires_at().is_some_and(|exp| exp <= now)
+    }
+}
+
+pub struct SessionKey(Key);
+
+impl SessionKey {
+    pub(crate) fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    fn from_encoded(encoded: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(encoded.trim()).ok()?;
+        let bytes: [u8; 32] = bytes.try_into().ok()?;
+        Some(Self(Key::from(bytes)))
+    }
+
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(&self.0[..])
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Key from the OS keyring, else from (or newly written to) the key file
+    pub fn load_or_create(dir: &Path) -> Result<Self, SecureStoreError> {
+        Self::load_or_create_as(dir, KEYRING_USER, KEY_FILE_NAME)
+    }
+
+    /// Like `load_or_create`, with a separate keyring entry and key file
+    /// for another purpose (e.g. signing exports)
+    pub fn load_or_create_as(
+        dir: &Path,
+        keyring_user: &str,
+        key_file: &str,
+    ) -> Result<Self, SecureStoreError> {
+        match keyring::Entry::new(KEYRING_SERVICE, keyring_user) {
+            Ok(entry) => match entry.get_password() {
+                Ok(encoded) => {
+                    if let Some(key) = Self::from_encoded(&encoded) {
+                        return Ok(key);
+                    }
+                }
+                Err(keyring::Error::NoEntry) => {
+                    let key = Self::generate();
+                    match entry.set_password(&key.encode()) {
+                        Ok(()) => return Ok(key),
+                        Err(e) => log::warn!("OS keyring not writable, using key file: {}", e),
+                    }
+                }
+                Err(e) => log::warn!("OS keyring unavailable, using key file: {}", e),
+            },
+            Err(e) => log::warn!("OS keyring unavailable, using key file: {}", e),
+        }
+        Self::from_named_file(dir, key_file)
+    }
+
+    #[cfg(test)]
+    fn from_file(dir: &Path) -> Result<Self, SecureStoreError> {
+        Self::from_named_file(dir, KEY_FILE_NAME)
+    }
+
+    fn from_named_file(dir: &Path, key_file: &str) -> Result<Self, SecureStoreError> {
+        let path = dir.join(key_file);
+        if let Some(key) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|encoded| Self::from_encoded(&encoded))
+        {
+            return Ok(key);
+        }
+        let key = Self::generate();
+        write_private(&path, key.encode().as_bytes())?;
+        Ok(key)
+    }
+}
+
+pub fn file_path(dir: &Path) -> PathBuf {
+    dir.join(FILE_NAME)
+}
+
+/// `version || nonce || ciphertext`
+pub fn seal(key: &SessionKey, session: &StoredSession) -> Result<Vec<u8>, SecureStoreError> {
+    let cipher = ChaCha20Poly1305::new(&key.0);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let plaintext = serde_json::to_vec(session)?;
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| SecureStoreError::Crypto)?;
+
+    let mut sealed = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+    sealed.push(FORMAT_VERSION);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+pub fn unseal(key: &SessionKey, sealed: &[u8]) -> Result<StoredSession, SecureStoreError> {
+    if sealed.len() <= 1 + NONCE_LEN || sealed[0] != FORMAT_VERSION {
+        return Err(SecureStoreError::Crypto);
+    }
+    let (nonce, ciphertext) = sealed[1..].split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into().map_err(|_| SecureStoreError::Crypto)?;
+    let plaintext = ChaCha20Poly1305::new(&key.0)
+        .decrypt(&Nonce::from(nonce), ciphertext)
+        .map_err(|_| SecureStoreError::Crypto)?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+/// Loads the stored session. A file that cannot be decrypted (e.g. the
+/// keyring entry was reset) is removed; the user simply logs in again.
+pub fn load(dir: &Path, key: &SessionKey) -> Result<Option<StoredSession>, SecureStoreError> {
+    let path = file_path(dir);
+    let sealed = match fs::read(&path) {
+        Ok(sealed) => sealed,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    match unseal(key, &sealed) {
+        Ok(session) => Ok(Some(session)),
+        Err(SecureStoreError::Crypto | SecureStoreError::Json(_)) => {
+            fs::remove_file(&path)?;
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+pub fn save(dir: &Path, key: &SessionKey, session: &StoredSession) -> Result<(), SecureStoreError> {
+    let path = file_path(dir);
+    write_private(&path, &seal(key, session)?)?;
+    Ok(())
+}
+
+pub fn clear(dir: &Path) -> io::Result<()> {
+    match fs::remove_file(file_path(dir)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("timetracker-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn session(token: &str) -> StoredSession {
+        StoredSession {
+            token: token.into(),
+            user: json!({ "id": 7, "username": "mmuster", "role": "employee" }),
+            api_base_url: "http://localhost:3000/api".into(),
+            logged_in_at: NaiveDateTime::parse_from_str("2026-03-02 08:00", "%Y-%m-%d %H:%M")
+                .unwrap(),
+        }
+    }
+
+    /// Unsigned JWT with the given `exp` claim
+    fn jwt(exp: i64) -> String {
+        let payload = URL_SAFE_NO_PAD.encode(json!({ "id": 7, "exp": exp }).to_string());
+        format!("eyJhbGciOiJIUzI1NiJ9.{}.c2lnbmF0dXJl", payload)
+    }
+
+    #[test]
+    fn sealed_file_round_trips_without_plaintext_token() {
+        let dir = temp_dir("secure-session");
+        let key = SessionKey::from_file(&dir).unwrap();
+        let stored = session("secret-token-value");
+        save(&dir, &key, &stored).unwrap();
+
+        let raw = fs::read(file_path(&dir)).unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("secret-token-value"));
+        assert_eq!(load(&dir, &key).unwrap(), Some(stored));
+
+        // Same key file on the next start
+        let key = SessionKey::from_file(&dir).unwrap();
+        assert!(load(&dir, &key).unwrap().is_some());
+
+        clear(&dir).unwrap();
+        assert_eq!(load(&dir, &key).unwrap(), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn file_sealed_with_another_key_is_discarded() {
+        let dir = temp_dir("secure-session-key");
+        save(&dir, &SessionKey::generate(), &session("token")).unwrap();
+
+        assert_eq!(load(&dir, &SessionKey::generate()).unwrap(), None);
+        assert!(!file_path(&dir).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn expiry_is_read_from_token_and_hidden_from_debug_output() {
+        // 2026-03-02 16:00:00 UTC
+        let stored = session(&jwt(1_772_467_200));
+        let expires = stored.expires_at().unwrap();
+        assert_eq!(
+            expires.format("%Y-%m-%d %H:%M").to_string(),
+            "2026-03-02 16:00"
+        );
+        assert!(!stored.is_expired(stored.logged_in_at));
+        assert!(stored.is_expired(expires));
+
+        assert!(!format!("{:?}", stored).contains(&stored.token));
+        assert_eq!(session("opaque").expires_at(), None);
+    }
+}
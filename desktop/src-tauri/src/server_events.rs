@@ -0,0 +1,253 @@
+//! Messages of the server's WebSocket (`server/src/websocket/server.ts`).
+//!
+//! After connecting, the client sends `{ type: "auth", userId }` and the
+//! server answers `auth:success`. From then on it pushes a `WSEvent` for
+//! every change that concerns the user (admins receive all of them). The
+//! events are re-emitted unchanged as Tauri events of the same name, so
+//! the webview gets them even when its own socket is throttled.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Client ping interval; the server pings every 30 s as well
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(25);
+/// Connection counts as dead without any frame for this long
+pub const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(70);
+const RECONNECT_BASE: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EventKind {
+    #[serde(rename = "overtime:updated")]
+    OvertimeUpdated,
+    #[serde(rename = "time-entry:created")]
+    TimeEntryCreated,
+    #[serde(rename = "time-entry:updated")]
+    TimeEntryUpdated,
+    #[serde(rename = "time-entry:deleted")]
+    TimeEntryDeleted,
+    #[serde(rename = "absence:created")]
+    AbsenceCreated,
+    #[serde(rename = "absence:approved")]
+    AbsenceApproved,
+    #[serde(rename = "absence:rejected")]
+    AbsenceRejected,
+    #[serde(rename = "correction:created")]
+    CorrectionCreated,
+    #[serde(rename = "correction:deleted")]
+    CorrectionDeleted,
+}
+
+impl EventKind {
+    /// Tauri event name, identical to the server's `type`
+    pub fn event_name(self) -> &'static str {
+        match self {
+            EventKind::OvertimeUpdated => "overtime:updated",
+            EventKind::TimeEntryCreated => "time-entry:created",
+            EventKind::TimeEntryUpdated => "time-entry:updated",
+            EventKind::TimeEntryDeleted => "time-entry:deleted",
+            EventKind::AbsenceCreated => "absence:created",
+            EventKind::AbsenceApproved => "absence:approved",
+            EventKind::AbsenceRejected => "absence:rejected",
+            EventKind::CorrectionCreated => "correction:created",
+            EventKind::CorrectionDeleted => "correction:deleted",
+        }
+    }
+}
+
+/// A `WSEvent` as broadcast by the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerEvent {
+    #[serde(rename = "type")]
+    pub kind: EventKind,
+    pub user_id: i64,
+    #[serde(default)]
+    pub data: Value,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Incoming {
+    AuthSuccess,
+    Event(ServerEvent),
+}
+
+/// Parses a text frame; `None` for unknown or malformed messages
+pub fn parse(text: &str) -> Option<Incoming> {
+    let value: Value = serde_json::from_str(text).ok()?;
+    if value.get("type")?.as_str()? == "auth:success" {
+        return Some(Incoming::AuthSuccess);
+    }
+    serde_json::from_value(value).ok().map(Incoming::Event)
+}
+
+/// First message after connecting
+pub fn auth_message(user_id: i64) -> String {
+    json!({ "type": "auth", "userId": user_id }).to_string()
+}
+
+/// Delay before reconnect attempt `attempt` (0-based): 1 s, 2 s, 4 s, …
+/// up to one minute
+pub fn reconnect_delay(attempt: u32) -> Duration {
+    RECONNECT_BASE
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(RECONNECT_MAX)
+}
+
+/// Native notification for the user's own absence decisions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventNotification {
+    pub title: String,
+    pub body: String,
+}
+
+pub fn notification(event: &ServerEvent, own_user_id: i64) -> Option<EventNotification> {
+    // Admins receive every user's events; only notify about one's own
+    if event.user_id != own_user_id {
+        return None;
+    }
+    let title = match event.kind {
+        EventKind::AbsenceApproved => "Antrag genehmigt",
+        EventKind::AbsenceRejected => "Antrag abgelehnt",
+        _ => return None,
+    };
+    let data = &event.data;
+    let request = match data.get("type").and_then(Value::as_str) {
+        Some("vacation") => "Ihr Urlaubsantrag",
+        Some("sick") => "Ihre Krankmeldung",
+        Some("overtime_comp") => "Ihr Antrag auf Überstundenausgleich",
+        Some("unpaid") => "Ihr Antrag auf unbezahlten Urlaub",
+        _ => "Ihr Abwesenheitsantrag",
+    };
+    let period = match (
+        data.get("startDate")
+            .and_then(Value::as_str)
+            .map(german_date),
+        data.get("endDate").and_then(Value::as_str).map(german_date),
+    ) {
+        (Some(start), Some(end)) if start == end => format!(" für den {}", start),
+        (Some(start), Some(end)) => format!(" vom {} bis {}", start, end),
+        _ => String::new(),
+    };
+    let decision = if event.kind == EventKind::AbsenceApproved {
+        "wurde genehmigt"
+    } else {
+        "wurde abgelehnt"
+    };
+    let mut body = format!("{}{} {}.", request, period, decision);
+    if let Some(note) = data
+        .get("adminNote")
+        .and_then(Value::as_str)
+        .filter(|n| !n.trim().is_empty())
+    {
+        body.push_str(&format!(" Hinweis: {}", note.trim()));
+    }
+    Some(EventNotification {
+        title: title.into(),
+        body,
+    })
+}
+
+/// `2026-03-02` → `02.03.2026`
+fn german_date(date: &str) -> String {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.format("%d.%m.%Y").to_string())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_server_messages() {
+        assert_eq!(
+            parse(r#"{"type":"auth:success","timestamp":"2026-03-02T08:00:00.000Z"}"#),
+            Some(Incoming::AuthSuccess)
+        );
+        let Some(Incoming::Event(event)) = parse(
+            r#"{"type":"time-entry:updated","userId":7,"data":{"id":12},"timestamp":"2026-03-02T08:00:00.000Z"}"#,
+        ) else {
+            panic!("event expected");
+        };
+        assert_eq!(event.kind, EventKind::TimeEntryUpdated);
+        assert_eq!(event.user_id, 7);
+        assert_eq!(event.data["id"], 12);
+
+        assert_eq!(
+            parse(r#"{"type":"unknown:event","userId":7,"timestamp":""}"#),
+            None
+        );
+        assert_eq!(parse("not json"), None);
+        assert_eq!(auth_message(7), r#"{"type":"auth","userId":7}"#);
+
+        for kind in [
+            EventKind::OvertimeUpdated,
+            EventKind::TimeEntryCreated,
+            EventKind::TimeEntryUpdated,
+            EventKind::TimeEntryDeleted,
+            EventKind::AbsenceCreated,
+            EventKind::AbsenceApproved,
+            EventKind::AbsenceRejected,
+            EventKind::CorrectionCreated,
+            EventKind::CorrectionDeleted,
+        ] {
+            let name = serde_json::to_value(kind).unwrap();
+            assert_eq!(name, kind.event_name());
+        }
+    }
+
+    #[test]
+    fn notifies_only_about_own_decisions() {
+        let event = |kind, user_id, data| ServerEvent {
+            kind,
+            user_id,
+            data,
+            timestamp: "2026-03-02T08:00:00.000Z".into(),
+        };
+        let vacation = json!({
+            "id": 3, "type": "vacation", "startDate": "2026-07-06", "endDate": "2026-07-17",
+            "status": "approved", "adminNote": null
+        });
+
+        let approved =
+            notification(&event(EventKind::AbsenceApproved, 7, vacation.clone()), 7).unwrap();
+        assert_eq!(approved.title, "Antrag genehmigt");
+        assert_eq!(
+            approved.body,
+            "Ihr Urlaubsantrag vom 06.07.2026 bis 17.07.2026 wurde genehmigt."
+        );
+
+        let sick_day = json!({
+            "type": "sick", "startDate": "2026-03-02", "endDate": "2026-03-02",
+            "adminNote": "Bitte AU nachreichen"
+        });
+        let rejected = notification(&event(EventKind::AbsenceRejected, 7, sick_day), 7).unwrap();
+        assert_eq!(
+            rejected.body,
+            "Ihre Krankmeldung für den 02.03.2026 wurde abgelehnt. Hinweis: Bitte AU nachreichen"
+        );
+
+        // Admin sees another user's approval, other event kinds stay silent
+        assert_eq!(
+            notification(&event(EventKind::AbsenceApproved, 9, vacation.clone()), 7),
+            None
+        );
+        assert_eq!(
+            notification(&event(EventKind::AbsenceCreated, 7, vacation), 7),
+            None
+        );
+    }
+
+    #[test]
+    fn reconnect_backoff_doubles_up_to_a_minute() {
+        assert_eq!(reconnect_delay(0), Duration::from_secs(1));
+        assert_eq!(reconnect_delay(1), Duration::from_secs(2));
+        assert_eq!(reconnect_delay(5), Duration::from_secs(32));
+        assert_eq!(reconnect_delay(6), Duration::from_secs(60));
+        assert_eq!(reconnect_delay(40), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,362 @@
+//! Server profiles: which server the app talks to.
+//!
+//! A profile names one deployment (local dev, the blue/green slots on the
+//! Oracle server, …) with its REST base URL, WebSocket URL and optionally a
+//! pinned certificate. Exactly one profile is active; `api_client` only
+//! sends requests to the origin of the active profile, so the capability
+//! file no longer needs wildcard hosts. The list is kept in
+//! `server_profiles.json` in the app data directory and can be edited in
+//! Settings without a rebuild.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::session_store::write_atomic;
+
+const FILE_NAME: &str = "server_profiles.json";
+const PEM_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----";
+
+#[derive(Debug)]
+pub enum ProfileError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// Field name and reason
+    Invalid(&'static str, String),
+    UnknownProfile(String),
+    /// The active profile cannot be deleted
+    ActiveProfile,
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::Io(e) => write!(f, "Serverprofile: {}", e),
+            ProfileError::Json(e) => write!(f, "Ungültige Serverprofile: {}", e),
+            ProfileError::Invalid(field, reason) => write!(f, "{}: {}", field, reason),
+            ProfileError::UnknownProfile(id) => write!(f, "Serverprofil \"{}\" nicht gefunden", id),
+            ProfileError::ActiveProfile => {
+                f.write_str("Das aktive Serverprofil kann nicht gelöscht werden")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+impl From<io::Error> for ProfileError {
+    fn from(e: io::Error) -> Self {
+        ProfileError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ProfileError {
+    fn from(e: serde_json::Error) -> Self {
+        ProfileError::Json(e)
+    }
+}
+
+pub type ProfileResult<T> = Result<T, ProfileError>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerProfile {
+    /// Stable key, e.g. `production`
+    pub id: String,
+    /// Shown in Settings, e.g. `Produktion (Blue)`
+    pub name: String,
+    /// e.g. `https://zeit.example.org/api`
+    pub api_base_url: String,
+    /// e.g. `wss://zeit.example.org/ws`
+    pub ws_url: String,
+    /// PEM of the server certificate (or its CA). When set, only this
+    /// certificate is trusted for the profile instead of the system roots.
+    #[serde(default)]
+    pub pinned_certificate: Option<String>,
+}
+
+impl ServerProfile {
+    fn new(id: &str, name: &str, host: &str) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            api_base_url: format!("http://{}/api", host),
+            ws_url: format!("ws://{}/ws", host),
+            pinned_certificate: None,
+        }
+    }
+
+    /// Trims the fields and checks URLs and certificate
+    pub fn normalized(mut self) -> ProfileResult<Self> {
+        self.id = self.id.trim().to_string();
+        self.name = self.name.trim().to_string();
+        self.api_base_url = self.api_base_url.trim().trim_end_matches('/').to_string();
+        self.ws_url = self.ws_url.trim().to_string();
+        self.pinned_certificate = self
+            .pinned_certificate
+            .map(|pem| pem.trim().to_string())
+            .filter(|pem| !pem.is_empty());
+
+        if self.id.is_empty() {
+            return Err(ProfileError::Invalid("ID", "darf nicht leer sein".into()));
+        }
+        if self.name.is_empty() {
+            return Err(ProfileError::Invalid("Name", "darf nicht leer sein".into()));
+        }
+        check_url("API-URL", &self.api_base_url, &["http", "https"])?;
+        check_url("WebSocket-URL", &self.ws_url, &["ws", "wss"])?;
+        if let Some(pem) = &self.pinned_certificate {
+            if !pem.starts_with(PEM_CERTIFICATE) {
+                return Err(ProfileError::Invalid(
+                    "Zertifikat",
+                    "erwartet ein PEM-Zertifikat (-----BEGIN CERTIFICATE-----)".into(),
+                ));
+            }
+            if self.api_base_url.starts_with("http:") {
+                return Err(ProfileError::Invalid(
+                    "Zertifikat",
+                    "kann nur für https-Server hinterlegt werden".into(),
+                ));
+            }
+        }
+        Ok(self)
+    }
+
+    /// Whether `url` is on the server of this profile (same scheme, host
+    /// and port as the API base URL)
+    pub fn allows(&self, url: &str) -> bool {
+        match (Url::parse(url), Url::parse(&self.api_base_url)) {
+            (Ok(url), Ok(base)) => url.origin() == base.origin(),
+            _ => false,
+        }
+    }
+}
+
+fn check_url(field: &'static str, value: &str, schemes: &[&str]) -> ProfileResult<()> {
+    let url = Url::parse(value).map_err(|e| ProfileError::Invalid(field, e.to_string()))?;
+    if !schemes.contains(&url.scheme()) {
+        return Err(ProfileError::Invalid(
+            field,
+            format!("erwartet {}://", schemes.join(":// oder ")),
+        ));
+    }
+    if url.host_str().is_none() {
+        return Err(ProfileError::Invalid(field, "Host fehlt".into()));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSettings {
+    pub active: String,
+    pub profiles: Vec<ServerProfile>,
+}
+
+impl Default for ProfileSettings {
+    /// Local dev server in debug builds, the production (blue) slot otherwise
+    fn default() -> Self {
+        let active = if cfg!(debug_assertions) {
+            "local"
+        } else {
+            "production"
+        };
+        Self {
+            active: active.into(),
+            profiles: vec![
+                ServerProfile::new("local", "Lokal (Entwicklung)", "localhost:3000"),
+                ServerProfile::new("staging", "Staging (Green)", "129.159.8.19:3001"),
+                ServerProfile::new("production", "Produktion (Blue)", "129.159.8.19:3000"),
+            ],
+        }
+    }
+}
+
+impl ProfileSettings {
+    pub fn active_profile(&self) -> &ServerProfile {
+        self.profiles
+            .iter()
+            .find(|p| p.id == self.active)
+            .or(self.profiles.first())
+            .expect("at least one server profile")
+    }
+
+    fn profile(&self, id: &str) -> ProfileResult<&ServerProfile> {
+        self.profiles
+            .iter()
+            .find(|p| p.id == id)
+            .ok_or_else(|| ProfileError::UnknownProfile(id.to_string()))
+    }
+
+    /// Adds the profile or replaces the one with the same id
+    pub fn upsert(&mut self, profile: ServerProfile) -> ProfileResult<&ServerProfile> {
+        let profile = profile.normalized()?;
+        let index = match self.profiles.iter().position(|p| p.id == profile.id) {
+            Some(index) => {
+                self.profiles[index] = profile;
+                index
+            }
+            None => {
+                self.profiles.push(profile);
+                self.profiles.len() - 1
+            }
+        };
+        Ok(&self.profiles[index])
+    }
+
+    pub fn remove(&mut self, id: &str) -> ProfileResult<()> {
+        self.profile(id)?;
+        if self.active_profile().id == id {
+            return Err(ProfileError::ActiveProfile);
+        }
+        self.profiles.retain(|p| p.id != id);
+        Ok(())
+    }
+
+    pub fn activate(&mut self, id: &str) -> ProfileResult<&ServerProfile> {
+        self.profile(id)?;
+        self.active = id.to_string();
+        Ok(self.active_profile())
+    }
+}
+
+pub fn file_path(dir: &Path) -> PathBuf {
+    dir.join(FILE_NAME)
+}
+
+/// Saved profiles, or the defaults on first start. Invalid entries in a
+/// hand-edited file are skipped.
+pub fn load(dir: &Path) -> ProfileResult<ProfileSettings> {
+    let content = match fs::read_to_string(file_path(dir)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(ProfileSettings::default()),
+        Err(e) => return Err(e.into()),
+    };
+    let saved: ProfileSettings = serde_json::from_str(&content)?;
+    let mut settings = ProfileSettings {
+        active: saved.active,
+        profiles: Vec::new(),
+    };
+    for profile in saved.profiles {
+        let id = profile.id.clone();
+        if let Err(error) = settings.upsert(profile) {
+            log::warn!("Skipping server profile \"{}\": {}", id, error);
+        }
+    }
+    if settings.profiles.is_empty() {
+        return Ok(ProfileSettings::default());
+    }
+    Ok(settings)
+}
+
+pub fn save(dir: &Path, settings: &ProfileSettings) -> ProfileResult<()> {
+    write_atomic(
+        &file_path(dir),
+        serde_json::to_string_pretty(settings)?.as_bytes(),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("timetracker-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn profile(id: &str, api: &str, ws: &str) -> ServerProfile {
+        ServerProfile {
+            id: id.into(),
+            name: id.into(),
+            api_base_url: api.into(),
+            ws_url: ws.into(),
+            pinned_certificate: None,
+        }
+    }
+
+    #[test]
+    fn scope_is_the_origin_of_the_active_profile() {
+        let settings = ProfileSettings::default();
+        let staging = settings.profile("staging").unwrap();
+
+        assert!(staging.allows("http://129.159.8.19:3001/api/time-entries"));
+        assert!(staging.allows("http://129.159.8.19:3001/api/exports/datev?year=2026"));
+        // Same host, other slot
+        assert!(!staging.allows("http://129.159.8.19:3000/api/time-entries"));
+        assert!(!staging.allows("https://129.159.8.19:3001/api/time-entries"));
+        assert!(!staging.allows("http://evil.example:3001/api"));
+        assert!(!staging.allows("not a url"));
+    }
+
+    #[test]
+    fn profiles_are_validated_and_normalized() {
+        let normalized = profile(
+            " blue ",
+            "https://zeit.example.org/api/",
+            "wss://zeit.example.org/ws",
+        )
+        .normalized()
+        .unwrap();
+        assert_eq!(normalized.id, "blue");
+        assert_eq!(normalized.api_base_url, "https://zeit.example.org/api");
+
+        assert!(matches!(
+            profile("x", "ftp://host/api", "ws://host/ws").normalized(),
+            Err(ProfileError::Invalid("API-URL", _))
+        ));
+        assert!(matches!(
+            profile("x", "http://host/api", "http://host/ws").normalized(),
+            Err(ProfileError::Invalid("WebSocket-URL", _))
+        ));
+
+        let mut pinned = profile("x", "https://host/api", "wss://host/ws");
+        pinned.pinned_certificate = Some("not a certificate".into());
+        assert!(matches!(
+            pinned.clone().normalized(),
+            Err(ProfileError::Invalid("Zertifikat", _))
+        ));
+        pinned.pinned_certificate = Some("  ".into());
+        assert_eq!(pinned.normalized().unwrap().pinned_certificate, None);
+    }
+
+    #[test]
+    fn switching_and_editing_profiles_survives_a_restart() {
+        let dir = temp_dir("server-profiles");
+        let mut settings = load(&dir).unwrap();
+        assert_eq!(settings.profiles.len(), 3);
+
+        settings
+            .upsert(profile(
+                "blue-tls",
+                "https://zeit.example.org/api",
+                "wss://zeit.example.org/ws",
+            ))
+            .unwrap();
+        settings.activate("blue-tls").unwrap();
+        assert!(matches!(
+            settings.remove("blue-tls"),
+            Err(ProfileError::ActiveProfile)
+        ));
+        settings.remove("staging").unwrap();
+        assert!(matches!(
+            settings.activate("staging"),
+            Err(ProfileError::UnknownProfile(_))
+        ));
+        save(&dir, &settings).unwrap();
+
+        let reloaded = load(&dir).unwrap();
+        assert_eq!(reloaded, settings);
+        assert_eq!(
+            reloaded.active_profile().ws_url,
+            "wss://zeit.example.org/ws"
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,285 @@
+//! Crash-safe persistence of the running work timer.
+//!
+//! The timer is written to `timer_session.json` in the app data directory
+//! after every state change and on every tray refresh. Writes go to a
+//! temporary file that is synced and then renamed over the old file, so a
+//! crash or OS update in the middle of a write never leaves a torn file.
+//! Drafts from "Gehen" that were not submitted yet are kept the same way in
+//! `timer_drafts.json`, and the time of the last "Gehen" in
+//! `last_clock_out.json` for the rest period check after a restart.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::timer::{TimeEntryDraft, WorkTimer};
+
+const FILE_NAME: &str = "timer_session.json";
+const DRAFTS_FILE_NAME: &str = "timer_drafts.json";
+const CLOCK_OUT_FILE_NAME: &str = "last_clock_out.json";
+const FORMAT_VERSION: u32 = 1;
+
+/// On-disk format of the timer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedTimer {
+    pub version: u32,
+    pub timer: WorkTimer,
+    /// Last moment the app was known to be alive; offered as the
+    /// clock-out time when a session is restored after a crash
+    pub last_seen_at: NaiveDateTime,
+}
+
+impl PersistedTimer {
+    pub fn new(timer: WorkTimer, last_seen_at: NaiveDateTime) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            timer,
+            last_seen_at,
+        }
+    }
+}
+
+pub fn file_path(dir: &Path) -> PathBuf {
+    dir.join(FILE_NAME)
+}
+
+/// Loads the persisted timer. A missing file yields `Ok(None)`; an
+/// unreadable or unknown file is moved aside so it does not block startup.
+pub fn load(dir: &Path) -> io::Result<Option<PersistedTimer>> {
+    let path = file_path(dir);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    match serde_json::from_str::<PersistedTimer>(&content) {
+        Ok(persisted) if persisted.version == FORMAT_VERSION => Ok(Some(persisted)),
+        _ => {
+            fs::rename(&path, path.with_extension("json.corrupt"))?;
+            Ok(None)
+        }
+    }
+}
+
+/// Writes the timer atomically (temp file + fsync + rename)
+pub fn save(dir: &Path, persisted: &PersistedTimer) -> io::Result<()> {
+    let json = serde_json::to_vec_pretty(persisted).map_err(io::Error::other)?;
+    write_atomic(&file_path(dir), &json)
+}
+
+/// Replaces `path` with `content` so that readers see either the old or
+/// the new file, never a partial one
+pub fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    write_atomic_with(path, content, false)
+}
+
+/// Like `write_atomic`, but the file is readable only by the current user
+/// from the moment it is created, not just after the rename
+pub fn write_private(path: &Path, content: &[u8]) -> io::Result<()> {
+    write_atomic_with(path, content, true)
+}
+
+fn write_atomic_with(path: &Path, content: &[u8], private: bool) -> io::Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    {
+        let mut file = create_new(&tmp_path, private)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp_path, path)?;
+
+    // Persist the rename itself (no-op on platforms without directory fsync)
+    if let Ok(dir_handle) = File::open(dir) {
+        let _ = dir_handle.sync_all();
+    }
+    Ok(())
+}
+
+/// Creates `path` afresh, so the mode is not inherited from a temp file
+/// left over by a crash
+fn create_new(path: &Path, private: bool) -> io::Result<File> {
+    remove(path)?;
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    // The app data directory is already private to the user on Windows
+    #[cfg(not(unix))]
+    let _ = private;
+    options.open(path)
+}
+
+pub fn clear(dir: &Path) -> io::Result<()> {
+    remove(&file_path(dir))
+}
+
+fn remove(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+pub fn drafts_path(dir: &Path) -> PathBuf {
+    dir.join(DRAFTS_FILE_NAME)
+}
+
+/// Loads the pending drafts; a missing file yields none, an unreadable one
+/// is moved aside like the timer file
+pub fn load_drafts(dir: &Path) -> io::Result<Vec<TimeEntryDraft>> {
+    let path = drafts_path(dir);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    match serde_json::from_str(&content) {
+        Ok(drafts) => Ok(drafts),
+        Err(_) => {
+            fs::rename(&path, path.with_extension("json.corrupt"))?;
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Writes the pending drafts atomically; removes the file when none are left
+pub fn save_drafts(dir: &Path, drafts: &[TimeEntryDraft]) -> io::Result<()> {
+    if drafts.is_empty() {
+        return remove(&drafts_path(dir));
+    }
+    let json = serde_json::to_vec_pretty(drafts).map_err(io::Error::other)?;
+    write_atomic(&drafts_path(dir), &json)
+}
+
+pub fn clock_out_path(dir: &Path) -> PathBuf {
+    dir.join(CLOCK_OUT_FILE_NAME)
+}
+
+/// Time of the last local "Gehen"; `None` when missing or unreadable
+pub fn load_last_clock_out(dir: &Path) -> io::Result<Option<NaiveDateTime>> {
+    match fs::read_to_string(clock_out_path(dir)) {
+        Ok(content) => Ok(serde_json::from_str(&content).ok()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn save_last_clock_out(dir: &Path, at: NaiveDateTime) -> io::Result<()> {
+    let json = serde_json::to_vec(&at).map_err(io::Error::other)?;
+    write_atomic(&clock_out_path(dir), &json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::SessionDetails;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("timetracker-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn round_trips_running_session_with_details() {
+        let dir = temp_dir("roundtrip");
+        let mut timer = WorkTimer::default();
+        timer.clock_in(at("2026-03-02 08:12")).unwrap();
+        timer.start_break(at("2026-03-02 12:00")).unwrap();
+        timer
+            .set_details(SessionDetails {
+                project: Some("Verwaltung".into()),
+                activity: None,
+                location: Some("homeoffice".into()),
+            })
+            .unwrap();
+
+        let persisted = PersistedTimer::new(timer, at("2026-03-02 12:05"));
+        save(&dir, &persisted).unwrap();
+
+        assert_eq!(load(&dir).unwrap(), Some(persisted));
+        assert!(!file_path(&dir).with_extension("json.tmp").exists());
+
+        clear(&dir).unwrap();
+        assert_eq!(load(&dir).unwrap(), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn pending_drafts_survive_a_restart() {
+        let dir = temp_dir("drafts");
+        let mut timer = WorkTimer::default();
+        timer.clock_in(at("2026-03-02 08:00")).unwrap();
+        let drafts = timer.clock_out(at("2026-03-02 16:30")).unwrap();
+        assert_eq!(drafts.len(), 1);
+
+        save_drafts(&dir, &drafts).unwrap();
+        assert_eq!(load_drafts(&dir).unwrap(), drafts);
+
+        save_drafts(&dir, &[]).unwrap();
+        assert!(!drafts_path(&dir).exists());
+        assert!(load_drafts(&dir).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn last_clock_out_survives_a_restart() {
+        let dir = temp_dir("clock-out");
+        assert_eq!(load_last_clock_out(&dir).unwrap(), None);
+        save_last_clock_out(&dir, at("2026-03-02 22:30")).unwrap();
+        assert_eq!(
+            load_last_clock_out(&dir).unwrap(),
+            Some(at("2026-03-02 22:30"))
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_files_are_never_readable_by_others() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir("private");
+        let path = dir.join("session.key");
+        fs::create_dir_all(&dir).unwrap();
+        // Temp file left over by a crash, with the default mode
+        fs::write(dir.join("session.key.tmp"), b"stale").unwrap();
+
+        write_private(&path, b"secret").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(fs::read(&path).unwrap(), b"secret");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn corrupt_file_is_moved_aside() {
+        let dir = temp_dir("corrupt");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(file_path(&dir), b"{\"version\": 1, \"tim").unwrap();
+
+        assert_eq!(load(&dir).unwrap(), None);
+        assert!(file_path(&dir).with_extension("json.corrupt").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
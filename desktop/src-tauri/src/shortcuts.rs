@@ -0,0 +1,654 @@
+//! OS-wide keyboard shortcuts for the timer and the quick entry.
+//!
+//! Registered through the global-shortcut plugin, so they also work while
+//! the main window is hidden in the tray. The bindings are stored in
+//! `shortcut_settings.json`. Before anything is registered they are
+//! normalized and checked against each other and against combinations the
+//! operating system or every text field already uses. Combinations another
+//! application has taken only show up when registering and are reported
+//! per action in the status.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::quick_entry;
+use crate::session_store::write_atomic;
+use crate::timer::TimerPhase;
+use crate::tracking::{self, TimerAction};
+use crate::tray;
+
+const FILE_NAME: &str = "shortcut_settings.json";
+
+/// Combinations that copy/paste, switch windows, lock the screen etc.
+const RESERVED: &[&str] = &[
+    "Ctrl+A",
+    "Ctrl+C",
+    "Ctrl+S",
+    "Ctrl+V",
+    "Ctrl+X",
+    "Ctrl+Y",
+    "Ctrl+Z",
+    "Ctrl+Tab",
+    "Ctrl+Alt+Delete",
+    "Alt+Tab",
+    "Alt+F4",
+    "Super+A",
+    "Super+C",
+    "Super+D",
+    "Super+H",
+    "Super+L",
+    "Super+M",
+    "Super+Q",
+    "Super+S",
+    "Super+V",
+    "Super+W",
+    "Super+X",
+    "Super+Z",
+    "Super+Tab",
+    "Super+Space",
+];
+
+/// Named keys besides letters, digits and F1–F24: (canonical, aliases)
+const NAMED_KEYS: &[(&str, &[&str])] = &[
+    ("Space", &["SPACE"]),
+    ("Enter", &["ENTER", "RETURN"]),
+    ("Tab", &["TAB"]),
+    ("Backspace", &["BACKSPACE"]),
+    ("Delete", &["DELETE", "DEL"]),
+    ("Insert", &["INSERT", "INS"]),
+    ("Home", &["HOME"]),
+    ("End", &["END"]),
+    ("PageUp", &["PAGEUP"]),
+    ("PageDown", &["PAGEDOWN"]),
+    ("Up", &["UP", "ARROWUP"]),
+    ("Down", &["DOWN", "ARROWDOWN"]),
+    ("Left", &["LEFT", "ARROWLEFT"]),
+    ("Right", &["RIGHT", "ARROWRIGHT"]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShortcutAction {
+    /// "Kommen" when idle, otherwise "Gehen"
+    ToggleTimer,
+    /// "Pause starten" / "Pause beenden"
+    ToggleBreak,
+    /// Opens the quick-entry window
+    QuickEntry,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 3] = [
+        ShortcutAction::ToggleTimer,
+        ShortcutAction::ToggleBreak,
+        ShortcutAction::QuickEntry,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleTimer => "Kommen/Gehen",
+            ShortcutAction::ToggleBreak => "Pause starten/beenden",
+            ShortcutAction::QuickEntry => "Schnellerfassung",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ShortcutError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Invalid {
+        action: ShortcutAction,
+        accelerator: String,
+        reason: String,
+    },
+    Reserved {
+        action: ShortcutAction,
+        accelerator: String,
+    },
+    Duplicate {
+        first: ShortcutAction,
+        second: ShortcutAction,
+        accelerator: String,
+    },
+}
+
+impl fmt::Display for ShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortcutError::Io(e) => write!(f, "Tastenkürzel: {}", e),
+            ShortcutError::Json(e) => write!(f, "Ungültige Tastenkürzel-Einstellungen: {}", e),
+            ShortcutError::Invalid {
+                action,
+                accelerator,
+                reason,
+            } => write!(
+                f,
+                "Tastenkürzel „{}“ für {} ist ungültig: {}",
+                accelerator,
+                action.label(),
+                reason
+            ),
+            ShortcutError::Reserved {
+                action,
+                accelerator,
+            } => write!(
+                f,
+                "„{}“ ist vom System belegt und kann nicht für {} verwendet werden",
+                accelerator,
+                action.label()
+            ),
+            ShortcutError::Duplicate {
+                first,
+                second,
+                accelerator,
+            } => write!(
+                f,
+                "„{}“ ist sowohl für {} als auch für {} eingetragen",
+                accelerator,
+                first.label(),
+                second.label()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ShortcutError {}
+
+impl From<io::Error> for ShortcutError {
+    fn from(e: io::Error) -> Self {
+        ShortcutError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ShortcutError {
+    fn from(e: serde_json::Error) -> Self {
+        ShortcutError::Json(e)
+    }
+}
+
+/// Bindings in the plugin's accelerator syntax (e.g. "CommandOrControl+Alt+K");
+/// `None` leaves the action without shortcut
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShortcutSettings {
+    pub enabled: bool,
+    pub toggle_timer: Option<String>,
+    pub toggle_break: Option<String>,
+    pub quick_entry: Option<String>,
+}
+
+impl Default for ShortcutSettings {
+    fn default() -> Self {
+        ShortcutSettings {
+            enabled: true,
+            toggle_timer: Some("CommandOrControl+Alt+K".into()),
+            toggle_break: Some("CommandOrControl+Alt+P".into()),
+            quick_entry: Some("CommandOrControl+Alt+N".into()),
+        }
+    }
+}
+
+impl ShortcutSettings {
+    pub fn binding(&self, action: ShortcutAction) -> Option<&str> {
+        let value = match action {
+            ShortcutAction::ToggleTimer => &self.toggle_timer,
+            ShortcutAction::ToggleBreak => &self.toggle_break,
+            ShortcutAction::QuickEntry => &self.quick_entry,
+        };
+        value.as_deref().filter(|v| !v.trim().is_empty())
+    }
+}
+
+/// Normalized key combination, e.g. "Ctrl+Alt+K"
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Accelerator {
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+    key: String,
+}
+
+impl fmt::Display for Accelerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = [
+            (self.ctrl, "Ctrl+"),
+            (self.alt, "Alt+"),
+            (self.shift, "Shift+"),
+            (self.meta, "Super+"),
+        ];
+        for (_, label) in modifiers.iter().filter(|(set, _)| *set) {
+            f.write_str(label)?;
+        }
+        f.write_str(&self.key)
+    }
+}
+
+fn parse_key(token: &str) -> Option<String> {
+    let upper = token.to_uppercase();
+    let mut chars = upper.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return c.is_ascii_alphanumeric().then(|| c.to_string());
+    }
+    if let Some(n) = upper.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+        return (1..=24).contains(&n).then(|| format!("F{}", n));
+    }
+    NAMED_KEYS
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&upper.as_str()))
+        .map(|(name, _)| name.to_string())
+}
+
+/// Parses "CommandOrControl+Shift+K" and the like. CommandOrControl is Cmd
+/// on macOS and Ctrl everywhere else, like in the plugin.
+pub fn parse_accelerator(value: &str) -> Result<Accelerator, String> {
+    let mut accelerator = Accelerator {
+        ctrl: false,
+        alt: false,
+        shift: false,
+        meta: false,
+        key: String::new(),
+    };
+    for token in value.split('+').map(str::trim) {
+        if token.is_empty() {
+            return Err("leerer Bestandteil".into());
+        }
+        if !accelerator.key.is_empty() {
+            return Err("die Taste muss am Ende stehen".into());
+        }
+        match token.to_uppercase().as_str() {
+            "CTRL" | "CONTROL" => accelerator.ctrl = true,
+            "ALT" | "OPTION" => accelerator.alt = true,
+            "SHIFT" => accelerator.shift = true,
+            "SUPER" | "CMD" | "COMMAND" | "META" => accelerator.meta = true,
+            "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
+                if cfg!(target_os = "macos") {
+                    accelerator.meta = true;
+                } else {
+                    accelerator.ctrl = true;
+                }
+            }
+            _ => {
+                accelerator.key =
+                    parse_key(token).ok_or_else(|| format!("unbekannte Taste „{}“", token))?
+            }
+        }
+    }
+    if accelerator.key.is_empty() {
+        return Err("keine Taste angegeben".into());
+    }
+
+    // Shift alone would swallow capital letters in every application
+    let has_modifier = accelerator.ctrl || accelerator.alt || accelerator.meta;
+    let high_function_key = accelerator
+        .key
+        .strip_prefix('F')
+        .and_then(|n| n.parse::<u8>().ok())
+        .is_some_and(|n| n >= 13);
+    if !has_modifier && !high_function_key {
+        return Err("Strg, Alt oder Cmd erforderlich".into());
+    }
+    Ok(accelerator)
+}
+
+/// Normalized bindings of all configured actions, or the first conflict.
+/// Disabled settings have no bindings.
+pub fn validate(
+    settings: &ShortcutSettings,
+) -> Result<Vec<(ShortcutAction, Accelerator)>, ShortcutError> {
+    let mut bindings: Vec<(ShortcutAction, Accelerator)> = Vec::new();
+    if !settings.enabled {
+        return Ok(bindings);
+    }
+    for action in ShortcutAction::ALL {
+        let Some(value) = settings.binding(action) else {
+            continue;
+        };
+        let accelerator = parse_accelerator(value).map_err(|reason| ShortcutError::Invalid {
+            action,
+            accelerator: value.to_string(),
+            reason,
+        })?;
+        let label = accelerator.to_string();
+        if RESERVED.contains(&label.as_str()) {
+            return Err(ShortcutError::Reserved {
+                action,
+                accelerator: label,
+            });
+        }
+        if let Some((first, _)) = bindings.iter().find(|(_, a)| *a == accelerator) {
+            return Err(ShortcutError::Duplicate {
+                first: *first,
+                second: action,
+                accelerator: label,
+            });
+        }
+        bindings.push((action, accelerator));
+    }
+    Ok(bindings)
+}
+
+/// Timer transition a shortcut triggers in the given phase
+pub fn timer_action(action: ShortcutAction, phase: TimerPhase) -> Option<TimerAction> {
+    match (action, phase) {
+        (ShortcutAction::ToggleTimer, TimerPhase::Idle) => Some(TimerAction::ClockIn),
+        (ShortcutAction::ToggleTimer, _) => Some(TimerAction::ClockOut),
+        (ShortcutAction::ToggleBreak, TimerPhase::Running) => Some(TimerAction::StartBreak),
+        (ShortcutAction::ToggleBreak, TimerPhase::OnBreak) => Some(TimerAction::EndBreak),
+        (ShortcutAction::ToggleBreak, TimerPhase::Idle) | (ShortcutAction::QuickEntry, _) => None,
+    }
+}
+
+pub fn file_path(dir: &Path) -> PathBuf {
+    dir.join(FILE_NAME)
+}
+
+pub fn load(dir: &Path) -> Result<ShortcutSettings, ShortcutError> {
+    match fs::read_to_string(file_path(dir)) {
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(ShortcutSettings::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn save(dir: &Path, settings: &ShortcutSettings) -> Result<(), ShortcutError> {
+    write_atomic(
+        &file_path(dir),
+        serde_json::to_string_pretty(settings)?.as_bytes(),
+    )?;
+    Ok(())
+}
+
+/// Registration result of one action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutStatus {
+    pub action: ShortcutAction,
+    pub accelerator: String,
+    pub registered: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct ShortcutsState {
+    settings: Mutex<ShortcutSettings>,
+    active: Mutex<Vec<(Shortcut, ShortcutAction)>>,
+    status: Mutex<Vec<ShortcutStatus>>,
+}
+
+fn settings_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+}
+
+/// Replaces all registered shortcuts with the ones from `settings`
+fn apply(app: &AppHandle, settings: ShortcutSettings) -> Result<Vec<ShortcutStatus>, String> {
+    let bindings = validate(&settings).map_err(|e| e.to_string())?;
+    let global = app.global_shortcut();
+    if let Err(error) = global.unregister_all() {
+        log::warn!("Failed to unregister shortcuts: {}", error);
+    }
+
+    let mut active = Vec::new();
+    let mut status = Vec::new();
+    for (action, accelerator) in bindings {
+        let label = accelerator.to_string();
+        let result = label
+            .parse::<Shortcut>()
+            .map_err(|e| e.to_string())
+            .and_then(|shortcut| {
+                global
+                    .register(shortcut)
+                    .map(|_| shortcut)
+                    .map_err(|e| format!("bereits von einer anderen Anwendung belegt ({})", e))
+            });
+        status.push(ShortcutStatus {
+            action,
+            accelerator: label,
+            registered: result.is_ok(),
+            error: result.as_ref().err().cloned(),
+        });
+        if let Ok(shortcut) = result {
+            active.push((shortcut, action));
+        }
+    }
+
+    let state = app.state::<ShortcutsState>();
+    *state.settings.lock().map_err(|e| e.to_string())? = settings;
+    *state.active.lock().map_err(|e| e.to_string())? = active;
+    *state.status.lock().map_err(|e| e.to_string())? = status.clone();
+    Ok(status)
+}
+
+/// Plugin handler for every registered shortcut
+pub fn handle(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let action = app.try_state::<ShortcutsState>().and_then(|state| {
+        let active = state.active.lock().ok()?;
+        active
+            .iter()
+            .find(|(s, _)| s == shortcut)
+            .map(|(_, action)| *action)
+    });
+    if let Some(action) = action {
+        trigger(app, action);
+    }
+}
+
+fn trigger(app: &AppHandle, action: ShortcutAction) {
+    if action == ShortcutAction::QuickEntry {
+        if let Err(error) = quick_entry::open(app) {
+            log::warn!("Failed to open quick entry: {}", error);
+        }
+        return;
+    }
+
+    let Some(snapshot) = tracking::snapshot(app) else {
+        return;
+    };
+    let Some(timer_action) = timer_action(action, snapshot.phase) else {
+        return;
+    };
+    if let Err(error) = tracking::perform(app, timer_action) {
+        log::warn!("Shortcut action {:?} failed: {}", timer_action, error);
+        return;
+    }
+    if timer_action == TimerAction::ClockOut {
+        // Show the draft in the main window for confirmation
+        tray::show_main_window(app);
+    }
+}
+
+pub fn setup(app: &AppHandle) {
+    app.manage(ShortcutsState::default());
+    let settings = match load(&settings_dir(app)) {
+        Ok(settings) => settings,
+        Err(error) => {
+            log::warn!("Failed to load shortcut settings: {}", error);
+            ShortcutSettings::default()
+        }
+    };
+    match apply(app, settings) {
+        Ok(status) => {
+            for failed in status.iter().filter(|s| !s.registered) {
+                log::warn!(
+                    "Shortcut {} for {:?} not registered: {}",
+                    failed.accelerator,
+                    failed.action,
+                    failed.error.as_deref().unwrap_or_default()
+                );
+            }
+        }
+        Err(error) => log::warn!("{}", error),
+    }
+}
+
+#[tauri::command]
+pub fn shortcut_settings(state: State<'_, ShortcutsState>) -> Result<ShortcutSettings, String> {
+    Ok(state.settings.lock().map_err(|e| e.to_string())?.clone())
+}
+
+#[tauri::command]
+pub fn shortcut_status(state: State<'_, ShortcutsState>) -> Result<Vec<ShortcutStatus>, String> {
+    Ok(state.status.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Checks, saves and registers the shortcuts. Conflicts between the
+/// bindings or with system shortcuts are rejected before saving; taken by
+/// another application is reported in the returned status.
+#[tauri::command]
+pub fn save_shortcut_settings(
+    app: AppHandle,
+    settings: ShortcutSettings,
+) -> Result<Vec<ShortcutStatus>, String> {
+    validate(&settings).map_err(|e| e.to_string())?;
+    save(&settings_dir(&app), &settings).map_err(|e| e.to_string())?;
+    apply(&app, settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(timer: &str, pause: &str, quick: &str) -> ShortcutSettings {
+        let binding = |v: &str| (!v.is_empty()).then(|| v.to_string());
+        ShortcutSettings {
+            enabled: true,
+            toggle_timer: binding(timer),
+            toggle_break: binding(pause),
+            quick_entry: binding(quick),
+        }
+    }
+
+    #[test]
+    fn accelerators_are_normalized() {
+        let parsed = parse_accelerator(" shift + control+option+k ").unwrap();
+        assert_eq!(parsed.to_string(), "Ctrl+Alt+Shift+K");
+        assert_eq!(
+            parse_accelerator("Cmd+ArrowUp").unwrap().to_string(),
+            "Super+Up"
+        );
+        assert_eq!(parse_accelerator("F13").unwrap().to_string(), "F13");
+
+        let expected = if cfg!(target_os = "macos") {
+            "Alt+Super+K"
+        } else {
+            "Ctrl+Alt+K"
+        };
+        assert_eq!(
+            parse_accelerator("CommandOrControl+Alt+K")
+                .unwrap()
+                .to_string(),
+            expected
+        );
+    }
+
+    #[test]
+    fn invalid_accelerators_are_rejected() {
+        for value in [
+            "Ctrl+Alt",
+            "Ctrl++K",
+            "Ctrl+K+Alt",
+            "Ctrl+Alt+Ä",
+            "Ctrl+F25",
+        ] {
+            assert!(parse_accelerator(value).is_err(), "{}", value);
+        }
+        assert_eq!(
+            parse_accelerator("Shift+K").unwrap_err(),
+            "Strg, Alt oder Cmd erforderlich"
+        );
+        assert!(parse_accelerator("F5").is_err());
+    }
+
+    #[test]
+    fn conflicts_are_detected() {
+        assert_eq!(validate(&ShortcutSettings::default()).unwrap().len(), 3);
+
+        let duplicate = validate(&settings("Ctrl+Alt+K", "", "control+option+k")).unwrap_err();
+        assert!(matches!(
+            duplicate,
+            ShortcutError::Duplicate {
+                first: ShortcutAction::ToggleTimer,
+                second: ShortcutAction::QuickEntry,
+                ..
+            }
+        ));
+        assert_eq!(
+            duplicate.to_string(),
+            "„Ctrl+Alt+K“ ist sowohl für Kommen/Gehen als auch für Schnellerfassung eingetragen"
+        );
+
+        let reserved = validate(&settings("Ctrl+Alt+K", "Alt+F4", "")).unwrap_err();
+        assert!(matches!(
+            reserved,
+            ShortcutError::Reserved {
+                action: ShortcutAction::ToggleBreak,
+                ..
+            }
+        ));
+        assert!(matches!(
+            validate(&settings("Ctrl+Alt+Ü", "", "")),
+            Err(ShortcutError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn disabled_or_empty_bindings_register_nothing() {
+        let disabled = ShortcutSettings {
+            enabled: false,
+            ..ShortcutSettings::default()
+        };
+        assert!(validate(&disabled).unwrap().is_empty());
+
+        let only_break = validate(&settings("", "Ctrl+Alt+P", " ")).unwrap();
+        assert_eq!(only_break.len(), 1);
+        assert_eq!(only_break[0].0, ShortcutAction::ToggleBreak);
+    }
+
+    #[test]
+    fn stored_settings_fall_back_to_defaults() {
+        let settings: ShortcutSettings =
+            serde_json::from_str(r#"{ "toggleBreak": null }"#).unwrap();
+        assert!(settings.enabled);
+        assert_eq!(settings.toggle_break, None);
+        assert_eq!(
+            settings.toggle_timer,
+            ShortcutSettings::default().toggle_timer
+        );
+    }
+
+    #[test]
+    fn toggles_follow_the_timer_phase() {
+        use ShortcutAction::*;
+        assert_eq!(
+            timer_action(ToggleTimer, TimerPhase::Idle),
+            Some(TimerAction::ClockIn)
+        );
+        assert_eq!(
+            timer_action(ToggleTimer, TimerPhase::OnBreak),
+            Some(TimerAction::ClockOut)
+        );
+        assert_eq!(
+            timer_action(ToggleBreak, TimerPhase::Running),
+            Some(TimerAction::StartBreak)
+        );
+        assert_eq!(
+            timer_action(ToggleBreak, TimerPhase::OnBreak),
+            Some(TimerAction::EndBreak)
+        );
+        assert_eq!(timer_action(ToggleBreak, TimerPhase::Idle), None);
+        assert_eq!(timer_action(QuickEntry, TimerPhase::Running), None);
+    }
+}
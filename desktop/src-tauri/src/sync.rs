@@ -0,0 +1,917 @@
+//! Outbox replay against the server and the Tauri commands of the
+//! offline store.
+//!
+//! Local changes are written to `offline_store` first. Whenever the server
+//! is reachable again, the outbox is replayed strictly in order; every item
+//! is reported to the UI through `sync:item`, the whole run through
+//! `sync:finished`.
+//!
+//! Before an update or delete is sent, the server copy is fetched and
+//! compared with the version the change was based on; time entries are
+//! also checked for overlaps. Conflicts and rejections park the item and
+//! are reported through `sync:conflict` until the user resolves them.
+//!
+//! The cache is filled from the server on login, every `PULL_INTERVAL` and
+//! after time entries or absences of the user changed on another device.
+//! A pull replaces the synced rows, so records deleted on the server
+//! disappear; rows with unsynced changes are kept.
+
+use std::{
+    collections::HashSet,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use chrono::{Datelike, Local, NaiveDate};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Listener, Manager, State};
+use tauri_plugin_http::reqwest::Method;
+
+use crate::api_client::{self, ApiClient};
+use crate::api_error::ApiErrorKind;
+use crate::arbzg::BookedEntry;
+use crate::auth::{self, AuthState};
+use crate::offline_store::{
+    self, AbsenceRequestInput, AbsenceRequestRecord, ConflictKind, DetectedConflict, OfflineStore,
+    OutboxEntity, OutboxItem, OutboxOperation, OutboxStatus, Resolution, StoreResult, SyncConflict,
+    TimeEntryInput, TimeEntryRecord,
+};
+use crate::overtime::{self, AbsenceKind, OvertimeInput, OvertimeLedger};
+use crate::server_events::{EventKind, ServerEvent};
+use crate::tracking;
+use crate::tray;
+use crate::vacation::{self, VacationInput, VacationProjection};
+
+/// Emitted with an `ItemResult` for every replayed outbox item
+pub const EVENT_SYNC_ITEM: &str = "sync:item";
+/// Emitted with a `SyncReport` after every replay run
+pub const EVENT_SYNC_FINISHED: &str = "sync:finished";
+/// Emitted with the number of queued changes after a local change
+pub const EVENT_OUTBOX_CHANGED: &str = "outbox:changed";
+/// Emitted with a `SyncConflict` whenever an item is parked
+pub const EVENT_SYNC_CONFLICT: &str = "sync:conflict";
+/// Emitted after the cache was refreshed from the server
+pub const EVENT_CACHE_UPDATED: &str = "offline:cache-updated";
+
+const SYNC_INTERVAL: Duration = Duration::from_secs(30);
+const PULL_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Server limits of `GET /time-entries` and `GET /absences`
+const TIME_ENTRY_LIMIT: usize = 10_000;
+const ABSENCE_PAGE_SIZE: usize = 100;
+
+/// Server events after which the user's records are pulled again
+const PULL_EVENTS: [EventKind; 6] = [
+    EventKind::TimeEntryCreated,
+    EventKind::TimeEntryUpdated,
+    EventKind::TimeEntryDeleted,
+    EventKind::AbsenceCreated,
+    EventKind::AbsenceApproved,
+    EventKind::AbsenceRejected,
+];
+
+/// Where to reach the server, taken from the active server profile
+#[derive(Debug, Clone)]
+pub struct SyncConfig {
+    /// e.g. `http://localhost:3000/api`
+    pub api_base_url: String,
+}
+
+pub struct SyncState {
+    store: Mutex<Option<OfflineStore>>,
+    /// Set while a replay or pull is running; they never overlap
+    replaying: AtomicBool,
+    /// User whose server copy has been pulled into the cache
+    pulled: Mutex<Option<i64>>,
+}
+
+impl SyncState {
+    pub fn open(path: &Path) -> Self {
+        let store = match OfflineStore::open(path) {
+            Ok(store) => Some(store),
+            Err(error) => {
+                log::warn!("Offline store unavailable: {}", error);
+                None
+            }
+        };
+        Self {
+            store: Mutex::new(store),
+            replaying: AtomicBool::new(false),
+            pulled: Mutex::new(None),
+        }
+    }
+
+    pub fn with_store<T>(
+        &self,
+        f: impl FnOnce(&mut OfflineStore) -> StoreResult<T>,
+    ) -> Result<T, String> {
+        let mut guard = self.store.lock().map_err(|e| e.to_string())?;
+        let store = guard
+            .as_mut()
+            .ok_or_else(|| "Lokale Datenbank nicht verfügbar".to_string())?;
+        f(store).map_err(|e| e.to_string())
+    }
+}
+
+/// Outcome of a single replayed item
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemResult {
+    pub seq: i64,
+    pub entity: OutboxEntity,
+    pub operation: OutboxOperation,
+    pub target_id: i64,
+    pub success: bool,
+    pub server_id: Option<i64>,
+    pub error: Option<String>,
+    pub conflict_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub online: bool,
+    pub results: Vec<ItemResult>,
+    pub pending: i64,
+}
+
+enum SendError {
+    /// Server not reachable or temporarily failing – keep item, stop run
+    Unavailable(String),
+    /// Token missing or expired – keep item, stop run
+    Unauthorized,
+    /// Server rejected the change – park item
+    Rejected(ApiErrorKind, String),
+    /// Server copy changed or the entry overlaps another – park item
+    Conflict(DetectedConflict),
+}
+
+struct Accepted {
+    server_id: Option<i64>,
+    record: Option<Value>,
+}
+
+pub fn setup(app: &AppHandle) {
+    let path = app
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join("offline.db"))
+        .unwrap_or_else(|_| "offline.db".into());
+    app.manage(SyncState::open(&path));
+
+    let handle = app.clone();
+    app.listen(auth::EVENT_AUTH_CHANGED, move |_| trigger_pull(&handle));
+    for kind in PULL_EVENTS {
+        let handle = app.clone();
+        app.listen(kind.event_name(), move |event| {
+            // Admins receive every user's events
+            let own = handle.state::<AuthState>().user_id();
+            let event = serde_json::from_str::<ServerEvent>(event.payload()).ok();
+            if event.is_some_and(|event| Some(event.user_id) == own) {
+                trigger_pull(&handle);
+            }
+        });
+    }
+    refresh_workday(app);
+    spawn_sync_loop(app.clone());
+}
+
+/// Hands yesterday's and today's cached entries to the ArbZG reminders
+fn refresh_workday(app: &AppHandle) {
+    let Some(user_id) = app.state::<AuthState>().user_id() else {
+        tracking::set_booked_entries(app, Vec::new());
+        return;
+    };
+    let today = Local::now().date_naive();
+    let from = today.pred_opt().unwrap_or(today).to_string();
+    let entries = app
+        .state::<SyncState>()
+        .with_store(|s| s.list_time_entries(user_id, &from, &today.to_string()));
+    match entries {
+        Ok(entries) => tracking::set_booked_entries(
+            app,
+            entries
+                .into_iter()
+                .map(|e| BookedEntry {
+                    id: e.id,
+                    date: e.date,
+                    start_time: e.start_time,
+                    end_time: e.end_time,
+                    hours: e.hours,
+                })
+                .collect(),
+        ),
+        Err(error) => log::warn!("Failed to read today's entries: {}", error),
+    }
+}
+
+fn spawn_sync_loop(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_pull = None;
+        loop {
+            let state = app.state::<SyncState>();
+            let pending = state.with_store(|s| s.pending_count()).unwrap_or(0);
+            if pending > 0 {
+                let _ = replay(&app).await;
+            }
+            if !last_pull.is_some_and(|at: Instant| at.elapsed() < PULL_INTERVAL) {
+                let _ = pull(&app).await;
+                last_pull = Some(Instant::now());
+            }
+            tokio::time::sleep(SYNC_INTERVAL).await;
+        }
+    });
+}
+
+/// Starts a replay in the background (after a local change)
+fn trigger_replay(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = replay(&app).await;
+    });
+}
+
+/// Starts a pull in the background (after login or a server event)
+fn trigger_pull(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = pull(&app).await;
+    });
+}
+
+/// Number of queued changes; 0 when the store is unavailable
+pub fn pending_count(app: &AppHandle) -> i64 {
+    app.try_state::<SyncState>()
+        .and_then(|state| state.with_store(|s| s.pending_count()).ok())
+        .unwrap_or(0)
+}
+
+fn emit_outbox_changed(app: &AppHandle) {
+    refresh_workday(app);
+    let _ = app.emit(EVENT_OUTBOX_CHANGED, pending_count(app));
+    tray::refresh(app);
+}
+
+/// Replays the outbox in order until it is empty, the server becomes
+/// unreachable or the token is rejected
+pub async fn replay(app: &AppHandle) -> Result<SyncReport, String> {
+    let state = app.state::<SyncState>();
+    let config = SyncConfig {
+        api_base_url: app.state::<ApiClient>().profile().api_base_url,
+    };
+
+    if state.replaying.swap(true, Ordering::SeqCst) {
+        return Err("Synchronisierung läuft bereits".to_string());
+    }
+
+    let report = replay_with(app, &state, &config).await;
+    state.replaying.store(false, Ordering::SeqCst);
+
+    let report = report?;
+    let _ = app.emit(EVENT_SYNC_FINISHED, &report);
+    tray::refresh(app);
+    Ok(report)
+}
+
+async fn replay_with(
+    app: &AppHandle,
+    state: &SyncState,
+    config: &SyncConfig,
+) -> Result<SyncReport, String> {
+    let mut results = Vec::new();
+    let mut attempted: HashSet<i64> = HashSet::new();
+    // Records with a parked item: later items for them must wait
+    let mut blocked: HashSet<(OutboxEntity, i64)> = HashSet::new();
+    let mut online = true;
+
+    loop {
+        // Re-read every round, creates rewrite the ids of later items
+        let next = state.with_store(|s| s.outbox())?.into_iter().find(|item| {
+            if item.status == OutboxStatus::Failed {
+                blocked.insert((item.entity, item.target_id));
+            }
+            item.status == OutboxStatus::Pending
+                && !attempted.contains(&item.seq)
+                && !blocked.contains(&(item.entity, item.target_id))
+        });
+        let Some(item) = next else {
+            break;
+        };
+        attempted.insert(item.seq);
+
+        let outcome = match check_overlap(state, &item)? {
+            Some(detected) => Err(SendError::Conflict(detected)),
+            None => match check_server(app, config, &item).await {
+                Ok(Check::Send) => send(app, config, &item).await,
+                Ok(Check::AlreadyDeleted) => Ok(Accepted {
+                    server_id: None,
+                    record: None,
+                }),
+                Err(error) => Err(error),
+            },
+        };
+        let result = match outcome {
+            Ok(accepted) => {
+                state.with_store(|s| {
+                    s.complete(&item, accepted.server_id, accepted.record.as_ref())
+                })?;
+                item_result(&item, accepted.server_id, None)
+            }
+            Err(SendError::Rejected(kind, message)) => {
+                let detected = rejection(kind, message);
+                park(app, state, &item, &detected, &mut blocked)?
+            }
+            Err(SendError::Conflict(detected)) => park(app, state, &item, &detected, &mut blocked)?,
+            Err(SendError::Unauthorized) => {
+                let message = "Anmeldung abgelaufen".to_string();
+                state.with_store(|s| s.record_failure(item.seq, &message, false))?;
+                let result = item_result(&item, None, Some(message));
+                let _ = app.emit(EVENT_SYNC_ITEM, &result);
+                results.push(result);
+                break;
+            }
+            Err(SendError::Unavailable(message)) => {
+                state.with_store(|s| s.record_failure(item.seq, &message, false))?;
+                online = false;
+                let result = item_result(&item, None, Some(message));
+                let _ = app.emit(EVENT_SYNC_ITEM, &result);
+                results.push(result);
+                break;
+            }
+        };
+        let _ = app.emit(EVENT_SYNC_ITEM, &result);
+        results.push(result);
+    }
+
+    let pending = state.with_store(|s| s.pending_count())?;
+    Ok(SyncReport {
+        online,
+        results,
+        pending,
+    })
+}
+
+fn item_result(item: &OutboxItem, server_id: Option<i64>, error: Option<String>) -> ItemResult {
+    ItemResult {
+        seq: item.seq,
+        entity: item.entity,
+        operation: item.operation,
+        target_id: item.target_id,
+        success: error.is_none(),
+        server_id,
+        error,
+        conflict_id: None,
+    }
+}
+
+/// Stores the conflict, parks the item and blocks the record for this run
+fn park(
+    app: &AppHandle,
+    state: &SyncState,
+    item: &OutboxItem,
+    detected: &DetectedConflict,
+    blocked: &mut HashSet<(OutboxEntity, i64)>,
+) -> Result<ItemResult, String> {
+    let conflict = state.with_store(|s| s.record_conflict(item, detected))?;
+    blocked.insert((item.entity, item.target_id));
+    let _ = app.emit(EVENT_SYNC_CONFLICT, &conflict);
+
+    let mut result = item_result(item, None, Some(conflict.message));
+    result.conflict_id = Some(conflict.id);
+    Ok(result)
+}
+
+/// The server answers overlaps with a plain 400
+fn rejection(kind: ApiErrorKind, message: String) -> DetectedConflict {
+    let kind = if kind == ApiErrorKind::Conflict || message.to_lowercase().contains("overlap") {
+        ConflictKind::Overlap
+    } else {
+        ConflictKind::Rejected
+    };
+    DetectedConflict {
+        kind,
+        server_version: None,
+        conflicting_entry: None,
+        message,
+    }
+}
+
+enum Check {
+    Send,
+    /// Delete of a record the server no longer has
+    AlreadyDeleted,
+}
+
+/// Overlap with a synced entry of the same day. Items re-queued by the
+/// user after resolving a conflict skip all checks.
+fn check_overlap(state: &SyncState, item: &OutboxItem) -> Result<Option<DetectedConflict>, String> {
+    if item.forced
+        || item.entity != OutboxEntity::TimeEntry
+        || item.operation == OutboxOperation::Delete
+    {
+        return Ok(None);
+    }
+    let overlap = state.with_store(|s| s.find_overlap(item.target_id))?;
+    Ok(overlap.map(|other| DetectedConflict {
+        kind: ConflictKind::Overlap,
+        message: format!(
+            "Überschneidet sich mit dem Eintrag {}–{} am {}",
+            other.start_time, other.end_time, other.date
+        ),
+        server_version: None,
+        conflicting_entry: serde_json::to_value(&other).ok(),
+    }))
+}
+
+/// Compares the server copy with the version an update or delete was
+/// based on
+async fn check_server(
+    app: &AppHandle,
+    config: &SyncConfig,
+    item: &OutboxItem,
+) -> Result<Check, SendError> {
+    let (false, OutboxOperation::Update | OutboxOperation::Delete, Some(base)) =
+        (item.forced, item.operation, &item.base_version)
+    else {
+        return Ok(Check::Send);
+    };
+
+    match fetch(app, config, item.entity, item.target_id).await? {
+        None if item.operation == OutboxOperation::Delete => Ok(Check::AlreadyDeleted),
+        None => Err(SendError::Conflict(DetectedConflict {
+            kind: ConflictKind::Deleted,
+            server_version: None,
+            conflicting_entry: None,
+            message: "Der Eintrag wurde inzwischen auf dem Server gelöscht".to_string(),
+        })),
+        Some(server) if offline_store::server_changed(item.entity, base, &server) => {
+            Err(SendError::Conflict(DetectedConflict {
+                kind: ConflictKind::Modified,
+                server_version: Some(server),
+                conflicting_entry: None,
+                message: "Der Eintrag wurde inzwischen auf dem Server geändert".to_string(),
+            }))
+        }
+        Some(_) => Ok(Check::Send),
+    }
+}
+
+/// Current server copy of a record, `None` if it no longer exists
+async fn fetch(
+    app: &AppHandle,
+    config: &SyncConfig,
+    entity: OutboxEntity,
+    id: i64,
+) -> Result<Option<Value>, SendError> {
+    let path = format!("{}/{}", entity.endpoint(), id);
+    match request(
+        app,
+        Method::GET,
+        &api_client::url(&config.api_base_url, &path),
+        None,
+    )
+    .await
+    {
+        Ok(body) => Ok(body.get("data").filter(|d| d.is_object()).cloned()),
+        Err(SendError::Rejected(ApiErrorKind::NotFound, _)) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+async fn send(
+    app: &AppHandle,
+    config: &SyncConfig,
+    item: &OutboxItem,
+) -> Result<Accepted, SendError> {
+    let collection = api_client::url(&config.api_base_url, item.entity.endpoint());
+    let (method, url, body) = match item.operation {
+        OutboxOperation::Create => (Method::POST, collection, Some(&item.payload)),
+        OutboxOperation::Update => (
+            Method::PUT,
+            format!("{}/{}", collection, item.target_id),
+            Some(&item.payload),
+        ),
+        OutboxOperation::Delete => (
+            Method::DELETE,
+            format!("{}/{}", collection, item.target_id),
+            None,
+        ),
+    };
+
+    match request(app, method, &url, body).await {
+        Ok(body) => {
+            let record = body.get("data").filter(|d| d.is_object()).cloned();
+            let server_id = record
+                .as_ref()
+                .and_then(|r| r.get("id"))
+                .and_then(Value::as_i64);
+            Ok(Accepted { server_id, record })
+        }
+        // Already gone on the server – nothing left to delete
+        Err(SendError::Rejected(ApiErrorKind::NotFound, _))
+            if item.operation == OutboxOperation::Delete =>
+        {
+            Ok(Accepted {
+                server_id: None,
+                record: None,
+            })
+        }
+        Err(error) => Err(error),
+    }
+}
+
+async fn request(
+    app: &AppHandle,
+    method: Method,
+    url: &str,
+    body: Option<&Value>,
+) -> Result<Value, SendError> {
+    let client = app
+        .try_state::<ApiClient>()
+        .ok_or_else(|| SendError::Unavailable("HTTP-Client nicht verfügbar".to_string()))?;
+    client
+        .json(app, method, url, body)
+        .await
+        .map_err(|error| match error.kind {
+            ApiErrorKind::Unauthorized => SendError::Unauthorized,
+            _ if error.is_transient() => SendError::Unavailable(error.message),
+            kind => SendError::Rejected(kind, error.message),
+        })
+}
+
+/// Refreshes the cache with the server copy of the logged-in user's time
+/// entries (previous and current year) and absence requests. Skipped
+/// while a replay is running, the next pull catches up.
+pub async fn pull(app: &AppHandle) -> Result<(), String> {
+    let Some(user_id) = app.state::<AuthState>().user_id() else {
+        return Ok(());
+    };
+    let state = app.state::<SyncState>();
+    let config = SyncConfig {
+        api_base_url: app.state::<ApiClient>().profile().api_base_url,
+    };
+
+    if state.replaying.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    let result = pull_with(app, &state, &config, user_id).await;
+    state.replaying.store(false, Ordering::SeqCst);
+
+    if let Err(error) = &result {
+        log::warn!("Failed to refresh the offline cache: {}", error);
+    }
+    result
+}
+
+/// Pulls unless the cache already holds the logged-in user's server copy.
+/// Readers of the cache call this first; offline they get what is cached.
+pub async fn ensure_pulled(app: &AppHandle) {
+    let Some(user_id) = app.state::<AuthState>().user_id() else {
+        return;
+    };
+    let pulled = app
+        .state::<SyncState>()
+        .pulled
+        .lock()
+        .map(|p| *p == Some(user_id))
+        .unwrap_or(false);
+    if !pulled {
+        let _ = pull(app).await;
+    }
+}
+
+async fn pull_with(
+    app: &AppHandle,
+    state: &SyncState,
+    config: &SyncConfig,
+    user_id: i64,
+) -> Result<(), String> {
+    let year = Local::now().year();
+    let from = NaiveDate::from_ymd_opt(year - 1, 1, 1).ok_or("Ungültiges Jahr")?;
+    let to = NaiveDate::from_ymd_opt(year, 12, 31).ok_or("Ungültiges Jahr")?;
+    let (from, to) = (from.to_string(), to.to_string());
+
+    let path = format!(
+        "/time-entries?userId={}&startDate={}&endDate={}&limit={}",
+        user_id, from, to, TIME_ENTRY_LIMIT
+    );
+    let body = pull_request(app, config, &path).await?;
+    let data = body.get("data");
+    let entries: Vec<TimeEntryRecord> = records(data.and_then(|d| d.get("rows")));
+    let complete = !data
+        .and_then(|d| d.get("pagination"))
+        .and_then(|p| p.get("hasMore"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let mut absences: Vec<AbsenceRequestRecord> = Vec::new();
+    for page in 1.. {
+        let path = format!(
+            "/absences?userId={}&limit={}&page={}",
+            user_id, ABSENCE_PAGE_SIZE, page
+        );
+        let body = pull_request(app, config, &path).await?;
+        absences.extend(records::<AbsenceRequestRecord>(body.get("data")));
+        let more = body
+            .get("pagination")
+            .and_then(|p| p.get("hasMore"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if !more {
+            break;
+        }
+    }
+
+    // Logged out or switched user while the requests were running
+    if app.state::<AuthState>().user_id() != Some(user_id) {
+        return Ok(());
+    }
+    state.with_store(|s| {
+        if complete {
+            s.replace_time_entries(user_id, &from, &to, &entries)?;
+        } else {
+            // Truncated: refresh what came back, delete nothing
+            s.cache_time_entries(&entries)?;
+        }
+        s.replace_absence_requests(user_id, &absences)
+    })?;
+    if let Ok(mut pulled) = state.pulled.lock() {
+        *pulled = Some(user_id);
+    }
+    refresh_workday(app);
+    let _ = app.emit(EVENT_CACHE_UPDATED, user_id);
+    Ok(())
+}
+
+async fn pull_request(app: &AppHandle, config: &SyncConfig, path: &str) -> Result<Value, String> {
+    request(
+        app,
+        Method::GET,
+        &api_client::url(&config.api_base_url, path),
+        None,
+    )
+    .await
+    .map_err(|error| match error {
+        SendError::Unavailable(message) | SendError::Rejected(_, message) => message,
+        SendError::Unauthorized => "Anmeldung abgelaufen".to_string(),
+        SendError::Conflict(detected) => detected.message,
+    })
+}
+
+/// Rows of a list response; malformed rows are skipped
+fn records<T: serde::de::DeserializeOwned>(rows: Option<&Value>) -> Vec<T> {
+    rows.and_then(Value::as_array)
+        .map(|rows| {
+            rows.iter()
+                .filter_map(|row| serde_json::from_value(row.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// ========================================
+// Commands
+// ========================================
+
+#[tauri::command]
+pub async fn sync_now(app: AppHandle) -> Result<SyncReport, String> {
+    let report = replay(&app).await?;
+    if report.online {
+        let _ = pull(&app).await;
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+pub fn outbox_list(state: State<'_, SyncState>) -> Result<Vec<OutboxItem>, String> {
+    state.with_store(|s| s.outbox())
+}
+
+#[tauri::command]
+pub fn outbox_retry(app: AppHandle, seq: i64) -> Result<(), String> {
+    app.state::<SyncState>().with_store(|s| s.retry(seq))?;
+    trigger_replay(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn outbox_discard(app: AppHandle, seq: i64) -> Result<(), String> {
+    app.state::<SyncState>().with_store(|s| s.discard(seq))?;
+    emit_outbox_changed(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_conflicts(state: State<'_, SyncState>) -> Result<Vec<SyncConflict>, String> {
+    state.with_store(|s| s.conflicts())
+}
+
+#[tauri::command]
+pub fn resolve_conflict(app: AppHandle, id: i64, resolution: Resolution) -> Result<(), String> {
+    app.state::<SyncState>()
+        .with_store(|s| s.resolve_conflict(id, &resolution))?;
+    emit_outbox_changed(&app);
+    trigger_replay(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn offline_cache_time_entries(
+    state: State<'_, SyncState>,
+    records: Vec<TimeEntryRecord>,
+) -> Result<(), String> {
+    state.with_store(|s| s.cache_time_entries(&records))
+}
+
+#[tauri::command]
+pub fn offline_list_time_entries(
+    state: State<'_, SyncState>,
+    user_id: i64,
+    from: String,
+    to: String,
+) -> Result<Vec<TimeEntryRecord>, String> {
+    state.with_store(|s| s.list_time_entries(user_id, &from, &to))
+}
+
+#[tauri::command]
+pub fn offline_create_time_entry(
+    app: AppHandle,
+    input: TimeEntryInput,
+) -> Result<TimeEntryRecord, String> {
+    let record = app
+        .state::<SyncState>()
+        .with_store(|s| s.create_time_entry(&input))?;
+    emit_outbox_changed(&app);
+    trigger_replay(&app);
+    Ok(record)
+}
+
+/// Bulk import from `time_import`; all entries are stored or none
+#[tauri::command]
+pub fn offline_import_time_entries(
+    app: AppHandle,
+    entries: Vec<TimeEntryInput>,
+) -> Result<usize, String> {
+    let ids = app
+        .state::<SyncState>()
+        .with_store(|s| s.create_time_entries(&entries))?;
+    emit_outbox_changed(&app);
+    trigger_replay(&app);
+    Ok(ids.len())
+}
+
+#[tauri::command]
+pub fn offline_update_time_entry(
+    app: AppHandle,
+    id: i64,
+    patch: Value,
+) -> Result<TimeEntryRecord, String> {
+    let record = app
+        .state::<SyncState>()
+        .with_store(|s| s.update_time_entry(id, &patch))?;
+    emit_outbox_changed(&app);
+    trigger_replay(&app);
+    Ok(record)
+}
+
+#[tauri::command]
+pub fn offline_delete_time_entry(app: AppHandle, id: i64) -> Result<(), String> {
+    app.state::<SyncState>()
+        .with_store(|s| s.delete_time_entry(id))?;
+    emit_outbox_changed(&app);
+    trigger_replay(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn offline_cache_absence_requests(
+    state: State<'_, SyncState>,
+    records: Vec<AbsenceRequestRecord>,
+) -> Result<(), String> {
+    state.with_store(|s| s.cache_absence_requests(&records))
+}
+
+#[tauri::command]
+pub fn offline_list_absence_requests(
+    state: State<'_, SyncState>,
+    user_id: i64,
+) -> Result<Vec<AbsenceRequestRecord>, String> {
+    state.with_store(|s| s.list_absence_requests(user_id))
+}
+
+#[tauri::command]
+pub fn offline_create_absence_request(
+    app: AppHandle,
+    input: AbsenceRequestInput,
+) -> Result<AbsenceRequestRecord, String> {
+    let record = app
+        .state::<SyncState>()
+        .with_store(|s| s.create_absence_request(&input))?;
+    emit_outbox_changed(&app);
+    trigger_replay(&app);
+    Ok(record)
+}
+
+#[tauri::command]
+pub fn offline_update_absence_request(
+    app: AppHandle,
+    id: i64,
+    patch: Value,
+) -> Result<AbsenceRequestRecord, String> {
+    let record = app
+        .state::<SyncState>()
+        .with_store(|s| s.update_absence_request(id, &patch))?;
+    emit_outbox_changed(&app);
+    trigger_replay(&app);
+    Ok(record)
+}
+
+#[tauri::command]
+pub fn offline_delete_absence_request(app: AppHandle, id: i64) -> Result<(), String> {
+    app.state::<SyncState>()
+        .with_store(|s| s.delete_absence_request(id))?;
+    emit_outbox_changed(&app);
+    trigger_replay(&app);
+    Ok(())
+}
+
+/// Overtime ledger from the cached entries and absences, including local
+/// changes that are not synced yet. `time_entries` and `absences` of
+/// `input` are replaced by the cached rows; employee, holidays and
+/// corrections come from the caller.
+#[tauri::command]
+pub fn offline_overtime_ledger(
+    app: AppHandle,
+    state: State<'_, SyncState>,
+    user_id: i64,
+    mut input: OvertimeInput,
+) -> Result<OvertimeLedger, String> {
+    let from = input.from.format("%Y-%m-%d").to_string();
+    let to = input.to.format("%Y-%m-%d").to_string();
+    let (entries, absences) = state.with_store(|s| {
+        Ok((
+            s.list_time_entries(user_id, &from, &to)?,
+            s.list_absence_requests(user_id)?,
+        ))
+    })?;
+
+    input.time_entries = entries
+        .iter()
+        .filter_map(|entry| {
+            Some(overtime::WorkedHours {
+                date: entry.date.parse().ok()?,
+                hours: entry.hours,
+            })
+        })
+        .collect();
+    input.absences = engine_absences(&absences);
+    let ledger = overtime::calculate(&input).map_err(|e| e.to_string())?;
+
+    // A ledger up to today is the current balance shown in the tray
+    if input.to == Local::now().date_naive() {
+        tray::set_balance(&app, user_id, ledger.balance);
+    }
+    Ok(ledger)
+}
+
+/// Vacation balance with the cached absence requests (including unsynced
+/// ones) instead of `input.absences`
+#[tauri::command]
+pub fn offline_vacation_projection(
+    state: State<'_, SyncState>,
+    user_id: i64,
+    mut input: VacationInput,
+) -> Result<VacationProjection, String> {
+    let absences = state.with_store(|s| s.list_absence_requests(user_id))?;
+    input.absences = engine_absences(&absences);
+    vacation::project(&input).map_err(|e| e.to_string())
+}
+
+/// Cached absence requests of `user_id` (including unsynced ones) for the
+/// engines
+pub fn cached_absences(app: &AppHandle, user_id: i64) -> Result<Vec<overtime::Absence>, String> {
+    let absences = app
+        .state::<SyncState>()
+        .with_store(|s| s.list_absence_requests(user_id))?;
+    Ok(engine_absences(&absences))
+}
+
+fn engine_absences(absences: &[AbsenceRequestRecord]) -> Vec<overtime::Absence> {
+    absences
+        .iter()
+        .filter_map(|absence| {
+            Some(overtime::Absence {
+                id: Some(absence.id),
+                kind: AbsenceKind::parse(&absence.absence_type),
+                start_date: absence.start_date.parse().ok()?,
+                end_date: absence.end_date.parse().ok()?,
+                status: absence.status.clone(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,970 @@
+//! Import of historical time entries from CSV or XLSX
+//!
+//! Replaces hand-run scripts like
+//! `server/src/scripts/addHistoricalTimeEntries.ts` when a department
+//! brings its past hours as spreadsheet. The import wizard works in steps:
+//!
+//! 1. `preview_time_import` reads the file and suggests a column mapping
+//!    from the header row,
+//! 2. `validate_time_import` turns every row into a `TimeEntryInput` or
+//!    row-level errors, plus ArbZG warnings,
+//! 3. `offline_import_time_entries` (`sync`) queues the valid entries in
+//!    the outbox in one transaction – nothing is uploaded before that.
+//!
+//! Accepted values: dates `TT.MM.JJJJ`, `TT.MM.JJ`, `JJJJ-MM-TT`; times
+//! `08:30`, `8.30`, `08:30 Uhr`; durations `8,5`, `8,5 h`, `8:30`, `30 min`
+//! (breaks without unit are minutes); date and time cells of XLSX files.
+//! Rows follow the server's `validateTimeEntry` middleware: location
+//! `office`/`homeoffice`/`field` (German names accepted), end after start
+//! on the same day, no negative break and, like `checkOverlap`, no overlap
+//! with another imported row of the same user. Without start time the
+//! entry starts at 08:00 like in the script. ArbZG findings are warnings,
+//! as on the server. Overlaps with entries already on the server are
+//! reported as sync conflicts after the upload.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, fs,
+    io::{self, Cursor},
+    path::Path,
+};
+
+use calamine::{open_workbook_from_rs, Data, Reader, Xlsx, XlsxError};
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+use crate::arbzg::{self, Severity};
+use crate::offline_store::TimeEntryInput;
+
+/// Start time for rows that only have hours (as in the import script)
+const DEFAULT_START: (u32, u32) = (8, 0);
+const PREVIEW_ROWS: usize = 5;
+
+#[derive(Debug)]
+pub enum ImportError {
+    Io(io::Error),
+    Csv(csv::Error),
+    Xlsx(XlsxError),
+    /// File extension
+    UnsupportedFile(String),
+    Empty,
+    /// Title of the required column
+    MissingColumn(&'static str),
+    NoUser,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(e) => write!(f, "Datei konnte nicht gelesen werden: {}", e),
+            ImportError::Csv(e) => write!(f, "Ungültige CSV-Datei: {}", e),
+            ImportError::Xlsx(e) => write!(f, "Ungültige Excel-Datei: {}", e),
+            ImportError::UnsupportedFile(ext) => write!(
+                f,
+                "Dateityp .{} wird nicht unterstützt (CSV oder XLSX)",
+                ext
+            ),
+            ImportError::Empty => write!(f, "Die Datei enthält keine Daten"),
+            ImportError::MissingColumn(column) => {
+                write!(f, "Spalte \"{}\" ist nicht zugeordnet", column)
+            }
+            ImportError::NoUser => write!(
+                f,
+                "Kein Mitarbeiter ausgewählt und keine Mitarbeiter-Spalte zugeordnet"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<io::Error> for ImportError {
+    fn from(e: io::Error) -> Self {
+        ImportError::Io(e)
+    }
+}
+
+impl From<csv::Error> for ImportError {
+    fn from(e: csv::Error) -> Self {
+        ImportError::Csv(e)
+    }
+}
+
+impl From<XlsxError> for ImportError {
+    fn from(e: XlsxError) -> Self {
+        ImportError::Xlsx(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Empty,
+    Text(String),
+    Number(f64),
+    Date(NaiveDate),
+    /// Time of day, or a duration below 24 h
+    Time(NaiveTime),
+    DateTime(NaiveDateTime),
+}
+
+impl Cell {
+    fn text(value: &str) -> Self {
+        match value.trim() {
+            "" => Cell::Empty,
+            value => Cell::Text(value.to_string()),
+        }
+    }
+
+    fn display(&self) -> String {
+        match self {
+            Cell::Empty => String::new(),
+            Cell::Text(text) => text.clone(),
+            Cell::Number(n) => n.to_string().replace('.', ","),
+            Cell::Date(d) => d.format("%d.%m.%Y").to_string(),
+            Cell::Time(t) => t.format("%H:%M").to_string(),
+            Cell::DateTime(dt) => dt.format("%d.%m.%Y %H:%M").to_string(),
+        }
+    }
+}
+
+/// Header row and data rows of the first sheet
+#[derive(Debug, Clone, PartialEq)]
+struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    fn new(mut rows: Vec<Vec<Cell>>) -> Result<Self, ImportError> {
+        rows.retain(|row| row.iter().any(|c| *c != Cell::Empty));
+        if rows.is_empty() {
+            return Err(ImportError::Empty);
+        }
+        let headers = rows.remove(0).iter().map(Cell::display).collect();
+        Ok(Table { headers, rows })
+    }
+}
+
+/// UTF-8 (with or without BOM) or Windows-1252 as written by Excel;
+/// the delimiter (`;`, `,` or tab) is taken from the header line
+fn read_csv(bytes: &[u8]) -> Result<Table, ImportError> {
+    let text = match std::str::from_utf8(bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes)) {
+        Ok(text) => text.to_string(),
+        Err(_) => encoding_rs::WINDOWS_1252.decode(bytes).0.into_owned(),
+    };
+    let header = text.lines().next().unwrap_or_default();
+    // On a tie the later one wins, so `;` is preferred
+    let delimiter = [b'\t', b',', b';']
+        .into_iter()
+        .max_by_key(|d| header.matches(*d as char).count())
+        .unwrap_or(b';');
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        rows.push(record?.iter().map(Cell::text).collect());
+    }
+    Table::new(rows)
+}
+
+fn xlsx_cell(data: &Data) -> Cell {
+    match data {
+        Data::Empty | Data::Error(_) => Cell::Empty,
+        Data::Int(n) => Cell::Number(*n as f64),
+        Data::Float(n) => Cell::Number(*n),
+        Data::Bool(b) => Cell::Text(b.to_string()),
+        Data::String(s) | Data::DateTimeIso(s) | Data::DurationIso(s) => Cell::text(s),
+        Data::DateTime(value) => {
+            let serial = value.as_f64();
+            match value.as_datetime() {
+                _ if value.is_duration() && serial >= 1.0 => Cell::Number(serial * 24.0),
+                Some(dt) if serial < 1.0 => Cell::Time(dt.time()),
+                Some(dt) if serial.fract() == 0.0 => Cell::Date(dt.date()),
+                Some(dt) => Cell::DateTime(dt),
+                None => Cell::Number(serial),
+            }
+        }
+    }
+}
+
+fn read_xlsx(bytes: Vec<u8>) -> Result<Table, ImportError> {
+    let mut workbook: Xlsx<_> = open_workbook_from_rs(Cursor::new(bytes))?;
+    let range = workbook.worksheet_range_at(0).ok_or(ImportError::Empty)??;
+    Table::new(
+        range
+            .rows()
+            .map(|r| r.iter().map(xlsx_cell).collect())
+            .collect(),
+    )
+}
+
+fn read_file(path: &Path) -> Result<Table, ImportError> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "csv" | "txt" => read_csv(&fs::read(path)?),
+        "xlsx" | "xlsm" => read_xlsx(fs::read(path)?),
+        _ => Err(ImportError::UnsupportedFile(extension)),
+    }
+}
+
+/// Column index (0-based) per field
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnMapping {
+    pub date: Option<usize>,
+    pub start_time: Option<usize>,
+    pub end_time: Option<usize>,
+    pub break_minutes: Option<usize>,
+    pub hours: Option<usize>,
+    pub location: Option<usize>,
+    pub activity: Option<usize>,
+    pub project: Option<usize>,
+    pub notes: Option<usize>,
+    /// Username or Personalnummer, resolved through `ImportOptions::users`
+    pub user: Option<usize>,
+}
+
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+/// Mapping from known German and English header names
+fn suggest_mapping(headers: &[String]) -> ColumnMapping {
+    let mut mapping = ColumnMapping::default();
+    let fields: [(&mut Option<usize>, &[&str]); 10] = [
+        (&mut mapping.date, &["datum", "date", "tag"]),
+        (
+            &mut mapping.start_time,
+            &[
+                "beginn",
+                "start",
+                "starttime",
+                "von",
+                "kommen",
+                "arbeitsbeginn",
+            ],
+        ),
+        (
+            &mut mapping.end_time,
+            &["ende", "end", "endtime", "bis", "gehen", "arbeitsende"],
+        ),
+        (
+            &mut mapping.break_minutes,
+            &["pause", "pausemin", "pauseminuten", "break", "breakminutes"],
+        ),
+        (
+            &mut mapping.hours,
+            &[
+                "stunden",
+                "std",
+                "dauer",
+                "hours",
+                "arbeitszeit",
+                "iststunden",
+                "ist",
+            ],
+        ),
+        (&mut mapping.location, &["ort", "arbeitsort", "location"]),
+        (
+            &mut mapping.activity,
+            &["tätigkeit", "taetigkeit", "aktivität", "activity"],
+        ),
+        (&mut mapping.project, &["projekt", "project"]),
+        (
+            &mut mapping.notes,
+            &["bemerkung", "notiz", "notizen", "kommentar", "notes"],
+        ),
+        (
+            &mut mapping.user,
+            &[
+                "mitarbeiter",
+                "benutzer",
+                "benutzername",
+                "username",
+                "personalnummer",
+                "personalnr",
+            ],
+        ),
+    ];
+    let headers: Vec<String> = headers.iter().map(|h| normalize(h)).collect();
+    for (field, names) in fields {
+        *field = headers.iter().position(|h| names.contains(&h.as_str()));
+    }
+    mapping
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOptions {
+    /// User of all rows when no user column is mapped
+    #[serde(default)]
+    pub user_id: Option<i64>,
+    /// Username or Personalnummer (lowercase) → user id
+    #[serde(default)]
+    pub users: HashMap<String, i64>,
+    /// For rows without location
+    #[serde(default = "default_location")]
+    pub default_location: String,
+}
+
+fn default_location() -> String {
+    "office".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPreview {
+    pub headers: Vec<String>,
+    /// First rows as displayed text
+    pub sample: Vec<Vec<String>>,
+    pub rows: usize,
+    pub mapping: ColumnMapping,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RowIssue {
+    /// Row in the file (1 = header)
+    pub row: usize,
+    /// Header of the column, if the issue belongs to one
+    pub column: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedRow {
+    pub row: usize,
+    pub entry: TimeEntryInput,
+    pub hours: f64,
+    /// `entry.date`, `entry.start_time` and `entry.end_time` as parsed
+    pub date: NaiveDate,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    /// Data rows in the file
+    pub rows: usize,
+    /// Rows without errors, ready for `offline_import_time_entries`
+    pub entries: Vec<ImportedRow>,
+    pub errors: Vec<RowIssue>,
+    pub warnings: Vec<RowIssue>,
+}
+
+fn parse_date(cell: &Cell) -> Result<NaiveDate, String> {
+    let invalid = || format!("Ungültiges Datum: {}", cell.display());
+    match cell {
+        Cell::Date(d) => Ok(*d),
+        Cell::DateTime(dt) => Ok(dt.date()),
+        Cell::Text(text) => {
+            if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+                return Ok(date);
+            }
+            let parts: Vec<&str> = text.split('.').map(str::trim).collect();
+            let [day, month, year] = parts[..] else {
+                return Err(invalid());
+            };
+            let number = |part: &str| part.parse::<u32>().map_err(|_| invalid());
+            let year = match year.len() {
+                2 => 2000 + number(year)? as i32,
+                4 => number(year)? as i32,
+                _ => return Err(invalid()),
+            };
+            NaiveDate::from_ymd_opt(year, number(month)?, number(day)?).ok_or_else(invalid)
+        }
+        Cell::Empty => Err("Datum fehlt".to_string()),
+        Cell::Number(_) | Cell::Time(_) => Err(invalid()),
+    }
+}
+
+/// `08:30`, `8.30`, `08:30 Uhr`
+fn parse_clock(text: &str) -> Option<NaiveTime> {
+    let text = text
+        .trim()
+        .trim_end_matches("Uhr")
+        .trim_end_matches("uhr")
+        .trim();
+    let (hours, minutes) = text.split_once([':', '.'])?;
+    if minutes.len() != 2 {
+        return None;
+    }
+    NaiveTime::from_hms_opt(hours.parse().ok()?, minutes.parse().ok()?, 0)
+}
+
+fn parse_time(cell: &Cell) -> Result<Option<NaiveTime>, String> {
+    let invalid = || format!("Ungültige Uhrzeit: {}", cell.display());
+    match cell {
+        Cell::Empty => Ok(None),
+        Cell::Time(t) => Ok(Some(*t)),
+        Cell::DateTime(dt) => Ok(Some(dt.time())),
+        // Time cell without format: fraction of a day
+        Cell::Number(n) if (0.0..1.0).contains(n) => {
+            let minutes = (n * 1440.0).round() as u32;
+            NaiveTime::from_hms_opt(minutes / 60, minutes % 60, 0)
+                .map(Some)
+                .ok_or_else(invalid)
+        }
+        Cell::Text(text) => parse_clock(text).map(Some).ok_or_else(invalid),
+        Cell::Number(_) | Cell::Date(_) => Err(invalid()),
+    }
+}
+
+/// Decimal number with `,` or `.`, or `h:mm`, in hours
+fn parse_hours_text(text: &str) -> Option<f64> {
+    if let Some((hours, minutes)) = text.split_once(':') {
+        let minutes: u32 = minutes.trim().parse().ok().filter(|m| *m < 60)?;
+        return Some(hours.trim().parse::<u32>().ok()? as f64 + minutes as f64 / 60.0);
+    }
+    text.trim()
+        .replace(',', ".")
+        .parse()
+        .ok()
+        .filter(|h: &f64| h.is_finite())
+}
+
+/// `8,5`, `8,5 h`, `8:30`, `8 Std`; between 0 and 24
+fn parse_hours(cell: &Cell) -> Result<Option<f64>, String> {
+    let invalid = || format!("Ungültige Stundenzahl: {}", cell.display());
+    let hours = match cell {
+        Cell::Empty => return Ok(None),
+        Cell::Number(n) => *n,
+        Cell::Time(t) => t.hour() as f64 + t.minute() as f64 / 60.0,
+        Cell::Text(text) => {
+            let lower = text.to_lowercase();
+            let value = ["stunden", "std.", "std", "h"]
+                .iter()
+                .find_map(|unit| lower.trim().strip_suffix(unit))
+                .unwrap_or(&lower);
+            parse_hours_text(value).ok_or_else(invalid)?
+        }
+        Cell::Date(_) | Cell::DateTime(_) => return Err(invalid()),
+    };
+    if !(0.0..=24.0).contains(&hours) {
+        return Err(format!(
+            "Stundenzahl muss zwischen 0 und 24 liegen: {}",
+            cell.display()
+        ));
+    }
+    Ok(Some(hours))
+}
+
+/// Break: `30`, `30 min`, `0:30`, `0,5 h`
+fn parse_break(cell: &Cell) -> Result<i64, String> {
+    let invalid = || format!("Ungültige Pause: {}", cell.display());
+    let minutes = match cell {
+        Cell::Empty => 0.0,
+        Cell::Number(n) => *n,
+        Cell::Time(t) => (t.hour() * 60 + t.minute()) as f64,
+        Cell::Text(text) => {
+            let lower = text.to_lowercase();
+            let lower = lower.trim();
+            if let Some(hours) = ["std", "h"].iter().find_map(|u| lower.strip_suffix(u)) {
+                parse_hours_text(hours).ok_or_else(invalid)? * 60.0
+            } else if lower.contains(':') {
+                parse_hours_text(lower).ok_or_else(invalid)? * 60.0
+            } else {
+                let value = ["minuten", "min", "m"]
+                    .iter()
+                    .find_map(|u| lower.strip_suffix(u))
+                    .unwrap_or(lower);
+                parse_hours_text(value).ok_or_else(invalid)?
+            }
+        }
+        Cell::Date(_) | Cell::DateTime(_) => return Err(invalid()),
+    };
+    if minutes < 0.0 {
+        return Err("Die Pause darf nicht negativ sein".to_string());
+    }
+    if !minutes.is_finite() || minutes > 24.0 * 60.0 {
+        return Err(invalid());
+    }
+    Ok(minutes.round() as i64)
+}
+
+fn parse_location(cell: &Cell, default: &str) -> Result<String, String> {
+    let text = cell.display();
+    let location = match normalize(&text).as_str() {
+        "" => default,
+        "office" | "büro" | "buero" => "office",
+        "homeoffice" | "home" | "mobil" | "mobilesarbeiten" => "homeoffice",
+        "field" | "außendienst" | "aussendienst" => "field",
+        _ => {
+            return Err(format!(
+                "Ungültiger Arbeitsort: {} (Büro, Homeoffice oder Außendienst)",
+                text
+            ))
+        }
+    };
+    Ok(location.to_string())
+}
+
+fn minutes_of(time: NaiveTime) -> i64 {
+    (time.hour() * 60 + time.minute()) as i64
+}
+
+fn clock(minutes: i64) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// `None` outside of the day
+fn time_of(minutes: i64) -> Option<NaiveTime> {
+    let seconds = u32::try_from(minutes).ok()?.checked_mul(60)?;
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0)
+}
+
+/// Messages per mapped column (`None`: the row as a whole)
+type RowErrors = Vec<(Option<usize>, String)>;
+
+/// Row `number` as entry with warnings; errors are collected per column
+fn parse_row(
+    number: usize,
+    row: &[Cell],
+    mapping: &ColumnMapping,
+    options: &ImportOptions,
+    today: NaiveDate,
+) -> Result<(ImportedRow, Vec<String>), RowErrors> {
+    let cell = |column: Option<usize>| column.and_then(|c| row.get(c)).unwrap_or(&Cell::Empty);
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let mut check = |column: Option<usize>, message: Option<String>| {
+        if let Some(message) = message {
+            errors.push((column, message));
+        }
+    };
+
+    let user_id = match mapping.user {
+        Some(column) => {
+            let key = cell(Some(column)).display().to_lowercase();
+            let user_id = options.users.get(&key).copied();
+            if user_id.is_none() {
+                check(
+                    Some(column),
+                    Some(format!("Unbekannter Mitarbeiter: {}", key)),
+                );
+            }
+            user_id
+        }
+        None => options.user_id,
+    };
+    let date = parse_date(cell(mapping.date)).and_then(|d| {
+        if d > today {
+            Err(format!(
+                "Datum liegt in der Zukunft: {}",
+                d.format("%d.%m.%Y")
+            ))
+        } else {
+            Ok(d)
+        }
+    });
+    let start = parse_time(cell(mapping.start_time));
+    let end = parse_time(cell(mapping.end_time));
+    let hours = parse_hours(cell(mapping.hours));
+    let break_minutes = parse_break(cell(mapping.break_minutes));
+    let location = parse_location(cell(mapping.location), &options.default_location);
+    check(mapping.date, date.clone().err());
+    check(mapping.start_time, start.clone().err());
+    check(mapping.end_time, end.clone().err());
+    check(mapping.hours, hours.clone().err());
+    check(mapping.break_minutes, break_minutes.clone().err());
+    check(mapping.location, location.clone().err());
+    let (Some(user_id), Ok(date), Ok(start), Ok(end), Ok(hours), Ok(break_minutes), Ok(location)) =
+        (user_id, date, start, end, hours, break_minutes, location)
+    else {
+        return Err(errors);
+    };
+
+    let start = start.map(minutes_of);
+    let end = end.map(minutes_of);
+    let worked = hours.map(|h| (h * 60.0).round() as i64);
+    let (start, end) = match (start, end, worked) {
+        (Some(start), Some(end), _) => (start, end),
+        (Some(start), None, Some(worked)) => (start, start + worked + break_minutes),
+        (None, Some(end), Some(worked)) => (end - worked - break_minutes, end),
+        (None, None, Some(worked)) => {
+            let start = (DEFAULT_START.0 * 60 + DEFAULT_START.1) as i64;
+            warnings.push(format!(
+                "Kein Beginn angegeben, {} angenommen",
+                clock(start)
+            ));
+            (start, start + worked + break_minutes)
+        }
+        _ => {
+            errors.push((None, "Ende oder Stunden fehlen".to_string()));
+            return Err(errors);
+        }
+    };
+    let (Some(start_time), Some(end_time)) = (time_of(start), time_of(end)) else {
+        errors.push((
+            None,
+            "Der Eintrag geht über Mitternacht hinaus; bitte pro Tag einen Eintrag anlegen"
+                .to_string(),
+        ));
+        return Err(errors);
+    };
+    if end <= start {
+        errors.push((None, "Das Ende muss nach dem Beginn liegen".to_string()));
+        return Err(errors);
+    }
+    let net = end - start - break_minutes;
+    if net <= 0 {
+        errors.push((
+            mapping.break_minutes,
+            "Die Pause ist länger als die Arbeitszeit".to_string(),
+        ));
+        return Err(errors);
+    }
+    let net_hours = (net as f64 / 60.0 * 100.0).round() / 100.0;
+    if let Some(given) = hours.filter(|h| (h - net_hours).abs() > 0.01) {
+        warnings.push(format!(
+            "Stunden ({}) passen nicht zu Beginn, Ende und Pause ({}); es gilt {}",
+            given, net_hours, net_hours
+        ));
+    }
+
+    let text = |column: Option<usize>| Some(cell(column).display()).filter(|t| !t.is_empty());
+    let entry = TimeEntryInput {
+        user_id,
+        date: date.format("%Y-%m-%d").to_string(),
+        start_time: clock(start),
+        end_time: clock(end),
+        break_minutes,
+        activity: text(mapping.activity),
+        project: text(mapping.project),
+        location,
+        notes: text(mapping.notes),
+    };
+    let imported = ImportedRow {
+        row: number,
+        entry,
+        hours: net_hours,
+        date,
+        start: start_time,
+        end: end_time,
+    };
+    Ok((imported, warnings))
+}
+
+fn validate(
+    table: &Table,
+    mapping: &ColumnMapping,
+    options: &ImportOptions,
+    today: NaiveDate,
+) -> Result<ImportReport, ImportError> {
+    if mapping.date.is_none() {
+        return Err(ImportError::MissingColumn("Datum"));
+    }
+    if mapping.start_time.is_none() && mapping.hours.is_none() {
+        return Err(ImportError::MissingColumn("Beginn oder Stunden"));
+    }
+    if mapping.user.is_none() && options.user_id.is_none() {
+        return Err(ImportError::NoUser);
+    }
+    let issue = |row: usize, column: Option<usize>, message: String| RowIssue {
+        row,
+        column: column.and_then(|c| table.headers.get(c).cloned()),
+        message,
+    };
+
+    let mut report = ImportReport {
+        rows: table.rows.len(),
+        ..ImportReport::default()
+    };
+    // Header is row 1
+    let mut parsed = Vec::new();
+    for (i, row) in table.rows.iter().enumerate() {
+        let number = i + 2;
+        match parse_row(number, row, mapping, options, today) {
+            Ok((imported, warnings)) => {
+                report
+                    .warnings
+                    .extend(warnings.into_iter().map(|w| issue(number, None, w)));
+                parsed.push(imported);
+            }
+            Err(errors) => report
+                .errors
+                .extend(errors.into_iter().map(|(c, m)| issue(number, c, m))),
+        }
+    }
+
+    // Per user and day, in time order
+    let mut days: BTreeMap<(i64, NaiveDate), Vec<ImportedRow>> = BTreeMap::new();
+    for row in parsed {
+        days.entry((row.entry.user_id, row.date))
+            .or_default()
+            .push(row);
+    }
+    let mut previous_end: Option<(i64, NaiveDateTime)> = None;
+    for ((user_id, day), mut rows) in days {
+        rows.sort_by_key(|r| r.start);
+
+        let mut accepted: Vec<ImportedRow> = Vec::new();
+        for row in rows {
+            if let Some(other) = accepted.iter().find(|other| row.start < other.end) {
+                report.errors.push(issue(
+                    row.row,
+                    None,
+                    format!("Überschneidet sich mit Zeile {}", other.row),
+                ));
+                continue;
+            }
+            if let Some(v) = arbzg::check_break_time(row.hours, row.entry.break_minutes) {
+                report.warnings.push(issue(row.row, None, v.message));
+            }
+            accepted.push(row);
+        }
+        let Some(first) = accepted.first() else {
+            continue;
+        };
+        let total: f64 = accepted.iter().map(|r| r.hours).sum();
+        if let Some(v) = arbzg::check_max_daily_hours(&first.entry.date, total, &[], None)
+            .filter(|v| v.severity >= Severity::Warning)
+        {
+            report.warnings.push(issue(first.row, None, v.message));
+        }
+        if let Some((_, end)) = previous_end.filter(|(user, _)| *user == user_id) {
+            if let Some(v) = arbzg::check_rest_between(end, day.and_time(first.start)) {
+                report.warnings.push(issue(first.row, None, v.message));
+            }
+        }
+        let last_end = accepted.iter().map(|r| day.and_time(r.end)).max();
+        previous_end = last_end.map(|end| (user_id, end));
+        report.entries.extend(accepted);
+    }
+
+    report.entries.sort_by_key(|r| r.row);
+    report.errors.sort_by_key(|i| i.row);
+    report.warnings.sort_by_key(|i| i.row);
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn preview_time_import(path: String) -> Result<ImportPreview, String> {
+    let table = read_file(Path::new(&path)).map_err(|e| e.to_string())?;
+    Ok(ImportPreview {
+        mapping: suggest_mapping(&table.headers),
+        sample: table
+            .rows
+            .iter()
+            .take(PREVIEW_ROWS)
+            .map(|row| row.iter().map(Cell::display).collect())
+            .collect(),
+        rows: table.rows.len(),
+        headers: table.headers,
+    })
+}
+
+#[tauri::command]
+pub async fn validate_time_import(
+    path: String,
+    mapping: ColumnMapping,
+    options: ImportOptions,
+) -> Result<ImportReport, String> {
+    let table = read_file(Path::new(&path)).map_err(|e| e.to_string())?;
+    let today = Local::now().date_naive();
+    validate(&table, &mapping, &options, today).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn today() -> NaiveDate {
+        date("2026-10-16")
+    }
+
+    fn options() -> ImportOptions {
+        ImportOptions {
+            user_id: Some(7),
+            users: HashMap::new(),
+            default_location: default_location(),
+        }
+    }
+
+    #[test]
+    fn german_formats_are_parsed() {
+        let text = |t: &str| Cell::Text(t.into());
+        assert_eq!(parse_date(&text("02.03.2026")), Ok(date("2026-03-02")));
+        assert_eq!(parse_date(&text("2.3.26")), Ok(date("2026-03-02")));
+        assert_eq!(parse_date(&text("2026-03-02")), Ok(date("2026-03-02")));
+        assert!(parse_date(&text("31.02.2026")).is_err());
+        assert!(parse_date(&text("02.03.226")).is_err());
+
+        let time = |h, m| Some(NaiveTime::from_hms_opt(h, m, 0).unwrap());
+        assert_eq!(parse_time(&text("08:30")), Ok(time(8, 30)));
+        assert_eq!(parse_time(&text("8.30 Uhr")), Ok(time(8, 30)));
+        assert_eq!(parse_time(&Cell::Number(0.75)), Ok(time(18, 0)));
+        assert!(parse_time(&text("8:3")).is_err());
+        assert!(parse_time(&text("25:00")).is_err());
+
+        assert_eq!(parse_hours(&text("8,5 h")), Ok(Some(8.5)));
+        assert_eq!(parse_hours(&text("8:45")), Ok(Some(8.75)));
+        assert_eq!(parse_hours(&text("7 Std")), Ok(Some(7.0)));
+        assert!(parse_hours(&text("acht")).is_err());
+        assert_eq!(parse_hours(&Cell::Number(24.0)), Ok(Some(24.0)));
+        assert!(parse_hours(&Cell::Number(25.0)).is_err());
+        assert!(parse_hours(&Cell::Number(-1.0)).is_err());
+        assert!(parse_hours(&Cell::Number(f64::NAN)).is_err());
+        assert!(parse_hours(&text("1e300")).is_err());
+
+        assert_eq!(parse_break(&text("30")), Ok(30));
+        assert_eq!(parse_break(&text("45 min")), Ok(45));
+        assert_eq!(parse_break(&text("0:30")), Ok(30));
+        assert_eq!(parse_break(&text("0,75 h")), Ok(45));
+        assert!(parse_break(&text("-15")).is_err());
+
+        assert_eq!(parse_location(&text("Büro"), "office").unwrap(), "office");
+        assert_eq!(
+            parse_location(&text("Home-Office"), "office").unwrap(),
+            "homeoffice"
+        );
+        assert_eq!(parse_location(&Cell::Empty, "field").unwrap(), "field");
+        assert!(parse_location(&text("Strand"), "office").is_err());
+    }
+
+    #[test]
+    fn csv_rows_are_validated_with_row_numbers() {
+        let csv = "Datum;Beginn;Ende;Pause (Min);Stunden;Ort;Bemerkung\n\
+                   02.03.2026;08:00;16:30;30;;Büro;\n\
+                   03.03.2026;;;;8,5 h;Homeoffice;nur Stunden\n\
+                   04.03.2026;09:00;08:00;;;Büro;\n\
+                   05.03.2026;08:00;;30;;Strand;\n\
+                   06.03.2026;07:00;19:00;;;Büro;\n\
+                   07.03.2026;06:00;12:00;;;;\n\
+                   07.03.2026;11:00;14:00;;;;\n\
+                   01.12.2026;08:00;12:00;;;;\n";
+        let table = read_csv(csv.as_bytes()).unwrap();
+        let mapping = suggest_mapping(&table.headers);
+        assert_eq!(
+            mapping,
+            ColumnMapping {
+                date: Some(0),
+                start_time: Some(1),
+                end_time: Some(2),
+                break_minutes: Some(3),
+                hours: Some(4),
+                location: Some(5),
+                notes: Some(6),
+                ..ColumnMapping::default()
+            }
+        );
+
+        let report = validate(&table, &mapping, &options(), today()).unwrap();
+        assert_eq!(report.rows, 8);
+        let rows: Vec<usize> = report.entries.iter().map(|r| r.row).collect();
+        assert_eq!(rows, vec![2, 3, 6, 7]);
+        let only_hours = &report.entries[1].entry;
+        assert_eq!(
+            (only_hours.start_time.as_str(), only_hours.end_time.as_str()),
+            ("08:00", "16:30")
+        );
+        assert_eq!(only_hours.location, "homeoffice");
+        assert_eq!(only_hours.notes.as_deref(), Some("nur Stunden"));
+
+        let errors: Vec<(usize, Option<&str>)> = report
+            .errors
+            .iter()
+            .map(|e| (e.row, e.column.as_deref()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![(4, None), (5, Some("Ort")), (8, None), (9, Some("Datum")),]
+        );
+        assert_eq!(
+            report.errors[0].message,
+            "Das Ende muss nach dem Beginn liegen"
+        );
+        assert_eq!(report.errors[2].message, "Überschneidet sich mit Zeile 7");
+
+        // Assumed start and missing break; 12 h without break
+        let warnings: Vec<usize> = report.warnings.iter().map(|w| w.row).collect();
+        assert_eq!(warnings, vec![3, 3, 6, 6]);
+    }
+
+    #[test]
+    fn user_column_and_missing_columns() {
+        let csv = "Personalnr.,Datum,Stunden\n48,02.03.2026,8\n99,02.03.2026,8\n";
+        let table = read_csv(csv.as_bytes()).unwrap();
+        let mapping = suggest_mapping(&table.headers);
+        let mut options = options();
+        options.user_id = None;
+        options.users.insert("48".into(), 12);
+        let report = validate(&table, &mapping, &options, today()).unwrap();
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].entry.user_id, 12);
+        assert_eq!(report.errors[0].message, "Unbekannter Mitarbeiter: 99");
+
+        let no_date = ColumnMapping {
+            date: None,
+            ..mapping
+        };
+        assert!(matches!(
+            validate(&table, &no_date, &options, today()),
+            Err(ImportError::MissingColumn("Datum"))
+        ));
+        let no_user = ColumnMapping {
+            user: None,
+            ..mapping
+        };
+        assert!(matches!(
+            validate(&table, &no_user, &options, today()),
+            Err(ImportError::NoUser)
+        ));
+    }
+
+    #[test]
+    fn xlsx_date_and_time_cells() {
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet();
+        let date_format = Format::new().set_num_format("dd.mm.yyyy");
+        let time_format = Format::new().set_num_format("hh:mm");
+        for (col, title) in ["Datum", "Von", "Bis", "Pause"].iter().enumerate() {
+            sheet.write_string(0, col as u16, *title).unwrap();
+        }
+        sheet
+            .write_datetime_with_format(
+                1,
+                0,
+                ExcelDateTime::from_ymd(2026, 3, 2).unwrap(),
+                &date_format,
+            )
+            .unwrap();
+        for (col, (h, m)) in [(8, 15), (17, 0), (0, 45)].into_iter().enumerate() {
+            sheet
+                .write_datetime_with_format(
+                    1,
+                    col as u16 + 1,
+                    ExcelDateTime::from_hms(h, m, 0).unwrap(),
+                    &time_format,
+                )
+                .unwrap();
+        }
+        let table = read_xlsx(workbook.save_to_buffer().unwrap()).unwrap();
+        assert_eq!(table.headers, vec!["Datum", "Von", "Bis", "Pause"]);
+
+        let mapping = suggest_mapping(&table.headers);
+        let report = validate(&table, &mapping, &options(), today()).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        let row = &report.entries[0];
+        assert_eq!(row.entry.date, "2026-03-02");
+        assert_eq!(row.entry.start_time, "08:15");
+        assert_eq!(row.entry.break_minutes, 45);
+        assert_eq!(row.hours, 8.0);
+    }
+}
@@ -0,0 +1,440 @@
+//! Running work timer (Stempeluhr) for "Kommen" / "Gehen" / "Pause".
+//!
+//! Pure state machine without any Tauri dependency. The tray and the
+//! frontend drive it through `tracking.rs`; a finished session is turned
+//! into one time-entry draft per calendar day that the frontend submits
+//! through the normal `POST /api/time-entries` flow.
+
+use std::fmt;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// Current phase of the timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimerPhase {
+    Idle,
+    Running,
+    OnBreak,
+}
+
+/// A single break; `end` is `None` while the break is still running
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakSpan {
+    pub start: NaiveDateTime,
+    pub end: Option<NaiveDateTime>,
+}
+
+impl BreakSpan {
+    fn duration_until(&self, now: NaiveDateTime) -> Duration {
+        let end = self.end.unwrap_or(now);
+        (end - self.start).max(Duration::zero())
+    }
+
+    /// Part of this break that lies inside `[from, to)`
+    fn overlap(&self, from: NaiveDateTime, to: NaiveDateTime, now: NaiveDateTime) -> Duration {
+        let start = self.start.max(from);
+        let end = self.end.unwrap_or(now).min(to);
+        (end - start).max(Duration::zero())
+    }
+}
+
+/// Project, activity and location chosen for the running session
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDetails {
+    pub project: Option<String>,
+    pub activity: Option<String>,
+    pub location: Option<String>, // 'office' | 'homeoffice' | 'field'
+}
+
+/// An active session between "Kommen" and "Gehen"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkSession {
+    pub started_at: NaiveDateTime,
+    pub breaks: Vec<BreakSpan>,
+    #[serde(default)]
+    pub details: SessionDetails,
+}
+
+impl WorkSession {
+    fn open_break(&self) -> Option<&BreakSpan> {
+        self.breaks.last().filter(|b| b.end.is_none())
+    }
+
+    fn break_duration(&self, now: NaiveDateTime) -> Duration {
+        self.breaks
+            .iter()
+            .fold(Duration::zero(), |acc, b| acc + b.duration_until(now))
+    }
+}
+
+/// Time-entry draft in the shape expected by `POST /api/time-entries`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntryDraft {
+    pub date: String,       // YYYY-MM-DD
+    pub start_time: String, // HH:MM
+    pub end_time: String,   // HH:MM
+    pub break_minutes: i64,
+    pub project: Option<String>,
+    pub activity: Option<String>,
+    pub location: Option<String>,
+}
+
+/// Serializable view of the timer for the frontend and the tray
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerSnapshot {
+    pub phase: TimerPhase,
+    pub started_at: Option<NaiveDateTime>,
+    pub worked_minutes: i64,
+    pub break_minutes: i64,
+    pub elapsed_label: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimerError {
+    AlreadyRunning,
+    NotRunning,
+    AlreadyOnBreak,
+    NotOnBreak,
+    BeforeStart,
+    InFuture,
+}
+
+impl fmt::Display for TimerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            TimerError::AlreadyRunning => "Die Zeiterfassung läuft bereits",
+            TimerError::NotRunning => "Die Zeiterfassung wurde noch nicht gestartet",
+            TimerError::AlreadyOnBreak => "Es läuft bereits eine Pause",
+            TimerError::NotOnBreak => "Es läuft keine Pause",
+            TimerError::BeforeStart => "Der Zeitpunkt liegt vor dem Arbeitsbeginn",
+            TimerError::InFuture => "Der Zeitpunkt liegt in der Zukunft",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for TimerError {}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkTimer {
+    session: Option<WorkSession>,
+}
+
+impl WorkTimer {
+    pub fn phase(&self) -> TimerPhase {
+        match &self.session {
+            None => TimerPhase::Idle,
+            Some(session) if session.open_break().is_some() => TimerPhase::OnBreak,
+            Some(_) => TimerPhase::Running,
+        }
+    }
+
+    /// "Kommen"
+    pub fn clock_in(&mut self, now: NaiveDateTime) -> Result<(), TimerError> {
+        if self.session.is_some() {
+            return Err(TimerError::AlreadyRunning);
+        }
+        self.session = Some(WorkSession {
+            started_at: truncate_to_minute(now),
+            breaks: Vec::new(),
+            details: SessionDetails::default(),
+        });
+        Ok(())
+    }
+
+    pub fn set_details(&mut self, details: SessionDetails) -> Result<(), TimerError> {
+        let session = self.session.as_mut().ok_or(TimerError::NotRunning)?;
+        session.details = details;
+        Ok(())
+    }
+
+    /// "Pause starten"
+    pub fn start_break(&mut self, now: NaiveDateTime) -> Result<(), TimerError> {
+        let session = self.session.as_mut().ok_or(TimerError::NotRunning)?;
+        if session.open_break().is_some() {
+            return Err(TimerError::AlreadyOnBreak);
+        }
+        if now < session.started_at {
+            return Err(TimerError::BeforeStart);
+        }
+        session.breaks.push(BreakSpan {
+            start: now,
+            end: None,
+        });
+        Ok(())
+    }
+
+    /// "Pause beenden"
+    pub fn end_break(&mut self, now: NaiveDateTime) -> Result<(), TimerError> {
+        let session = self.session.as_mut().ok_or(TimerError::NotRunning)?;
+        let open = session
+            .breaks
+            .last_mut()
+            .filter(|b| b.end.is_none())
+            .ok_or(TimerError::NotOnBreak)?;
+        open.end = Some(now.max(open.start));
+        Ok(())
+    }
+
+    /// "Gehen" – closes a running break and turns the session into drafts.
+    ///
+    /// Sessions that run past midnight are split into one draft per
+    /// calendar day, because a time entry cannot span two dates.
+    pub fn clock_out(&mut self, now: NaiveDateTime) -> Result<Vec<TimeEntryDraft>, TimerError> {
+        let session = self.session.as_ref().ok_or(TimerError::NotRunning)?;
+        let end = truncate_to_minute(now);
+        if end < session.started_at {
+            return Err(TimerError::BeforeStart);
+        }
+
+        let mut session = self.session.take().expect("session checked above");
+        if let Some(open) = session.breaks.last_mut().filter(|b| b.end.is_none()) {
+            open.end = Some(now.max(open.start));
+        }
+
+        Ok(split_into_drafts(&session, end))
+    }
+
+    /// Whether the session can be closed at a chosen `end` (e.g. after a
+    /// crash): not before it started and not after `now`
+    pub fn check_end(&self, end: NaiveDateTime, now: NaiveDateTime) -> Result<(), TimerError> {
+        let session = self.session.as_ref().ok_or(TimerError::NotRunning)?;
+        if end > now {
+            return Err(TimerError::InFuture);
+        }
+        if truncate_to_minute(end) < session.started_at {
+            return Err(TimerError::BeforeStart);
+        }
+        Ok(())
+    }
+
+    /// Drafts "Gehen" would produce right now, without stopping the timer
+    pub fn preview(&self, now: NaiveDateTime) -> Vec<TimeEntryDraft> {
+        let mut copy = self.clone();
+        copy.clock_out(now).unwrap_or_default()
+    }
+
+    /// Worked time so far, breaks excluded
+    pub fn worked(&self, now: NaiveDateTime) -> Duration {
+        match &self.session {
+            None => Duration::zero(),
+            Some(session) => {
+                let gross = (now - session.started_at).max(Duration::zero());
+                (gross - session.break_duration(now)).max(Duration::zero())
+            }
+        }
+    }
+
+    pub fn break_taken(&self, now: NaiveDateTime) -> Duration {
+        self.session
+            .as_ref()
+            .map(|s| s.break_duration(now))
+            .unwrap_or_else(Duration::zero)
+    }
+
+    pub fn snapshot(&self, now: NaiveDateTime) -> TimerSnapshot {
+        let worked = self.worked(now);
+        TimerSnapshot {
+            phase: self.phase(),
+            started_at: self.session.as_ref().map(|s| s.started_at),
+            worked_minutes: worked.num_minutes(),
+            break_minutes: self.break_taken(now).num_minutes(),
+            elapsed_label: self.session.as_ref().map(|_| format_hours_minutes(worked)),
+        }
+    }
+}
+
+fn split_into_drafts(session: &WorkSession, end: NaiveDateTime) -> Vec<TimeEntryDraft> {
+    let mut drafts = Vec::new();
+    let mut day_start = session.started_at;
+
+    while day_start < end {
+        let next_midnight = next_midnight(day_start.date());
+        let day_end = end.min(next_midnight);
+
+        let break_minutes = session
+            .breaks
+            .iter()
+            .fold(Duration::zero(), |acc, b| {
+                acc + b.overlap(day_start, day_end, end)
+            })
+            .num_minutes();
+
+        // A day that ends exactly at midnight is booked until 23:59
+        let end_label = if day_end == next_midnight {
+            "23:59".to_string()
+        } else {
+            day_end.format("%H:%M").to_string()
+        };
+
+        drafts.push(TimeEntryDraft {
+            date: day_start.date().format("%Y-%m-%d").to_string(),
+            start_time: day_start.format("%H:%M").to_string(),
+            end_time: end_label,
+            break_minutes,
+            project: session.details.project.clone(),
+            activity: session.details.activity.clone(),
+            location: session.details.location.clone(),
+        });
+
+        day_start = next_midnight;
+    }
+
+    // Clock-in and clock-out within the same minute still produces an entry
+    if drafts.is_empty() {
+        drafts.push(TimeEntryDraft {
+            date: session.started_at.date().format("%Y-%m-%d").to_string(),
+            start_time: session.started_at.format("%H:%M").to_string(),
+            end_time: end.format("%H:%M").to_string(),
+            break_minutes: 0,
+            project: session.details.project.clone(),
+            activity: session.details.activity.clone(),
+            location: session.details.location.clone(),
+        });
+    }
+
+    drafts
+}
+
+fn next_midnight(date: NaiveDate) -> NaiveDateTime {
+    date.succ_opt().unwrap_or(date).and_time(NaiveTime::MIN)
+}
+
+fn truncate_to_minute(value: NaiveDateTime) -> NaiveDateTime {
+    value
+        .with_second(0)
+        .and_then(|v| v.with_nanosecond(0))
+        .unwrap_or(value)
+}
+
+/// Formats a duration as "H:MM" (e.g. "5:43")
+pub fn format_hours_minutes(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    let sign = if minutes < 0 { "-" } else { "" };
+    let minutes = minutes.abs();
+    format!("{}{}:{:02}", sign, minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn chosen_end_must_lie_between_start_and_now() {
+        let mut timer = WorkTimer::default();
+        assert_eq!(
+            timer.check_end(at("2026-03-02", "12:00"), at("2026-03-02", "12:00")),
+            Err(TimerError::NotRunning)
+        );
+
+        timer.clock_in(at("2026-03-02", "08:00")).unwrap();
+        let now = at("2026-03-03", "09:15");
+        assert_eq!(timer.check_end(at("2026-03-02", "17:30"), now), Ok(()));
+        assert_eq!(timer.check_end(now, now), Ok(()));
+        assert_eq!(
+            timer.check_end(at("2026-03-03", "09:16"), now),
+            Err(TimerError::InFuture)
+        );
+        assert_eq!(
+            timer.check_end(at("2026-03-02", "07:59"), now),
+            Err(TimerError::BeforeStart)
+        );
+        assert_eq!(timer.phase(), TimerPhase::Running);
+    }
+
+    #[test]
+    fn full_day_with_break_produces_single_draft() {
+        let mut timer = WorkTimer::default();
+        timer.clock_in(at("2026-03-02", "08:12")).unwrap();
+        timer.start_break(at("2026-03-02", "12:00")).unwrap();
+        assert_eq!(timer.phase(), TimerPhase::OnBreak);
+        timer.end_break(at("2026-03-02", "12:30")).unwrap();
+
+        assert_eq!(
+            timer.worked(at("2026-03-02", "13:00")).num_minutes(),
+            4 * 60 + 18
+        );
+
+        let drafts = timer.clock_out(at("2026-03-02", "16:45")).unwrap();
+        assert_eq!(
+            drafts,
+            vec![TimeEntryDraft {
+                date: "2026-03-02".into(),
+                start_time: "08:12".into(),
+                end_time: "16:45".into(),
+                break_minutes: 30,
+                project: None,
+                activity: None,
+                location: None,
+            }]
+        );
+        assert_eq!(timer.phase(), TimerPhase::Idle);
+    }
+
+    #[test]
+    fn clock_out_closes_running_break() {
+        let mut timer = WorkTimer::default();
+        timer.clock_in(at("2026-03-02", "08:00")).unwrap();
+        timer.start_break(at("2026-03-02", "15:45")).unwrap();
+        let drafts = timer.clock_out(at("2026-03-02", "16:00")).unwrap();
+        assert_eq!(drafts[0].break_minutes, 15);
+    }
+
+    #[test]
+    fn session_over_midnight_is_split_per_day() {
+        let mut timer = WorkTimer::default();
+        timer.clock_in(at("2026-03-02", "22:00")).unwrap();
+        timer.start_break(at("2026-03-02", "23:50")).unwrap();
+        timer.end_break(at("2026-03-03", "00:20")).unwrap();
+        let drafts = timer.clock_out(at("2026-03-03", "02:00")).unwrap();
+
+        assert_eq!(drafts.len(), 2);
+        assert_eq!(drafts[0].date, "2026-03-02");
+        assert_eq!(drafts[0].end_time, "23:59");
+        assert_eq!(drafts[0].break_minutes, 10);
+        assert_eq!(drafts[1].date, "2026-03-03");
+        assert_eq!(drafts[1].start_time, "00:00");
+        assert_eq!(drafts[1].break_minutes, 20);
+    }
+
+    #[test]
+    fn invalid_transitions_are_rejected() {
+        let mut timer = WorkTimer::default();
+        assert_eq!(
+            timer.start_break(at("2026-03-02", "08:00")),
+            Err(TimerError::NotRunning)
+        );
+        assert_eq!(
+            timer.clock_out(at("2026-03-02", "08:00")),
+            Err(TimerError::NotRunning)
+        );
+
+        timer.clock_in(at("2026-03-02", "08:00")).unwrap();
+        assert_eq!(
+            timer.clock_in(at("2026-03-02", "08:05")),
+            Err(TimerError::AlreadyRunning)
+        );
+        assert_eq!(
+            timer.end_break(at("2026-03-02", "08:05")),
+            Err(TimerError::NotOnBreak)
+        );
+    }
+
+    #[test]
+    fn formats_elapsed_time() {
+        assert_eq!(format_hours_minutes(Duration::minutes(343)), "5:43");
+        assert_eq!(format_hours_minutes(Duration::minutes(-90)), "-1:30");
+    }
+}
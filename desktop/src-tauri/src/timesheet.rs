@@ -0,0 +1,759 @@
+//! Monthly timesheet (Stundenzettel) as PDF
+//!
+//! Renders one employee's month from the rows of the DATEV export
+//! (`server/src/services/exportService.ts`): every calendar day with
+//! start/end/break, Soll/Ist and the difference, absences and holidays as
+//! remarks, totals and signature lines for employee and supervisor. Long
+//! months continue on further pages with the table header repeated.
+//!
+//! Overtime per day follows the overtime engine: an approved absence
+//! credits the day's target, so an absence day without work is neutral;
+//! unpaid leave removes the target instead of crediting it.
+//! Days without a time entry take their target from `daily_targets`
+//! (`resolve_daily_targets`), since the DATEV rows only carry Soll for
+//! worked days.
+//!
+//! The PDF uses the standard Helvetica fonts with WinAnsiEncoding, so no
+//! font is embedded; characters outside Windows-1252 are printed as `?`.
+
+use std::{collections::HashMap, fmt, io};
+
+use chrono::{Datelike, Local, NaiveDate, Weekday};
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+use crate::overtime::AbsenceKind;
+use crate::session_store::write_atomic;
+use crate::work_schedule::{self, DailyTarget};
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 40.0;
+const FONT_SIZE: f32 = 9.0;
+const ROW_HEIGHT: f32 = 14.0;
+/// Space below the table header on every page
+const TABLE_TOP: f32 = PAGE_HEIGHT - 150.0;
+/// Space for the page footer
+const TABLE_BOTTOM: f32 = MARGIN + 30.0;
+/// Height of totals and signature lines on the last page
+const SUMMARY_HEIGHT: f32 = 130.0;
+const REMARK_MAX_CHARS: usize = 34;
+
+const REGULAR: Name = Name(b"F1");
+const BOLD: Name = Name(b"F2");
+
+/// Table columns: title, x position, right-aligned
+const COLUMNS: [(&str, f32, bool); 8] = [
+    ("Datum", MARGIN, false),
+    ("Beginn", 110.0, false),
+    ("Ende", 150.0, false),
+    ("Pause", 222.0, true),
+    ("Soll", 272.0, true),
+    ("Ist", 322.0, true),
+    ("Differenz", 382.0, true),
+    ("Bemerkung", 395.0, false),
+];
+
+#[derive(Debug)]
+pub enum TimesheetError {
+    InvalidMonth(i32, u32),
+    /// Row of another employee (Personalnummer)
+    ForeignRow(i64),
+    Io(io::Error),
+}
+
+impl fmt::Display for TimesheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimesheetError::InvalidMonth(year, month) => {
+                write!(f, "Ungültiger Monat: {:02}/{}", month, year)
+            }
+            TimesheetError::ForeignRow(id) => write!(
+                f,
+                "Der Export enthält Zeilen eines anderen Mitarbeiters (Personalnummer {})",
+                id
+            ),
+            TimesheetError::Io(e) => {
+                write!(f, "Stundenzettel konnte nicht gespeichert werden: {}", e)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimesheetError {}
+
+impl From<io::Error> for TimesheetError {
+    fn from(e: io::Error) -> Self {
+        TimesheetError::Io(e)
+    }
+}
+
+/// One row of the DATEV export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatevRow {
+    /// Personalnummer
+    pub personnel_number: i64,
+    /// Datum; for absences the first day
+    pub date: NaiveDate,
+    /// Last day of an absence
+    #[serde(default)]
+    pub end_date: Option<NaiveDate>,
+    /// Sollstunden
+    pub target_hours: f64,
+    /// Iststunden
+    pub actual_hours: f64,
+    /// Pause (Min)
+    #[serde(default)]
+    pub break_minutes: i64,
+    /// Abwesenheitsart (`Urlaub`, `Krank`, …); absence rows have no hours
+    #[serde(default)]
+    pub absence_type: Option<String>,
+    /// Beginn (`HH:MM`)
+    #[serde(default)]
+    pub start_time: Option<String>,
+    /// Ende (`HH:MM`)
+    #[serde(default)]
+    pub end_time: Option<String>,
+    /// Bemerkung
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl DatevRow {
+    pub(crate) fn is_absence(&self) -> bool {
+        self.absence_type.is_some()
+    }
+
+    pub(crate) fn covers(&self, date: NaiveDate) -> bool {
+        self.date <= date && date <= self.end_date.unwrap_or(self.date)
+    }
+}
+
+/// Absence label of the DATEV export (`Urlaub`, `Krank`, …) or type code
+pub(crate) fn absence_kind(label: &str) -> AbsenceKind {
+    match label {
+        "Urlaub" => AbsenceKind::Vacation,
+        "Krank" => AbsenceKind::Sick,
+        "Überstundenausgleich" => AbsenceKind::OvertimeComp,
+        "Unbezahlt" => AbsenceKind::Unpaid,
+        code => AbsenceKind::parse(code),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamedDay {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimesheetInput {
+    pub personnel_number: i64,
+    pub last_name: String,
+    pub first_name: String,
+    pub year: i32,
+    pub month: u32,
+    pub rows: Vec<DatevRow>,
+    #[serde(default)]
+    pub holidays: Vec<NamedDay>,
+    #[serde(default)]
+    pub daily_targets: Vec<DailyTarget>,
+    /// Printed above the title, e.g. the company name
+    #[serde(default)]
+    pub organization: Option<String>,
+}
+
+/// One printed table row; a day with several entries has several lines
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Line {
+    cells: [String; 8],
+    /// First line of a new week
+    week_start: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Totals {
+    target: f64,
+    actual: f64,
+    credit: f64,
+    overtime: f64,
+}
+
+/// German number format with two decimals (`1.234,50`)
+pub fn format_hours(hours: f64) -> String {
+    let rounded = (hours * 100.0).round() / 100.0;
+    let text = format!("{:.2}", rounded.abs());
+    let (int, frac) = text.split_once('.').unwrap_or((&text, "00"));
+    let mut grouped = String::new();
+    for (i, digit) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            grouped.push('.');
+        }
+        grouped.push(digit);
+    }
+    let sign = if rounded < 0.0 { "-" } else { "" };
+    format!("{}{},{}", sign, grouped, frac)
+}
+
+fn signed_hours(hours: f64) -> String {
+    if (hours * 100.0).round() > 0.0 {
+        format!("+{}", format_hours(hours))
+    } else {
+        format_hours(hours)
+    }
+}
+
+pub(crate) fn weekday_short(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Mo",
+        Weekday::Tue => "Di",
+        Weekday::Wed => "Mi",
+        Weekday::Thu => "Do",
+        Weekday::Fri => "Fr",
+        Weekday::Sat => "Sa",
+        Weekday::Sun => "So",
+    }
+}
+
+pub fn month_name(month: u32) -> &'static str {
+    const NAMES: [&str; 12] = [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ];
+    NAMES[(month as usize).saturating_sub(1) % 12]
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut short: String = text.chars().take(max - 1).collect();
+    short.push('…');
+    short
+}
+
+fn month_range(year: i32, month: u32) -> Result<(NaiveDate, NaiveDate), TimesheetError> {
+    work_schedule::month_range(year, month).ok_or(TimesheetError::InvalidMonth(year, month))
+}
+
+fn build_lines(input: &TimesheetInput) -> Result<(Vec<Line>, Totals), TimesheetError> {
+    let (first, last) = month_range(input.year, input.month)?;
+    if let Some(row) = input
+        .rows
+        .iter()
+        .find(|r| r.personnel_number != input.personnel_number)
+    {
+        return Err(TimesheetError::ForeignRow(row.personnel_number));
+    }
+    let targets: HashMap<NaiveDate, f64> = input
+        .daily_targets
+        .iter()
+        .map(|t| (t.date, t.target_hours))
+        .collect();
+
+    let mut lines = Vec::new();
+    let mut totals = Totals::default();
+    for date in first.iter_days().take_while(|d| *d <= last) {
+        let entries: Vec<&DatevRow> = input
+            .rows
+            .iter()
+            .filter(|r| !r.is_absence() && r.date == date)
+            .collect();
+        let absence = input.rows.iter().find(|r| r.is_absence() && r.covers(date));
+        let holiday = input.holidays.iter().find(|h| h.date == date);
+
+        let unpaid = absence
+            .and_then(|a| a.absence_type.as_deref())
+            .is_some_and(|label| absence_kind(label) == AbsenceKind::Unpaid);
+        let target = if unpaid {
+            0.0
+        } else {
+            entries
+                .first()
+                .map(|e| e.target_hours)
+                .or_else(|| targets.get(&date).copied())
+                .unwrap_or(0.0)
+        };
+        let actual: f64 = entries.iter().map(|e| e.actual_hours).sum();
+        let credit = if absence.is_some() { target } else { 0.0 };
+        let overtime = actual + credit - target;
+        totals.target += target;
+        totals.actual += actual;
+        totals.credit += credit;
+        totals.overtime += overtime;
+
+        let mut remarks: Vec<&str> = Vec::new();
+        remarks.extend(holiday.map(|h| h.name.as_str()));
+        remarks.extend(absence.and_then(|a| a.absence_type.as_deref()));
+        let has_day_values = target != 0.0 || actual != 0.0 || !entries.is_empty();
+        let day_cells = |line: &mut Line| {
+            line.cells[0] = format!(
+                "{} {}",
+                weekday_short(date.weekday()),
+                date.format("%d.%m.")
+            );
+            if has_day_values {
+                line.cells[4] = format_hours(target);
+                line.cells[6] = signed_hours(overtime);
+            }
+        };
+
+        if entries.is_empty() {
+            let mut line = Line {
+                week_start: date.weekday() == Weekday::Mon,
+                ..Line::default()
+            };
+            day_cells(&mut line);
+            line.cells[7] = truncate(&remarks.join(", "), REMARK_MAX_CHARS);
+            lines.push(line);
+            continue;
+        }
+        for (i, entry) in entries.iter().enumerate() {
+            let mut line = Line {
+                week_start: i == 0 && date.weekday() == Weekday::Mon,
+                ..Line::default()
+            };
+            if i == 0 {
+                day_cells(&mut line);
+            }
+            line.cells[1] = entry.start_time.clone().unwrap_or_default();
+            line.cells[2] = entry.end_time.clone().unwrap_or_default();
+            if entry.break_minutes > 0 {
+                line.cells[3] = format!("{} min", entry.break_minutes);
+            }
+            line.cells[5] = format_hours(entry.actual_hours);
+            let mut entry_remarks = if i == 0 { remarks.clone() } else { Vec::new() };
+            entry_remarks.extend(entry.note.as_deref().filter(|n| !n.is_empty()));
+            line.cells[7] = truncate(&entry_remarks.join(", "), REMARK_MAX_CHARS);
+            lines.push(line);
+        }
+    }
+    Ok((lines, totals))
+}
+
+/// Lines per page and the number of pages; the last page also needs room
+/// for totals and signatures
+fn paginate(line_count: usize) -> Vec<std::ops::Range<usize>> {
+    let per_page = ((TABLE_TOP - TABLE_BOTTOM) / ROW_HEIGHT) as usize;
+    let last_page = ((TABLE_TOP - TABLE_BOTTOM - SUMMARY_HEIGHT) / ROW_HEIGHT) as usize;
+    let mut pages = Vec::new();
+    let mut start = 0;
+    while line_count - start > last_page {
+        let end = (start + per_page).min(line_count);
+        pages.push(start..end);
+        start = end;
+    }
+    // Remaining lines (possibly none) plus the summary
+    pages.push(start..line_count);
+    pages
+}
+
+/// Text as Windows-1252 for the standard fonts
+pub(crate) fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '€' => 0x80,
+            '„' => 0x84,
+            '…' => 0x85,
+            '“' => 0x93,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Width of `text` in Helvetica at `size`; exact for digits and the signs
+/// used in numbers, an average for everything else
+fn text_width(text: &str, size: f32) -> f32 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c {
+            '0'..='9' | '+' => 556,
+            ',' | '.' | ' ' => 278,
+            '-' => 333,
+            'm' => 833,
+            'i' => 222,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+struct Page {
+    content: Content,
+}
+
+impl Page {
+    fn new() -> Self {
+        Self {
+            content: Content::new(),
+        }
+    }
+
+    fn text(&mut self, font: Name, size: f32, x: f32, y: f32, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.content
+            .begin_text()
+            .set_font(font, size)
+            .next_line(x, y)
+            .show(Str(&win_ansi(text)))
+            .end_text();
+    }
+
+    fn text_right(&mut self, font: Name, size: f32, right: f32, y: f32, text: &str) {
+        self.text(font, size, right - text_width(text, size), y, text);
+    }
+
+    fn rule(&mut self, x1: f32, x2: f32, y: f32, width: f32) {
+        self.content
+            .set_line_width(width)
+            .move_to(x1, y)
+            .line_to(x2, y)
+            .stroke();
+    }
+
+    fn row(&mut self, font: Name, y: f32, cells: &[String; 8]) {
+        for ((_, x, right), cell) in COLUMNS.iter().zip(cells) {
+            if *right {
+                self.text_right(font, FONT_SIZE, *x, y, cell);
+            } else {
+                self.text(font, FONT_SIZE, *x, y, cell);
+            }
+        }
+    }
+}
+
+/// Renders the timesheet; `created` is printed in the footer
+pub fn render(input: &TimesheetInput, created: NaiveDate) -> Result<Vec<u8>, TimesheetError> {
+    let (lines, totals) = build_lines(input)?;
+    let (first, last) = month_range(input.year, input.month)?;
+    let pages = paginate(lines.len());
+    let title = format!("Stundenzettel {} {}", month_name(input.month), input.year);
+    let employee = format!(
+        "{}, {} (Personalnummer {})",
+        input.last_name, input.first_name, input.personnel_number
+    );
+    let header: [String; 8] = COLUMNS.map(|(title, _, _)| title.to_string());
+
+    let catalog_id = Ref::new(1);
+    let tree_id = Ref::new(2);
+    let regular_id = Ref::new(3);
+    let bold_id = Ref::new(4);
+    let info_id = Ref::new(5);
+    let page_ids: Vec<Ref> = (0..pages.len())
+        .map(|i| Ref::new(6 + 2 * i as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(tree_id);
+    pdf.pages(tree_id)
+        .kids(page_ids.iter().copied())
+        .count(pages.len() as i32);
+    pdf.type1_font(regular_id)
+        .base_font(Name(b"Helvetica"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.type1_font(bold_id)
+        .base_font(Name(b"Helvetica-Bold"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.document_info(info_id)
+        .title(TextStr(&format!("{} – {}", title, employee)))
+        .creator(TextStr("TimeTracking Desktop"));
+
+    let right = PAGE_WIDTH - MARGIN;
+    for (index, (range, page_id)) in pages.iter().zip(&page_ids).enumerate() {
+        let mut page = Page::new();
+        let mut y = PAGE_HEIGHT - MARGIN - 12.0;
+        if let Some(organization) = &input.organization {
+            page.text(REGULAR, 9.0, MARGIN, y + 12.0, organization);
+        }
+        page.text(BOLD, 16.0, MARGIN, y - 8.0, &title);
+        y -= 34.0;
+        page.text(
+            REGULAR,
+            10.0,
+            MARGIN,
+            y,
+            &format!("Mitarbeiter/in: {}", employee),
+        );
+        y -= 14.0;
+        page.text(
+            REGULAR,
+            10.0,
+            MARGIN,
+            y,
+            &format!(
+                "Zeitraum: {} – {}",
+                first.format("%d.%m.%Y"),
+                last.format("%d.%m.%Y")
+            ),
+        );
+
+        y = TABLE_TOP + 6.0;
+        page.row(BOLD, y, &header);
+        page.rule(MARGIN, right, y - 4.0, 0.8);
+        y -= ROW_HEIGHT;
+        for line in &lines[range.clone()] {
+            if line.week_start {
+                page.rule(MARGIN, right, y + ROW_HEIGHT - 4.0, 0.2);
+            }
+            page.row(REGULAR, y, &line.cells);
+            y -= ROW_HEIGHT;
+        }
+
+        if index == pages.len() - 1 {
+            page.rule(MARGIN, right, y + ROW_HEIGHT - 4.0, 0.8);
+            y -= 6.0;
+            for (label, value) in [
+                ("Soll", format_hours(totals.target)),
+                ("Ist", format_hours(totals.actual)),
+                ("Gutschrift Abwesenheiten", format_hours(totals.credit)),
+                ("Überstunden", signed_hours(totals.overtime)),
+            ] {
+                page.text(BOLD, FONT_SIZE, MARGIN, y, label);
+                page.text_right(BOLD, FONT_SIZE, 222.0, y, &format!("{} h", value));
+                y -= ROW_HEIGHT;
+            }
+            y -= 36.0;
+            for (x, label) in [
+                (MARGIN, "Datum, Unterschrift Mitarbeiter/in"),
+                (320.0, "Datum, Unterschrift Vorgesetzte/r"),
+            ] {
+                page.rule(x, x + 235.0, y, 0.5);
+                page.text(REGULAR, 8.0, x, y - 10.0, label);
+            }
+        }
+
+        page.text(
+            REGULAR,
+            8.0,
+            MARGIN,
+            MARGIN,
+            &format!("Erstellt am {}", created.format("%d.%m.%Y")),
+        );
+        page.text_right(
+            REGULAR,
+            8.0,
+            right,
+            MARGIN,
+            &format!("Seite {} von {}", index + 1, pages.len()),
+        );
+
+        let content_id = Ref::new(page_id.get() + 1);
+        let mut writer = pdf.page(*page_id);
+        writer
+            .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+            .parent(tree_id)
+            .contents(content_id);
+        writer
+            .resources()
+            .fonts()
+            .pair(REGULAR, regular_id)
+            .pair(BOLD, bold_id);
+        writer.finish();
+        pdf.stream(content_id, &page.content.finish());
+    }
+    Ok(pdf.finish())
+}
+
+/// Asks for a location with the save dialog and writes the timesheet there.
+/// Returns the path, `None` when the dialog was cancelled.
+#[tauri::command]
+pub async fn export_timesheet_pdf(
+    app: AppHandle,
+    input: TimesheetInput,
+) -> Result<Option<String>, String> {
+    let pdf = render(&input, Local::now().date_naive()).map_err(|e| e.to_string())?;
+    let file_name = format!(
+        "Stundenzettel_{}_{}-{:02}.pdf",
+        input.last_name, input.year, input.month
+    );
+    let chosen = app
+        .dialog()
+        .file()
+        .set_file_name(file_name)
+        .add_filter("PDF", &["pdf"])
+        .blocking_save_file();
+    let path = match chosen {
+        Some(chosen) => chosen.into_path().map_err(|e| e.to_string())?,
+        None => return Ok(None),
+    };
+    write_atomic(&path, &pdf)
+        .map_err(TimesheetError::from)
+        .map_err(|e| e.to_string())?;
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn entry(day: &str, start: &str, end: &str, hours: f64) -> DatevRow {
+        DatevRow {
+            personnel_number: 7,
+            date: date(day),
+            end_date: None,
+            target_hours: 8.0,
+            actual_hours: hours,
+            break_minutes: 30,
+            absence_type: None,
+            start_time: Some(start.into()),
+            end_time: Some(end.into()),
+            note: None,
+        }
+    }
+
+    fn march() -> TimesheetInput {
+        // 2026-03-02 is a Monday
+        let weekdays = date("2026-03-02")
+            .iter_days()
+            .take(30)
+            .filter(|d| d.weekday().num_days_from_monday() < 5);
+        TimesheetInput {
+            personnel_number: 7,
+            last_name: "Müller".into(),
+            first_name: "Jörg".into(),
+            year: 2026,
+            month: 3,
+            rows: vec![
+                entry("2026-03-02", "08:00", "12:00", 4.0),
+                DatevRow {
+                    note: Some("Kundentermin".into()),
+                    ..entry("2026-03-02", "13:00", "18:30", 5.5)
+                },
+                entry("2026-03-03", "08:00", "16:30", 8.0),
+                DatevRow {
+                    end_date: Some(date("2026-03-06")),
+                    target_hours: 0.0,
+                    actual_hours: 0.0,
+                    break_minutes: 0,
+                    absence_type: Some("Urlaub".into()),
+                    start_time: None,
+                    end_time: None,
+                    ..entry("2026-03-04", "", "", 0.0)
+                },
+            ],
+            holidays: Vec::new(),
+            daily_targets: weekdays
+                .map(|date| DailyTarget {
+                    date,
+                    target_hours: 8.0,
+                    is_holiday: false,
+                })
+                .collect(),
+            organization: Some("DPolG Stiftung".into()),
+        }
+    }
+
+    #[test]
+    fn numbers_use_german_format() {
+        assert_eq!(format_hours(7.5), "7,50");
+        assert_eq!(format_hours(-0.25), "-0,25");
+        assert_eq!(format_hours(1234.5), "1.234,50");
+        assert_eq!(format_hours(-0.001), "0,00");
+        assert_eq!(signed_hours(1.5), "+1,50");
+        assert_eq!(month_name(3), "März");
+        assert_eq!(win_ansi("Überstunden – 5 €"), b"\xdcberstunden \x96 5 \x80");
+    }
+
+    #[test]
+    fn lines_cover_every_day_with_absences_credited() {
+        let (lines, totals) = build_lines(&march()).unwrap();
+        // 31 days plus the second entry on 02.03.
+        assert_eq!(lines.len(), 32);
+        assert_eq!(lines[0].cells[0], "So 01.03.");
+        assert_eq!(lines[1].cells[0], "Mo 02.03.");
+        assert_eq!(lines[1].cells[6], "+1,50");
+        assert_eq!(lines[2].cells[0], "");
+        assert_eq!(lines[2].cells[7], "Kundentermin");
+        assert_eq!(lines[4].cells[7], "Urlaub");
+        assert_eq!(lines[4].cells[6], "0,00");
+        assert!(lines[1].week_start && !lines[2].week_start);
+
+        // 22 weekdays; 3 vacation days credited, 17 days not worked
+        assert_eq!(totals.target, 176.0);
+        assert_eq!(totals.actual, 17.5);
+        assert_eq!(totals.credit, 24.0);
+        assert_eq!(totals.overtime, 17.5 + 24.0 - 176.0);
+    }
+
+    #[test]
+    fn unpaid_leave_removes_the_target_without_credit() {
+        let mut input = march();
+        input.rows.push(DatevRow {
+            end_date: Some(date("2026-03-10")),
+            absence_type: Some("Unbezahlt".into()),
+            ..input.rows[3].clone()
+        });
+        input.rows.last_mut().unwrap().date = date("2026-03-09");
+        let (lines, totals) = build_lines(&input).unwrap();
+        assert_eq!(lines[9].cells[0], "Mo 09.03.");
+        assert_eq!(lines[9].cells[7], "Unbezahlt");
+        assert_eq!(lines[9].cells[4], "");
+
+        // Two workdays less target, credit only for the vacation
+        assert_eq!(totals.target, 160.0);
+        assert_eq!(totals.credit, 24.0);
+        assert_eq!(totals.overtime, 17.5 + 24.0 - 160.0);
+    }
+
+    #[test]
+    fn rows_of_other_employees_and_invalid_months_are_rejected() {
+        let mut input = march();
+        input.rows[0].personnel_number = 8;
+        assert!(matches!(
+            build_lines(&input),
+            Err(TimesheetError::ForeignRow(8))
+        ));
+        input = march();
+        input.month = 13;
+        assert!(matches!(
+            build_lines(&input),
+            Err(TimesheetError::InvalidMonth(2026, 13))
+        ));
+    }
+
+    #[test]
+    fn long_months_are_paginated() {
+        assert_eq!(paginate(31), vec![0..31]);
+        let pages = paginate(60);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages.last().unwrap().end, 60);
+
+        let mut busy = march();
+        for day in 9..=27 {
+            let day = format!("2026-03-{:02}", day);
+            busy.rows.push(entry(&day, "06:00", "08:00", 2.0));
+            busy.rows.push(entry(&day, "18:00", "20:00", 2.0));
+        }
+        let pdf = render(&busy, date("2026-04-01")).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(pdf.starts_with(b"%PDF-"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("Seite 2 von 2"));
+        assert!(text.contains("WinAnsiEncoding"));
+    }
+}
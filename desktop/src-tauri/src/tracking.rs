@@ -0,0 +1,419 @@
+//! Glue between the pure `timer` state machine, the system tray and the
+//! frontend. Every state change goes through `perform` so the tray and the
+//! React app always see the same timer. Every change is also persisted
+//! through `session_store`, so a session survives restarts and crashes.
+
+use std::{path::PathBuf, sync::Mutex};
+
+use chrono::{Local, NaiveDateTime};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogResult};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::arbzg::{self, ArbzgReport, BookedEntry, EntryCandidate};
+use crate::reminders::{Reminder, ReminderKind, WorkdayTracker};
+use crate::session_store::{self, PersistedTimer};
+use crate::timer::{SessionDetails, TimeEntryDraft, TimerPhase, TimerSnapshot, WorkTimer};
+use crate::tray;
+
+/// Emitted with a `TimerSnapshot` after every state change
+pub const EVENT_TIMER_CHANGED: &str = "timer:changed";
+/// Emitted with `Vec<TimeEntryDraft>` after "Gehen"
+pub const EVENT_TIMER_DRAFT: &str = "timer:draft";
+/// Emitted with a `RestoredSession` when a session was found on startup
+pub const EVENT_TIMER_RESTORED: &str = "timer:restored";
+/// Emitted with a `RestoredSession` when the user wants to pick the end
+/// of a restored session
+pub const EVENT_TIMER_CHOOSE_END: &str = "timer:choose-end";
+/// Emitted with a `Reminder` whenever an ArbZG notification is shown
+pub const EVENT_ARBZG_REMINDER: &str = "arbzg:reminder";
+
+#[derive(Default)]
+pub struct TrackingState {
+    pub timer: Mutex<WorkTimer>,
+    /// Drafts not yet submitted or discarded in the frontend
+    pub drafts: Mutex<Vec<TimeEntryDraft>>,
+    pub workday: Mutex<WorkdayTracker>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerAction {
+    ClockIn,
+    ClockOut,
+    StartBreak,
+    EndBreak,
+}
+
+/// Payload of `timer:restored` and `timer:choose-end`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoredSession {
+    pub snapshot: TimerSnapshot,
+    pub last_seen_at: NaiveDateTime,
+}
+
+pub fn now() -> NaiveDateTime {
+    Local::now().naive_local()
+}
+
+/// Applies an action to the shared timer, refreshes the tray and notifies
+/// the frontend. Returns the drafts produced by "Gehen" (empty otherwise).
+pub fn perform(app: &AppHandle, action: TimerAction) -> Result<Vec<TimeEntryDraft>, String> {
+    perform_at(app, action, now())
+}
+
+/// Same as `perform`, but at an explicit point in time (e.g. closing a
+/// restored session at the moment the app was last alive)
+pub fn perform_at(
+    app: &AppHandle,
+    action: TimerAction,
+    at: NaiveDateTime,
+) -> Result<Vec<TimeEntryDraft>, String> {
+    perform_keeping(app, action, at, |_| true)
+}
+
+/// "Gehen" for an entry that was saved from the timer's values (quick
+/// entry): the draft of that entry's day is dropped, drafts of earlier
+/// days stay pending for confirmation
+pub fn clock_out_for_entry(app: &AppHandle, at: NaiveDateTime, date: &str) -> Result<(), String> {
+    perform_keeping(app, TimerAction::ClockOut, at, |d| d.date != date).map(|_| ())
+}
+
+fn perform_keeping(
+    app: &AppHandle,
+    action: TimerAction,
+    at: NaiveDateTime,
+    keep: impl Fn(&TimeEntryDraft) -> bool,
+) -> Result<Vec<TimeEntryDraft>, String> {
+    let state = app.state::<TrackingState>();
+    let now = now();
+
+    let (drafts, snapshot) = {
+        let mut timer = state.timer.lock().map_err(|e| e.to_string())?;
+        let mut drafts = match action {
+            TimerAction::ClockIn => timer.clock_in(at).map(|_| Vec::new()),
+            TimerAction::StartBreak => timer.start_break(at).map(|_| Vec::new()),
+            TimerAction::EndBreak => timer.end_break(at).map(|_| Vec::new()),
+            TimerAction::ClockOut => timer.clock_out(at),
+        }
+        .map_err(|e| e.to_string())?;
+        drafts.retain(|d| keep(d));
+        persist(app, &timer, now);
+        (drafts, timer.snapshot(now))
+    };
+
+    let rest_reminder = match state.workday.lock() {
+        Ok(mut workday) => match action {
+            TimerAction::ClockIn => workday.on_clock_in(at),
+            TimerAction::ClockOut => {
+                workday.record_clock_out(at);
+                persist_clock_out(app, at);
+                None
+            }
+            _ => None,
+        },
+        Err(_) => None,
+    };
+    if let Some(reminder) = rest_reminder {
+        notify(app, &reminder);
+    }
+
+    if !drafts.is_empty() {
+        if let Ok(mut pending) = state.drafts.lock() {
+            pending.extend(drafts.iter().cloned());
+            persist_drafts(app, &pending);
+        }
+        let _ = app.emit(EVENT_TIMER_DRAFT, &drafts);
+    }
+
+    let _ = app.emit(EVENT_TIMER_CHANGED, &snapshot);
+    tray::refresh(app);
+
+    Ok(drafts)
+}
+
+fn data_dir(app: &AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok()
+}
+
+fn persist(app: &AppHandle, timer: &WorkTimer, now: NaiveDateTime) {
+    let Some(dir) = data_dir(app) else {
+        return;
+    };
+    let result = if timer.phase() == TimerPhase::Idle {
+        session_store::clear(&dir)
+    } else {
+        session_store::save(&dir, &PersistedTimer::new(timer.clone(), now))
+    };
+    if let Err(error) = result {
+        log::warn!("Failed to persist timer session: {}", error);
+    }
+}
+
+fn persist_drafts(app: &AppHandle, drafts: &[TimeEntryDraft]) {
+    let Some(dir) = data_dir(app) else {
+        return;
+    };
+    if let Err(error) = session_store::save_drafts(&dir, drafts) {
+        log::warn!("Failed to persist time entry drafts: {}", error);
+    }
+}
+
+fn persist_clock_out(app: &AppHandle, at: NaiveDateTime) {
+    let Some(dir) = data_dir(app) else {
+        return;
+    };
+    if let Err(error) = session_store::save_last_clock_out(&dir, at) {
+        log::warn!("Failed to persist last clock-out: {}", error);
+    }
+}
+
+/// Entries of the logged-in user around today, from the offline cache;
+/// used for "worked today" and the last clock-out of the previous shift
+pub fn set_booked_entries(app: &AppHandle, entries: Vec<BookedEntry>) {
+    if let Ok(mut workday) = app.state::<TrackingState>().workday.lock() {
+        workday.set_booked(entries);
+    }
+}
+
+/// Refreshes `lastSeenAt` of a running session; called periodically and
+/// right before the app exits
+pub fn heartbeat(app: &AppHandle) {
+    let state = app.state::<TrackingState>();
+    let Ok(timer) = state.timer.lock() else {
+        return;
+    };
+    if timer.phase() != TimerPhase::Idle {
+        persist(app, &timer, now());
+    }
+}
+
+/// Restores drafts and a session left over from the previous run and asks
+/// the user whether to continue the session or to close it when the app
+/// was last alive
+pub fn restore(app: &AppHandle) {
+    let Some(dir) = data_dir(app) else {
+        return;
+    };
+    let state = app.state::<TrackingState>();
+    match session_store::load_last_clock_out(&dir) {
+        Ok(Some(at)) => {
+            if let Ok(mut workday) = state.workday.lock() {
+                workday.record_clock_out(at);
+            }
+        }
+        Ok(None) => {}
+        Err(error) => log::warn!("Failed to restore last clock-out: {}", error),
+    }
+    match session_store::load_drafts(&dir) {
+        Ok(drafts) => {
+            if let Ok(mut pending) = state.drafts.lock() {
+                *pending = drafts;
+            }
+        }
+        Err(error) => log::warn!("Failed to restore time entry drafts: {}", error),
+    }
+
+    let persisted = match session_store::load(&dir) {
+        Ok(Some(persisted)) if persisted.timer.phase() != TimerPhase::Idle => persisted,
+        Ok(_) => return,
+        Err(error) => {
+            log::warn!("Failed to restore timer session: {}", error);
+            return;
+        }
+    };
+
+    let snapshot = match state.timer.lock() {
+        Ok(mut timer) => {
+            *timer = persisted.timer.clone();
+            timer.snapshot(now())
+        }
+        Err(_) => return,
+    };
+
+    let last_seen_at = persisted.last_seen_at;
+    let started_at = snapshot
+        .started_at
+        .map(|t| t.format("%d.%m.%Y %H:%M").to_string())
+        .unwrap_or_default();
+
+    let restored = RestoredSession {
+        snapshot,
+        last_seen_at,
+    };
+    let _ = app.emit(EVENT_TIMER_RESTORED, &restored);
+
+    let end_label = format!("Um {} beenden", last_seen_at.format("%H:%M"));
+    let choose_label = "Andere Uhrzeit…".to_string();
+    let handle = app.clone();
+    // Closing the dialog keeps the session running
+    app.dialog()
+        .message(format!(
+            "Es läuft noch eine Zeiterfassung seit {}.\nDie App war zuletzt am {} aktiv.\n\nSoll die Zeiterfassung fortgesetzt werden?",
+            started_at,
+            last_seen_at.format("%d.%m.%Y um %H:%M"),
+        ))
+        .title("Laufende Zeiterfassung gefunden")
+        .buttons(MessageDialogButtons::YesNoCancelCustom(
+            end_label.clone(),
+            choose_label.clone(),
+            "Fortsetzen".to_string(),
+        ))
+        .show_with_result(move |result| match result {
+            MessageDialogResult::Yes => close_restored(&handle, last_seen_at),
+            MessageDialogResult::Custom(label) if label == end_label => {
+                close_restored(&handle, last_seen_at)
+            }
+            MessageDialogResult::No => choose_end(&handle, &restored),
+            MessageDialogResult::Custom(label) if label == choose_label => {
+                choose_end(&handle, &restored)
+            }
+            _ => {}
+        });
+}
+
+fn close_restored(app: &AppHandle, at: NaiveDateTime) {
+    if let Err(error) = perform_at(app, TimerAction::ClockOut, at) {
+        log::warn!("Failed to close restored session: {}", error);
+    }
+    tray::show_main_window(app);
+}
+
+/// The main window asks for the end and calls `timer_clock_out_at`
+fn choose_end(app: &AppHandle, restored: &RestoredSession) {
+    tray::show_main_window(app);
+    let _ = app.emit(EVENT_TIMER_CHOOSE_END, restored);
+}
+
+/// Shows ArbZG reminders that became due; called from the tray refresh loop
+pub fn check_reminders(app: &AppHandle) {
+    let state = app.state::<TrackingState>();
+    let due = {
+        let (Ok(timer), Ok(mut workday)) = (state.timer.lock(), state.workday.lock()) else {
+            return;
+        };
+        workday.evaluate(&timer, now())
+    };
+    for reminder in &due {
+        notify(app, reminder);
+    }
+}
+
+fn notify(app: &AppHandle, reminder: &Reminder) {
+    let _ = app.emit(EVENT_ARBZG_REMINDER, reminder);
+    if let Err(error) = app
+        .notification()
+        .builder()
+        .title(&reminder.title)
+        .body(&reminder.body)
+        .show()
+    {
+        log::warn!("Failed to show ArbZG reminder: {}", error);
+    }
+}
+
+/// Most severe ArbZG limit the running session exceeds right now
+pub fn arbzg_warning(app: &AppHandle) -> Option<ReminderKind> {
+    let state = app.state::<TrackingState>();
+    let (timer, workday) = (state.timer.lock().ok()?, state.workday.lock().ok()?);
+    workday.violations(&timer, now()).first().copied()
+}
+
+pub fn snapshot(app: &AppHandle) -> Option<TimerSnapshot> {
+    let state = app.state::<TrackingState>();
+    let timer = state.timer.lock().ok()?;
+    Some(timer.snapshot(now()))
+}
+
+#[tauri::command]
+pub fn timer_status(state: State<'_, TrackingState>) -> Result<TimerSnapshot, String> {
+    let timer = state.timer.lock().map_err(|e| e.to_string())?;
+    Ok(timer.snapshot(now()))
+}
+
+#[tauri::command]
+pub fn timer_clock_in(app: AppHandle) -> Result<(), String> {
+    perform(&app, TimerAction::ClockIn).map(|_| ())
+}
+
+/// The drafts arrive through `timer:draft` and stay pending until the
+/// frontend resolves them
+#[tauri::command]
+pub fn timer_clock_out(app: AppHandle) -> Result<(), String> {
+    perform(&app, TimerAction::ClockOut).map(|_| ())
+}
+
+/// Closes the running session at a user-chosen time (e.g. after a crash);
+/// the drafts arrive through `timer:draft` like after "Gehen"
+#[tauri::command]
+pub fn timer_clock_out_at(app: AppHandle, end: NaiveDateTime) -> Result<(), String> {
+    app.state::<TrackingState>()
+        .timer
+        .lock()
+        .map_err(|e| e.to_string())?
+        .check_end(end, now())
+        .map_err(|e| e.to_string())?;
+    perform_at(&app, TimerAction::ClockOut, end).map(|_| ())
+}
+
+#[tauri::command]
+pub fn timer_set_details(app: AppHandle, details: SessionDetails) -> Result<(), String> {
+    let state = app.state::<TrackingState>();
+    let mut timer = state.timer.lock().map_err(|e| e.to_string())?;
+    timer.set_details(details).map_err(|e| e.to_string())?;
+    persist(&app, &timer, now());
+    Ok(())
+}
+
+/// Checks the running session as if it ended now against the ArbZG rules
+#[tauri::command]
+pub fn timer_check_arbzg(
+    state: State<'_, TrackingState>,
+    booked: Vec<BookedEntry>,
+) -> Result<Option<ArbzgReport>, String> {
+    let timer = state.timer.lock().map_err(|e| e.to_string())?;
+    let Some(draft) = timer.preview(now()).pop() else {
+        return Ok(None);
+    };
+
+    let candidate = EntryCandidate {
+        date: draft.date,
+        start_time: draft.start_time,
+        end_time: draft.end_time,
+        break_minutes: draft.break_minutes,
+        exclude_entry_id: None,
+    };
+    arbzg::validate_entry(&candidate, &booked)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn timer_start_break(app: AppHandle) -> Result<(), String> {
+    perform(&app, TimerAction::StartBreak).map(|_| ())
+}
+
+#[tauri::command]
+pub fn timer_end_break(app: AppHandle) -> Result<(), String> {
+    perform(&app, TimerAction::EndBreak).map(|_| ())
+}
+
+/// Drafts waiting for the user to submit or discard them
+#[tauri::command]
+pub fn time_entry_drafts(state: State<'_, TrackingState>) -> Result<Vec<TimeEntryDraft>, String> {
+    let pending = state.drafts.lock().map_err(|e| e.to_string())?;
+    Ok(pending.clone())
+}
+
+/// Removes a draft after it was submitted or discarded
+#[tauri::command]
+pub fn resolve_time_entry_draft(app: AppHandle, draft: TimeEntryDraft) -> Result<(), String> {
+    let state = app.state::<TrackingState>();
+    let mut pending = state.drafts.lock().map_err(|e| e.to_string())?;
+    if let Some(index) = pending.iter().position(|d| *d == draft) {
+        pending.remove(index);
+        persist_drafts(&app, &pending);
+    }
+    Ok(())
+}
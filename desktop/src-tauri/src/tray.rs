@@ -0,0 +1,284 @@
+//! System tray: menu, icon, tooltip and the periodic refresh of the
+//! running timer. What icon and tooltip show is decided in `tray_status`.
+
+use std::{sync::Mutex, thread, time::Duration};
+
+use chrono::{Datelike, Local};
+use tauri::{
+    image::Image,
+    menu::{Menu, MenuItem, PredefinedMenuItem},
+    tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
+    App, AppHandle, Manager, Wry,
+};
+
+use crate::api_client::ApiClient;
+use crate::auth::AuthState;
+use crate::balances::Balances;
+use crate::quick_entry;
+use crate::sync;
+use crate::timer::{TimerPhase, TimerSnapshot};
+use crate::tracking::{self, TimerAction};
+use crate::tray_status::{self, TrayIconKind, TrayStatus, APP_TITLE};
+
+pub const TRAY_ID: &str = "main";
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Menu items whose label or enabled state depends on the timer
+pub struct TrayMenu {
+    status: MenuItem<Wry>,
+    clock_in: MenuItem<Wry>,
+    clock_out: MenuItem<Wry>,
+    start_break: MenuItem<Wry>,
+    end_break: MenuItem<Wry>,
+}
+
+/// Disabled info items filled by `balances`
+struct BalanceMenu {
+    overtime: MenuItem<Wry>,
+    vacation: MenuItem<Wry>,
+    requests: MenuItem<Wry>,
+}
+
+/// App icon the variants are rendered from, and the variant shown
+struct TrayIcons {
+    base: Image<'static>,
+    current: Mutex<Option<TrayIconKind>>,
+}
+
+/// Last overtime balance computed for a user, see `set_balance`
+#[derive(Default)]
+struct TrayBalance(Mutex<Option<(i64, f64)>>);
+
+pub fn create(app: &App) -> tauri::Result<()> {
+    // System Tray Menü erstellen
+    let status_item = MenuItem::with_id(app, "status", "Nicht eingestempelt", false, None::<&str>)?;
+    let empty = Balances::default();
+    let overtime_item =
+        MenuItem::with_id(app, "overtime", empty.overtime_label(), false, None::<&str>)?;
+    let vacation_item = MenuItem::with_id(
+        app,
+        "vacation",
+        empty.vacation_label(Local::now().year()),
+        false,
+        None::<&str>,
+    )?;
+    let requests_item =
+        MenuItem::with_id(app, "requests", empty.pending_label(), false, None::<&str>)?;
+    let clock_in_item = MenuItem::with_id(app, "clock_in", "Kommen", true, None::<&str>)?;
+    let clock_out_item = MenuItem::with_id(app, "clock_out", "Gehen", false, None::<&str>)?;
+    let start_break_item =
+        MenuItem::with_id(app, "start_break", "Pause starten", false, None::<&str>)?;
+    let end_break_item = MenuItem::with_id(app, "end_break", "Pause beenden", false, None::<&str>)?;
+    let quick_entry_item =
+        MenuItem::with_id(app, "quick_entry", "Schnellerfassung…", true, None::<&str>)?;
+    let show_item = MenuItem::with_id(app, "show", "Anzeigen", true, None::<&str>)?;
+    let hide_item = MenuItem::with_id(app, "hide", "Verstecken", true, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app, "quit", "Beenden", true, None::<&str>)?;
+
+    let menu = Menu::with_items(
+        app,
+        &[
+            &status_item,
+            &PredefinedMenuItem::separator(app)?,
+            &overtime_item,
+            &vacation_item,
+            &requests_item,
+            &PredefinedMenuItem::separator(app)?,
+            &clock_in_item,
+            &start_break_item,
+            &end_break_item,
+            &clock_out_item,
+            &PredefinedMenuItem::separator(app)?,
+            &quick_entry_item,
+            &show_item,
+            &hide_item,
+            &quit_item,
+        ],
+    )?;
+
+    app.manage(TrayMenu {
+        status: status_item,
+        clock_in: clock_in_item,
+        clock_out: clock_out_item,
+        start_break: start_break_item,
+        end_break: end_break_item,
+    });
+    app.manage(BalanceMenu {
+        overtime: overtime_item,
+        vacation: vacation_item,
+        requests: requests_item,
+    });
+
+    // System Tray Icon erstellen
+    // Load icon from embedded resources
+    let icon = app.default_window_icon().cloned().unwrap();
+    app.manage(TrayIcons {
+        base: icon.clone().to_owned(),
+        current: Mutex::new(Some(TrayIconKind::Idle)),
+    });
+    app.manage(TrayBalance::default());
+
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
+        .icon(icon)
+        .tooltip(APP_TITLE)
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "clock_in" => run_action(app, TimerAction::ClockIn),
+            "start_break" => run_action(app, TimerAction::StartBreak),
+            "end_break" => run_action(app, TimerAction::EndBreak),
+            "clock_out" => {
+                run_action(app, TimerAction::ClockOut);
+                // Show the draft in the main window for confirmation
+                show_main_window(app);
+            }
+            "quick_entry" => {
+                if let Err(error) = quick_entry::open(app) {
+                    log::warn!("Failed to open quick entry: {}", error);
+                }
+            }
+            "show" => show_main_window(app),
+            "hide" => {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.hide();
+                }
+            }
+            "quit" => {
+                tracking::heartbeat(app);
+                app.exit(0);
+            }
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                // Bei Linksklick: Fenster anzeigen/fokussieren
+                show_main_window(tray.app_handle());
+            }
+        })
+        .build(app)?;
+
+    spawn_refresh_loop(app.handle().clone());
+
+    Ok(())
+}
+
+fn run_action(app: &AppHandle, action: TimerAction) {
+    if let Err(error) = tracking::perform(app, action) {
+        log::warn!("Tray action {:?} failed: {}", action, error);
+    }
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Updates menu labels, enabled states, icon and tooltip
+pub fn refresh(app: &AppHandle) {
+    let Some(snapshot) = tracking::snapshot(app) else {
+        return;
+    };
+
+    if let Some(menu) = app.try_state::<TrayMenu>() {
+        let phase = snapshot.phase;
+        let _ = menu.status.set_text(status_label(&snapshot));
+        let _ = menu.clock_in.set_enabled(phase == TimerPhase::Idle);
+        let _ = menu.clock_out.set_enabled(phase != TimerPhase::Idle);
+        let _ = menu.start_break.set_enabled(phase == TimerPhase::Running);
+        let _ = menu.end_break.set_enabled(phase == TimerPhase::OnBreak);
+    }
+
+    let status = TrayStatus {
+        snapshot,
+        online: app
+            .try_state::<ApiClient>()
+            .is_none_or(|client| client.is_online()),
+        pending: sync::pending_count(app),
+        balance: balance(app),
+        warning: tracking::arbzg_warning(app),
+    };
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_tooltip(Some(status.tooltip()));
+        set_icon(app, &tray, status.icon());
+    }
+}
+
+/// Remembers the balance of `user_id` up to today for the tooltip
+pub fn set_balance(app: &AppHandle, user_id: i64, balance: f64) {
+    if let Some(state) = app.try_state::<TrayBalance>() {
+        if let Ok(mut current) = state.0.lock() {
+            *current = Some((user_id, balance));
+        }
+    }
+    refresh(app);
+}
+
+/// Shows the figures in the info items of the menu
+pub fn set_balances(app: &AppHandle, balances: &Balances) {
+    if let Some(menu) = app.try_state::<BalanceMenu>() {
+        let _ = menu.overtime.set_text(balances.overtime_label());
+        let _ = menu
+            .vacation
+            .set_text(balances.vacation_label(Local::now().year()));
+        let _ = menu.requests.set_text(balances.pending_label());
+    }
+}
+
+/// Balance of the logged-in user, if one was computed
+fn balance(app: &AppHandle) -> Option<f64> {
+    let user_id = app.try_state::<AuthState>()?.user_id()?;
+    let state = app.try_state::<TrayBalance>()?;
+    let (id, balance) = (*state.0.lock().ok()?)?;
+    (id == user_id).then_some(balance)
+}
+
+/// Renders and shows the icon variant unless it is already shown
+fn set_icon(app: &AppHandle, tray: &TrayIcon, kind: TrayIconKind) {
+    let Some(icons) = app.try_state::<TrayIcons>() else {
+        return;
+    };
+    let Ok(mut current) = icons.current.lock() else {
+        return;
+    };
+    if *current == Some(kind) {
+        return;
+    }
+
+    let image = match kind.badge_color() {
+        None => icons.base.clone(),
+        Some(color) => {
+            let (width, height) = (icons.base.width(), icons.base.height());
+            let rgba = tray_status::with_badge(icons.base.rgba(), width, height, color);
+            Image::new_owned(rgba, width, height)
+        }
+    };
+    match tray.set_icon(Some(image)) {
+        Ok(()) => *current = Some(kind),
+        Err(error) => log::warn!("Failed to update tray icon: {}", error),
+    }
+}
+
+fn status_label(snapshot: &TimerSnapshot) -> String {
+    let elapsed = snapshot.elapsed_label.as_deref().unwrap_or("0:00");
+    match snapshot.phase {
+        TimerPhase::Idle => "Nicht eingestempelt".to_string(),
+        TimerPhase::Running => format!("Arbeitszeit: {}h", elapsed),
+        TimerPhase::OnBreak => format!("Pause – Arbeitszeit: {}h", elapsed),
+    }
+}
+
+fn spawn_refresh_loop(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(REFRESH_INTERVAL);
+        tracking::heartbeat(&app);
+        tracking::check_reminders(&app);
+        refresh(&app);
+    });
+}
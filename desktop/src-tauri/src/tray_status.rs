@@ -0,0 +1,228 @@
+//! What the tray shows: icon variant and tooltip.
+//!
+//! Derived from the timer, the connection, the sync queue, the last
+//! overtime balance and the ArbZG limits. Pure, so `tray.rs` only collects
+//! the inputs and applies the result. The icon variants are the app icon
+//! with a coloured badge, rendered at runtime from the window icon.
+
+use chrono::Duration;
+
+use crate::reminders::ReminderKind;
+use crate::timer::{format_hours_minutes, TimerPhase, TimerSnapshot};
+
+pub const APP_TITLE: &str = "Stiftung der DPolG TimeTracker";
+
+/// Icon variant, in increasing priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrayIconKind {
+    Idle,
+    Running,
+    OnBreak,
+    Offline,
+    Warning,
+}
+
+impl TrayIconKind {
+    /// Badge colour; the idle icon has no badge
+    pub fn badge_color(self) -> Option<[u8; 3]> {
+        match self {
+            TrayIconKind::Idle => None,
+            TrayIconKind::Running => Some([22, 163, 74]),
+            TrayIconKind::OnBreak => Some([245, 158, 11]),
+            TrayIconKind::Offline => Some([107, 114, 128]),
+            TrayIconKind::Warning => Some([220, 38, 38]),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrayStatus {
+    pub snapshot: TimerSnapshot,
+    pub online: bool,
+    /// Queued changes in the outbox
+    pub pending: i64,
+    /// Overtime balance of the logged-in user in hours
+    pub balance: Option<f64>,
+    /// Most severe ArbZG limit exceeded right now
+    pub warning: Option<ReminderKind>,
+}
+
+impl TrayStatus {
+    pub fn icon(&self) -> TrayIconKind {
+        if self.warning.is_some() {
+            TrayIconKind::Warning
+        } else if !self.online {
+            TrayIconKind::Offline
+        } else {
+            match self.snapshot.phase {
+                TimerPhase::Idle => TrayIconKind::Idle,
+                TimerPhase::Running => TrayIconKind::Running,
+                TimerPhase::OnBreak => TrayIconKind::OnBreak,
+            }
+        }
+    }
+
+    /// e.g. "Läuft seit 08:12 – 5:43h – Saldo +12:30h", plus one line each
+    /// for the connection and an ArbZG warning
+    pub fn tooltip(&self) -> String {
+        let mut parts = Vec::new();
+        match self.snapshot.started_at {
+            Some(started_at) => {
+                let prefix = match self.snapshot.phase {
+                    TimerPhase::OnBreak => "Pause – eingestempelt",
+                    _ => "Läuft",
+                };
+                parts.push(format!("{} seit {}", prefix, started_at.format("%H:%M")));
+                let elapsed = self.snapshot.elapsed_label.as_deref().unwrap_or("0:00");
+                parts.push(format!("{}h", elapsed));
+            }
+            None => parts.push(APP_TITLE.to_string()),
+        }
+        if let Some(balance) = self.balance {
+            parts.push(format!("Saldo {}", format_balance(balance)));
+        }
+
+        let mut lines = vec![parts.join(" – ")];
+        if !self.online {
+            lines.push(match self.pending {
+                0 => "Offline".to_string(),
+                1 => "Offline – 1 Änderung ausstehend".to_string(),
+                n => format!("Offline – {} Änderungen ausstehend", n),
+            });
+        }
+        if let Some(warning) = self.warning {
+            lines.push(format!("ArbZG: {}", warning_label(warning)));
+        }
+        lines.join("\n")
+    }
+}
+
+fn warning_label(kind: ReminderKind) -> &'static str {
+    match kind {
+        ReminderKind::BreakAfterSixHours => "30 Min Pause fällig",
+        ReminderKind::BreakAfterNineHours => "45 Min Pause fällig",
+        ReminderKind::MaxDailyHours => "10 Stunden Höchstarbeitszeit erreicht",
+        ReminderKind::RestPeriod => "Ruhezeit unterschritten",
+    }
+}
+
+/// Hours as signed "H:MM" (e.g. "+12:30h")
+pub fn format_balance(hours: f64) -> String {
+    let minutes = (hours * 60.0).round() as i64;
+    let sign = if minutes >= 0 { "+" } else { "" };
+    format!(
+        "{}{}h",
+        sign,
+        format_hours_minutes(Duration::minutes(minutes))
+    )
+}
+
+/// Copy of `rgba` with a filled circle and a white ring in the bottom
+/// right corner
+pub fn with_badge(rgba: &[u8], width: u32, height: u32, color: [u8; 3]) -> Vec<u8> {
+    let mut out = rgba.to_vec();
+    let radius = (width.min(height) as f64 * 0.22).max(2.0);
+    let ring = (radius / 5.0).max(1.0);
+    let cx = width as f64 - radius - 0.5;
+    let cy = height as f64 - radius - 0.5;
+
+    for y in 0..height {
+        for x in 0..width {
+            let distance = ((x as f64 - cx).powi(2) + (y as f64 - cy).powi(2)).sqrt();
+            let pixel = if distance <= radius - ring {
+                [color[0], color[1], color[2], 255]
+            } else if distance <= radius {
+                [255, 255, 255, 255]
+            } else {
+                continue;
+            };
+            let offset = ((y * width + x) * 4) as usize;
+            if let Some(target) = out.get_mut(offset..offset + 4) {
+                target.copy_from_slice(&pixel);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn status(phase: TimerPhase) -> TrayStatus {
+        let running = phase != TimerPhase::Idle;
+        TrayStatus {
+            snapshot: TimerSnapshot {
+                phase,
+                started_at: running.then(|| {
+                    NaiveDateTime::parse_from_str("2026-03-02 08:12", "%Y-%m-%d %H:%M").unwrap()
+                }),
+                worked_minutes: 343,
+                break_minutes: 0,
+                elapsed_label: running.then(|| "5:43".to_string()),
+            },
+            online: true,
+            pending: 0,
+            balance: None,
+            warning: None,
+        }
+    }
+
+    #[test]
+    fn running_tooltip_shows_start_elapsed_and_balance() {
+        let mut running = status(TimerPhase::Running);
+        running.balance = Some(12.5);
+        assert_eq!(running.icon(), TrayIconKind::Running);
+        assert_eq!(
+            running.tooltip(),
+            "Läuft seit 08:12 – 5:43h – Saldo +12:30h"
+        );
+
+        let mut idle = status(TimerPhase::Idle);
+        idle.balance = Some(-1.25);
+        assert_eq!(idle.icon(), TrayIconKind::Idle);
+        assert_eq!(idle.tooltip(), format!("{} – Saldo -1:15h", APP_TITLE));
+    }
+
+    #[test]
+    fn warning_and_offline_take_precedence() {
+        let mut on_break = status(TimerPhase::OnBreak);
+        assert_eq!(on_break.icon(), TrayIconKind::OnBreak);
+
+        on_break.online = false;
+        on_break.pending = 3;
+        assert_eq!(on_break.icon(), TrayIconKind::Offline);
+        assert_eq!(
+            on_break.tooltip(),
+            "Pause – eingestempelt seit 08:12 – 5:43h\nOffline – 3 Änderungen ausstehend"
+        );
+
+        on_break.warning = Some(ReminderKind::BreakAfterSixHours);
+        assert_eq!(on_break.icon(), TrayIconKind::Warning);
+        assert!(on_break.tooltip().ends_with("\nArbZG: 30 Min Pause fällig"));
+    }
+
+    #[test]
+    fn balance_is_signed() {
+        assert_eq!(format_balance(0.0), "+0:00h");
+        assert_eq!(format_balance(7.99), "+7:59h");
+        assert_eq!(format_balance(-0.5), "-0:30h");
+    }
+
+    #[test]
+    fn badge_is_drawn_in_bottom_right_corner() {
+        let (width, height) = (32, 32);
+        let icon = vec![10u8; (width * height * 4) as usize];
+        let badged = with_badge(&icon, width, height, [220, 38, 38]);
+        let pixel = |x: u32, y: u32| {
+            let offset = ((y * width + x) * 4) as usize;
+            badged[offset..offset + 4].to_vec()
+        };
+
+        assert_eq!(badged.len(), icon.len());
+        assert_eq!(pixel(0, 0), vec![10, 10, 10, 10]);
+        assert_eq!(pixel(25, 25), vec![220, 38, 38, 255]);
+        assert_eq!(pixel(31, 25), vec![255, 255, 255, 255]);
+    }
+}
@@ -0,0 +1,336 @@
+//! Vacation entitlement and balance projection
+//!
+//! Native port of `calculateProRataVacationDays`
+//! (`server/src/services/vacationBalanceService.ts`) and of the day count
+//! of vacation requests (`countWorkingDaysForUser`), so an employee can see
+//! offline how many days are left after booking a request:
+//!
+//! - available = entitlement + carryover (as in `vacation_balance`)
+//! - remaining = available − approved vacation days
+//! - projected = remaining − pending vacation days − proposed range
+//!
+//! A vacation day is a day with a target (`work_schedule`) that is no
+//! holiday, so days with 0 h in the `workSchedule` and weekends are free.
+//! Requests are counted from their dates rather than the stored `days`, and
+//! only the part inside `year` counts. Differences to the server:
+//! - Pro-rata uses the actual length of the year (365/366) and also
+//!   shortens the entitlement for an end date in `year`; the server only
+//!   looks at the hire date and counts one day too many.
+
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    overtime::{Absence, AbsenceKind, Employee},
+    work_schedule::{ScheduleError, ScheduleHistory},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DateRange {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VacationInput {
+    pub employee: Employee,
+    pub vacation_days_per_year: f64,
+    pub year: i32,
+    /// `vacation_balance.entitlement` when set by an admin; otherwise the
+    /// pro-rata entitlement is used
+    #[serde(default)]
+    pub entitlement: Option<f64>,
+    #[serde(default)]
+    pub carryover: f64,
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
+    /// Absence requests of the user; only `vacation` counts
+    #[serde(default)]
+    pub absences: Vec<Absence>,
+    /// Request that is about to be booked
+    #[serde(default)]
+    pub proposed: Option<DateRange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VacationProjection {
+    pub year: i32,
+    pub entitlement: f64,
+    pub carryover: f64,
+    pub available: f64,
+    /// Approved vacation days
+    pub taken: f64,
+    pub pending: f64,
+    pub remaining: f64,
+    /// Vacation days of `proposed` within `year`
+    pub proposed_days: f64,
+    /// Remaining after pending requests and `proposed`; negative when the
+    /// request exceeds the balance
+    pub projected: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VacationError {
+    InvalidRange(NaiveDate, NaiveDate),
+    InvalidYear(i32),
+    Schedule(ScheduleError),
+}
+
+impl fmt::Display for VacationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VacationError::InvalidRange(from, to) => write!(
+                f,
+                "Ungültiger Zeitraum: {} bis {}",
+                from.format("%d.%m.%Y"),
+                to.format("%d.%m.%Y")
+            ),
+            VacationError::InvalidYear(year) => write!(f, "Ungültiges Jahr: {}", year),
+            VacationError::Schedule(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for VacationError {}
+
+impl From<ScheduleError> for VacationError {
+    fn from(e: ScheduleError) -> Self {
+        VacationError::Schedule(e)
+    }
+}
+
+fn year_bounds(year: i32) -> Result<(NaiveDate, NaiveDate), VacationError> {
+    match (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) {
+        (Some(first), Some(last)) => Ok((first, last)),
+        _ => Err(VacationError::InvalidYear(year)),
+    }
+}
+
+/// Entitlement for the days employed in `year`, rounded to half days
+pub fn pro_rata_entitlement(
+    hire_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+    days_per_year: f64,
+    year: i32,
+) -> Result<f64, VacationError> {
+    let (first, last) = year_bounds(year)?;
+    let from = hire_date.max(first);
+    let to = end_date.map_or(last, |end| end.min(last));
+    if from > to {
+        return Ok(0.0);
+    }
+    if from == first && to == last {
+        return Ok(days_per_year);
+    }
+    let employed = (to - from).num_days() + 1;
+    let days_in_year = last.ordinal() as f64;
+    Ok((employed as f64 / days_in_year * days_per_year * 2.0).round() / 2.0)
+}
+
+/// Vacation days in `from..=to`: days with a target, holidays excluded
+pub fn vacation_days(
+    schedule: &ScheduleHistory,
+    from: NaiveDate,
+    to: NaiveDate,
+    holidays: &[NaiveDate],
+) -> f64 {
+    from.iter_days()
+        .take_while(|d| *d <= to)
+        .filter(|d| schedule.daily_target(*d, holidays.contains(d)) > 0.0)
+        .count() as f64
+}
+
+pub fn project(input: &VacationInput) -> Result<VacationProjection, VacationError> {
+    let (first, last) = year_bounds(input.year)?;
+    let employee = &input.employee;
+    let schedule = employee.schedule()?;
+    let days_in_year = |start: NaiveDate, end: NaiveDate| {
+        vacation_days(&schedule, start.max(first), end.min(last), &input.holidays)
+    };
+
+    let entitlement = match input.entitlement {
+        Some(days) => days,
+        None => pro_rata_entitlement(
+            employee.hire_date,
+            employee.end_date,
+            input.vacation_days_per_year,
+            input.year,
+        )?,
+    };
+    let mut taken = 0.0;
+    let mut pending = 0.0;
+    for absence in &input.absences {
+        if absence.kind != AbsenceKind::Vacation {
+            continue;
+        }
+        let days = days_in_year(absence.start_date, absence.end_date);
+        match absence.status.as_str() {
+            "approved" => taken += days,
+            "pending" => pending += days,
+            _ => {}
+        }
+    }
+    let proposed_days = match &input.proposed {
+        Some(range) if range.start_date > range.end_date => {
+            return Err(VacationError::InvalidRange(
+                range.start_date,
+                range.end_date,
+            ));
+        }
+        Some(range) => days_in_year(range.start_date, range.end_date),
+        None => 0.0,
+    };
+
+    let available = entitlement + input.carryover;
+    let remaining = available - taken;
+    Ok(VacationProjection {
+        year: input.year,
+        entitlement,
+        carryover: input.carryover,
+        available,
+        taken,
+        pending,
+        remaining,
+        proposed_days,
+        projected: remaining - pending - proposed_days,
+    })
+}
+
+#[tauri::command]
+pub fn project_vacation(input: VacationInput) -> Result<VacationProjection, String> {
+    project(&input).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::work_schedule::{TargetRule, WorkSchedule};
+    use serde_json::json;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn input(work_schedule: Option<serde_json::Value>) -> VacationInput {
+        VacationInput {
+            employee: Employee {
+                rule: TargetRule {
+                    weekly_hours: 40.0,
+                    work_schedule: work_schedule.map(|s| WorkSchedule::try_from(s).unwrap()),
+                },
+                hire_date: date("2020-01-01"),
+                end_date: None,
+                schedule_history: Vec::new(),
+            },
+            vacation_days_per_year: 30.0,
+            year: 2026,
+            entitlement: None,
+            carryover: 0.0,
+            holidays: vec![date("2026-01-01"), date("2026-01-06")],
+            absences: Vec::new(),
+            proposed: None,
+        }
+    }
+
+    fn vacation(status: &str, start: &str, end: &str) -> Absence {
+        Absence {
+            id: None,
+            kind: AbsenceKind::Vacation,
+            start_date: date(start),
+            end_date: date(end),
+            status: status.into(),
+        }
+    }
+
+    #[test]
+    fn pro_rata_from_hire_and_end_date() {
+        let full = pro_rata_entitlement(date("2020-05-01"), None, 30.0, 2026).unwrap();
+        assert_eq!(full, 30.0);
+        // 01.07.–31.12.: 184 of 365 days → 15.12 → 15
+        let july = pro_rata_entitlement(date("2026-07-01"), None, 30.0, 2026).unwrap();
+        assert_eq!(july, 15.0);
+        // 01.01.–31.03.: 90 days → 7.4 → 7.5
+        let left =
+            pro_rata_entitlement(date("2020-01-01"), Some(date("2026-03-31")), 30.0, 2026).unwrap();
+        assert_eq!(left, 7.5);
+        assert_eq!(
+            pro_rata_entitlement(date("2027-01-01"), None, 30.0, 2026).unwrap(),
+            0.0
+        );
+        assert_eq!(
+            pro_rata_entitlement(date("2020-01-01"), Some(date("2025-12-31")), 30.0, 2026).unwrap(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn proposed_range_counts_working_days_of_the_schedule() {
+        // Fri 02.01.–Fri 09.01.: 6 weekdays, 06.01. is a holiday
+        let mut full_time = input(None);
+        full_time.proposed = Some(DateRange {
+            start_date: date("2026-01-02"),
+            end_date: date("2026-01-09"),
+        });
+        assert_eq!(project(&full_time).unwrap().proposed_days, 5.0);
+
+        // Mon + Wed only: 05.01. and 07.01.
+        let mut part_time = input(Some(json!({ "monday": 8, "wednesday": 8 })));
+        part_time.proposed = full_time.proposed.clone();
+        assert_eq!(project(&part_time).unwrap().proposed_days, 2.0);
+
+        // Across the turn of the year only January counts
+        full_time.proposed = Some(DateRange {
+            start_date: date("2025-12-29"),
+            end_date: date("2026-01-02"),
+        });
+        assert_eq!(project(&full_time).unwrap().proposed_days, 1.0);
+
+        full_time.proposed = Some(DateRange {
+            start_date: date("2026-01-09"),
+            end_date: date("2026-01-02"),
+        });
+        assert!(matches!(
+            project(&full_time),
+            Err(VacationError::InvalidRange(_, _))
+        ));
+    }
+
+    #[test]
+    fn balance_after_pending_and_approved_requests() {
+        let mut input = input(None);
+        input.carryover = 3.0;
+        input.absences = vec![
+            // 5 days
+            vacation("approved", "2026-02-02", "2026-02-06"),
+            // 3 days (Mon–Wed)
+            vacation("pending", "2026-03-02", "2026-03-04"),
+            vacation("rejected", "2026-04-06", "2026-04-10"),
+            Absence {
+                kind: AbsenceKind::Sick,
+                ..vacation("approved", "2026-05-04", "2026-05-08")
+            },
+        ];
+        input.proposed = Some(DateRange {
+            start_date: date("2026-08-03"),
+            end_date: date("2026-08-14"),
+        });
+        let projection = project(&input).unwrap();
+        assert_eq!(projection.available, 33.0);
+        assert_eq!(projection.taken, 5.0);
+        assert_eq!(projection.pending, 3.0);
+        assert_eq!(projection.remaining, 28.0);
+        assert_eq!(projection.proposed_days, 10.0);
+        assert_eq!(projection.projected, 15.0);
+
+        input.entitlement = Some(10.0);
+        assert_eq!(project(&input).unwrap().projected, -5.0);
+    }
+}
@@ -0,0 +1,405 @@
+//! Daily target hours (Soll) from `weeklyHours` and `workSchedule`
+//!
+//! Native port of `getDailyTargetHours` (`server/src/utils/workingDays.ts`):
+//! holidays have no target, an individual `workSchedule`
+//! (`{"monday":8,…,"friday":2}`) wins over `weeklyHours`, otherwise the
+//! week is spread over Monday–Friday (`weeklyHours / 5`, weekends 0).
+//!
+//! Unlike the server, schedules are validated (no negative values, at most
+//! 24 h per day, only weekday keys), and a user can have several versions
+//! with a `validFrom` date, so a contract change from 40 h to 30 h in June
+//! leaves the targets before June untouched.
+
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub const MAX_DAILY_HOURS: f64 = 24.0;
+const MAX_WEEKLY_HOURS: f64 = 7.0 * MAX_DAILY_HOURS;
+const DAY_NAMES: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleError {
+    /// Not a JSON object (or a string containing one)
+    NotAnObject,
+    UnknownDay(String),
+    NotANumber(String),
+    Negative(String, f64),
+    TooLong(String, f64),
+    InvalidWeeklyHours(f64),
+    DuplicateValidFrom(NaiveDate),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::NotAnObject => {
+                f.write_str("Arbeitszeitmodell: erwartet ein Objekt mit Wochentagen")
+            }
+            ScheduleError::UnknownDay(key) => {
+                write!(f, "Arbeitszeitmodell: unbekannter Wochentag \"{}\"", key)
+            }
+            ScheduleError::NotANumber(day) => {
+                write!(f, "Arbeitszeitmodell: {} ist keine Zahl", day)
+            }
+            ScheduleError::Negative(day, hours) => write!(
+                f,
+                "Arbeitszeitmodell: {} darf nicht negativ sein ({}h)",
+                day, hours
+            ),
+            ScheduleError::TooLong(day, hours) => write!(
+                f,
+                "Arbeitszeitmodell: {} hat mehr als {}h ({}h)",
+                day, MAX_DAILY_HOURS, hours
+            ),
+            ScheduleError::InvalidWeeklyHours(hours) => {
+                write!(f, "Ungültige Wochenstunden: {}h", hours)
+            }
+            ScheduleError::DuplicateValidFrom(date) => write!(
+                f,
+                "Zwei Arbeitszeitmodelle gelten ab dem {}",
+                date.format("%d.%m.%Y")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+/// Validated `workSchedule`: hours per weekday, missing days are 0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Value", into = "Value")]
+pub struct WorkSchedule {
+    /// Monday first
+    hours: [f64; 7],
+}
+
+impl WorkSchedule {
+    pub fn hours(&self, weekday: Weekday) -> f64 {
+        self.hours[weekday.num_days_from_monday() as usize]
+    }
+
+    pub fn weekly_hours(&self) -> f64 {
+        self.hours.iter().sum()
+    }
+}
+
+impl TryFrom<Value> for WorkSchedule {
+    type Error = ScheduleError;
+
+    /// Accepts the object or, as stored in `users.workSchedule`, a string
+    /// containing it. `null` days count as 0.
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let object = match value {
+            Value::Object(object) => object,
+            Value::String(text) => match serde_json::from_str(&text) {
+                Ok(Value::Object(object)) => object,
+                _ => return Err(ScheduleError::NotAnObject),
+            },
+            _ => return Err(ScheduleError::NotAnObject),
+        };
+        let mut schedule = WorkSchedule::default();
+        for (key, value) in object {
+            let day = DAY_NAMES
+                .iter()
+                .position(|name| *name == key)
+                .ok_or_else(|| ScheduleError::UnknownDay(key.clone()))?;
+            let hours = match value {
+                Value::Null => 0.0,
+                Value::Number(n) => n.as_f64().ok_or(ScheduleError::NotANumber(key.clone()))?,
+                _ => return Err(ScheduleError::NotANumber(key)),
+            };
+            if hours < 0.0 {
+                return Err(ScheduleError::Negative(key, hours));
+            }
+            if hours > MAX_DAILY_HOURS {
+                return Err(ScheduleError::TooLong(key, hours));
+            }
+            schedule.hours[day] = hours;
+        }
+        Ok(schedule)
+    }
+}
+
+impl From<WorkSchedule> for Value {
+    fn from(schedule: WorkSchedule) -> Self {
+        let object: Map<String, Value> = DAY_NAMES
+            .iter()
+            .zip(schedule.hours)
+            .filter(|(_, hours)| *hours > 0.0)
+            .map(|(day, hours)| (day.to_string(), Value::from(hours)))
+            .collect();
+        Value::Object(object)
+    }
+}
+
+/// Contract hours as stored on the user
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetRule {
+    pub weekly_hours: f64,
+    /// Overrides `weekly_hours` when set
+    #[serde(default)]
+    pub work_schedule: Option<WorkSchedule>,
+}
+
+impl TargetRule {
+    pub fn validate(&self) -> Result<(), ScheduleError> {
+        if !(0.0..=MAX_WEEKLY_HOURS).contains(&self.weekly_hours) {
+            return Err(ScheduleError::InvalidWeeklyHours(self.weekly_hours));
+        }
+        Ok(())
+    }
+
+    /// Target hours of a day, same as the server's `getDailyTargetHours`
+    pub fn daily_target(&self, date: NaiveDate, is_holiday: bool) -> f64 {
+        if is_holiday {
+            return 0.0;
+        }
+        if let Some(schedule) = &self.work_schedule {
+            return schedule.hours(date.weekday());
+        }
+        if self.weekly_hours == 0.0 || matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return 0.0;
+        }
+        ((self.weekly_hours / 5.0) * 100.0).round() / 100.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleVersion {
+    pub valid_from: NaiveDate,
+    #[serde(flatten)]
+    pub rule: TargetRule,
+}
+
+/// All versions of a user's contract hours, oldest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleHistory {
+    versions: Vec<ScheduleVersion>,
+}
+
+impl ScheduleHistory {
+    pub fn new(mut versions: Vec<ScheduleVersion>) -> Result<Self, ScheduleError> {
+        versions.sort_by_key(|v| v.valid_from);
+        for pair in versions.windows(2) {
+            if pair[0].valid_from == pair[1].valid_from {
+                return Err(ScheduleError::DuplicateValidFrom(pair[1].valid_from));
+            }
+        }
+        for version in &versions {
+            version.rule.validate()?;
+        }
+        Ok(Self { versions })
+    }
+
+    /// Rule in force on `date`. Days before the first version use the
+    /// first one; `None` only for an empty history.
+    pub fn rule_at(&self, date: NaiveDate) -> Option<&TargetRule> {
+        self.versions
+            .iter()
+            .rev()
+            .find(|v| v.valid_from <= date)
+            .or(self.versions.first())
+            .map(|v| &v.rule)
+    }
+
+    pub fn daily_target(&self, date: NaiveDate, is_holiday: bool) -> f64 {
+        self.rule_at(date)
+            .map_or(0.0, |rule| rule.daily_target(date, is_holiday))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyTarget {
+    pub date: NaiveDate,
+    pub target_hours: f64,
+    pub is_holiday: bool,
+}
+
+/// Target hours of every day in `from..=to`
+pub fn daily_targets(
+    history: &ScheduleHistory,
+    from: NaiveDate,
+    to: NaiveDate,
+    holidays: &[NaiveDate],
+) -> Vec<DailyTarget> {
+    from.iter_days()
+        .take_while(|d| *d <= to)
+        .map(|date| {
+            let is_holiday = holidays.contains(&date);
+            DailyTarget {
+                date,
+                target_hours: history.daily_target(date, is_holiday),
+                is_holiday,
+            }
+        })
+        .collect()
+}
+
+/// First and last day of a month; `None` for an invalid month or year
+pub fn month_range(year: i32, month: u32) -> Option<(NaiveDate, NaiveDate)> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let last = first
+        .checked_add_months(chrono::Months::new(1))?
+        .pred_opt()?;
+    Some((first, last))
+}
+
+/// Checks a `workSchedule` before it is saved
+#[tauri::command]
+pub fn validate_work_schedule(schedule: Value) -> Result<WorkSchedule, String> {
+    WorkSchedule::try_from(schedule).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn resolve_daily_targets(
+    versions: Vec<ScheduleVersion>,
+    from: NaiveDate,
+    to: NaiveDate,
+    holidays: Vec<NaiveDate>,
+) -> Result<Vec<DailyTarget>, String> {
+    let history = ScheduleHistory::new(versions).map_err(|e| e.to_string())?;
+    Ok(daily_targets(&history, from, to, &holidays))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn schedule(value: Value) -> Result<WorkSchedule, ScheduleError> {
+        WorkSchedule::try_from(value)
+    }
+
+    #[test]
+    fn schedules_are_validated() {
+        let part_time = schedule(json!({ "monday": 8, "friday": 2.5, "sunday": null })).unwrap();
+        assert_eq!(part_time.hours(Weekday::Fri), 2.5);
+        assert_eq!(part_time.hours(Weekday::Tue), 0.0);
+        assert_eq!(part_time.weekly_hours(), 10.5);
+        assert_eq!(
+            Value::from(part_time),
+            json!({ "monday": 8.0, "friday": 2.5 })
+        );
+        // As stored in users.workSchedule
+        assert_eq!(
+            schedule(json!(r#"{"monday":8,"friday":2.5}"#)).unwrap(),
+            part_time
+        );
+
+        assert_eq!(
+            schedule(json!({ "monday": -1 })),
+            Err(ScheduleError::Negative("monday".into(), -1.0))
+        );
+        assert_eq!(
+            schedule(json!({ "tuesday": 24.5 })),
+            Err(ScheduleError::TooLong("tuesday".into(), 24.5))
+        );
+        assert_eq!(
+            schedule(json!({ "montag": 8 })),
+            Err(ScheduleError::UnknownDay("montag".into()))
+        );
+        assert_eq!(
+            schedule(json!({ "monday": "8" })),
+            Err(ScheduleError::NotANumber("monday".into()))
+        );
+        assert_eq!(schedule(json!([8, 8])), Err(ScheduleError::NotAnObject));
+    }
+
+    #[test]
+    fn targets_match_get_daily_target_hours() {
+        let full_time = TargetRule {
+            weekly_hours: 38.5,
+            work_schedule: None,
+        };
+        // 2026-01-05 is a Monday
+        assert_eq!(full_time.daily_target(date("2026-01-05"), false), 7.7);
+        assert_eq!(full_time.daily_target(date("2026-01-05"), true), 0.0);
+        assert_eq!(full_time.daily_target(date("2026-01-10"), false), 0.0);
+
+        let helper = TargetRule {
+            weekly_hours: 0.0,
+            work_schedule: None,
+        };
+        assert_eq!(helper.daily_target(date("2026-01-05"), false), 0.0);
+
+        let weekend = TargetRule {
+            weekly_hours: 40.0,
+            work_schedule: Some(schedule(json!({ "saturday": 8, "sunday": 8 })).unwrap()),
+        };
+        assert_eq!(weekend.daily_target(date("2026-01-05"), false), 0.0);
+        assert_eq!(weekend.daily_target(date("2026-01-10"), false), 8.0);
+        assert_eq!(weekend.daily_target(date("2026-01-11"), true), 0.0);
+    }
+
+    #[test]
+    fn contract_changes_only_affect_later_days() {
+        let version: ScheduleVersion = serde_json::from_value(json!({
+            "validFrom": "2026-06-01", "weeklyHours": 30, "workSchedule": null
+        }))
+        .unwrap();
+        let history = ScheduleHistory::new(vec![
+            version,
+            ScheduleVersion {
+                valid_from: date("2024-01-01"),
+                rule: TargetRule {
+                    weekly_hours: 40.0,
+                    work_schedule: None,
+                },
+            },
+        ])
+        .unwrap();
+
+        // Before the first version, in the first, on and after the change
+        assert_eq!(history.daily_target(date("2023-12-29"), false), 8.0);
+        assert_eq!(history.daily_target(date("2026-05-29"), false), 8.0);
+        assert_eq!(history.daily_target(date("2026-06-01"), false), 6.0);
+        let june: f64 = daily_targets(&history, date("2026-05-25"), date("2026-06-07"), &[])
+            .iter()
+            .map(|d| d.target_hours)
+            .sum();
+        assert_eq!(june, 5.0 * 8.0 + 5.0 * 6.0);
+
+        assert_eq!(
+            ScheduleHistory::new(vec![version, version]),
+            Err(ScheduleError::DuplicateValidFrom(date("2026-06-01")))
+        );
+        let mut invalid = version;
+        invalid.rule.weekly_hours = -5.0;
+        assert_eq!(
+            ScheduleHistory::new(vec![invalid]),
+            Err(ScheduleError::InvalidWeeklyHours(-5.0))
+        );
+    }
+
+    #[test]
+    fn month_ranges() {
+        assert_eq!(
+            month_range(2028, 2),
+            Some((date("2028-02-01"), date("2028-02-29")))
+        );
+        assert_eq!(
+            month_range(2026, 12),
+            Some((date("2026-12-01"), date("2026-12-31")))
+        );
+        assert_eq!(month_range(2026, 0), None);
+        assert_eq!(month_range(2026, 13), None);
+    }
+}
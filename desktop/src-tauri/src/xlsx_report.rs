@@ -0,0 +1,832 @@
+//! Monthly report as Excel workbook (XLSX)
+//!
+//! Same data as the timesheet PDF (`timesheet`), but for managers who keep
+//! working with the numbers: a summary sheet ("Übersicht") plus one sheet
+//! per employee with typed cells, totals as formulas and conditional
+//! highlighting of ArbZG hints and negative differences.
+//!
+//! - Datum is a date cell, Beginn/Ende are time cells and Pause is a
+//!   duration (`[h]:mm`).
+//! - Soll, Gutschrift, Ist and Differenz are decimal hours like in the DATEV
+//!   export: Excel's 1900 date system cannot show negative times.
+//! - Formulas carry their computed result, so viewers that don't
+//!   recalculate on load (LibreOffice by default) show the right totals.
+//!
+//! ArbZG hints come from the `arbzg` rules: daily hours (warning level and
+//! above), break per entry and the rest period between days. The weekly
+//! rule needs the neighbouring months and is left to the server.
+
+use std::{collections::HashMap, fmt, io};
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use rust_xlsxwriter::{
+    Color, ConditionalFormatCell, ConditionalFormatCellRule, ConditionalFormatFormula,
+    ExcelDateTime, Format, FormatBorder, Formula, Workbook, Worksheet, XlsxError,
+};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+use crate::arbzg::{self, Severity};
+use crate::overtime::AbsenceKind;
+use crate::session_store::write_atomic;
+use crate::timesheet::{absence_kind, month_name, weekday_short, DatevRow, NamedDay};
+use crate::work_schedule::{self, DailyTarget};
+
+const SUMMARY_SHEET: &str = "Übersicht";
+/// Excel limit for sheet names
+const SHEET_NAME_MAX_CHARS: usize = 31;
+/// Header row and first data row (0-based) of an employee sheet
+const HEADER_ROW: u32 = 2;
+const FIRST_ROW: u32 = HEADER_ROW + 1;
+
+/// Columns of an employee sheet: title and width
+const COLUMNS: [(&str, f64); 11] = [
+    ("Datum", 11.0),
+    ("Tag", 5.0),
+    ("Beginn", 8.0),
+    ("Ende", 8.0),
+    ("Pause", 8.0),
+    ("Soll", 9.0),
+    ("Gutschrift", 10.0),
+    ("Ist", 9.0),
+    ("Differenz", 10.0),
+    ("Bemerkung", 30.0),
+    ("ArbZG", 70.0),
+];
+const COL_PAUSE: u16 = 4;
+const COL_TARGET: u16 = 5;
+const COL_CREDIT: u16 = 6;
+const COL_ACTUAL: u16 = 7;
+const COL_DIFFERENCE: u16 = 8;
+const COL_ARBZG: u16 = 10;
+
+const SUMMARY_COLUMNS: [(&str, f64); 7] = [
+    ("Personalnr.", 12.0),
+    ("Name", 28.0),
+    ("Soll", 10.0),
+    ("Gutschrift", 10.0),
+    ("Ist", 10.0),
+    ("Differenz", 10.0),
+    ("ArbZG-Hinweise", 15.0),
+];
+
+#[derive(Debug)]
+pub enum ReportError {
+    InvalidMonth(i32, u32),
+    NoEmployees,
+    /// Row of another employee (Personalnummer)
+    ForeignRow(i64),
+    Xlsx(XlsxError),
+    Io(io::Error),
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportError::InvalidMonth(year, month) => {
+                write!(f, "Ungültiger Monat: {:02}/{}", month, year)
+            }
+            ReportError::NoEmployees => write!(f, "Keine Mitarbeiter für den Bericht ausgewählt"),
+            ReportError::ForeignRow(id) => write!(
+                f,
+                "Der Export enthält Zeilen eines anderen Mitarbeiters (Personalnummer {})",
+                id
+            ),
+            ReportError::Xlsx(e) => write!(f, "Excel-Datei konnte nicht erstellt werden: {}", e),
+            ReportError::Io(e) => write!(f, "Bericht konnte nicht gespeichert werden: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+impl From<XlsxError> for ReportError {
+    fn from(e: XlsxError) -> Self {
+        ReportError::Xlsx(e)
+    }
+}
+
+impl From<io::Error> for ReportError {
+    fn from(e: io::Error) -> Self {
+        ReportError::Io(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportEmployee {
+    pub personnel_number: i64,
+    pub last_name: String,
+    pub first_name: String,
+    pub rows: Vec<DatevRow>,
+    #[serde(default)]
+    pub daily_targets: Vec<DailyTarget>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportInput {
+    pub year: i32,
+    pub month: u32,
+    pub employees: Vec<ReportEmployee>,
+    #[serde(default)]
+    pub holidays: Vec<NamedDay>,
+}
+
+/// One sheet row; a day with several entries has several lines
+#[derive(Debug, Clone, PartialEq)]
+struct Line {
+    date: NaiveDate,
+    first_of_day: bool,
+    start: Option<NaiveTime>,
+    end: Option<NaiveTime>,
+    break_minutes: Option<i64>,
+    target: Option<f64>,
+    credit: Option<f64>,
+    actual: Option<f64>,
+    remark: String,
+    arbzg: Vec<String>,
+}
+
+impl Line {
+    fn new(date: NaiveDate, first_of_day: bool) -> Self {
+        Line {
+            date,
+            first_of_day,
+            start: None,
+            end: None,
+            break_minutes: None,
+            target: None,
+            credit: None,
+            actual: None,
+            remark: String::new(),
+            arbzg: Vec::new(),
+        }
+    }
+
+    fn has_hours(&self) -> bool {
+        self.target.is_some() || self.credit.is_some() || self.actual.is_some()
+    }
+
+    fn difference(&self) -> f64 {
+        self.actual.unwrap_or(0.0) + self.credit.unwrap_or(0.0) - self.target.unwrap_or(0.0)
+    }
+}
+
+fn month_range(year: i32, month: u32) -> Result<(NaiveDate, NaiveDate), ReportError> {
+    work_schedule::month_range(year, month).ok_or(ReportError::InvalidMonth(year, month))
+}
+
+fn parse_time(value: Option<&str>) -> Option<NaiveTime> {
+    value.and_then(|v| arbzg::parse_time(v).ok())
+}
+
+fn build_lines(
+    employee: &ReportEmployee,
+    holidays: &[NamedDay],
+    (first, last): (NaiveDate, NaiveDate),
+) -> Result<Vec<Line>, ReportError> {
+    if let Some(row) = employee
+        .rows
+        .iter()
+        .find(|r| r.personnel_number != employee.personnel_number)
+    {
+        return Err(ReportError::ForeignRow(row.personnel_number));
+    }
+    let targets: HashMap<NaiveDate, f64> = employee
+        .daily_targets
+        .iter()
+        .map(|t| (t.date, t.target_hours))
+        .collect();
+
+    let mut lines = Vec::new();
+    // End of the last entry on an earlier day, for the rest period
+    let mut previous_end: Option<NaiveDateTime> = None;
+    for date in first.iter_days().take_while(|d| *d <= last) {
+        let mut entries: Vec<&DatevRow> = employee
+            .rows
+            .iter()
+            .filter(|r| !r.is_absence() && r.date == date)
+            .collect();
+        entries.sort_by_key(|e| parse_time(e.start_time.as_deref()));
+        let absence = employee
+            .rows
+            .iter()
+            .find(|r| r.is_absence() && r.covers(date));
+        let holiday = holidays.iter().find(|h| h.date == date);
+
+        // Unpaid leave removes the target instead of crediting it
+        let unpaid = absence
+            .and_then(|a| a.absence_type.as_deref())
+            .is_some_and(|label| absence_kind(label) == AbsenceKind::Unpaid);
+        let target = if unpaid {
+            0.0
+        } else {
+            entries
+                .first()
+                .map(|e| e.target_hours)
+                .or_else(|| targets.get(&date).copied())
+                .unwrap_or(0.0)
+        };
+        let mut day = Line::new(date, true);
+        if target != 0.0 || !entries.is_empty() {
+            day.target = Some(target);
+        }
+        if absence.is_some() && target != 0.0 {
+            day.credit = Some(target);
+        }
+        let remarks: Vec<&str> = holiday
+            .map(|h| h.name.as_str())
+            .into_iter()
+            .chain(absence.and_then(|a| a.absence_type.as_deref()))
+            .collect();
+        day.remark = remarks.join(", ");
+
+        let day_hours: f64 = entries.iter().map(|e| e.actual_hours).sum();
+        if let Some(v) =
+            arbzg::check_max_daily_hours(&date.format("%Y-%m-%d").to_string(), day_hours, &[], None)
+        {
+            if v.severity >= Severity::Warning {
+                day.arbzg.push(v.message);
+            }
+        }
+
+        if entries.is_empty() {
+            lines.push(day);
+            continue;
+        }
+        let mut day = Some(day);
+        let mut day_end = None;
+        for entry in entries {
+            let mut line = day.take().unwrap_or_else(|| Line::new(date, false));
+            line.start = parse_time(entry.start_time.as_deref());
+            line.end = parse_time(entry.end_time.as_deref());
+            line.break_minutes = Some(entry.break_minutes);
+            line.actual = Some(entry.actual_hours);
+            if let Some(note) = entry.note.as_deref().filter(|n| !n.is_empty()) {
+                if !line.remark.is_empty() {
+                    line.remark.push_str(", ");
+                }
+                line.remark.push_str(note);
+            }
+            if let Some(v) = arbzg::check_break_time(entry.actual_hours, entry.break_minutes) {
+                line.arbzg.push(v.message);
+            }
+            if let (Some(start), Some(end)) = (line.start, line.end) {
+                let start = date.and_time(start);
+                if let Some(v) = previous_end.and_then(|e| arbzg::check_rest_between(e, start)) {
+                    line.arbzg.push(v.message);
+                }
+                // Overnight shifts end on the next day
+                let end = if line.end < line.start {
+                    date.and_time(end) + Duration::days(1)
+                } else {
+                    date.and_time(end)
+                };
+                day_end = day_end.max(Some(end));
+            }
+            lines.push(line);
+        }
+        if day_end.is_some() {
+            previous_end = day_end;
+        }
+    }
+    Ok(lines)
+}
+
+/// Sheet name for an employee: Excel allows 31 characters and no `[]:*?/\`
+fn sheet_name(employee: &ReportEmployee, taken: &[String]) -> String {
+    let base: String = format!("{} {}", employee.personnel_number, employee.last_name)
+        .chars()
+        .filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\'))
+        .take(SHEET_NAME_MAX_CHARS)
+        .collect();
+    let base = base.trim().trim_matches('\'').to_string();
+    let mut name = base.clone();
+    let mut n = 2;
+    while taken
+        .iter()
+        .any(|t| t.to_lowercase() == name.to_lowercase())
+    {
+        let suffix = format!(" ({})", n);
+        let keep = SHEET_NAME_MAX_CHARS - suffix.chars().count();
+        name = base.chars().take(keep).collect::<String>() + &suffix;
+        n += 1;
+    }
+    name
+}
+
+/// Excel column letter (0 → `A`); the sheets have less than 27 columns
+fn column(col: u16) -> char {
+    (b'A' + col as u8) as char
+}
+
+/// `=SUM(F4:F34)` with the computed result
+fn sum_formula(col: u16, first_row: u32, last_row: u32, result: f64) -> Formula {
+    Formula::new(format!(
+        "=SUM({c}{}:{c}{})",
+        first_row + 1,
+        last_row + 1,
+        c = column(col)
+    ))
+    .set_result(result.to_string())
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+struct Formats {
+    title: Format,
+    header: Format,
+    date: Format,
+    time: Format,
+    duration: Format,
+    hours: Format,
+    text: Format,
+    total_label: Format,
+    total_duration: Format,
+    total_hours: Format,
+    total_count: Format,
+    negative: Format,
+    violation: Format,
+}
+
+impl Formats {
+    fn new() -> Self {
+        let total = Format::new().set_bold().set_border_top(FormatBorder::Thin);
+        Formats {
+            title: Format::new().set_bold().set_font_size(14),
+            header: Format::new()
+                .set_bold()
+                .set_background_color(Color::RGB(0xD9E1F2))
+                .set_border_top(FormatBorder::Thin),
+            date: Format::new().set_num_format("dd.mm.yyyy"),
+            time: Format::new().set_num_format("hh:mm"),
+            duration: Format::new().set_num_format("[h]:mm"),
+            hours: Format::new().set_num_format("#,##0.00"),
+            text: Format::new(),
+            total_label: total.clone(),
+            total_duration: total.clone().set_num_format("[h]:mm"),
+            total_hours: total.clone().set_num_format("#,##0.00"),
+            total_count: total.set_num_format("0"),
+            negative: Format::new()
+                .set_font_color(Color::RGB(0x9C0006))
+                .set_background_color(Color::RGB(0xFFC7CE)),
+            violation: Format::new().set_background_color(Color::RGB(0xFFEB9C)),
+        }
+    }
+}
+
+fn excel_date(date: NaiveDate) -> Result<ExcelDateTime, XlsxError> {
+    ExcelDateTime::from_ymd(date.year() as u16, date.month() as u8, date.day() as u8)
+}
+
+fn excel_time(time: NaiveTime) -> Result<ExcelDateTime, XlsxError> {
+    ExcelDateTime::from_hms(time.hour() as u16, time.minute() as u8, 0)
+}
+
+/// Totals of an employee sheet, referenced by the summary
+struct SheetTotals {
+    row: u32,
+    target: f64,
+    credit: f64,
+    actual: f64,
+    violations: usize,
+}
+
+fn write_employee_sheet(
+    sheet: &mut Worksheet,
+    employee: &ReportEmployee,
+    lines: &[Line],
+    input: &ReportInput,
+    formats: &Formats,
+) -> Result<SheetTotals, XlsxError> {
+    sheet.write_string_with_format(
+        0,
+        0,
+        format!(
+            "Arbeitszeitbericht {} {} – {}, {} (Personalnr. {})",
+            month_name(input.month),
+            input.year,
+            employee.last_name,
+            employee.first_name,
+            employee.personnel_number
+        ),
+        &formats.title,
+    )?;
+    for (col, (title, width)) in COLUMNS.iter().enumerate() {
+        sheet.write_string_with_format(HEADER_ROW, col as u16, *title, &formats.header)?;
+        sheet.set_column_width(col as u16, *width)?;
+    }
+    sheet.set_freeze_panes(FIRST_ROW, 0)?;
+
+    let mut row = FIRST_ROW;
+    let mut pause_minutes = 0;
+    for line in lines {
+        if line.first_of_day {
+            sheet.write_datetime_with_format(row, 0, excel_date(line.date)?, &formats.date)?;
+            sheet.write_string(row, 1, weekday_short(line.date.weekday()))?;
+        }
+        if let Some(start) = line.start {
+            sheet.write_datetime_with_format(row, 2, excel_time(start)?, &formats.time)?;
+        }
+        if let Some(end) = line.end {
+            sheet.write_datetime_with_format(row, 3, excel_time(end)?, &formats.time)?;
+        }
+        if let Some(minutes) = line.break_minutes.filter(|m| *m > 0) {
+            pause_minutes += minutes;
+            sheet.write_number_with_format(
+                row,
+                COL_PAUSE,
+                minutes as f64 / 1440.0,
+                &formats.duration,
+            )?;
+        }
+        for (col, value) in [
+            (COL_TARGET, line.target),
+            (COL_CREDIT, line.credit),
+            (COL_ACTUAL, line.actual),
+        ] {
+            if let Some(value) = value {
+                sheet.write_number_with_format(row, col, value, &formats.hours)?;
+            }
+        }
+        if line.has_hours() {
+            let formula = Formula::new(format!(
+                "={a}{r}+{c}{r}-{t}{r}",
+                a = column(COL_ACTUAL),
+                c = column(COL_CREDIT),
+                t = column(COL_TARGET),
+                r = row + 1
+            ))
+            .set_result(round2(line.difference()).to_string());
+            sheet.write_formula_with_format(row, COL_DIFFERENCE, formula, &formats.hours)?;
+        }
+        if !line.remark.is_empty() {
+            sheet.write_string_with_format(row, 9, &line.remark, &formats.text)?;
+        }
+        if !line.arbzg.is_empty() {
+            sheet.write_string_with_format(row, COL_ARBZG, line.arbzg.join(" "), &formats.text)?;
+        }
+        row += 1;
+    }
+
+    let last = row.saturating_sub(1).max(FIRST_ROW);
+    let sum = |f: fn(&Line) -> Option<f64>| round2(lines.iter().filter_map(f).sum());
+    let totals = SheetTotals {
+        row,
+        target: sum(|l| l.target),
+        credit: sum(|l| l.credit),
+        actual: sum(|l| l.actual),
+        violations: lines.iter().filter(|l| !l.arbzg.is_empty()).count(),
+    };
+    sheet.write_string_with_format(row, 0, "Summe", &formats.total_label)?;
+    for col in 1..COL_PAUSE {
+        sheet.write_blank(row, col, &formats.total_label)?;
+    }
+    sheet.write_formula_with_format(
+        row,
+        COL_PAUSE,
+        sum_formula(COL_PAUSE, FIRST_ROW, last, pause_minutes as f64 / 1440.0),
+        &formats.total_duration,
+    )?;
+    for (col, value) in [
+        (COL_TARGET, totals.target),
+        (COL_CREDIT, totals.credit),
+        (COL_ACTUAL, totals.actual),
+        (
+            COL_DIFFERENCE,
+            round2(totals.actual + totals.credit - totals.target),
+        ),
+    ] {
+        sheet.write_formula_with_format(
+            row,
+            col,
+            sum_formula(col, FIRST_ROW, last, value),
+            &formats.total_hours,
+        )?;
+    }
+    sheet.write_blank(row, 9, &formats.total_label)?;
+    sheet.write_formula_with_format(
+        row,
+        COL_ARBZG,
+        Formula::new(format!(
+            "=COUNTA({c}{}:{c}{})",
+            FIRST_ROW + 1,
+            last + 1,
+            c = column(COL_ARBZG)
+        ))
+        .set_result(totals.violations.to_string()),
+        &formats.total_count,
+    )?;
+
+    sheet.add_conditional_format(
+        FIRST_ROW,
+        COL_DIFFERENCE,
+        row,
+        COL_DIFFERENCE,
+        &ConditionalFormatCell::new()
+            .set_rule(ConditionalFormatCellRule::LessThan(0))
+            .set_format(&formats.negative),
+    )?;
+    sheet.add_conditional_format(
+        FIRST_ROW,
+        0,
+        last,
+        COL_ARBZG,
+        &ConditionalFormatFormula::new()
+            .set_rule(format!("=${}{}<>\"\"", column(COL_ARBZG), FIRST_ROW + 1).as_str())
+            .set_format(&formats.violation),
+    )?;
+    Ok(totals)
+}
+
+fn write_summary_sheet(
+    sheet: &mut Worksheet,
+    input: &ReportInput,
+    sheets: &[(String, SheetTotals)],
+    formats: &Formats,
+) -> Result<(), XlsxError> {
+    sheet.write_string_with_format(
+        0,
+        0,
+        format!(
+            "Arbeitszeitbericht {} {}",
+            month_name(input.month),
+            input.year
+        ),
+        &formats.title,
+    )?;
+    for (col, (title, width)) in SUMMARY_COLUMNS.iter().enumerate() {
+        sheet.write_string_with_format(HEADER_ROW, col as u16, *title, &formats.header)?;
+        sheet.set_column_width(col as u16, *width)?;
+    }
+    sheet.set_freeze_panes(FIRST_ROW, 0)?;
+
+    let mut row = FIRST_ROW;
+    let mut sums = [0.0; 4];
+    let mut violations = 0;
+    for (employee, (name, totals)) in input.employees.iter().zip(sheets) {
+        let reference = |col: u16, result: String| {
+            Formula::new(format!(
+                "='{}'!{}{}",
+                name.replace('\'', "''"),
+                column(col),
+                totals.row + 1
+            ))
+            .set_result(result)
+        };
+        sheet.write_number(row, 0, employee.personnel_number as f64)?;
+        sheet.write_string(
+            row,
+            1,
+            format!("{}, {}", employee.last_name, employee.first_name),
+        )?;
+        let difference = round2(totals.actual + totals.credit - totals.target);
+        let values = [totals.target, totals.credit, totals.actual, difference];
+        for (i, (col, value)) in [COL_TARGET, COL_CREDIT, COL_ACTUAL, COL_DIFFERENCE]
+            .into_iter()
+            .zip(values)
+            .enumerate()
+        {
+            sums[i] += value;
+            sheet.write_formula_with_format(
+                row,
+                2 + i as u16,
+                reference(col, value.to_string()),
+                &formats.hours,
+            )?;
+        }
+        violations += totals.violations;
+        sheet.write_formula(row, 6, reference(COL_ARBZG, totals.violations.to_string()))?;
+        row += 1;
+    }
+
+    let last = row - 1;
+    sheet.write_string_with_format(row, 0, "Summe", &formats.total_label)?;
+    sheet.write_blank(row, 1, &formats.total_label)?;
+    for (i, value) in sums.into_iter().enumerate() {
+        let col = 2 + i as u16;
+        sheet.write_formula_with_format(
+            row,
+            col,
+            sum_formula(col, FIRST_ROW, last, round2(value)),
+            &formats.total_hours,
+        )?;
+    }
+    sheet.write_formula_with_format(
+        row,
+        6,
+        sum_formula(6, FIRST_ROW, last, violations as f64),
+        &formats.total_count,
+    )?;
+
+    sheet.add_conditional_format(
+        FIRST_ROW,
+        5,
+        row,
+        5,
+        &ConditionalFormatCell::new()
+            .set_rule(ConditionalFormatCellRule::LessThan(0))
+            .set_format(&formats.negative),
+    )?;
+    sheet.add_conditional_format(
+        FIRST_ROW,
+        6,
+        row,
+        6,
+        &ConditionalFormatCell::new()
+            .set_rule(ConditionalFormatCellRule::GreaterThan(0))
+            .set_format(&formats.violation),
+    )?;
+    Ok(())
+}
+
+/// Builds the workbook: "Übersicht" first, then one sheet per employee
+pub fn render(input: &ReportInput) -> Result<Vec<u8>, ReportError> {
+    let range = month_range(input.year, input.month)?;
+    if input.employees.is_empty() {
+        return Err(ReportError::NoEmployees);
+    }
+    let formats = Formats::new();
+    let mut workbook = Workbook::new();
+    workbook.add_worksheet().set_name(SUMMARY_SHEET)?;
+
+    let mut names = vec![SUMMARY_SHEET.to_string()];
+    let mut sheets = Vec::new();
+    for employee in &input.employees {
+        let lines = build_lines(employee, &input.holidays, range)?;
+        let name = sheet_name(employee, &names);
+        names.push(name.clone());
+        let sheet = workbook.add_worksheet().set_name(&name)?;
+        let totals = write_employee_sheet(sheet, employee, &lines, input, &formats)?;
+        sheets.push((name, totals));
+    }
+    write_summary_sheet(workbook.worksheet_from_index(0)?, input, &sheets, &formats)?;
+    Ok(workbook.save_to_buffer()?)
+}
+
+/// Writes the workbook to the location chosen with the save dialog. Returns
+/// the saved path, `None` when the dialog was cancelled.
+#[tauri::command]
+pub async fn export_report_xlsx(
+    app: AppHandle,
+    input: ReportInput,
+) -> Result<Option<String>, String> {
+    let xlsx = render(&input).map_err(|e| e.to_string())?;
+    let file_name = format!("Arbeitszeitbericht_{}-{:02}.xlsx", input.year, input.month);
+    let chosen = app
+        .dialog()
+        .file()
+        .set_file_name(file_name)
+        .add_filter("Excel", &["xlsx"])
+        .blocking_save_file();
+    let path = match chosen {
+        Some(chosen) => chosen.into_path().map_err(|e| e.to_string())?,
+        None => return Ok(None),
+    };
+    write_atomic(&path, &xlsx)
+        .map_err(ReportError::from)
+        .map_err(|e| e.to_string())?;
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn entry(day: &str, start: &str, end: &str, break_minutes: i64, hours: f64) -> DatevRow {
+        DatevRow {
+            personnel_number: 48,
+            date: date(day),
+            end_date: None,
+            target_hours: 8.0,
+            actual_hours: hours,
+            break_minutes,
+            absence_type: None,
+            start_time: Some(start.into()),
+            end_time: Some(end.into()),
+            note: None,
+        }
+    }
+
+    fn employee(rows: Vec<DatevRow>) -> ReportEmployee {
+        ReportEmployee {
+            personnel_number: 48,
+            last_name: "Müller".into(),
+            first_name: "Anna".into(),
+            rows,
+            daily_targets: Vec::new(),
+        }
+    }
+
+    fn march() -> (NaiveDate, NaiveDate) {
+        month_range(2026, 3).unwrap()
+    }
+
+    #[test]
+    fn lines_carry_arbzg_hints_and_absence_credit() {
+        let rows = vec![
+            // 11 h without break, then only 8 h rest until Tuesday
+            entry("2026-03-02", "08:00", "19:00", 0, 11.0),
+            entry("2026-03-03", "03:00", "11:30", 30, 8.0),
+            // Split shift: no rest period check within the day
+            entry("2026-03-04", "13:00", "17:00", 0, 4.0),
+            entry("2026-03-04", "07:00", "11:00", 0, 4.0),
+            DatevRow {
+                end_date: Some(date("2026-03-06")),
+                absence_type: Some("Urlaub".into()),
+                target_hours: 0.0,
+                ..entry("2026-03-05", "", "", 0, 0.0)
+            },
+            DatevRow {
+                absence_type: Some("Unbezahlt".into()),
+                target_hours: 0.0,
+                ..entry("2026-03-09", "", "", 0, 0.0)
+            },
+        ];
+        let mut employee = employee(rows);
+        employee.daily_targets = ["2026-03-05", "2026-03-06", "2026-03-09"]
+            .iter()
+            .map(|d| DailyTarget {
+                date: date(d),
+                target_hours: 8.0,
+                is_holiday: false,
+            })
+            .collect();
+        let lines = build_lines(&employee, &[], march()).unwrap();
+        assert_eq!(lines.len(), 32);
+
+        let monday = &lines[1];
+        assert_eq!(monday.arbzg.len(), 2, "{:?}", monday.arbzg);
+        assert_eq!(monday.difference(), 3.0);
+        assert_eq!(lines[2].arbzg.len(), 1, "{:?}", lines[2].arbzg);
+        assert!(lines[2].arbzg[0].contains("Ruhezeit"));
+
+        // Wednesday: sorted by start, target only on the first line
+        let (first, second) = (&lines[3], &lines[4]);
+        assert_eq!(first.start, NaiveTime::from_hms_opt(7, 0, 0));
+        assert!(first.first_of_day && !second.first_of_day);
+        assert_eq!((first.target, second.target), (Some(8.0), None));
+        assert!(first.arbzg.is_empty() && second.arbzg.is_empty());
+
+        let vacation = &lines[5];
+        assert_eq!(vacation.remark, "Urlaub");
+        assert_eq!(vacation.difference(), 0.0);
+        // Weekend without target: no hours at all
+        assert!(!lines[7].has_hours());
+        // Unpaid leave: neither target nor credit
+        let unpaid = &lines[9];
+        assert_eq!(unpaid.remark, "Unbezahlt");
+        assert!(!unpaid.has_hours());
+    }
+
+    #[test]
+    fn sheet_names_are_valid_and_unique() {
+        let mut long = employee(Vec::new());
+        long.last_name = "Schmidt-Leutheusser [Vertretung]/Nord".into();
+        let name = sheet_name(&long, &[]);
+        assert_eq!(name, "48 Schmidt-Leutheusser Vertretu");
+        assert_eq!(name.chars().count(), SHEET_NAME_MAX_CHARS);
+        let again = sheet_name(&long, std::slice::from_ref(&name));
+        assert_eq!(again, "48 Schmidt-Leutheusser Vert (2)");
+    }
+
+    #[test]
+    fn workbook_has_summary_and_employee_sheets() {
+        let mut input = ReportInput {
+            year: 2026,
+            month: 3,
+            employees: vec![employee(vec![entry(
+                "2026-03-02",
+                "08:00",
+                "16:30",
+                30,
+                8.0,
+            )])],
+            holidays: Vec::new(),
+        };
+        let xlsx = render(&input).unwrap();
+        assert_eq!(&xlsx[..2], b"PK");
+        assert!(
+            sum_formula(COL_TARGET, FIRST_ROW, 33, 8.0)
+                == Formula::new("=SUM(F4:F34)").set_result("8")
+        );
+
+        input.employees[0].rows[0].personnel_number = 7;
+        assert!(matches!(render(&input), Err(ReportError::ForeignRow(7))));
+        input.employees.clear();
+        assert!(matches!(render(&input), Err(ReportError::NoEmployees)));
+        input.month = 13;
+        assert!(matches!(
+            render(&input),
+            Err(ReportError::InvalidMonth(2026, 13))
+        ));
+    }
+}
@@ -26,10 +26,15 @@ import { useGlobalKeyboardShortcuts, useWebSocket } from './hooks';
 import { PrivacyPolicyModal } from './components/privacy/PrivacyPolicyModal';
 import { useDesktopNotifications } from './hooks/useDesktopNotifications';
 import { useAutoUpdater } from './hooks/useAutoUpdater';
+import { useLaunchView } from './hooks/useLaunchView';
+import { useOfflineSync } from './hooks/useOfflineSync';
 import { SplashScreen } from './components/SplashScreen';
 import { UpdateNotification } from './components/ui/UpdateNotification';
 import { OfflineBanner } from './components/ui/OfflineBanner';
 import { ConnectionStatusIndicator } from './components/ui/ConnectionStatusIndicator';
+import { SyncConflictIndicator } from './components/ui/SyncConflictIndicator';
+import { TimerDraftModal } from './components/timeEntries/TimerDraftModal';
+import { TimerEndModal } from './components/timeEntries/TimerEndModal';
 import maxflowLogo from './assets/maxflow-logo.png';
 
 export default function App() {
@@ -60,6 +65,12 @@ export default function App() {
   // WebSocket Real-Time Updates (auto-invalidates TanStack Query caches)
   useWebSocket({ userId: user?.id, enabled: isAuthenticated });
 
+  // Views requested by a second launch or a deep link (desktop only)
+  useLaunchView(isAuthenticated);
+
+  // Local writes and outbox replays refresh the queries (desktop only)
+  useOfflineSync(isAuthenticated);
+
   // Auto-Updater (checks for updates on app start)
   const updater = useAutoUpdater();
 
@@ -152,6 +163,7 @@ export default function App() {
 
           {/* Actions */}
           <div className="flex items-center gap-2">
+            <SyncConflictIndicator />
             <ConnectionStatusIndicator />
             <ThemeToggle />
             <NotificationBell />
@@ -188,6 +200,12 @@ export default function App() {
 
       {/* Privacy Policy Modal (DSGVO) */}
       <PrivacyPolicyModal isOpen={showPrivacyModal} onAccept={handlePrivacyAccept} />
+
+      {/* Drafts from "Gehen" in the tray (desktop only) */}
+      <TimerDraftModal />
+
+      {/* End of a session found on startup (desktop only) */}
+      <TimerEndModal />
     </>
   );
 }
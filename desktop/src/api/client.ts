@@ -1,64 +1,84 @@
 // API Client for communicating with backend server
+import { invoke } from '@tauri-apps/api/core';
 import { universalFetch } from '../lib/tauriHttpClient';
 import { debugLog } from '../components/DebugPanel';
 import { toast } from 'sonner';
+import { isTauri } from '../utils/tauri';
+import { offlineRead, offlineWrite, type OfflineCommand } from './offline';
 
-// DEVELOPMENT: Use localhost
-// PRODUCTION: Use your Oracle Cloud server IP (change after deployment!)
+// Browser (dev mode): VITE_API_URL. Desktop: the active server profile,
+// loaded by initServerProfile() before the app renders.
 const rawApiUrl = import.meta.env.VITE_API_URL || 'http://localhost:3000/api';
 
 // Ensure API_BASE_URL always ends with /api
-export const API_BASE_URL = rawApiUrl.endsWith('/api')
+export let API_BASE_URL = rawApiUrl.endsWith('/api')
   ? rawApiUrl
   : `${rawApiUrl}/api`;
 
 // Base URL without /api suffix (for direct exports endpoints that include /api in path)
-export const SERVER_BASE_URL = API_BASE_URL.replace(/\/api$/, '');
-
-console.log('✅ FINALE Werte:');
-console.log('🌐 API Base URL:', API_BASE_URL);
-console.log('🌐 Server Base URL:', SERVER_BASE_URL);
-
-// ========================================
-// 🔥 LAYER 2: RUNTIME ENVIRONMENT VERIFICATION 🔥
-// ========================================
-console.log('');
-console.log('🔥 LAYER 2 DEBUG - Runtime Environment:');
-console.log('🔍 ALLE import.meta.env Keys:', Object.keys(import.meta.env));
-console.log('🔍 Window Location:', typeof window !== 'undefined' ? window.location.href : 'N/A (SSR)');
-console.log('🔍 Fetch wird gehen zu:', API_BASE_URL);
-
-// Test: Kann der Green Server erreicht werden?
-console.log('');
-console.log('🧪 Testing Green Server reachability...');
-fetch('http://129.159.8.19:3001/api/health', {
-  method: 'GET',
-  mode: 'cors',
-  cache: 'no-cache'
-})
-  .then(r => r.json())
-  .then(d => {
-    console.log('✅ Green Server REACHABLE:', d);
-    console.log('   → Server Status:', d.status);
-    console.log('   → Server Message:', d.message);
-  })
-  .catch(e => {
-    console.error('❌ Green Server UNREACHABLE:', e.message);
-    console.error('   → Error Type:', e.name);
-  });
-
-console.log('==========================================');
+export let SERVER_BASE_URL = API_BASE_URL.replace(/\/api$/, '');
+
+// Backend WebSocket is on /ws, not /api/ws
+export let WS_URL = `${SERVER_BASE_URL.replace(/^http/, 'ws')}/ws`;
+
+/** Server profile as stored by the Rust side (server_profile.rs) */
+export interface ServerProfile {
+  id: string;
+  name: string;
+  apiBaseUrl: string;
+  wsUrl: string;
+  pinnedCertificate?: string | null;
+}
+
+export interface ServerProfileSettings {
+  active: string;
+  profiles: ServerProfile[];
+}
+
+/**
+ * Desktop: point the client at the active server profile.
+ * Must run before the first request.
+ */
+export async function initServerProfile(): Promise<void> {
+  if (!isTauri()) return;
+
+  try {
+    const settings = await invoke<ServerProfileSettings>('server_profiles');
+    const profile = settings.profiles.find((p) => p.id === settings.active) ?? settings.profiles[0];
+    API_BASE_URL = profile.apiBaseUrl;
+    SERVER_BASE_URL = API_BASE_URL.replace(/\/api$/, '');
+    WS_URL = profile.wsUrl;
+    apiClient.setBaseUrl(API_BASE_URL);
+    console.log(`🌐 Server profile: ${profile.name} (${API_BASE_URL})`);
+  } catch (error) {
+    console.error('❌ Failed to load server profile:', error);
+  }
+}
 
 export interface ApiResponse<T> {
   success: boolean;
   data?: T;
   error?: string;
   message?: string;
-  token?: string; // JWT token (returned on login)
 }
 
-// JWT Token Storage
-const TOKEN_KEY = 'timetracking_jwt_token';
+/** Error returned by the Rust `api_request` command */
+export interface NativeApiError {
+  kind:
+    | 'offline'
+    | 'timeout'
+    | 'unauthorized'
+    | 'forbidden'
+    | 'notFound'
+    | 'conflict'
+    | 'validation'
+    | 'rateLimited'
+    | 'server'
+    | 'unexpected';
+  status: number | null;
+  message: string;
+  requestId: string;
+}
 
 class ApiClient {
   private baseUrl: string;
@@ -67,25 +87,96 @@ class ApiClient {
     this.baseUrl = baseUrl;
   }
 
-  /**
-   * Store JWT token in localStorage
-   */
-  setToken(token: string): void {
-    localStorage.setItem(TOKEN_KEY, token);
+  setBaseUrl(baseUrl: string) {
+    this.baseUrl = baseUrl;
   }
 
   /**
-   * Get JWT token from localStorage
+   * Desktop: requests go through the Rust API client, which attaches the
+   * session token, retries GETs and maps errors (see api_client.rs)
    */
-  getToken(): string | null {
-    return localStorage.getItem(TOKEN_KEY);
+  private async requestNative<T>(
+    endpoint: string,
+    url: string,
+    method: string,
+    body?: BodyInit | null
+  ): Promise<ApiResponse<T>> {
+    const data = typeof body === 'string' ? JSON.parse(body) : undefined;
+
+    const write = offlineWrite(method, endpoint, data);
+    if (write) {
+      return this.invokeOffline<T>(write, method, url);
+    }
+
+    try {
+      return await invoke<ApiResponse<T>>('api_request', {
+        request: { url, method, body: data },
+      });
+    } catch (error) {
+      const apiError = error as NativeApiError;
+      debugLog({
+        type: 'error',
+        method,
+        url,
+        status: apiError.status ?? undefined,
+        data: apiError,
+        message: `❌ API Error: ${apiError.message} (Request-ID ${apiError.requestId})`,
+      });
+
+      // Offline: answer reads of the user's own records from the cache
+      const unreachable = ['offline', 'timeout'].includes(apiError.kind);
+      const read = method === 'GET' && unreachable ? offlineRead(endpoint) : null;
+      if (read) {
+        return this.invokeOffline<T>(read, method, url);
+      }
+
+      // SUPPRESS: 401 (auth:expired event), offline/timeout (OfflineBanner)
+      // SUPPRESS: 403 on /users endpoint (employees calling admin-only endpoint is expected)
+      const silent =
+        ['unauthorized', 'offline', 'timeout'].includes(apiError.kind) ||
+        (apiError.kind === 'forbidden' && endpoint === '/users');
+      if (!silent) {
+        toast.error(apiError.message, {
+          description: 'Die Anfrage konnte nicht verarbeitet werden.',
+        });
+      }
+
+      return {
+        success: false,
+        error: apiError.message,
+      };
+    }
   }
 
-  /**
-   * Remove JWT token from localStorage (logout)
-   */
-  clearToken(): void {
-    localStorage.removeItem(TOKEN_KEY);
+  /** Runs a command of the offline store (see api/offline.ts) */
+  private async invokeOffline<T>(
+    offline: OfflineCommand,
+    method: string,
+    url: string
+  ): Promise<ApiResponse<T>> {
+    try {
+      const result = await invoke<unknown>(offline.command, offline.args);
+      return {
+        success: true,
+        data: (offline.wrap ? offline.wrap(result) : result) as T,
+      };
+    } catch (error) {
+      const message = String(error);
+      debugLog({
+        type: 'error',
+        method,
+        url,
+        data: { command: offline.command },
+        message: `❌ Offline store: ${message}`,
+      });
+      toast.error(message, {
+        description:
+          method === 'GET'
+            ? 'Die lokalen Daten konnten nicht geladen werden.'
+            : 'Die Änderung konnte nicht gespeichert werden.',
+      });
+      return { success: false, error: message };
+    }
   }
 
   private async request<T>(
@@ -95,6 +186,10 @@ class ApiClient {
     const url = `${this.baseUrl}${endpoint}`;
     const method = options?.method || 'GET';
 
+    if (isTauri()) {
+      return this.requestNative<T>(endpoint, url, method, options?.body);
+    }
+
     try {
       debugLog({
         type: 'request',
@@ -114,18 +209,12 @@ class ApiClient {
       console.log('🎯 Target Origin:', new URL(url).origin);
       console.log('🔀 Cross-Origin?', typeof window !== 'undefined' ? window.location.origin !== new URL(url).origin : false);
 
-      // Get JWT token and add Authorization header
-      const token = this.getToken();
       const headers: Record<string, string> = {
         'Content-Type': 'application/json',
         ...(options?.headers as Record<string, string>),
       };
 
-      if (token) {
-        headers['Authorization'] = `Bearer ${token}`;
-      }
-
-      // CRITICAL: Use universalFetch (Tauri HTTP in Tauri, browser fetch in browser)
+      // Browser (dev mode): session cookies authenticate the request
       // credentials: 'include' is kept for backwards compatibility with session-based auth
       const response = await universalFetch(url, {
         ...options,
@@ -0,0 +1,232 @@
+/**
+ * Desktop exports rendered natively (timesheet.rs, datev.rs, xlsx_report.rs)
+ *
+ * All of them start from the server's DATEV CSV (`exportDATEV`), which is
+ * parsed here into the `DatevRow`s the Rust side expects. The commands ask
+ * for the target file with the save dialog and resolve to `null` when it
+ * was cancelled.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { exportDATEV } from './exports';
+import { getDateRangeFromFilters } from '../utils/dateRangeUtils';
+import type { User } from '../types';
+
+/** `DatevRow` of timesheet.rs */
+export interface DatevRow {
+  personnelNumber: number;
+  date: string;
+  endDate: string | null;
+  targetHours: number;
+  actualHours: number;
+  breakMinutes: number;
+  absenceType: string | null;
+  startTime: string | null;
+  endTime: string | null;
+  note: string | null;
+}
+
+/** `DailyTarget` of work_schedule.rs */
+export interface DailyTarget {
+  date: string;
+  targetHours: number;
+  isHoliday: boolean;
+}
+
+/** Rows of one employee of the DATEV CSV */
+export interface DatevEmployee {
+  personnelNumber: number;
+  lastName: string;
+  firstName: string;
+  rows: DatevRow[];
+  /** Target of every day of the month, see `withDailyTargets` */
+  dailyTargets: DailyTarget[];
+}
+
+export interface NamedDay {
+  date: string;
+  name: string;
+}
+
+/** "31.01.2026" → "2026-01-31" */
+function isoDate(value: string): string {
+  const [day, month, year] = value.trim().split('.');
+  return `${year}-${month}-${day}`;
+}
+
+/** "7,50" → 7.5 */
+function germanNumber(value: string): number {
+  return parseFloat(value.replace(',', '.')) || 0;
+}
+
+/** Employees of the server's DATEV CSV, in file order */
+export function parseDatevCsv(csv: string): DatevEmployee[] {
+  const employees = new Map<number, DatevEmployee>();
+  const lines = csv.replace(/^\uFEFF/, '').split(/\r?\n/).slice(1);
+
+  for (const line of lines) {
+    if (!line.trim()) continue;
+    const cells = line.split(';');
+    const personnelNumber = Number(cells[0]);
+    if (!personnelNumber || cells.length < 12) continue;
+
+    const [from, to] = cells[3].split(' - ');
+    const row: DatevRow = {
+      personnelNumber,
+      date: isoDate(from),
+      endDate: to ? isoDate(to) : null,
+      targetHours: germanNumber(cells[4]),
+      actualHours: germanNumber(cells[5]),
+      breakMinutes: parseInt(cells[7]) || 0,
+      absenceType: cells[8] || null,
+      startTime: cells[9] || null,
+      endTime: cells[10] || null,
+      // Notes are not quoted and may contain the separator
+      note: cells.slice(11).join(';') || null,
+    };
+
+    const employee = employees.get(personnelNumber) ?? {
+      personnelNumber,
+      lastName: cells[1],
+      firstName: cells[2],
+      rows: [],
+      dailyTargets: [],
+    };
+    employee.rows.push(row);
+    employees.set(personnelNumber, employee);
+  }
+
+  return [...employees.values()];
+}
+
+/** DATEV rows of all employees for one month */
+export async function fetchDatevMonth(year: number, month: number): Promise<DatevEmployee[]> {
+  const { startDate, endDate } = getDateRangeFromFilters(year, month);
+  const blob = await exportDATEV(startDate, endDate);
+  return parseDatevCsv(await blob.text());
+}
+
+/**
+ * Daily targets of the month from the user's contract hours
+ * (`resolve_daily_targets`); 0 before the hire date and after the end date
+ */
+export async function resolveMonthTargets(
+  user: Pick<User, 'weeklyHours' | 'workSchedule' | 'hireDate' | 'endDate'>,
+  year: number,
+  month: number,
+  holidays: NamedDay[]
+): Promise<DailyTarget[]> {
+  const { startDate, endDate } = getDateRangeFromFilters(year, month);
+  const targets = await invoke<DailyTarget[]>('resolve_daily_targets', {
+    versions: [
+      { validFrom: startDate, weeklyHours: user.weeklyHours, workSchedule: user.workSchedule ?? null },
+    ],
+    from: startDate,
+    to: endDate,
+    holidays: holidays.map((h) => h.date),
+  });
+  return targets.map((t) =>
+    t.date < user.hireDate || (user.endDate && t.date > user.endDate) ? { ...t, targetHours: 0 } : t
+  );
+}
+
+/** Fills `dailyTargets` of the employees whose user is known (Personalnummer = user id) */
+export function withDailyTargets(
+  employees: DatevEmployee[],
+  users: User[],
+  year: number,
+  month: number,
+  holidays: NamedDay[]
+): Promise<DatevEmployee[]> {
+  return Promise.all(
+    employees.map(async (employee) => {
+      const user = users.find((u) => u.id === employee.personnelNumber);
+      return user
+        ? { ...employee, dailyTargets: await resolveMonthTargets(user, year, month, holidays) }
+        : employee;
+    })
+  );
+}
+
+/** Monthly timesheet of one employee as PDF; the saved path or `null` */
+export function exportTimesheetPdf(
+  employee: DatevEmployee,
+  year: number,
+  month: number,
+  holidays: NamedDay[]
+): Promise<string | null> {
+  return invoke<string | null>('export_timesheet_pdf', {
+    input: {
+      personnelNumber: employee.personnelNumber,
+      lastName: employee.lastName,
+      firstName: employee.firstName,
+      year,
+      month,
+      rows: employee.rows,
+      holidays,
+      dailyTargets: employee.dailyTargets,
+    },
+  });
+}
+
+/** `WageTypes` of datev.rs; `null` = not exported */
+export interface DatevWageTypes {
+  workedHours: number | null;
+  overtime: number | null;
+  vacation: number | null;
+  sick: number | null;
+  unpaid: number | null;
+  overtimeComp: number | null;
+}
+
+/** `DatevSettings` of datev.rs (Settings → DATEV) */
+export interface DatevSettings {
+  consultantNumber: number;
+  clientNumber: number;
+  wageTypes: DatevWageTypes;
+}
+
+export interface LodasExport {
+  bookings: number;
+  warnings: string[];
+}
+
+/** `null` until Berater- and Mandantennummer are set up */
+export function datevSettings(): Promise<DatevSettings | null> {
+  return invoke<DatevSettings | null>('datev_settings');
+}
+
+export function saveDatevSettings(settings: DatevSettings): Promise<void> {
+  return invoke('save_datev_settings', { settings });
+}
+
+/** LODAS import file of all employees; `null` when the dialog was cancelled */
+export function exportDatevLodas(
+  employees: DatevEmployee[],
+  year: number,
+  month: number
+): Promise<LodasExport | null> {
+  return invoke<LodasExport | null>('export_datev_lodas', {
+    input: {
+      year,
+      month,
+      employees: employees.map(({ personnelNumber, rows, dailyTargets }) => ({
+        personnelNumber,
+        rows,
+        dailyTargets,
+      })),
+    },
+  });
+}
+
+/** Monthly report of all employees as XLSX; the saved path or `null` */
+export function exportReportXlsx(
+  employees: DatevEmployee[],
+  year: number,
+  month: number,
+  holidays: NamedDay[]
+): Promise<string | null> {
+  return invoke<string | null>('export_report_xlsx', {
+    input: { year, month, employees, holidays },
+  });
+}
@@ -0,0 +1,87 @@
+/**
+ * Desktop: routes time entry and absence request writes through the
+ * offline store (sync.rs). The change is stored and queued locally first
+ * and replayed to the server in order, so it also works without a
+ * connection. Reads of the user's own records fall back to the cache when
+ * the server cannot be reached.
+ */
+
+export interface OfflineCommand {
+  command: string;
+  args: Record<string, unknown>;
+  /** Wraps the command result like the server's `data` */
+  wrap?: (result: unknown) => unknown;
+}
+
+const TIME_ENTRY = /^\/time-entries\/(-?\d+)$/;
+const ABSENCE = /^\/absences\/(-?\d+)$/;
+
+/** Offline command for a write, `null` if it has to go to the server */
+export function offlineWrite(method: string, endpoint: string, body: unknown): OfflineCommand | null {
+  if (endpoint === '/time-entries' && method === 'POST') {
+    return { command: 'offline_create_time_entry', args: { input: body } };
+  }
+  if (endpoint === '/absences' && method === 'POST') {
+    return { command: 'offline_create_absence_request', args: { input: body } };
+  }
+
+  const entry = endpoint.match(TIME_ENTRY);
+  if (entry) {
+    const id = Number(entry[1]);
+    if (method === 'PUT') return { command: 'offline_update_time_entry', args: { id, patch: body ?? {} } };
+    if (method === 'DELETE') return { command: 'offline_delete_time_entry', args: { id } };
+  }
+
+  const absence = endpoint.match(ABSENCE);
+  if (absence) {
+    const id = Number(absence[1]);
+    if (method === 'PUT') return { command: 'offline_update_absence_request', args: { id, patch: body ?? {} } };
+    // Admin cancellations carry a reason and need the server
+    if (method === 'DELETE' && body === undefined) {
+      return { command: 'offline_delete_absence_request', args: { id } };
+    }
+  }
+
+  return null;
+}
+
+/** Cached copy of a read, `null` if the cache cannot answer it */
+export function offlineRead(endpoint: string): OfflineCommand | null {
+  const [path, query = ''] = endpoint.split('?');
+  const params = new URLSearchParams(query);
+  const userId = Number(params.get('userId'));
+  if (!userId) return null;
+
+  if (path === '/time-entries') {
+    const from = params.get('startDate');
+    const to = params.get('endDate');
+    if (!from || !to) return null;
+    return {
+      command: 'offline_list_time_entries',
+      args: { userId, from, to },
+      wrap: (rows) => ({
+        rows,
+        pagination: { cursor: null, hasMore: false, total: (rows as unknown[]).length },
+      }),
+    };
+  }
+
+  if (path === '/absences') {
+    const status = params.get('status');
+    const type = params.get('type');
+    const year = params.get('year');
+    return {
+      command: 'offline_list_absence_requests',
+      args: { userId },
+      wrap: (rows) =>
+        (rows as Array<{ status: string; type: string; startDate: string; endDate: string }>).filter(
+          (r) =>
+            (!status || r.status === status) &&
+            (!type || r.type === type) &&
+            (!year || r.startDate.startsWith(year) || r.endDate.startsWith(year))
+        ),
+    };
+  }
+
+  return null;
+}
@@ -5,7 +5,13 @@ import { Select } from '../ui/Select';
 import { Textarea } from '../ui/Textarea';
 import { Button } from '../ui/Button';
 import { LoadingSpinner } from '../ui/LoadingSpinner';
-import { useCreateAbsenceRequest, useRemainingVacationDays, useCurrentOvertimeStats, useUsers } from '../../hooks';
+import {
+  useCreateAbsenceRequest,
+  useRemainingVacationDays,
+  useCurrentOvertimeStats,
+  useUsers,
+  useLocalVacationProjection,
+} from '../../hooks';
 import { useAuthStore } from '../../store/authStore';
 import {
   getTodayDate,
@@ -42,6 +48,26 @@ export function AbsenceRequestForm({ isOpen, onClose }: AbsenceRequestFormProps)
   // Get total yearly overtime hours
   const overtimeHours = overtimeStats?.totalYear || 0;
 
+  // Desktop: vacation balance after this request, computed from the cached
+  // requests (own requests only, including unsynced ones)
+  const currentYear = new Date().getFullYear();
+  const requestYear = Number(startDate.substring(0, 4)) || currentYear;
+  const hasValidRange = isValidDate(startDate) && isValidDate(endDate) && isValidDateRange(startDate, endDate);
+  const { data: projection } = useLocalVacationProjection(
+    type === 'vacation' && selectedUserId === user?.id ? user : null,
+    requestYear,
+    hasValidRange ? { startDate, endDate } : null,
+    vacationBalance && requestYear === currentYear
+      ? { entitlement: vacationBalance.entitlement, carryover: vacationBalance.carryover }
+      : null
+  );
+  // Offline the server balance is missing; the projection still knows it
+  const availableVacationDays = vacationBalance
+    ? vacationDays
+    : projection
+      ? projection.remaining - projection.pending
+      : vacationDays;
+
   // Error state
   const [startDateError, setStartDateError] = useState('');
   const [endDateError, setEndDateError] = useState('');
@@ -111,10 +137,13 @@ export function AbsenceRequestForm({ isOpen, onClose }: AbsenceRequestFormProps)
       }
     }
 
-    // Validate vacation balance
-    if (type === 'vacation' && requiredDays > vacationDays) {
-      setEndDateError(`Du hast nur noch ${vacationDays} Urlaubstage verfügbar`);
-      isValid = false;
+    // Validate vacation balance (projection also counts pending requests)
+    if (type === 'vacation') {
+      const exceeds = projection ? projection.projected < 0 : requiredDays > availableVacationDays;
+      if (exceeds) {
+        setEndDateError(`Du hast nur noch ${availableVacationDays} Urlaubstage verfügbar`);
+        isValid = false;
+      }
     }
 
     // Validate overtime compensation
@@ -194,7 +223,7 @@ export function AbsenceRequestForm({ isOpen, onClose }: AbsenceRequestFormProps)
       return (
         <div className="p-3 bg-purple-50 dark:bg-purple-900/20 rounded-lg">
           <p className="text-sm text-purple-900 dark:text-purple-200">
-            <strong>Verfügbar:</strong> {loadingVacation ? '...' : `${vacationDays} Urlaubstage`}
+            <strong>Verfügbar:</strong> {loadingVacation ? '...' : `${availableVacationDays} Urlaubstage`}
           </p>
           {!loadingVacation && (pending > 0 || taken > 0) && (
             <p className="text-xs text-purple-700 dark:text-purple-300 mt-1">
@@ -203,6 +232,17 @@ export function AbsenceRequestForm({ isOpen, onClose }: AbsenceRequestFormProps)
               {taken > 0 && `${taken} genehmigt`}
             </p>
           )}
+          {projection && hasValidRange && (
+            <p
+              className={`text-xs mt-1 ${
+                projection.projected < 0
+                  ? 'text-red-600 dark:text-red-400'
+                  : 'text-purple-700 dark:text-purple-300'
+              }`}
+            >
+              Nach diesem Antrag: {projection.projected} {projection.projected === 1 ? 'Tag' : 'Tage'}
+            </p>
+          )}
         </div>
       );
     }
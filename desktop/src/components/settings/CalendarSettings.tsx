@@ -0,0 +1,230 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { toast } from 'sonner';
+import type { GermanState, RegionalHolidayOptions } from '../../hooks/useHolidays';
+
+/** `CalendarSettings` of calendar.rs */
+interface Settings {
+  state: GermanState | null;
+  options: RegionalHolidayOptions;
+  includePending: boolean;
+  feedEnabled: boolean;
+  feedPort: number;
+}
+
+interface FeedStatus {
+  running: boolean;
+  url: string | null;
+}
+
+const STATES: Array<{ code: GermanState; name: string }> = [
+  { code: 'BW', name: 'Baden-Württemberg' },
+  { code: 'BY', name: 'Bayern' },
+  { code: 'BE', name: 'Berlin' },
+  { code: 'BB', name: 'Brandenburg' },
+  { code: 'HB', name: 'Bremen' },
+  { code: 'HH', name: 'Hamburg' },
+  { code: 'HE', name: 'Hessen' },
+  { code: 'MV', name: 'Mecklenburg-Vorpommern' },
+  { code: 'NI', name: 'Niedersachsen' },
+  { code: 'NW', name: 'Nordrhein-Westfalen' },
+  { code: 'RP', name: 'Rheinland-Pfalz' },
+  { code: 'SL', name: 'Saarland' },
+  { code: 'SN', name: 'Sachsen' },
+  { code: 'ST', name: 'Sachsen-Anhalt' },
+  { code: 'SH', name: 'Schleswig-Holstein' },
+  { code: 'TH', name: 'Thüringen' },
+];
+
+const inputClass =
+  'w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 dark:bg-gray-700 dark:text-white';
+
+/**
+ * Calendar export (desktop only): own absences and the holidays of a state
+ * as `.ics` file or as a local feed that calendar clients subscribe to.
+ * The feed URL contains a secret token of this installation.
+ */
+export default function CalendarSettings() {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [status, setStatus] = useState<FeedStatus | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    invoke<Settings>('calendar_settings')
+      .then(setSettings)
+      .catch((error) => toast.error(String(error)));
+    invoke<FeedStatus>('calendar_feed_status')
+      .then(setStatus)
+      .catch(() => undefined);
+  }, []);
+
+  const save = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!settings) return;
+    setSaving(true);
+    try {
+      setStatus(await invoke<FeedStatus>('save_calendar_settings', { settings }));
+      toast.success('Kalender-Einstellungen gespeichert');
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const exportIcs = async () => {
+    try {
+      const path = await invoke<string | null>('export_calendar_ics');
+      if (path) toast.success(`Kalender gespeichert: ${path}`);
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const copyUrl = async () => {
+    if (!status?.url) return;
+    try {
+      await navigator.clipboard.writeText(status.url);
+      toast.success('Adresse kopiert');
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  if (!settings) {
+    return <p className="text-gray-600 dark:text-gray-400">Lade Kalender-Einstellungen...</p>;
+  }
+
+  const setOption = (changes: RegionalHolidayOptions) =>
+    setSettings({ ...settings, options: { ...settings.options, ...changes } });
+
+  return (
+    <form onSubmit={save} className="space-y-6">
+      <div>
+        <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+          Feiertage des Bundeslands
+        </label>
+        <select
+          value={settings.state ?? ''}
+          onChange={(e) =>
+            setSettings({ ...settings, state: (e.target.value || null) as GermanState | null })
+          }
+          className={inputClass}
+        >
+          <option value="">Keine Feiertage</option>
+          {STATES.map(({ code, name }) => (
+            <option key={code} value={code}>
+              {name}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      {settings.state && (
+        <div className="space-y-2 text-gray-900 dark:text-white">
+          {settings.state === 'BY' && (
+            <label className="flex items-center gap-3">
+              <input
+                type="checkbox"
+                checked={settings.options.augsburg ?? false}
+                onChange={(e) => setOption({ augsburg: e.target.checked })}
+                className="w-4 h-4"
+              />
+              Stadt Augsburg (Friedensfest)
+            </label>
+          )}
+          {['BY', 'SN', 'TH'].includes(settings.state) && (
+            <label className="flex items-center gap-3">
+              <input
+                type="checkbox"
+                checked={settings.options.catholicCommunity ?? settings.state === 'BY'}
+                onChange={(e) => setOption({ catholicCommunity: e.target.checked })}
+                className="w-4 h-4"
+              />
+              Überwiegend katholische Gemeinde
+            </label>
+          )}
+          <label className="flex items-center gap-3">
+            <input
+              type="checkbox"
+              checked={settings.options.halfDays ?? false}
+              onChange={(e) => setOption({ halfDays: e.target.checked })}
+              className="w-4 h-4"
+            />
+            Heiligabend und Silvester als halbe Tage
+          </label>
+        </div>
+      )}
+
+      <label className="flex items-center gap-3 text-gray-900 dark:text-white">
+        <input
+          type="checkbox"
+          checked={settings.includePending}
+          onChange={(e) => setSettings({ ...settings, includePending: e.target.checked })}
+          className="w-4 h-4"
+        />
+        Beantragte Abwesenheiten als vorläufig anzeigen
+      </label>
+
+      <div className="space-y-3 pt-4 border-t border-gray-200 dark:border-gray-700">
+        <label className="flex items-center gap-3 text-gray-900 dark:text-white">
+          <input
+            type="checkbox"
+            checked={settings.feedEnabled}
+            onChange={(e) => setSettings({ ...settings, feedEnabled: e.target.checked })}
+            className="w-4 h-4"
+          />
+          Kalender-Abo für Outlook, Thunderbird usw. bereitstellen
+        </label>
+        <div>
+          <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+            Port
+          </label>
+          <input
+            type="number"
+            min={1024}
+            max={65535}
+            value={settings.feedPort}
+            disabled={!settings.feedEnabled}
+            onChange={(e) => setSettings({ ...settings, feedPort: parseInt(e.target.value) || 0 })}
+            className={inputClass}
+          />
+        </div>
+        {status?.running && status.url && (
+          <div className="flex items-center gap-2">
+            <input type="text" readOnly value={status.url} className={inputClass} />
+            <button
+              type="button"
+              onClick={copyUrl}
+              className="px-4 py-2 border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-700 transition-colors whitespace-nowrap"
+            >
+              Kopieren
+            </button>
+          </div>
+        )}
+        {status?.running && (
+          <p className="text-sm text-gray-600 dark:text-gray-400">
+            Die Adresse enthält einen geheimen Schlüssel dieses Geräts – nicht weitergeben.
+          </p>
+        )}
+      </div>
+
+      <div className="flex gap-3">
+        <button
+          type="submit"
+          disabled={saving}
+          className="px-4 py-2 bg-blue-600 text-white rounded-lg hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+        >
+          {saving ? 'Speichern...' : 'Speichern'}
+        </button>
+        <button
+          type="button"
+          onClick={exportIcs}
+          className="px-4 py-2 border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-700 transition-colors"
+        >
+          Als .ics-Datei speichern
+        </button>
+      </div>
+    </form>
+  );
+}
@@ -0,0 +1,139 @@
+import { useEffect, useState } from 'react';
+import { toast } from 'sonner';
+import {
+  datevSettings,
+  saveDatevSettings,
+  type DatevSettings as Settings,
+  type DatevWageTypes,
+} from '../../api/nativeExports';
+
+const inputClass =
+  'w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 dark:bg-gray-700 dark:text-white';
+
+const EMPTY_SETTINGS: Settings = {
+  consultantNumber: 0,
+  clientNumber: 0,
+  wageTypes: {
+    workedHours: null,
+    overtime: null,
+    vacation: null,
+    sick: null,
+    unpaid: null,
+    overtimeComp: null,
+  },
+};
+
+const WAGE_TYPES: Array<{ key: keyof DatevWageTypes; label: string; unit: string }> = [
+  { key: 'workedHours', label: 'Arbeitsstunden', unit: 'Stunden' },
+  { key: 'overtime', label: 'Überstunden', unit: 'Stunden' },
+  { key: 'vacation', label: 'Urlaub', unit: 'Tage' },
+  { key: 'sick', label: 'Krankheit', unit: 'Tage' },
+  { key: 'unpaid', label: 'Unbezahlter Urlaub', unit: 'Tage' },
+  { key: 'overtimeComp', label: 'Überstundenausgleich', unit: 'Tage' },
+];
+
+/**
+ * DATEV settings (desktop only): Berater- and Mandantennummer and the
+ * Lohnarten of the LODAS export. Stored per installation (datev.rs).
+ */
+export default function DatevSettings() {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    datevSettings()
+      .then((stored) => setSettings(stored ?? EMPTY_SETTINGS))
+      .catch((error) => toast.error(String(error)));
+  }, []);
+
+  const save = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!settings) return;
+    setSaving(true);
+    try {
+      await saveDatevSettings(settings);
+      toast.success('DATEV-Einstellungen gespeichert');
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  if (!settings) {
+    return <p className="text-gray-600 dark:text-gray-400">Lade DATEV-Einstellungen...</p>;
+  }
+
+  const setWageType = (key: keyof DatevWageTypes, value: string) =>
+    setSettings({
+      ...settings,
+      wageTypes: { ...settings.wageTypes, [key]: value ? parseInt(value) : null },
+    });
+
+  return (
+    <form onSubmit={save} className="space-y-6">
+      <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+        <div>
+          <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+            Beraternummer
+          </label>
+          <input
+            type="number"
+            min={1}
+            max={9999999}
+            value={settings.consultantNumber || ''}
+            onChange={(e) => setSettings({ ...settings, consultantNumber: parseInt(e.target.value) || 0 })}
+            className={inputClass}
+            required
+          />
+        </div>
+        <div>
+          <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+            Mandantennummer
+          </label>
+          <input
+            type="number"
+            min={1}
+            max={99999}
+            value={settings.clientNumber || ''}
+            onChange={(e) => setSettings({ ...settings, clientNumber: parseInt(e.target.value) || 0 })}
+            className={inputClass}
+            required
+          />
+        </div>
+      </div>
+
+      <div>
+        <h4 className="font-medium text-gray-900 dark:text-white mb-1">Lohnarten</h4>
+        <p className="text-sm text-gray-600 dark:text-gray-400 mb-4">
+          Leere Felder werden nicht exportiert und im Export als Hinweis gemeldet.
+        </p>
+        <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+          {WAGE_TYPES.map(({ key, label, unit }) => (
+            <div key={key}>
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+                {label} <span className="text-gray-500">({unit})</span>
+              </label>
+              <input
+                type="number"
+                min={1}
+                max={9999}
+                value={settings.wageTypes[key] ?? ''}
+                onChange={(e) => setWageType(key, e.target.value)}
+                className={inputClass}
+              />
+            </div>
+          ))}
+        </div>
+      </div>
+
+      <button
+        type="submit"
+        disabled={saving}
+        className="px-4 py-2 bg-blue-600 text-white rounded-lg hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+      >
+        {saving ? 'Speichern...' : 'Speichern'}
+      </button>
+    </form>
+  );
+}
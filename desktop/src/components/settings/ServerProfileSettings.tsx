@@ -0,0 +1,237 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Server, Plus, Pencil, Trash2, Check, ShieldCheck } from 'lucide-react';
+import { toast } from 'sonner';
+import type { ServerProfile, ServerProfileSettings as Settings } from '../../api/client';
+
+const EMPTY_PROFILE: ServerProfile = {
+  id: '',
+  name: '',
+  apiBaseUrl: 'https://',
+  wsUrl: 'wss://',
+  pinnedCertificate: null,
+};
+
+const inputClass =
+  'w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 dark:bg-gray-700 dark:text-white';
+
+/**
+ * Server profiles (desktop only): which server the app connects to.
+ * Switching reloads the app; a session of another server ends.
+ */
+export default function ServerProfileSettings() {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [editing, setEditing] = useState<ServerProfile | null>(null);
+  const [isNew, setIsNew] = useState(false);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    invoke<Settings>('server_profiles')
+      .then(setSettings)
+      .catch((error) => toast.error(String(error)));
+  }, []);
+
+  const activate = async (profile: ServerProfile) => {
+    if (!confirm(`Zum Server "${profile.name}" wechseln?\n\nDie App wird neu geladen. Bei einem anderen Server müssen Sie sich neu anmelden.`)) {
+      return;
+    }
+    setBusy(true);
+    try {
+      await invoke<ServerProfile>('activate_server_profile', { id: profile.id });
+      window.location.reload();
+    } catch (error) {
+      toast.error(String(error));
+      setBusy(false);
+    }
+  };
+
+  const save = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!editing) return;
+    setBusy(true);
+    try {
+      const updated = await invoke<Settings>('save_server_profile', { profile: editing });
+      setSettings(updated);
+      setEditing(null);
+      toast.success('Serverprofil gespeichert');
+      if (editing.id === updated.active) {
+        // Active server changed its address: start over with the new one
+        window.location.reload();
+      }
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const remove = async (profile: ServerProfile) => {
+    if (!confirm(`Serverprofil "${profile.name}" löschen?`)) return;
+    try {
+      setSettings(await invoke<Settings>('delete_server_profile', { id: profile.id }));
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  if (!settings) {
+    return <p className="text-gray-600 dark:text-gray-400">Lade Serverprofile...</p>;
+  }
+
+  return (
+    <div className="space-y-6">
+      <div className="space-y-3">
+        {settings.profiles.map((profile) => {
+          const active = profile.id === settings.active;
+          return (
+            <div
+              key={profile.id}
+              className={`flex items-center justify-between p-4 rounded-lg border ${
+                active
+                  ? 'border-blue-600 bg-blue-50 dark:bg-blue-900/20'
+                  : 'border-gray-200 dark:border-gray-700'
+              }`}
+            >
+              <div className="flex items-center gap-3">
+                <Server className="w-5 h-5 text-gray-500 dark:text-gray-400" />
+                <div>
+                  <div className="flex items-center gap-2 font-medium text-gray-900 dark:text-white">
+                    {profile.name}
+                    {active && (
+                      <span className="text-xs px-2 py-0.5 rounded-full bg-blue-600 text-white">Aktiv</span>
+                    )}
+                    {profile.pinnedCertificate && (
+                      <span title="Zertifikat hinterlegt">
+                        <ShieldCheck className="w-4 h-4 text-green-600" />
+                      </span>
+                    )}
+                  </div>
+                  <p className="text-sm text-gray-600 dark:text-gray-400">{profile.apiBaseUrl}</p>
+                </div>
+              </div>
+              <div className="flex items-center gap-2">
+                {!active && (
+                  <button
+                    onClick={() => activate(profile)}
+                    disabled={busy}
+                    className="flex items-center gap-1 px-3 py-1.5 text-sm bg-blue-600 text-white rounded-lg hover:bg-blue-700 disabled:opacity-50"
+                  >
+                    <Check className="w-4 h-4" />
+                    Verwenden
+                  </button>
+                )}
+                <button
+                  onClick={() => {
+                    setEditing({ ...profile });
+                    setIsNew(false);
+                  }}
+                  className="p-2 text-gray-500 hover:text-gray-900 dark:text-gray-400 dark:hover:text-white"
+                  title="Bearbeiten"
+                >
+                  <Pencil className="w-4 h-4" />
+                </button>
+                {!active && (
+                  <button
+                    onClick={() => remove(profile)}
+                    className="p-2 text-gray-500 hover:text-red-600 dark:text-gray-400"
+                    title="Löschen"
+                  >
+                    <Trash2 className="w-4 h-4" />
+                  </button>
+                )}
+              </div>
+            </div>
+          );
+        })}
+      </div>
+
+      {!editing && (
+        <button
+          onClick={() => {
+            setEditing({ ...EMPTY_PROFILE });
+            setIsNew(true);
+          }}
+          className="flex items-center gap-2 px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg text-gray-700 dark:text-gray-300 hover:bg-gray-50 dark:hover:bg-gray-700"
+        >
+          <Plus className="w-5 h-5" />
+          Serverprofil hinzufügen
+        </button>
+      )}
+
+      {editing && (
+        <form onSubmit={save} className="max-w-xl space-y-4 p-4 rounded-lg border border-gray-200 dark:border-gray-700">
+          <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+            <div>
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">ID</label>
+              <input
+                value={editing.id}
+                onChange={(e) => setEditing({ ...editing, id: e.target.value })}
+                disabled={!isNew}
+                className={`${inputClass} disabled:opacity-60`}
+                placeholder="z.B. produktion"
+              />
+            </div>
+            <div>
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">Name</label>
+              <input
+                value={editing.name}
+                onChange={(e) => setEditing({ ...editing, name: e.target.value })}
+                className={inputClass}
+                placeholder="z.B. Produktion"
+              />
+            </div>
+          </div>
+          <div>
+            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">API-URL</label>
+            <input
+              value={editing.apiBaseUrl}
+              onChange={(e) => setEditing({ ...editing, apiBaseUrl: e.target.value })}
+              className={inputClass}
+              placeholder="https://zeit.example.org/api"
+            />
+          </div>
+          <div>
+            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">WebSocket-URL</label>
+            <input
+              value={editing.wsUrl}
+              onChange={(e) => setEditing({ ...editing, wsUrl: e.target.value })}
+              className={inputClass}
+              placeholder="wss://zeit.example.org/ws"
+            />
+          </div>
+          <div>
+            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+              Zertifikat (optional, PEM)
+            </label>
+            <textarea
+              value={editing.pinnedCertificate ?? ''}
+              onChange={(e) => setEditing({ ...editing, pinnedCertificate: e.target.value || null })}
+              rows={4}
+              className={`${inputClass} font-mono text-xs`}
+              placeholder="-----BEGIN CERTIFICATE-----"
+            />
+            <p className="text-sm text-gray-500 dark:text-gray-400 mt-1">
+              Ist ein Zertifikat hinterlegt, vertraut die App für diesen Server nur diesem Zertifikat.
+            </p>
+          </div>
+          <div className="flex gap-2">
+            <button
+              type="submit"
+              disabled={busy}
+              className="px-4 py-2 bg-blue-600 text-white rounded-lg hover:bg-blue-700 disabled:opacity-50"
+            >
+              Speichern
+            </button>
+            <button
+              type="button"
+              onClick={() => setEditing(null)}
+              className="px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg text-gray-700 dark:text-gray-300"
+            >
+              Abbrechen
+            </button>
+          </div>
+        </form>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,157 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { toast } from 'sonner';
+
+/** `ShortcutSettings` of shortcuts.rs; `null` = no shortcut */
+interface Settings {
+  enabled: boolean;
+  toggleTimer: string | null;
+  toggleBreak: string | null;
+  quickEntry: string | null;
+}
+
+type ShortcutAction = 'toggleTimer' | 'toggleBreak' | 'quickEntry';
+
+interface ShortcutStatus {
+  action: ShortcutAction;
+  accelerator: string;
+  registered: boolean;
+  error: string | null;
+}
+
+const ACTIONS: Array<{ key: ShortcutAction; label: string }> = [
+  { key: 'toggleTimer', label: 'Kommen / Gehen' },
+  { key: 'toggleBreak', label: 'Pause starten / beenden' },
+  { key: 'quickEntry', label: 'Schnellerfassung öffnen' },
+];
+
+const inputClass =
+  'w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 dark:bg-gray-700 dark:text-white';
+
+/** Accelerator of a key press ("CommandOrControl+Alt+K"), `null` for a lone modifier */
+function accelerator(e: React.KeyboardEvent): string | null {
+  if (['Control', 'Alt', 'Shift', 'Meta'].includes(e.key)) return null;
+  const key = e.code.startsWith('Key') || e.code.startsWith('Digit')
+    ? e.code.replace(/^(Key|Digit)/, '')
+    : e.key.length === 1
+      ? e.key.toUpperCase()
+      : e.key;
+  const modifiers = [
+    e.ctrlKey || e.metaKey ? 'CommandOrControl' : null,
+    e.altKey ? 'Alt' : null,
+    e.shiftKey ? 'Shift' : null,
+  ].filter(Boolean);
+  return [...modifiers, key].join('+');
+}
+
+/**
+ * OS-wide keyboard shortcuts (desktop only). Conflicts between the bindings
+ * or with system shortcuts are rejected when saving; a combination another
+ * application has taken shows up per action after saving.
+ */
+export default function ShortcutSettings() {
+  const [settings, setSettings] = useState<Settings | null>(null);
+  const [status, setStatus] = useState<ShortcutStatus[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    invoke<Settings>('shortcut_settings')
+      .then(setSettings)
+      .catch((e) => toast.error(String(e)));
+    invoke<ShortcutStatus[]>('shortcut_status')
+      .then(setStatus)
+      .catch(() => undefined);
+  }, []);
+
+  const save = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!settings) return;
+    setSaving(true);
+    setError(null);
+    try {
+      const result = await invoke<ShortcutStatus[]>('save_shortcut_settings', { settings });
+      setStatus(result);
+      if (result.some((s) => s.error)) {
+        toast.warning('Nicht alle Tastenkürzel konnten registriert werden');
+      } else {
+        toast.success('Tastenkürzel gespeichert');
+      }
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  if (!settings) {
+    return <p className="text-gray-600 dark:text-gray-400">Lade Tastenkürzel...</p>;
+  }
+
+  const set = (key: ShortcutAction, value: string | null) => {
+    setSettings({ ...settings, [key]: value });
+    setError(null);
+  };
+
+  return (
+    <form onSubmit={save} className="space-y-6">
+      <label className="flex items-center gap-3 text-gray-900 dark:text-white">
+        <input
+          type="checkbox"
+          checked={settings.enabled}
+          onChange={(e) => setSettings({ ...settings, enabled: e.target.checked })}
+          className="w-4 h-4"
+        />
+        Systemweite Tastenkürzel verwenden
+      </label>
+
+      <div className="space-y-4">
+        {ACTIONS.map(({ key, label }) => {
+          const actionStatus = status.find((s) => s.action === key);
+          return (
+            <div key={key}>
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+                {label}
+              </label>
+              <input
+                type="text"
+                readOnly
+                value={settings[key] ?? ''}
+                placeholder="Tastenkombination drücken (Entf = keine)"
+                disabled={!settings.enabled}
+                onKeyDown={(e) => {
+                  if (e.key === 'Tab') return;
+                  e.preventDefault();
+                  if (e.key === 'Backspace' || e.key === 'Delete') {
+                    set(key, null);
+                    return;
+                  }
+                  const combination = accelerator(e);
+                  if (combination) set(key, combination);
+                }}
+                className={inputClass}
+              />
+              {settings.enabled && actionStatus?.error && (
+                <p className="text-sm text-red-600 dark:text-red-400 mt-1">{actionStatus.error}</p>
+              )}
+            </div>
+          );
+        })}
+      </div>
+
+      {error && (
+        <div className="bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg p-4">
+          <p className="text-sm text-red-800 dark:text-red-200">{error}</p>
+        </div>
+      )}
+
+      <button
+        type="submit"
+        disabled={saving}
+        className="px-4 py-2 bg-blue-600 text-white rounded-lg hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+      >
+        {saving ? 'Speichern...' : 'Speichern'}
+      </button>
+    </form>
+  );
+}
@@ -0,0 +1,39 @@
+import type { ArbzgViolation } from '../../hooks';
+
+interface ArbzgWarningsProps {
+  violations: ArbzgViolation[] | undefined;
+}
+
+/** Hints of the ArbZG check (`useArbzgCheck`); saving stays possible */
+export function ArbzgWarnings({ violations }: ArbzgWarningsProps) {
+  if (!violations || violations.length === 0) return null;
+
+  const critical = violations.some((v) => v.severity === 'critical');
+
+  return (
+    <div
+      className={`p-3 rounded-lg border ${
+        critical
+          ? 'bg-red-50 dark:bg-red-900/20 border-red-200 dark:border-red-800'
+          : 'bg-yellow-50 dark:bg-yellow-900/20 border-yellow-200 dark:border-yellow-800'
+      }`}
+    >
+      <p
+        className={`text-sm font-medium ${
+          critical ? 'text-red-900 dark:text-red-200' : 'text-yellow-900 dark:text-yellow-200'
+        }`}
+      >
+        Arbeitszeitgesetz
+      </p>
+      <ul
+        className={`text-xs mt-1 space-y-0.5 ${
+          critical ? 'text-red-800 dark:text-red-300' : 'text-yellow-800 dark:text-yellow-300'
+        }`}
+      >
+        {violations.map((violation) => (
+          <li key={violation.rule}>⚠️ {violation.message}</li>
+        ))}
+      </ul>
+    </div>
+  );
+}
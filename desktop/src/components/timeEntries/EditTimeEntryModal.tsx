@@ -5,7 +5,8 @@ import { Select } from '../ui/Select';
 import { Textarea } from '../ui/Textarea';
 import { Button } from '../ui/Button';
 import { LoadingSpinner } from '../ui/LoadingSpinner';
-import { useUpdateTimeEntry } from '../../hooks';
+import { ArbzgWarnings } from './ArbzgWarnings';
+import { useArbzgCheck, useUpdateTimeEntry } from '../../hooks';
 import {
   isValidTime,
   isValidTimeRange,
@@ -154,6 +155,15 @@ export function EditTimeEntryModal({ isOpen, onClose, entry }: EditTimeEntryModa
     ? calculateHours(startTime, endTime, parseInt(breakMinutes) || 0)
     : null;
 
+  // Native ArbZG check against the cached entries (desktop only)
+  const { data: arbzg } = useArbzgCheck(entry.userId, {
+    date,
+    startTime,
+    endTime,
+    breakMinutes: parseInt(breakMinutes) || 0,
+    excludeEntryId: entry.id,
+  });
+
   return (
     <Modal isOpen={isOpen} onClose={handleClose} title="Zeiteintrag bearbeiten" size="lg">
       <form onSubmit={handleSubmit} className="space-y-6">
@@ -253,6 +263,8 @@ export function EditTimeEntryModal({ isOpen, onClose, entry }: EditTimeEntryModa
           </div>
         )}
 
+        <ArbzgWarnings violations={arbzg?.violations} />
+
         {/* Actions */}
         <div className="flex justify-end space-x-3 pt-4 border-t border-gray-200 dark:border-gray-700">
           <Button type="button" variant="ghost" onClick={handleClose}>
@@ -0,0 +1,180 @@
+import { useEffect, useState, FormEvent } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Input } from '../ui/Input';
+import { Select } from '../ui/Select';
+import { Button } from '../ui/Button';
+import { isValidTime, getTimeRangeError, calculateHours, formatHours } from '../../utils';
+
+/**
+ * Quick Entry Window
+ *
+ * Content of the small frameless "quick-entry" window (desktop only).
+ * The window's capability only allows the quick_entry_* commands, so
+ * this component must not use the API client or any other command.
+ */
+
+interface QuickEntryPrefill {
+  date: string;
+  startTime: string | null;
+  endTime: string;
+  breakMinutes: number;
+  project: string | null;
+  activity: string | null;
+  location: 'office' | 'homeoffice' | 'field' | null;
+  fromTimer: boolean;
+}
+
+type Location = 'office' | 'homeoffice' | 'field';
+
+export function QuickEntryWindow() {
+  const [date, setDate] = useState('');
+  const [startTime, setStartTime] = useState('');
+  const [endTime, setEndTime] = useState('');
+  const [breakMinutes, setBreakMinutes] = useState('0');
+  const [location, setLocation] = useState<Location>('office');
+  const [notes, setNotes] = useState('');
+  const [details, setDetails] = useState<Pick<QuickEntryPrefill, 'project' | 'activity'>>({
+    project: null,
+    activity: null,
+  });
+  const [fromTimer, setFromTimer] = useState(false);
+  const [error, setError] = useState('');
+  const [isSaving, setIsSaving] = useState(false);
+
+  useEffect(() => {
+    invoke<QuickEntryPrefill>('quick_entry_prefill')
+      .then((prefill) => {
+        setDate(prefill.date);
+        setStartTime(prefill.startTime ?? '');
+        setEndTime(prefill.endTime);
+        setBreakMinutes(String(prefill.breakMinutes));
+        setLocation(prefill.location ?? 'office');
+        setDetails({ project: prefill.project, activity: prefill.activity });
+        setFromTimer(prefill.fromTimer);
+      })
+      .catch((err) => setError(String(err)));
+
+    const handleKeyDown = (event: KeyboardEvent) => {
+      if (event.key === 'Escape') invoke('close_quick_entry');
+    };
+    window.addEventListener('keydown', handleKeyDown);
+    return () => window.removeEventListener('keydown', handleKeyDown);
+  }, []);
+
+  const handleSubmit = async (e: FormEvent) => {
+    e.preventDefault();
+    setError('');
+
+    if (!date || !isValidTime(startTime) || !isValidTime(endTime)) {
+      setError('Bitte Datum, Start- und Endzeit angeben (Format: HH:MM)');
+      return;
+    }
+    const rangeError = getTimeRangeError(startTime, endTime);
+    if (rangeError) {
+      setError(rangeError);
+      return;
+    }
+
+    setIsSaving(true);
+    try {
+      // Closes the window on success; an entry from the timer also stops it
+      await invoke('quick_entry_submit', {
+        input: {
+          date,
+          startTime,
+          endTime,
+          breakMinutes: parseInt(breakMinutes) || 0,
+          location,
+          project: details.project,
+          activity: details.activity,
+          notes: notes.trim() || null,
+          fromTimer,
+        },
+      });
+    } catch (err) {
+      setError(String(err));
+      setIsSaving(false);
+    }
+  };
+
+  const previewHours =
+    isValidTime(startTime) && isValidTime(endTime) && !getTimeRangeError(startTime, endTime)
+      ? calculateHours(startTime, endTime, parseInt(breakMinutes) || 0)
+      : 0;
+
+  return (
+    <div className="h-screen flex flex-col bg-white dark:bg-gray-900 border border-gray-200 dark:border-gray-700">
+      <div
+        data-tauri-drag-region
+        className="flex items-center justify-between px-4 py-2 border-b border-gray-200 dark:border-gray-700 cursor-move"
+      >
+        <span data-tauri-drag-region className="text-sm font-semibold text-gray-900 dark:text-gray-100">
+          Schnellerfassung
+        </span>
+        <button
+          type="button"
+          onClick={() => invoke('close_quick_entry')}
+          className="text-gray-500 hover:text-gray-900 dark:hover:text-gray-100"
+          aria-label="Schließen"
+        >
+          ✕
+        </button>
+      </div>
+
+      <form onSubmit={handleSubmit} className="flex-1 p-4 space-y-3">
+        {fromTimer && (
+          <p className="text-xs text-blue-800 dark:text-blue-300">
+            Vorbelegt aus der laufenden Zeiterfassung
+          </p>
+        )}
+
+        <Input type="date" label="Datum" value={date} onChange={(e) => setDate(e.target.value)} required />
+
+        <div className="grid grid-cols-3 gap-3">
+          <Input
+            type="time"
+            label="Start"
+            value={startTime}
+            onChange={(e) => setStartTime(e.target.value)}
+            required
+            autoFocus
+          />
+          <Input type="time" label="Ende" value={endTime} onChange={(e) => setEndTime(e.target.value)} required />
+          <Input
+            type="number"
+            label="Pause"
+            value={breakMinutes}
+            onChange={(e) => setBreakMinutes(e.target.value)}
+            min="0"
+            max="480"
+            step="15"
+          />
+        </div>
+
+        <Select
+          label="Arbeitsort"
+          value={location}
+          onChange={(e) => setLocation(e.target.value as Location)}
+          options={[
+            { value: 'office', label: 'Büro' },
+            { value: 'homeoffice', label: 'Home Office' },
+            { value: 'field', label: 'Außendienst' },
+          ]}
+        />
+
+        <Input label="Notiz" value={notes} onChange={(e) => setNotes(e.target.value)} placeholder="Optional" />
+
+        {error && <p className="text-sm text-red-600 dark:text-red-400">{error}</p>}
+
+        <div className="flex items-center justify-between pt-2">
+          <span className="text-sm text-gray-600 dark:text-gray-400">
+            {previewHours > 0 && <>Arbeitszeit: {formatHours(previewHours)}</>}
+          </span>
+          <Button type="submit" variant="primary" disabled={isSaving}>
+            Speichern
+          </Button>
+        </div>
+      </form>
+    </div>
+  );
+}
@@ -5,7 +5,8 @@ import { Select } from '../ui/Select';
 import { Textarea } from '../ui/Textarea';
 import { Button } from '../ui/Button';
 import { LoadingSpinner } from '../ui/LoadingSpinner';
-import { useCreateTimeEntry, useUsers } from '../../hooks';
+import { ArbzgWarnings } from './ArbzgWarnings';
+import { useArbzgCheck, useCreateTimeEntry, useUsers } from '../../hooks';
 import { useAuthStore } from '../../store/authStore';
 import {
   getTodayDate,
@@ -136,6 +137,14 @@ export function TimeEntryForm({ isOpen, onClose }: TimeEntryFormProps) {
     ? calculateHours(startTime, endTime, parseInt(breakMinutes) || 0)
     : 0;
 
+  // Native ArbZG check against the cached entries (desktop only)
+  const { data: arbzg } = useArbzgCheck(selectedUserId, {
+    date,
+    startTime,
+    endTime,
+    breakMinutes: parseInt(breakMinutes) || 0,
+  });
+
   return (
     <Modal isOpen={isOpen} onClose={handleClose} title="Zeit erfassen" size="md">
       <form onSubmit={handleSubmit} className="space-y-4">
@@ -244,6 +253,8 @@ export function TimeEntryForm({ isOpen, onClose }: TimeEntryFormProps) {
           </div>
         )}
 
+        <ArbzgWarnings violations={arbzg?.violations} />
+
         {/* Buttons */}
         <div className="flex justify-end space-x-3 pt-4">
           <Button
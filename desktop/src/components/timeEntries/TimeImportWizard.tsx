@@ -0,0 +1,310 @@
+/**
+ * Time Import Wizard (desktop only)
+ * Imports historical time entries from CSV or XLSX (see time_import.rs):
+ * pick a file, check the suggested column mapping, review the rows with
+ * errors and warnings, then queue the valid entries in the outbox with
+ * `offline_import_time_entries`. Nothing is uploaded before the last step.
+ */
+
+import { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { open } from '@tauri-apps/plugin-dialog';
+import { toast } from 'sonner';
+import { Modal } from '../ui/Modal';
+import { Select } from '../ui/Select';
+import { Button } from '../ui/Button';
+import { LoadingSpinner } from '../ui/LoadingSpinner';
+import { useAuthStore } from '../../store/authStore';
+import { useUsers } from '../../hooks';
+
+/** `ColumnMapping` of time_import.rs: column index per field */
+interface ColumnMapping {
+  date: number | null;
+  startTime: number | null;
+  endTime: number | null;
+  breakMinutes: number | null;
+  hours: number | null;
+  location: number | null;
+  activity: number | null;
+  project: number | null;
+  notes: number | null;
+  user: number | null;
+}
+
+interface ImportPreview {
+  headers: string[];
+  sample: string[][];
+  rows: number;
+  mapping: ColumnMapping;
+}
+
+interface RowIssue {
+  row: number;
+  column: string | null;
+  message: string;
+}
+
+interface ImportReport {
+  rows: number;
+  entries: Array<{ row: number; entry: unknown; hours: number }>;
+  errors: RowIssue[];
+  warnings: RowIssue[];
+}
+
+const FIELDS: Array<{ key: keyof ColumnMapping; label: string }> = [
+  { key: 'date', label: 'Datum' },
+  { key: 'startTime', label: 'Beginn' },
+  { key: 'endTime', label: 'Ende' },
+  { key: 'breakMinutes', label: 'Pause' },
+  { key: 'hours', label: 'Stunden' },
+  { key: 'location', label: 'Arbeitsort' },
+  { key: 'activity', label: 'Tätigkeit' },
+  { key: 'project', label: 'Projekt' },
+  { key: 'notes', label: 'Bemerkung' },
+  { key: 'user', label: 'Mitarbeiter' },
+];
+
+interface TimeImportWizardProps {
+  isOpen: boolean;
+  onClose: () => void;
+}
+
+export function TimeImportWizard({ isOpen, onClose }: TimeImportWizardProps) {
+  const { user } = useAuthStore();
+  const isAdmin = user?.role === 'admin';
+  const { data: users } = useUsers(isAdmin);
+
+  const [path, setPath] = useState<string | null>(null);
+  const [preview, setPreview] = useState<ImportPreview | null>(null);
+  const [mapping, setMapping] = useState<ColumnMapping | null>(null);
+  const [selectedUserId, setSelectedUserId] = useState<number | undefined>();
+  const userId = selectedUserId ?? user?.id;
+  const [defaultLocation, setDefaultLocation] = useState('office');
+  const [report, setReport] = useState<ImportReport | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const reset = () => {
+    setPath(null);
+    setPreview(null);
+    setMapping(null);
+    setReport(null);
+  };
+
+  const close = () => {
+    reset();
+    onClose();
+  };
+
+  const chooseFile = async () => {
+    const chosen = await open({
+      multiple: false,
+      filters: [{ name: 'Tabellen', extensions: ['csv', 'xlsx'] }],
+    });
+    if (typeof chosen !== 'string') return;
+
+    setBusy(true);
+    try {
+      const result = await invoke<ImportPreview>('preview_time_import', { path: chosen });
+      setPath(chosen);
+      setPreview(result);
+      setMapping(result.mapping);
+      setReport(null);
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const validate = async () => {
+    if (!path || !mapping) return;
+
+    // Username or Personalnummer (user id) → user id
+    const userMap: Record<string, number> = {};
+    for (const u of isAdmin ? users ?? [] : user ? [user] : []) {
+      userMap[u.username.toLowerCase()] = u.id;
+      userMap[String(u.id)] = u.id;
+    }
+
+    setBusy(true);
+    try {
+      const result = await invoke<ImportReport>('validate_time_import', {
+        path,
+        mapping,
+        options: {
+          userId: mapping.user === null ? userId : null,
+          users: userMap,
+          defaultLocation,
+        },
+      });
+      setReport(result);
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const upload = async () => {
+    if (!report || report.entries.length === 0) return;
+
+    setBusy(true);
+    try {
+      const count = await invoke<number>('offline_import_time_entries', {
+        entries: report.entries.map((r) => r.entry),
+      });
+      toast.success(`${count} Zeiteinträge importiert; sie werden mit dem Server synchronisiert`);
+      close();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const columnOptions = [
+    { value: '', label: '—' },
+    ...(preview?.headers.map((header, index) => ({ value: index, label: header || `Spalte ${index + 1}` })) ?? []),
+  ];
+
+  return (
+    <Modal isOpen={isOpen} onClose={close} title="Zeiteinträge importieren" size="xl">
+      <div className="space-y-6">
+        <div className="flex items-center justify-between gap-4">
+          <p className="text-sm text-gray-600 dark:text-gray-400 truncate">
+            {path ? `${path} · ${preview?.rows ?? 0} Zeilen` : 'CSV- oder Excel-Datei (.xlsx) mit einer Kopfzeile'}
+          </p>
+          <Button variant="secondary" onClick={chooseFile} disabled={busy}>
+            {path ? 'Andere Datei' : 'Datei auswählen'}
+          </Button>
+        </div>
+
+        {preview && mapping && (
+          <>
+            <div>
+              <h4 className="font-medium text-gray-900 dark:text-white mb-3">Spaltenzuordnung</h4>
+              <div className="grid grid-cols-2 md:grid-cols-5 gap-3">
+                {FIELDS.map(({ key, label }) => (
+                  <Select
+                    key={key}
+                    label={label}
+                    value={mapping[key] ?? ''}
+                    onChange={(e) => {
+                      setMapping({ ...mapping, [key]: e.target.value === '' ? null : Number(e.target.value) });
+                      setReport(null);
+                    }}
+                    options={columnOptions}
+                  />
+                ))}
+              </div>
+            </div>
+
+            <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+              {mapping.user === null && (
+                <Select
+                  label="Mitarbeiter (alle Zeilen)"
+                  value={userId ?? ''}
+                  onChange={(e) => {
+                    setSelectedUserId(Number(e.target.value));
+                    setReport(null);
+                  }}
+                  options={
+                    isAdmin
+                      ? (users ?? []).map((u) => ({ value: u.id, label: `${u.firstName} ${u.lastName}` }))
+                      : user
+                        ? [{ value: user.id, label: `${user.firstName} ${user.lastName}` }]
+                        : []
+                  }
+                />
+              )}
+              <Select
+                label="Arbeitsort ohne Angabe"
+                value={defaultLocation}
+                onChange={(e) => {
+                  setDefaultLocation(e.target.value);
+                  setReport(null);
+                }}
+                options={[
+                  { value: 'office', label: 'Büro' },
+                  { value: 'homeoffice', label: 'Home Office' },
+                  { value: 'field', label: 'Außendienst' },
+                ]}
+              />
+            </div>
+
+            <div className="overflow-x-auto">
+              <table className="min-w-full text-sm">
+                <thead>
+                  <tr className="border-b border-gray-200 dark:border-gray-700">
+                    {preview.headers.map((header, index) => (
+                      <th key={index} className="px-2 py-1 text-left font-medium text-gray-700 dark:text-gray-300">
+                        {header}
+                      </th>
+                    ))}
+                  </tr>
+                </thead>
+                <tbody>
+                  {preview.sample.map((row, i) => (
+                    <tr key={i} className="border-b border-gray-100 dark:border-gray-800">
+                      {row.map((value, j) => (
+                        <td key={j} className="px-2 py-1 text-gray-600 dark:text-gray-400 whitespace-nowrap">
+                          {value}
+                        </td>
+                      ))}
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            </div>
+          </>
+        )}
+
+        {report && (
+          <div className="space-y-3">
+            <p className="text-sm text-gray-700 dark:text-gray-300">
+              {report.entries.length} von {report.rows} Zeilen können importiert werden
+              {report.errors.length > 0 && `, ${report.errors.length} Fehler`}
+              {report.warnings.length > 0 && `, ${report.warnings.length} Hinweise`}
+            </p>
+            {[
+              { title: 'Fehler (werden nicht importiert)', issues: report.errors, color: 'text-red-600 dark:text-red-400' },
+              { title: 'Hinweise', issues: report.warnings, color: 'text-yellow-700 dark:text-yellow-400' },
+            ]
+              .filter(({ issues }) => issues.length > 0)
+              .map(({ title, issues, color }) => (
+                <div key={title}>
+                  <h4 className={`font-medium mb-1 ${color}`}>{title}</h4>
+                  <ul className="max-h-40 overflow-y-auto text-sm text-gray-700 dark:text-gray-300 space-y-0.5">
+                    {issues.map((issue, i) => (
+                      <li key={i}>
+                        Zeile {issue.row}
+                        {issue.column ? ` (${issue.column})` : ''}: {issue.message}
+                      </li>
+                    ))}
+                  </ul>
+                </div>
+              ))}
+          </div>
+        )}
+
+        <div className="flex justify-end space-x-3 pt-4 border-t border-gray-200 dark:border-gray-700">
+          <Button type="button" variant="secondary" onClick={close}>
+            Abbrechen
+          </Button>
+          {preview && !report && (
+            <Button variant="primary" onClick={validate} disabled={busy}>
+              {busy && <LoadingSpinner size="sm" className="mr-2" />}
+              Prüfen
+            </Button>
+          )}
+          {report && (
+            <Button variant="primary" onClick={upload} disabled={busy || report.entries.length === 0}>
+              {busy && <LoadingSpinner size="sm" className="mr-2" />}
+              {report.entries.length} Einträge importieren
+            </Button>
+          )}
+        </div>
+      </div>
+    </Modal>
+  );
+}
@@ -0,0 +1,229 @@
+/**
+ * Timer Draft Modal (desktop only)
+ * Shows the time entry drafts "Gehen" in the tray produced (see
+ * tracking.rs) for confirmation. A draft stays pending on the Rust side
+ * until it is saved or discarded; "Später" only hides the dialog.
+ */
+
+import { useCallback, useEffect, useState, FormEvent } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { Modal } from '../ui/Modal';
+import { Input } from '../ui/Input';
+import { Select } from '../ui/Select';
+import { Textarea } from '../ui/Textarea';
+import { Button } from '../ui/Button';
+import { LoadingSpinner } from '../ui/LoadingSpinner';
+import { ArbzgWarnings } from './ArbzgWarnings';
+import { useArbzgCheck, useCreateTimeEntry } from '../../hooks';
+import { useAuthStore } from '../../store/authStore';
+import { isValidTimeRange, getTimeRangeError, calculateHours, formatHours } from '../../utils';
+import { isTauri } from '../../utils/tauri';
+
+type Location = 'office' | 'homeoffice' | 'field';
+
+/** `TimeEntryDraft` of timer.rs */
+interface TimeEntryDraft {
+  date: string;
+  startTime: string;
+  endTime: string;
+  breakMinutes: number;
+  project: string | null;
+  activity: string | null;
+  location: string | null;
+}
+
+function toLocation(value: string | null): Location {
+  return value === 'homeoffice' || value === 'field' ? value : 'office';
+}
+
+export function TimerDraftModal() {
+  const { user } = useAuthStore();
+  const createEntry = useCreateTimeEntry();
+
+  const [drafts, setDrafts] = useState<TimeEntryDraft[]>([]);
+  const [isOpen, setIsOpen] = useState(false);
+  const draft = drafts[0];
+
+  const [startTime, setStartTime] = useState('');
+  const [endTime, setEndTime] = useState('');
+  const [breakMinutes, setBreakMinutes] = useState('0');
+  const [location, setLocation] = useState<Location>('office');
+  const [notes, setNotes] = useState('');
+  const [timeError, setTimeError] = useState('');
+
+  const { data: arbzg } = useArbzgCheck(
+    user?.id,
+    draft ? { date: draft.date, startTime, endTime, breakMinutes: parseInt(breakMinutes) || 0 } : null
+  );
+
+  const loadDrafts = useCallback(() => {
+    invoke<TimeEntryDraft[]>('time_entry_drafts')
+      .then((pending) => {
+        setDrafts(pending);
+        setIsOpen(pending.length > 0);
+      })
+      .catch(() => undefined);
+  }, []);
+
+  useEffect(() => {
+    if (!isTauri() || !user) {
+      return;
+    }
+    loadDrafts();
+    const unlisten = listen('timer:draft', loadDrafts);
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => undefined);
+    };
+  }, [user, loadDrafts]);
+
+  // Form follows the draft on display
+  useEffect(() => {
+    if (!draft) return;
+    setStartTime(draft.startTime);
+    setEndTime(draft.endTime);
+    setBreakMinutes(String(draft.breakMinutes));
+    setLocation(toLocation(draft.location));
+    setNotes('');
+    setTimeError('');
+  }, [draft]);
+
+  const resolve = async (resolved: TimeEntryDraft) => {
+    await invoke('resolve_time_entry_draft', { draft: resolved });
+    const rest = drafts.slice(1);
+    setDrafts(rest);
+    setIsOpen(rest.length > 0);
+  };
+
+  const handleSubmit = async (e: FormEvent) => {
+    e.preventDefault();
+    if (!draft || !user) return;
+
+    const rangeError = getTimeRangeError(startTime, endTime);
+    if (rangeError) {
+      setTimeError(rangeError);
+      return;
+    }
+
+    try {
+      await createEntry.mutateAsync({
+        userId: user.id,
+        date: draft.date,
+        startTime,
+        endTime,
+        breakMinutes: parseInt(breakMinutes) || 0,
+        location,
+        activity: draft.activity,
+        project: draft.project,
+        notes: notes || undefined,
+      });
+      await resolve(draft);
+    } catch (error) {
+      console.error('Failed to submit timer draft:', error);
+    }
+  };
+
+  if (!draft) return null;
+
+  const hoursPreview = isValidTimeRange(startTime, endTime)
+    ? calculateHours(startTime, endTime, parseInt(breakMinutes) || 0)
+    : null;
+  const date = new Date(`${draft.date}T00:00:00`).toLocaleDateString('de-DE');
+
+  return (
+    <Modal
+      isOpen={isOpen}
+      onClose={() => setIsOpen(false)}
+      title={drafts.length > 1 ? `Erfasste Zeit bestätigen (${drafts.length})` : 'Erfasste Zeit bestätigen'}
+      size="lg"
+    >
+      <form onSubmit={handleSubmit} className="space-y-6">
+        <p className="text-sm text-gray-600 dark:text-gray-400">
+          Zeiterfassung vom {date}
+          {draft.project ? ` · ${draft.project}` : ''}
+          {draft.activity ? ` · ${draft.activity}` : ''}
+        </p>
+
+        <div className="grid grid-cols-2 gap-4">
+          <Input
+            label="Start"
+            type="time"
+            value={startTime}
+            onChange={(e) => setStartTime(e.target.value)}
+            required
+          />
+          <Input
+            label="Ende"
+            type="time"
+            value={endTime}
+            onChange={(e) => setEndTime(e.target.value)}
+            error={timeError}
+            required
+          />
+        </div>
+
+        <Input
+          label="Pause (Minuten)"
+          type="number"
+          min="0"
+          max="480"
+          step="5"
+          value={breakMinutes}
+          onChange={(e) => setBreakMinutes(e.target.value)}
+          helperText="Pflichtpause: > 6h = min. 30 Min, > 9h = min. 45 Min"
+        />
+
+        <Select
+          label="Arbeitsort"
+          value={location}
+          onChange={(e) => setLocation(e.target.value as Location)}
+          options={[
+            { value: 'office', label: 'Büro' },
+            { value: 'homeoffice', label: 'Home Office' },
+            { value: 'field', label: 'Außendienst' },
+          ]}
+          required
+        />
+
+        <Textarea
+          label="Notiz (optional)"
+          value={notes}
+          onChange={(e) => setNotes(e.target.value)}
+          rows={2}
+        />
+
+        {hoursPreview !== null && (
+          <div className="rounded-lg p-4 bg-blue-50 dark:bg-blue-900/20 border border-blue-200 dark:border-blue-800">
+            <div className="flex items-center justify-between">
+              <span className="text-sm font-medium text-blue-900 dark:text-blue-100">Arbeitsstunden:</span>
+              <span className="text-xl font-bold text-blue-600 dark:text-blue-400">{formatHours(hoursPreview)}</span>
+            </div>
+          </div>
+        )}
+
+        <ArbzgWarnings violations={arbzg?.violations} />
+
+        <div className="flex justify-between pt-4 border-t border-gray-200 dark:border-gray-700">
+          <Button type="button" variant="ghost" onClick={() => resolve(draft)}>
+            Verwerfen
+          </Button>
+          <div className="flex space-x-3">
+            <Button type="button" variant="secondary" onClick={() => setIsOpen(false)}>
+              Später
+            </Button>
+            <Button type="submit" variant="primary" disabled={createEntry.isPending}>
+              {createEntry.isPending ? (
+                <>
+                  <LoadingSpinner size="sm" className="mr-2" />
+                  Speichern...
+                </>
+              ) : (
+                'Speichern'
+              )}
+            </Button>
+          </div>
+        </div>
+      </form>
+    </Modal>
+  );
+}
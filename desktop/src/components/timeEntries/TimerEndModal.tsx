@@ -0,0 +1,105 @@
+/**
+ * Timer End Modal (desktop only)
+ * Asks for the end of a session found on startup when "Andere Uhrzeit…"
+ * was chosen in the restore prompt (see tracking.rs). The end must lie
+ * between the start of the session and now; the resulting drafts show up
+ * in `TimerDraftModal`.
+ */
+
+import { useEffect, useState, FormEvent } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { Modal } from '../ui/Modal';
+import { Input } from '../ui/Input';
+import { Button } from '../ui/Button';
+import { useAuthStore } from '../../store/authStore';
+import { isTauri } from '../../utils/tauri';
+
+/** `RestoredSession` of tracking.rs */
+interface RestoredSession {
+  snapshot: { startedAt: string | null };
+  lastSeenAt: string;
+}
+
+/** "2026-03-02T08:00:00" → "2026-03-02T08:00" (value of a datetime-local input) */
+function minutes(value: string): string {
+  return value.substring(0, 16);
+}
+
+function localNow(): string {
+  const now = new Date();
+  now.setMinutes(now.getMinutes() - now.getTimezoneOffset());
+  return now.toISOString().substring(0, 16);
+}
+
+export function TimerEndModal() {
+  const { user } = useAuthStore();
+  const [session, setSession] = useState<RestoredSession | null>(null);
+  const [end, setEnd] = useState('');
+  const [error, setError] = useState('');
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    if (!isTauri() || !user) {
+      return;
+    }
+    const unlisten = listen<RestoredSession>('timer:choose-end', ({ payload }) => {
+      setSession(payload);
+      setEnd(minutes(payload.lastSeenAt));
+      setError('');
+    });
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => undefined);
+    };
+  }, [user]);
+
+  const handleSubmit = async (e: FormEvent) => {
+    e.preventDefault();
+    setSaving(true);
+    setError('');
+    try {
+      await invoke('timer_clock_out_at', { end: `${end}:00` });
+      setSession(null);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  if (!session) return null;
+
+  const startedAt = session.snapshot.startedAt;
+
+  return (
+    <Modal isOpen onClose={() => setSession(null)} title="Zeiterfassung beenden" size="sm">
+      <form onSubmit={handleSubmit} className="space-y-6">
+        {startedAt && (
+          <p className="text-sm text-gray-600 dark:text-gray-400">
+            Begonnen am {new Date(startedAt).toLocaleString('de-DE', { dateStyle: 'medium', timeStyle: 'short' })}
+          </p>
+        )}
+
+        <Input
+          label="Ende"
+          type="datetime-local"
+          value={end}
+          min={startedAt ? minutes(startedAt) : undefined}
+          max={localNow()}
+          onChange={(e) => setEnd(e.target.value)}
+          error={error}
+          required
+        />
+
+        <div className="flex justify-end space-x-3 pt-4 border-t border-gray-200 dark:border-gray-700">
+          <Button type="button" variant="secondary" onClick={() => setSession(null)}>
+            Weiterlaufen lassen
+          </Button>
+          <Button type="submit" variant="primary" disabled={saving || !end}>
+            Beenden
+          </Button>
+        </div>
+      </form>
+    </Modal>
+  );
+}
@@ -0,0 +1,289 @@
+/**
+ * Sync Conflict Indicator (desktop only)
+ * Header button with the number of parked outbox items (see sync.rs) and
+ * the dialog to resolve them: keep the local version, take the server copy
+ * or pick per field which side wins.
+ */
+
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { toast } from 'sonner';
+import { AlertTriangle } from 'lucide-react';
+import { Modal } from './Modal';
+import { Button } from './Button';
+import { isTauri } from '../../utils/tauri';
+
+type ConflictKind = 'modified' | 'deleted' | 'overlap' | 'rejected';
+type Fields = Record<string, unknown>;
+
+/** `SyncConflict` of offline_store.rs */
+interface SyncConflict {
+  id: number;
+  entity: 'time_entry' | 'absence_request';
+  recordId: number;
+  kind: ConflictKind;
+  localVersion: Fields | null;
+  serverVersion: Fields | null;
+  conflictingEntry: Fields | null;
+  outboxSeq: number;
+  message: string;
+  createdAt: string;
+}
+
+/** `Resolution` of offline_store.rs */
+type Resolution =
+  | { strategy: 'keep-mine' }
+  | { strategy: 'keep-server' }
+  | { strategy: 'merge'; fields: Fields };
+
+const KIND_LABELS: Record<ConflictKind, string> = {
+  modified: 'Auf dem Server geändert',
+  deleted: 'Auf dem Server gelöscht',
+  overlap: 'Überschneidung',
+  rejected: 'Vom Server abgelehnt',
+};
+
+/** Fields the user edits; bookkeeping columns are not compared */
+const FIELDS: Record<SyncConflict['entity'], Array<{ key: string; label: string }>> = {
+  time_entry: [
+    { key: 'date', label: 'Datum' },
+    { key: 'startTime', label: 'Beginn' },
+    { key: 'endTime', label: 'Ende' },
+    { key: 'breakMinutes', label: 'Pause (Min.)' },
+    { key: 'location', label: 'Arbeitsort' },
+    { key: 'activity', label: 'Tätigkeit' },
+    { key: 'project', label: 'Projekt' },
+    { key: 'notes', label: 'Notiz' },
+  ],
+  absence_request: [
+    { key: 'type', label: 'Art' },
+    { key: 'startDate', label: 'Von' },
+    { key: 'endDate', label: 'Bis' },
+    { key: 'reason', label: 'Grund' },
+  ],
+};
+
+function display(value: unknown): string {
+  return value === null || value === undefined || value === '' ? '—' : String(value);
+}
+
+function summary(conflict: SyncConflict): string {
+  const version = conflict.localVersion ?? conflict.serverVersion;
+  if (!version) return `#${conflict.recordId}`;
+  return conflict.entity === 'time_entry'
+    ? `Zeiteintrag ${display(version.date)}, ${display(version.startTime)}–${display(version.endTime)}`
+    : `Abwesenheit ${display(version.startDate)} bis ${display(version.endDate)}`;
+}
+
+export function SyncConflictIndicator() {
+  const [conflicts, setConflicts] = useState<SyncConflict[]>([]);
+  const [isOpen, setIsOpen] = useState(false);
+  const [selectedId, setSelectedId] = useState<number | null>(null);
+  const [useLocal, setUseLocal] = useState<Record<string, boolean>>({});
+  const [busy, setBusy] = useState(false);
+
+  const selected = conflicts.find((c) => c.id === selectedId) ?? null;
+
+  const load = useCallback(() => {
+    invoke<SyncConflict[]>('list_conflicts')
+      .then((list) => {
+        setConflicts(list);
+        if (list.length === 0) setIsOpen(false);
+      })
+      .catch(() => undefined);
+  }, []);
+
+  useEffect(() => {
+    if (!isTauri()) {
+      return;
+    }
+    load();
+    const unlisteners = ['sync:conflict', 'outbox:changed', 'sync:finished'].map((event) =>
+      listen(event, load)
+    );
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()).catch(() => undefined));
+    };
+  }, [load]);
+
+  const differing = selected
+    ? FIELDS[selected.entity].filter(
+        ({ key }) =>
+          selected.localVersion &&
+          selected.serverVersion &&
+          display(selected.localVersion[key]) !== display(selected.serverVersion[key])
+      )
+    : [];
+
+  const select = (conflict: SyncConflict) => {
+    setSelectedId(conflict.id);
+    setUseLocal({});
+  };
+
+  const resolve = async (resolution: Resolution) => {
+    if (!selected) return;
+    setBusy(true);
+    try {
+      await invoke('resolve_conflict', { id: selected.id, resolution });
+      toast.success('Konflikt gelöst; die Änderung wird synchronisiert');
+      setSelectedId(null);
+      load();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const merge = () => {
+    if (!selected?.localVersion) return;
+    const fields: Fields = {};
+    for (const { key } of differing) {
+      if (useLocal[key]) fields[key] = selected.localVersion[key];
+    }
+    resolve({ strategy: 'merge', fields });
+  };
+
+  if (conflicts.length === 0) return null;
+
+  const canMerge = differing.length > 0;
+
+  return (
+    <>
+      <button
+        type="button"
+        onClick={() => setIsOpen(true)}
+        title="Synchronisierungskonflikte"
+        className="flex items-center gap-1 px-3 py-1.5 rounded-lg bg-red-100 dark:bg-red-900/20 text-red-600 dark:text-red-400 text-sm font-medium"
+      >
+        <AlertTriangle className="w-4 h-4" />
+        {conflicts.length}
+      </button>
+
+      <Modal
+        isOpen={isOpen}
+        onClose={() => {
+          setIsOpen(false);
+          setSelectedId(null);
+        }}
+        title="Synchronisierungskonflikte"
+        size="xl"
+      >
+        {!selected ? (
+          <ul className="divide-y divide-gray-200 dark:divide-gray-700">
+            {conflicts.map((conflict) => (
+              <li key={conflict.id} className="py-3 flex items-center justify-between gap-4">
+                <div className="min-w-0">
+                  <p className="font-medium text-gray-900 dark:text-white">{summary(conflict)}</p>
+                  <p className="text-sm text-gray-600 dark:text-gray-400">
+                    {KIND_LABELS[conflict.kind]}: {conflict.message}
+                  </p>
+                </div>
+                <Button variant="secondary" size="sm" onClick={() => select(conflict)}>
+                  Lösen
+                </Button>
+              </li>
+            ))}
+          </ul>
+        ) : (
+          <div className="space-y-6">
+            <div>
+              <p className="font-medium text-gray-900 dark:text-white">{summary(selected)}</p>
+              <p className="text-sm text-gray-600 dark:text-gray-400">
+                {KIND_LABELS[selected.kind]}: {selected.message}
+              </p>
+            </div>
+
+            {selected.conflictingEntry && (
+              <div className="rounded-lg p-4 bg-yellow-50 dark:bg-yellow-900/20 border border-yellow-200 dark:border-yellow-800 text-sm text-yellow-800 dark:text-yellow-200">
+                Überschneidet sich mit dem Eintrag vom {display(selected.conflictingEntry.date)},{' '}
+                {display(selected.conflictingEntry.startTime)}–{display(selected.conflictingEntry.endTime)}
+              </div>
+            )}
+
+            <div className="overflow-x-auto">
+              <table className="min-w-full text-sm">
+                <thead>
+                  <tr className="border-b border-gray-200 dark:border-gray-700 text-left text-gray-700 dark:text-gray-300">
+                    <th className="px-2 py-1 font-medium">Feld</th>
+                    <th className="px-2 py-1 font-medium">Meine Version</th>
+                    <th className="px-2 py-1 font-medium">Server</th>
+                  </tr>
+                </thead>
+                <tbody>
+                  {FIELDS[selected.entity].map(({ key, label }) => {
+                    const isDifferent = differing.some((field) => field.key === key);
+                    return (
+                      <tr
+                        key={key}
+                        className={`border-b border-gray-100 dark:border-gray-800 ${
+                          isDifferent ? 'text-gray-900 dark:text-white' : 'text-gray-500 dark:text-gray-400'
+                        }`}
+                      >
+                        <td className="px-2 py-1">{label}</td>
+                        <td className="px-2 py-1">
+                          {isDifferent ? (
+                            <label className="flex items-center gap-2">
+                              <input
+                                type="radio"
+                                name={key}
+                                checked={!!useLocal[key]}
+                                onChange={() => setUseLocal({ ...useLocal, [key]: true })}
+                              />
+                              {display(selected.localVersion?.[key])}
+                            </label>
+                          ) : selected.localVersion ? (
+                            display(selected.localVersion[key])
+                          ) : (
+                            'gelöscht'
+                          )}
+                        </td>
+                        <td className="px-2 py-1">
+                          {isDifferent ? (
+                            <label className="flex items-center gap-2">
+                              <input
+                                type="radio"
+                                name={key}
+                                checked={!useLocal[key]}
+                                onChange={() => setUseLocal({ ...useLocal, [key]: false })}
+                              />
+                              {display(selected.serverVersion?.[key])}
+                            </label>
+                          ) : selected.serverVersion ? (
+                            display(selected.serverVersion[key])
+                          ) : (
+                            '—'
+                          )}
+                        </td>
+                      </tr>
+                    );
+                  })}
+                </tbody>
+              </table>
+            </div>
+
+            <div className="flex justify-between pt-4 border-t border-gray-200 dark:border-gray-700">
+              <Button type="button" variant="ghost" onClick={() => setSelectedId(null)}>
+                Zurück
+              </Button>
+              <div className="flex space-x-3">
+                <Button variant="secondary" onClick={() => resolve({ strategy: 'keep-server' })} disabled={busy}>
+                  Server-Version übernehmen
+                </Button>
+                {canMerge && (
+                  <Button variant="secondary" onClick={merge} disabled={busy}>
+                    Auswahl übernehmen
+                  </Button>
+                )}
+                <Button variant="primary" onClick={() => resolve({ strategy: 'keep-mine' })} disabled={busy}>
+                  Meine Version behalten
+                </Button>
+              </div>
+            </div>
+          </div>
+        )}
+      </Modal>
+    </>
+  );
+}
@@ -4,11 +4,14 @@
  *
  * Shows: Total Balance = Carryover from 2025 + Earned in 2026
  * Inspired by: Personio, DATEV
+ *
+ * Desktop: when the server is unreachable, the user's own balance is
+ * computed from the offline cache (including unsynced entries).
  */
 
 import { Card } from '../ui/Card';
 import { LoadingSpinner } from '../ui/LoadingSpinner';
-import { useOvertimeReport } from '../../hooks';
+import { useLocalOvertimeLedger, useOvertimeReport, useOvertimeYearBreakdown } from '../../hooks';
 import { useAuthStore } from '../../store/authStore';
 import {
   TrendingUp,
@@ -39,7 +42,19 @@ export function BalanceSummaryWidget({ userId }: BalanceSummaryWidgetProps) {
   // Fetch yearly data from /overtime/balance/:userId/year/:year
   const { data: yearlyReport, isLoading: loadingYear, error: yearError } = useOvertimeReport(targetUserId, currentYear);
 
-  const isLoading = loadingMonth || loadingYear;
+  // Desktop fallback: ledger over the offline cache (own balance only)
+  const isOwnBalance = !!currentUser && targetUserId === currentUser.id;
+  const { data: yearBreakdown } = useOvertimeYearBreakdown(isOwnBalance ? targetUserId : 0);
+  const { data: localLedger } = useLocalOvertimeLedger(
+    isOwnBalance ? currentUser : null,
+    currentYear,
+    yearBreakdown?.carryoverFromPreviousYear ?? 0
+  );
+  const monthKey = `${currentYear}-${String(currentMonth).padStart(2, '0')}`;
+  const localMonth = localLedger?.months.find((m) => m.month === monthKey);
+  const showLocal = (!monthlyReport || !yearlyReport) && !!localLedger;
+
+  const isLoading = (loadingMonth || loadingYear) && !showLocal;
 
   if (isLoading) {
     return (
@@ -58,7 +73,7 @@ export function BalanceSummaryWidget({ userId }: BalanceSummaryWidgetProps) {
   }
 
   // Show error details if data is missing
-  if (!monthlyReport || !yearlyReport) {
+  if ((!monthlyReport || !yearlyReport) && !showLocal) {
     console.error('Dashboard data missing:', {
       monthlyReport,
       yearlyReport,
@@ -92,9 +107,16 @@ export function BalanceSummaryWidget({ userId }: BalanceSummaryWidgetProps) {
   }
 
   // Calculate breakdown from yearly and monthly data
-  const totalBalance = yearlyReport.summary.overtime;
-  const earnedThisYear = yearlyReport.summary.overtime;
-  const currentMonthEarned = monthlyReport.summary.overtime;
+  const totalBalance = showLocal ? localLedger!.balance : yearlyReport!.summary.overtime;
+  const earnedThisYear = showLocal ? localLedger!.overtime : yearlyReport!.summary.overtime;
+  const monthSummary = showLocal
+    ? {
+        targetHours: localMonth?.targetHours ?? 0,
+        actualHours: localMonth?.actualHours ?? 0,
+        overtime: localMonth?.overtime ?? 0,
+      }
+    : monthlyReport!.summary;
+  const currentMonthEarned = monthSummary.overtime;
   const previousYear = currentYear - 1;
 
   // Calculate carryover: total - earned this year
@@ -198,10 +220,10 @@ export function BalanceSummaryWidget({ userId }: BalanceSummaryWidgetProps) {
         </div>
         <div className="flex items-center gap-4 text-xs text-gray-600 dark:text-gray-400">
           <div>
-            <span className="font-medium">Soll:</span> {formatHours(monthlyReport.summary.targetHours)}
+            <span className="font-medium">Soll:</span> {formatHours(monthSummary.targetHours)}
           </div>
           <div>
-            <span className="font-medium">Ist:</span> {formatHours(monthlyReport.summary.actualHours)}
+            <span className="font-medium">Ist:</span> {formatHours(monthSummary.actualHours)}
           </div>
         </div>
       </div>
@@ -231,7 +253,9 @@ export function BalanceSummaryWidget({ userId }: BalanceSummaryWidgetProps) {
       {/* Info Footer */}
       <div className="mt-4 pt-4 border-t border-gray-200 dark:border-gray-700">
         <p className="text-xs text-gray-500 dark:text-gray-400 text-center">
-          Live-Berechnung • Basis: Ist-Stunden - Soll-Stunden
+          {showLocal
+            ? 'Offline berechnet • inkl. noch nicht synchronisierter Einträge'
+            : 'Live-Berechnung • Basis: Ist-Stunden - Soll-Stunden'}
         </p>
       </div>
     </Card>
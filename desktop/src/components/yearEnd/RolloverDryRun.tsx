@@ -0,0 +1,242 @@
+/**
+ * Year-end rollover dry run (desktop only)
+ * Runs the server's rollover rules on an exported snapshot (rollover.rs):
+ * load the export, adjust the policy, review the result and save it as a
+ * signed summary. A saved summary can be checked for changes later.
+ */
+
+import { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { toast } from 'sonner';
+import { FileCheck, FolderOpen, Save } from 'lucide-react';
+import { Button } from '../ui/Button';
+
+/** `RolloverSnapshot` of rollover.rs, passed through unchanged */
+interface RolloverSnapshot {
+  year: number;
+  users: unknown[];
+}
+
+interface RolloverPolicy {
+  maxVacationCarryover: number | null;
+  vacationExpires: boolean;
+  maxOvertimeCarryover: number | null;
+}
+
+interface UserRollover {
+  userId: number;
+  name: string;
+  vacationRemaining: number;
+  vacationCarryover: number;
+  vacationCapped: number;
+  vacationExpiring: number;
+  overtimeBalance: number;
+  overtimeCarryover: number;
+  overtimeCapped: number;
+  changed: boolean;
+  warnings: string[];
+}
+
+interface RolloverReport {
+  year: number;
+  previousYear: number;
+  policy: RolloverPolicy;
+  expiryDate: string | null;
+  users: UserRollover[];
+  totals: {
+    vacationCarryover: number;
+    vacationCapped: number;
+    vacationExpiring: number;
+    overtimeCarryover: number;
+    overtimeCapped: number;
+    usersChanged: number;
+  };
+}
+
+const inputClass =
+  'w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-white';
+
+/** Empty input = no cap */
+function parseCap(value: string): number | null {
+  return value.trim() === '' ? null : Math.max(0, Number(value.replace(',', '.')) || 0);
+}
+
+export function RolloverDryRun() {
+  const [snapshot, setSnapshot] = useState<RolloverSnapshot | null>(null);
+  const [policy, setPolicy] = useState<RolloverPolicy>({
+    maxVacationCarryover: null,
+    vacationExpires: false,
+    maxOvertimeCarryover: null,
+  });
+  const [report, setReport] = useState<RolloverReport | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const simulate = async (source: RolloverSnapshot, nextPolicy: RolloverPolicy) => {
+    setBusy(true);
+    try {
+      setReport(await invoke<RolloverReport>('simulate_rollover', { snapshot: source, policy: nextPolicy }));
+    } catch (error) {
+      setReport(null);
+      toast.error(String(error));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const loadSnapshot = async () => {
+    try {
+      const loaded = await invoke<RolloverSnapshot | null>('load_rollover_snapshot');
+      if (!loaded) return;
+      setSnapshot(loaded);
+      await simulate(loaded, policy);
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const updatePolicy = (changes: Partial<RolloverPolicy>) => {
+    const next = { ...policy, ...changes };
+    setPolicy(next);
+    if (snapshot) simulate(snapshot, next);
+  };
+
+  const saveSummary = async () => {
+    if (!report) return;
+    try {
+      const summary = await invoke<object | null>('save_rollover_summary', { report });
+      if (summary) toast.success('Signierte Zusammenfassung gespeichert');
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const verifySummary = async () => {
+    try {
+      const valid = await invoke<boolean | null>('verify_rollover_summary');
+      if (valid === null) return;
+      if (valid) {
+        toast.success('Zusammenfassung ist unverändert');
+      } else {
+        toast.error('Zusammenfassung wurde verändert oder auf einem anderen Gerät signiert');
+      }
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  return (
+    <div className="bg-white dark:bg-gray-800 rounded-lg shadow p-6 mt-6">
+      <div className="flex items-start justify-between gap-4 mb-4">
+        <div>
+          <h2 className="text-xl font-semibold text-gray-900 dark:text-white">Probelauf</h2>
+          <p className="text-sm text-gray-600 dark:text-gray-400">
+            Jahreswechsel auf einem exportierten Stand berechnen, ohne Daten zu ändern
+          </p>
+        </div>
+        <div className="flex gap-2">
+          <Button variant="secondary" onClick={loadSnapshot} disabled={busy}>
+            <FolderOpen className="w-4 h-4 mr-2" />
+            Export laden
+          </Button>
+          <Button variant="secondary" onClick={verifySummary}>
+            <FileCheck className="w-4 h-4 mr-2" />
+            Zusammenfassung prüfen
+          </Button>
+        </div>
+      </div>
+
+      <div className="grid grid-cols-1 md:grid-cols-3 gap-4 mb-4">
+        <div>
+          <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+            Max. Resturlaub (Tage)
+          </label>
+          <input
+            type="number"
+            min={0}
+            placeholder="unbegrenzt"
+            value={policy.maxVacationCarryover ?? ''}
+            onChange={(e) => updatePolicy({ maxVacationCarryover: parseCap(e.target.value) })}
+            className={inputClass}
+          />
+        </div>
+        <div>
+          <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2">
+            Max. Überstunden (h)
+          </label>
+          <input
+            type="number"
+            min={0}
+            placeholder="unbegrenzt"
+            value={policy.maxOvertimeCarryover ?? ''}
+            onChange={(e) => updatePolicy({ maxOvertimeCarryover: parseCap(e.target.value) })}
+            className={inputClass}
+          />
+        </div>
+        <label className="flex items-center gap-3 text-sm text-gray-900 dark:text-white md:mt-8">
+          <input
+            type="checkbox"
+            checked={policy.vacationExpires}
+            onChange={(e) => updatePolicy({ vacationExpires: e.target.checked })}
+            className="w-4 h-4"
+          />
+          Resturlaub verfällt am 31.03.
+        </label>
+      </div>
+
+      {report && (
+        <div className="space-y-4">
+          <div className="bg-gray-50 dark:bg-gray-700 rounded-lg p-4 text-sm text-gray-700 dark:text-gray-300">
+            <div className="font-semibold text-gray-900 dark:text-white mb-1">
+              Übertragung: {report.previousYear} → {report.year}
+            </div>
+            Urlaub: {report.totals.vacationCarryover} Tage
+            {report.totals.vacationCapped > 0 && `, ${report.totals.vacationCapped} gekappt`}
+            {report.totals.vacationExpiring > 0 &&
+              `, ${report.totals.vacationExpiring} verfallen am ${new Date(report.expiryDate!).toLocaleDateString('de-DE')}`}
+            {' · '}Überstunden: {report.totals.overtimeCarryover.toFixed(1)}h
+            {report.totals.overtimeCapped > 0 && `, ${report.totals.overtimeCapped.toFixed(1)}h gekappt`}
+            {' · '}
+            {report.totals.usersChanged} von {report.users.length} Mitarbeitern mit Änderung
+          </div>
+
+          <div className="overflow-x-auto max-h-96">
+            <table className="min-w-full text-sm">
+              <thead>
+                <tr className="border-b border-gray-200 dark:border-gray-700 text-left text-gray-700 dark:text-gray-300">
+                  <th className="px-2 py-1 font-medium">Mitarbeiter</th>
+                  <th className="px-2 py-1 font-medium text-right">Resturlaub</th>
+                  <th className="px-2 py-1 font-medium text-right">Übertrag</th>
+                  <th className="px-2 py-1 font-medium text-right">Überstunden</th>
+                  <th className="px-2 py-1 font-medium text-right">Übertrag</th>
+                  <th className="px-2 py-1 font-medium">Hinweise</th>
+                </tr>
+              </thead>
+              <tbody>
+                {report.users.map((user) => (
+                  <tr
+                    key={user.userId}
+                    className={`border-b border-gray-100 dark:border-gray-800 ${
+                      user.changed ? 'text-gray-900 dark:text-white' : 'text-gray-500 dark:text-gray-400'
+                    }`}
+                  >
+                    <td className="px-2 py-1">{user.name}</td>
+                    <td className="px-2 py-1 text-right">{user.vacationRemaining}</td>
+                    <td className="px-2 py-1 text-right">{user.vacationCarryover}</td>
+                    <td className="px-2 py-1 text-right">{user.overtimeBalance.toFixed(1)}h</td>
+                    <td className="px-2 py-1 text-right">{user.overtimeCarryover.toFixed(1)}h</td>
+                    <td className="px-2 py-1 text-amber-600 dark:text-amber-400">{user.warnings.join(', ')}</td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+
+          <Button variant="primary" onClick={saveSummary} disabled={busy}>
+            <Save className="w-4 h-4 mr-2" />
+            Signierte Zusammenfassung speichern
+          </Button>
+        </div>
+      )}
+    </div>
+  );
+}
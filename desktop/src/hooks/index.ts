@@ -80,7 +80,9 @@ export {
   useHolidays,
   useCurrentYearHolidays,
   useMultiYearHolidays,
+  useLocalHolidays,
 } from './useHolidays';
+export type { GermanState, RegionalHolidayOptions, LocalHoliday } from './useHolidays';
 
 // Keyboard Shortcuts
 export {
@@ -113,3 +115,16 @@ export type {
 // WebSocket
 export { useWebSocket } from './useWebSocket';
 export type { WSEventType, WSEvent } from './useWebSocket';
+
+// Local overtime engine (desktop)
+export { useLocalOvertimeLedger, useLocalVacationProjection } from './useLocalOvertime';
+export type {
+  LocalOvertimeLedger,
+  LocalOvertimeMonth,
+  LocalOvertimeTransaction,
+  LocalVacationProjection,
+} from './useLocalOvertime';
+
+// ArbZG check (desktop)
+export { useArbzgCheck } from './useArbzgCheck';
+export type { ArbzgCandidate, ArbzgReport, ArbzgViolation, ArbzgSeverity } from './useArbzgCheck';
@@ -38,12 +38,14 @@ export const QUERY_GROUPS = {
     'weeklyOvertime',                // For weekly overtime calculations
     'allUsersOvertimeSummary',       // For all users overtime summary
     'overtime-balances',             // For year-end rollover
+    'localOvertime',                 // Desktop: ledger over the offline cache
   ],
 
   // Vacation-related queries
   vacation: [
     'vacationBalance',
     'vacation-balances',
+    'localVacation',                 // Desktop: projection over the offline cache
   ],
 
   // Absence-related queries
@@ -0,0 +1,74 @@
+/**
+ * ArbZG check of an entry while it is edited (desktop only)
+ *
+ * Runs the native rule engine (arbzg.rs) against the user's entries of the
+ * offline cache: the Monday–Sunday week of the entry for the weekly limit
+ * and the day before for the rest period. Like the server, the result only
+ * warns and never blocks saving.
+ */
+
+import { useQuery } from '@tanstack/react-query';
+import { invoke } from '@tauri-apps/api/core';
+import { isTauri } from '../utils/tauri';
+import { isValidTime, isValidTimeRange } from '../utils';
+
+export type ArbzgSeverity = 'info' | 'warning' | 'critical';
+
+/** `Violation` of arbzg.rs */
+export interface ArbzgViolation {
+  rule: 'maxDailyHours' | 'breakTime' | 'restPeriod' | 'maxWeeklyHours';
+  severity: ArbzgSeverity;
+  requiredMinutes: number;
+  actualMinutes: number;
+  message: string;
+}
+
+export interface ArbzgReport {
+  hours: number;
+  violations: ArbzgViolation[];
+}
+
+/** `EntryCandidate` of arbzg.rs */
+export interface ArbzgCandidate {
+  date: string;
+  startTime: string;
+  endTime: string;
+  breakMinutes: number;
+  /** Entry being edited, so it is not counted twice */
+  excludeEntryId?: number;
+}
+
+/** "2026-03-04" shifted by `days`, without a UTC round trip */
+function shiftDate(date: string, days: number): string {
+  const [year, month, day] = date.split('-').map(Number);
+  const shifted = new Date(year, month - 1, day + days);
+  return `${shifted.getFullYear()}-${String(shifted.getMonth() + 1).padStart(2, '0')}-${String(shifted.getDate()).padStart(2, '0')}`;
+}
+
+/** Cached entries from the day before the entry's week to its Sunday */
+function bookedRange(date: string): { from: string; to: string } {
+  const [year, month, day] = date.split('-').map(Number);
+  const weekday = new Date(year, month - 1, day).getDay(); // 0 = Sunday
+  const toMonday = weekday === 0 ? -6 : 1 - weekday;
+  return { from: shiftDate(date, toMonday - 1), to: shiftDate(date, toMonday + 6) };
+}
+
+export function useArbzgCheck(userId: number | undefined, candidate: ArbzgCandidate | null) {
+  const valid =
+    !!candidate &&
+    /^\d{4}-\d{2}-\d{2}$/.test(candidate.date) &&
+    isValidTime(candidate.startTime) &&
+    isValidTime(candidate.endTime) &&
+    isValidTimeRange(candidate.startTime, candidate.endTime);
+
+  return useQuery({
+    queryKey: ['arbzgCheck', userId, candidate],
+    queryFn: async () => {
+      const { from, to } = bookedRange(candidate!.date);
+      const booked = await invoke<unknown[]>('offline_list_time_entries', { userId, from, to });
+      return invoke<ArbzgReport>('validate_arbzg', { entry: candidate, booked });
+    },
+    enabled: isTauri() && !!userId && valid,
+    staleTime: 0,
+  });
+}
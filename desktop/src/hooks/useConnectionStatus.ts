@@ -1,5 +1,6 @@
 import { useState, useEffect } from 'react';
 import { universalFetch } from '../lib/tauriHttpClient';
+import { API_BASE_URL } from '../api/client';
 
 export type ConnectionStatus = 'online' | 'offline' | 'server-offline';
 
@@ -22,8 +23,8 @@ export function useConnectionStatus(): ConnectionState {
   const [serverReachable, setServerReachable] = useState(true);
   const [lastChecked, setLastChecked] = useState<Date | null>(null);
 
-  // Get the API URL being used (for debugging)
-  const apiUrl = import.meta.env.VITE_API_URL || 'http://localhost:3000';
+  // Get the API URL being used (active server profile on desktop)
+  const apiUrl = API_BASE_URL;
 
   // Monitor browser online/offline events
   useEffect(() => {
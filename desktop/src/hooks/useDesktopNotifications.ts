@@ -7,6 +7,7 @@ import { useEffect, useRef } from 'react';
 import { useNotifications } from './useNotifications';
 import { notificationService } from '../services/notificationService';
 import type { Notification } from '../types';
+import { isTauri } from '../utils/tauri';
 
 export function useDesktopNotifications(userId: number | undefined) {
   const { data: notificationsData } = useNotifications(userId!);
@@ -42,6 +43,12 @@ export function useDesktopNotifications(userId: number | undefined) {
 function sendDesktopNotification(notification: Notification): void {
   const { type, title, message } = notification;
 
+  // Desktop: approvals and rejections are already notified natively by the
+  // Rust WebSocket client (realtime.rs), also while the window is hidden
+  if (isTauri() && (type === 'absence_approved' || type === 'absence_rejected')) {
+    return;
+  }
+
   // Map database notification types to notification service types
   switch (type) {
     case 'absence_approved':
@@ -3,7 +3,26 @@
  */
 
 import { useQuery } from '@tanstack/react-query';
+import { invoke } from '@tauri-apps/api/core';
 import { apiClient } from '../api/client';
+import { isTauri } from '../utils/tauri';
+
+export type GermanState =
+  | 'BW' | 'BY' | 'BE' | 'BB' | 'HB' | 'HH' | 'HE' | 'MV'
+  | 'NI' | 'NW' | 'RP' | 'SL' | 'SN' | 'ST' | 'SH' | 'TH';
+
+export interface RegionalHolidayOptions {
+  augsburg?: boolean;
+  catholicCommunity?: boolean | null;
+  halfDays?: boolean;
+}
+
+export interface LocalHoliday {
+  date: string;
+  name: string;
+  federal: boolean;
+  halfDay: boolean;
+}
 
 /**
  * Get all holidays for a specific year
@@ -67,3 +86,17 @@ export function useMultiYearHolidays() {
     staleTime: 24 * 60 * 60 * 1000, // 24 hours - holidays don't change often
   });
 }
+
+/**
+ * Holidays computed offline by the desktop app for any Bundesland
+ * @param state - Bundesland of the user's workplace
+ * @param options - Municipality rules (Augsburg, Catholic community, half days)
+ */
+export function useLocalHolidays(state: GermanState, year: number, options?: RegionalHolidayOptions) {
+  return useQuery({
+    queryKey: ['holidays', 'local', state, year, options],
+    queryFn: () => invoke<LocalHoliday[]>('holidays_for_year', { state, year, options: options ?? null }),
+    enabled: isTauri(),
+    staleTime: Infinity, // Pure calculation
+  });
+}
@@ -0,0 +1,42 @@
+/**
+ * Launch View Hook
+ * Opens the view a launch command asked for (e.g. `--new-absence` or
+ * `timetracker://absences/new`, see `launch.rs`). The request is kept on
+ * the Rust side until taken, so one made before login or before the
+ * window was ready is not lost.
+ */
+
+import { useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { useUIStore } from '../store/uiStore';
+import { isTauri } from '../utils/tauri';
+
+type LaunchView = 'absence-request';
+
+export function useLaunchView(enabled: boolean) {
+  const { setCurrentView, setAbsenceFormRequested } = useUIStore();
+
+  useEffect(() => {
+    if (!enabled || !isTauri()) {
+      return;
+    }
+
+    const takeView = () => {
+      invoke<LaunchView | null>('take_launch_view')
+        .then((view) => {
+          if (view === 'absence-request') {
+            setCurrentView('absences');
+            setAbsenceFormRequested(true);
+          }
+        })
+        .catch(() => undefined);
+    };
+
+    takeView();
+    const unlisten = listen('launch:view', takeView);
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => undefined);
+    };
+  }, [enabled, setCurrentView, setAbsenceFormRequested]);
+}
@@ -0,0 +1,147 @@
+/**
+ * Locally computed overtime and vacation balance (desktop only)
+ *
+ * Runs the native overtime engine over the offline cache, so the balance
+ * is available without a connection and already includes entries that are
+ * not synced yet. Holidays and corrections come from the regular queries.
+ */
+
+import { useQuery } from '@tanstack/react-query';
+import { invoke } from '@tauri-apps/api/core';
+import { isTauri } from '../utils/tauri';
+import { useHolidays } from './useHolidays';
+import { useOvertimeCorrections } from './useOvertimeCorrections';
+import type { User } from '../types';
+
+export type OvertimeTransactionType =
+  | 'time_entry'
+  | 'vacation_credit'
+  | 'sick_credit'
+  | 'overtime_comp_credit'
+  | 'special_credit'
+  | 'unpaid_deduction'
+  | 'correction'
+  | 'carryover';
+
+export interface LocalOvertimeTransaction {
+  date: string;
+  type: OvertimeTransactionType;
+  hours: number;
+  balanceBefore: number;
+  balanceAfter: number;
+  description: string;
+  absenceId?: number;
+}
+
+export interface LocalOvertimeMonth {
+  month: string; // YYYY-MM
+  targetHours: number;
+  actualHours: number;
+  overtime: number;
+  balance: number;
+}
+
+export interface LocalOvertimeLedger {
+  carryover: number;
+  targetHours: number;
+  actualHours: number;
+  overtime: number;
+  balance: number;
+  months: LocalOvertimeMonth[];
+  days: Array<{
+    date: string;
+    targetHours: number;
+    workedHours: number;
+    absence: string | null;
+    creditHours: number;
+    correctionHours: number;
+    overtime: number;
+    balance: number;
+  }>;
+  transactions: LocalOvertimeTransaction[];
+}
+
+/**
+ * Ledger of the user from January 1st (or the hire date) up to today
+ * @param carryover Balance brought forward from the previous year
+ */
+export function useLocalOvertimeLedger(user: User | null | undefined, year: number, carryover = 0) {
+  const { data: holidays } = useHolidays(year);
+  const { data: corrections } = useOvertimeCorrections(user?.id, year);
+
+  const today = new Date().toISOString().substring(0, 10);
+  const to = today.startsWith(`${year}-`) ? today : `${year}-12-31`;
+
+  return useQuery({
+    queryKey: ['localOvertime', user?.id, year, to, carryover, holidays, corrections],
+    queryFn: () =>
+      invoke<LocalOvertimeLedger>('offline_overtime_ledger', {
+        userId: user!.id,
+        input: {
+          employee: {
+            weeklyHours: user!.weeklyHours,
+            workSchedule: user!.workSchedule ?? null,
+            hireDate: user!.hireDate,
+            endDate: user!.endDate ?? null,
+          },
+          from: `${year}-01-01`,
+          to,
+          carryover,
+          holidays: (holidays || []).map((h) => h.date),
+          corrections: (corrections || []).map((c) => ({ date: c.date, hours: c.hours, reason: c.reason })),
+        },
+      }),
+    enabled: isTauri() && !!user && holidays !== undefined,
+    staleTime: 0,
+  });
+}
+
+export interface LocalVacationProjection {
+  year: number;
+  entitlement: number;
+  carryover: number;
+  available: number;
+  taken: number;
+  pending: number;
+  remaining: number;
+  proposedDays: number;
+  projected: number;
+}
+
+/**
+ * Vacation days left after approved, pending and a proposed request
+ * @param proposed Range of the request that is about to be booked
+ * @param balance Entitlement/carryover from `vacation_balance`, if known
+ */
+export function useLocalVacationProjection(
+  user: User | null | undefined,
+  year: number,
+  proposed?: { startDate: string; endDate: string } | null,
+  balance?: { entitlement: number; carryover: number } | null
+) {
+  const { data: holidays } = useHolidays(year);
+
+  return useQuery({
+    queryKey: ['localVacation', user?.id, year, proposed, balance, holidays],
+    queryFn: () =>
+      invoke<LocalVacationProjection>('offline_vacation_projection', {
+        userId: user!.id,
+        input: {
+          employee: {
+            weeklyHours: user!.weeklyHours,
+            workSchedule: user!.workSchedule ?? null,
+            hireDate: user!.hireDate,
+            endDate: user!.endDate ?? null,
+          },
+          vacationDaysPerYear: user!.vacationDaysPerYear,
+          year,
+          entitlement: balance?.entitlement ?? null,
+          carryover: balance?.carryover ?? 0,
+          holidays: (holidays || []).map((h) => h.date),
+          proposed: proposed ?? null,
+        },
+      }),
+    enabled: isTauri() && !!user && holidays !== undefined,
+    staleTime: 0,
+  });
+}
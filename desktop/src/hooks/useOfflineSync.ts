@@ -0,0 +1,51 @@
+/**
+ * Offline Sync Hook
+ * Keeps the queries in step with the offline store (desktop only): local
+ * changes, replayed outbox items and cache refreshes from the server all
+ * invalidate the affected queries. Parked items are reported as a toast
+ * and resolved with `SyncConflictIndicator` in the header.
+ */
+
+import { useEffect } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { listen } from '@tauri-apps/api/event';
+import { toast } from 'sonner';
+import { isTauri } from '../utils/tauri';
+import { invalidateAbsenceAffectedQueries, invalidateTimeEntryAffectedQueries } from './invalidationHelpers';
+
+interface SyncConflict {
+  id: number;
+  entity: 'time_entry' | 'absence_request';
+  message: string;
+}
+
+const SYNC_EVENTS = ['outbox:changed', 'sync:finished', 'offline:cache-updated'];
+
+export function useOfflineSync(enabled: boolean) {
+  const queryClient = useQueryClient();
+
+  useEffect(() => {
+    if (!enabled || !isTauri()) {
+      return;
+    }
+
+    const refresh = () => {
+      invalidateTimeEntryAffectedQueries(queryClient);
+      invalidateAbsenceAffectedQueries(queryClient);
+    };
+
+    const unlisteners = [
+      ...SYNC_EVENTS.map((event) => listen(event, refresh)),
+      listen<SyncConflict>('sync:conflict', ({ payload }) => {
+        const what = payload.entity === 'time_entry' ? 'Zeiteintrag' : 'Abwesenheitsantrag';
+        toast.error(`${what} konnte nicht synchronisiert werden`, {
+          description: payload.message,
+        });
+        refresh();
+      }),
+    ];
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()).catch(() => undefined));
+    };
+  }, [enabled, queryClient]);
+}
@@ -18,6 +18,8 @@ interface CreateTimeEntryData {
   endTime: string;
   breakMinutes?: number;
   location: 'office' | 'homeoffice' | 'field';
+  activity?: string | null;
+  project?: string | null;
   notes?: string;
 }
 
@@ -1,5 +1,9 @@
 import { useEffect, useRef, useState, useCallback } from 'react';
 import { useQueryClient } from '@tanstack/react-query';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { WS_URL } from '../api/client';
+import { isTauri } from '../utils/tauri';
 
 // 🔥 VERSION MARKER - Beweist dass neue Version läuft
 console.log('🚀 useWebSocket.ts LOADED - VERSION 3.0 (Post-Connection Auth) - ' + new Date().toISOString());
@@ -13,19 +17,25 @@ console.log('🚀 useWebSocket.ts LOADED - VERSION 3.0 (Post-Connection Auth) -
  * - Auto-invalidates TanStack Query caches on backend events
  * - Automatic reconnection with exponential backoff
  * - Graceful degradation (app works without WebSocket)
+ *
+ * In the desktop app the connection is kept by Rust (realtime.rs) so it
+ * survives a hidden window; the hook only listens to the re-emitted events.
  */
 
 // Event Types (must match server/src/websocket/server.ts)
-export type WSEventType =
-  | 'overtime:updated'
-  | 'time-entry:created'
-  | 'time-entry:updated'
-  | 'time-entry:deleted'
-  | 'absence:created'
-  | 'absence:approved'
-  | 'absence:rejected'
-  | 'correction:created'
-  | 'correction:deleted';
+export const WS_EVENT_TYPES = [
+  'overtime:updated',
+  'time-entry:created',
+  'time-entry:updated',
+  'time-entry:deleted',
+  'absence:created',
+  'absence:approved',
+  'absence:rejected',
+  'correction:created',
+  'correction:deleted',
+] as const;
+
+export type WSEventType = (typeof WS_EVENT_TYPES)[number];
 
 export interface WSEvent {
   type: WSEventType;
@@ -44,21 +54,8 @@ interface UseWebSocketReturn {
   reconnecting: boolean;
 }
 
-// WebSocket URL (development vs production)
-const getWebSocketUrl = (): string => {
-  console.log('[WebSocket] 🔥 getWebSocketUrl called (v3.0 - Post-Connection Auth)');
-  const baseUrl = import.meta.env.VITE_API_URL || 'http://localhost:3000';
-  console.log('[WebSocket] baseUrl:', baseUrl);
-  // Remove /api prefix if present (backend WebSocket is on /ws, not /api/ws)
-  const cleanUrl = baseUrl.replace('/api', '');
-  console.log('[WebSocket] cleanUrl:', cleanUrl);
-  const wsUrl = cleanUrl.replace(/^http/, 'ws');
-  console.log('[WebSocket] wsUrl:', wsUrl);
-
-  const finalUrl = `${wsUrl}/ws`;
-  console.log('[WebSocket] 🔥🔥🔥 v3.0 - Professional Post-Connection Auth 🔥🔥🔥 URL:', finalUrl);
-  return finalUrl;
-};
+/** Payload of the Rust `realtime:status` event */
+type RealtimeStatus = UseWebSocketReturn;
 
 export function useWebSocket({ userId, enabled = true }: UseWebSocketOptions): UseWebSocketReturn {
   const queryClient = useQueryClient();
@@ -154,6 +151,11 @@ export function useWebSocket({ userId, enabled = true }: UseWebSocketOptions): U
    * Connect to WebSocket server
    */
   const connect = useCallback(() => {
+    // Desktop: Rust owns the connection (see effect below)
+    if (isTauri()) {
+      return;
+    }
+
     if (!enabled || !userId) {
       console.log('[WebSocket] Connection disabled or no userId');
       return;
@@ -165,7 +167,7 @@ export function useWebSocket({ userId, enabled = true }: UseWebSocketOptions): U
     }
 
     try {
-      const wsUrl = getWebSocketUrl();
+      const wsUrl = WS_URL;
       console.log('[WebSocket] Connecting to:', wsUrl);
 
       const ws = new WebSocket(wsUrl);
@@ -256,6 +258,28 @@ export function useWebSocket({ userId, enabled = true }: UseWebSocketOptions): U
     return () => disconnect();
   }, [connect, disconnect]);
 
+  // Desktop: server events re-emitted by the Rust WebSocket client
+  useEffect(() => {
+    if (!isTauri() || !enabled || !userId) {
+      return;
+    }
+
+    const applyStatus = (status: RealtimeStatus) => {
+      setConnected(status.connected);
+      setReconnecting(status.reconnecting);
+    };
+
+    const unlisteners = [
+      ...WS_EVENT_TYPES.map((type) => listen<WSEvent>(type, (event) => handleEvent(event.payload))),
+      listen<RealtimeStatus>('realtime:status', (event) => applyStatus(event.payload)),
+    ];
+    invoke<RealtimeStatus>('realtime_status').then(applyStatus).catch(() => undefined);
+
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()).catch(() => undefined));
+    };
+  }, [enabled, userId, handleEvent]);
+
   return {
     connected,
     reconnecting,
@@ -1,84 +1,77 @@
+import { invoke } from '@tauri-apps/api/core';
 import { debugLog } from '../components/DebugPanel';
+import { isTauri } from '../utils/tauri';
 
 /**
  * Tauri HTTP Client Wrapper
  *
- * CRITICAL FIX: In development mode, we use browser's native fetch()
- * because it automatically handles cookies correctly.
+ * In the desktop app, requests go through the Rust `session_fetch` command.
+ * The JWT lives in the Rust session store (encrypted, never exposed to
+ * the webview) and the Authorization header is attached there.
  *
- * The Tauri HTTP plugin doesn't have built-in cookie persistence,
- * so for now we rely on the browser's cookie management which works
- * perfectly in dev mode (http://localhost:1420).
+ * In a plain browser (dev mode at http://localhost:1420) we fall back to
+ * the native fetch() with session cookies.
  */
 
 interface FetchOptions extends RequestInit {
   credentials?: RequestCredentials;
 }
 
+/** Response of the Rust `session_fetch` command (body is base64) */
+interface SessionFetchResponse {
+  status: number;
+  statusText: string;
+  headers: Record<string, string>;
+  body: string;
+}
+
+async function sessionFetch(url: string, options: FetchOptions, headers: Headers): Promise<Response> {
+  const result = await invoke<SessionFetchResponse>('session_fetch', {
+    request: {
+      url,
+      method: options.method || 'GET',
+      headers: Object.fromEntries(headers.entries()),
+      body: typeof options.body === 'string' ? options.body : undefined,
+    },
+  });
+
+  const bytes = Uint8Array.from(atob(result.body), (c) => c.charCodeAt(0));
+  // Responses with these statuses must not have a body
+  const body = [204, 205, 304].includes(result.status) ? null : bytes;
+  return new Response(body, {
+    status: result.status,
+    statusText: result.statusText,
+    headers: result.headers,
+  });
+}
+
 /**
  * Universal fetch that works in both Tauri and browser
- *
- * CRITICAL: Automatically adds JWT token from localStorage to Authorization header.
- * This ensures all requests (including binary downloads) are authenticated.
  */
 export async function universalFetch(
   url: string | URL,
   options: FetchOptions = {}
 ): Promise<Response> {
   const urlString = url.toString();
-
-  // Get JWT token from localStorage and add to headers
-  const TOKEN_KEY = 'timetracking_jwt_token';
-  const token = typeof localStorage !== 'undefined' ? localStorage.getItem(TOKEN_KEY) : null;
-
   const headers = new Headers(options.headers);
-  if (token) {
-    headers.set('Authorization', `Bearer ${token}`);
-  }
-
-  // Update options with headers including JWT
-  const fetchOptions: FetchOptions = {
-    ...options,
-    headers,
-  };
 
   // Log request
   debugLog({
     type: 'request',
     method: options.method || 'GET',
     url: urlString,
-    data: options.body ? JSON.parse(options.body as string) : undefined,
-    message: `🌐 Making request (credentials: ${options.credentials}, JWT: ${token ? 'present' : 'none'})`,
+    data: typeof options.body === 'string' ? JSON.parse(options.body) : undefined,
+    message: `🌐 Making request (credentials: ${options.credentials})`,
   });
 
-  // ========================================
-  // 🔥 LAYER 3: NETWORK LAYER VERIFICATION 🔥
-  // ========================================
-  console.log('');
-  console.log('🔥 LAYER 3 DEBUG - Final Fetch Call:');
-  console.log('  🌐 URL (toString):', url.toString());
-  console.log('  🌐 URL (typeof):', typeof url);
-  console.log('  📝 Method:', options.method || 'GET');
-  console.log('  🔑 Headers:', Object.fromEntries(headers.entries()));
-  console.log('  🍪 Credentials:', options.credentials);
-  console.log('  🔐 JWT Token:', token ? `${token.substring(0, 20)}...` : 'NONE');
-  console.log('');
-
   try {
-    // Use browser's native fetch which handles cookies correctly
-    const response = await fetch(url, fetchOptions);
-
-    // ========================================
-    // 🔥 LAYER 3: FETCH RESPONSE DEBUG 🔥
-    // ========================================
-    console.log('🔥 LAYER 3 DEBUG - Fetch Response:');
-    console.log('  ✅ Status:', response.status, response.statusText);
-    console.log('  🌐 Response URL (actual):', response.url); // WICHTIG: Zeigt Redirects!
-    console.log('  🌐 Response URL differs from request?', response.url !== url.toString());
-    console.log('');
-
-    // Read response
-    const text = await response.text();
+    const response = isTauri()
+      ? await sessionFetch(urlString, options, headers)
+      : await fetch(url, { ...options, headers });
+
+    // Read response (binary downloads are passed through untouched)
+    const buffer = await response.arrayBuffer();
+    const text = new TextDecoder().decode(buffer);
     let data: any;
 
     try {
@@ -126,7 +119,7 @@ export async function universalFetch(
     }
 
     // Return response with text already consumed, re-create it
-    return new Response(text, {
+    return new Response(buffer.byteLength > 0 ? buffer : null, {
       status: response.status,
       statusText: response.statusText,
       headers: response.headers,
@@ -2,7 +2,11 @@ import React from 'react';
 import ReactDOM from 'react-dom/client';
 import { QueryClient, QueryClientProvider } from '@tanstack/react-query';
 import { Toaster } from 'sonner';
+import { getCurrentWindow } from '@tauri-apps/api/window';
 import App from './App';
+import { QuickEntryWindow } from './components/timeEntries/QuickEntryWindow';
+import { initServerProfile } from './api/client';
+import { isTauri } from './utils/tauri';
 import './styles.css';
 
 // Create React Query client
@@ -16,11 +20,23 @@ const queryClient = new QueryClient({
   },
 });
 
-ReactDOM.createRoot(document.getElementById('root')!).render(
-  <React.StrictMode>
-    <QueryClientProvider client={queryClient}>
-      <App />
-      <Toaster position="top-right" richColors />
-    </QueryClientProvider>
-  </React.StrictMode>
-);
+// The quick-entry window only renders its form (its capability allows nothing else)
+if (isTauri() && getCurrentWindow().label === 'quick-entry') {
+  ReactDOM.createRoot(document.getElementById('root')!).render(
+    <React.StrictMode>
+      <QuickEntryWindow />
+    </React.StrictMode>
+  );
+} else {
+  // Desktop: the server comes from the active server profile
+  initServerProfile().finally(() => {
+    ReactDOM.createRoot(document.getElementById('root')!).render(
+      <React.StrictMode>
+        <QueryClientProvider client={queryClient}>
+          <App />
+          <Toaster position="top-right" richColors />
+        </QueryClientProvider>
+      </React.StrictMode>
+    );
+  });
+}
@@ -10,8 +10,9 @@
  * - Statistics (vacation days, sick days, etc.)
  */
 
-import { useState, useMemo } from 'react';
+import { useState, useMemo, useEffect } from 'react';
 import { useAuthStore } from '../store/authStore';
+import { useUIStore } from '../store/uiStore';
 import { Card, CardHeader, CardTitle, CardContent } from '../components/ui/Card';
 import { Button } from '../components/ui/Button';
 import { Select } from '../components/ui/Select';
@@ -54,6 +55,15 @@ export function AbsencesPage() {
 
   // Create Absence Modal State
   const [createModalOpen, setCreateModalOpen] = useState(false);
+  const { absenceFormRequested, setAbsenceFormRequested } = useUIStore();
+
+  // Requested by a launch command (e.g. timetracker://absences/new)
+  useEffect(() => {
+    if (absenceFormRequested) {
+      setCreateModalOpen(true);
+      setAbsenceFormRequested(false);
+    }
+  }, [absenceFormRequested, setAbsenceFormRequested]);
 
   // Cancel Modal State (Admin)
   const [cancelModalOpen, setCancelModalOpen] = useState(false);
@@ -13,6 +13,7 @@
 import { useState } from 'react';
 import { useAuthStore } from '../store/authStore';
 import { useUsers } from '../hooks';
+import { useHolidays } from '../hooks/useHolidays';
 import { useAllUsersOvertimeReports, useOvertimeReport } from '../hooks/useOvertimeReports';
 import { Card, CardHeader, CardTitle, CardContent } from '../components/ui/Card';
 import { Button } from '../components/ui/Button';
@@ -26,11 +27,19 @@ import { OvertimeTransactions } from '../components/worktime/OvertimeTransaction
 import { AbsencesBreakdown } from '../components/reports/AbsencesBreakdown';
 import { CorrectionsTable } from '../components/corrections/CorrectionsTable';
 import { OvertimeCorrectionModal } from '../components/corrections/OvertimeCorrectionModal';
-import { Download, BarChart3, TrendingUp } from 'lucide-react';
+import { Download, BarChart3, TrendingUp, FileText, FileSpreadsheet } from 'lucide-react';
 import { toast } from 'sonner';
 import { exportDATEV, exportHistoricalCSV } from '../api/exports';
 import { getDateRangeFromFilters } from '../utils/dateRangeUtils';
 import { downloadBlob } from '../utils/downloadFile';
+import { isTauri } from '../utils/tauri';
+import {
+  exportDatevLodas,
+  exportReportXlsx,
+  exportTimesheetPdf,
+  fetchDatevMonth,
+  withDailyTargets,
+} from '../api/nativeExports';
 
 export function ReportsPage() {
   const { user: currentUser } = useAuthStore();
@@ -53,6 +62,7 @@ export function ReportsPage() {
 
   // Fetch data
   const { data: users } = useUsers(isAdmin);
+  const { data: holidays } = useHolidays(selectedYear);
   const { data: reports, isLoading } = useAllUsersOvertimeReports(selectedYear, selectedMonth, isAdmin);
 
   // For single user view: Use overtime_balance (Single Source of Truth)
@@ -154,6 +164,136 @@ export function ReportsPage() {
     }
   };
 
+  // Desktop: month exports rendered natively from the DATEV rows
+  const monthPrefix = selectedMonth
+    ? `${selectedYear}-${String(selectedMonth).padStart(2, '0')}`
+    : undefined;
+  const monthHolidays = (holidays || [])
+    .filter((h) => monthPrefix && h.date.startsWith(monthPrefix))
+    .map((h) => ({ date: h.date, name: h.name }));
+
+  const handleExportTimesheet = async () => {
+    if (!selectedMonth || selectedUserId === 'all') {
+      toast.error('Bitte einen Monat und einen Mitarbeiter auswählen');
+      return;
+    }
+
+    try {
+      setIsExporting(true);
+      toast.loading('Stundenzettel wird erstellt...', { id: 'timesheet-export' });
+
+      const employees = await fetchDatevMonth(selectedYear, selectedMonth);
+      const user = users?.find((u) => u.id === selectedUserId);
+      const employee = employees.find((e) => e.personnelNumber === selectedUserId) ?? {
+        personnelNumber: selectedUserId,
+        lastName: user?.lastName ?? '',
+        firstName: user?.firstName ?? '',
+        rows: [],
+        dailyTargets: [],
+      };
+      const [withTargets] = await withDailyTargets(
+        [employee],
+        users ?? [],
+        selectedYear,
+        selectedMonth,
+        monthHolidays
+      );
+
+      const path = await exportTimesheetPdf(withTargets, selectedYear, selectedMonth, monthHolidays);
+      if (path) {
+        toast.success(`Stundenzettel gespeichert: ${path}`, { id: 'timesheet-export' });
+      } else {
+        toast.dismiss('timesheet-export');
+      }
+    } catch (error) {
+      console.error('Timesheet Export Error:', error);
+      toast.error(
+        error instanceof Error ? error.message : String(error),
+        { id: 'timesheet-export' }
+      );
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
+  const handleExportXlsx = async () => {
+    if (!selectedMonth) {
+      toast.error('Bitte einen Monat auswählen');
+      return;
+    }
+
+    try {
+      setIsExporting(true);
+      toast.loading('Monatsbericht wird erstellt...', { id: 'xlsx-export' });
+
+      const employees = await fetchDatevMonth(selectedYear, selectedMonth);
+      const selected =
+        selectedUserId === 'all'
+          ? employees
+          : employees.filter((e) => e.personnelNumber === selectedUserId);
+
+      const withTargets = await withDailyTargets(
+        selected,
+        users ?? [],
+        selectedYear,
+        selectedMonth,
+        monthHolidays
+      );
+
+      const path = await exportReportXlsx(withTargets, selectedYear, selectedMonth, monthHolidays);
+      if (path) {
+        toast.success(`Monatsbericht gespeichert: ${path}`, { id: 'xlsx-export' });
+      } else {
+        toast.dismiss('xlsx-export');
+      }
+    } catch (error) {
+      console.error('XLSX Export Error:', error);
+      toast.error(
+        error instanceof Error ? error.message : String(error),
+        { id: 'xlsx-export' }
+      );
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
+  const handleExportLodas = async () => {
+    if (!selectedMonth) {
+      toast.error('Bitte einen Monat auswählen');
+      return;
+    }
+
+    try {
+      setIsExporting(true);
+      toast.loading('DATEV LODAS Datei wird erstellt...', { id: 'lodas-export' });
+
+      const employees = await withDailyTargets(
+        await fetchDatevMonth(selectedYear, selectedMonth),
+        users ?? [],
+        selectedYear,
+        selectedMonth,
+        monthHolidays
+      );
+      const result = await exportDatevLodas(employees, selectedYear, selectedMonth);
+      if (!result) {
+        toast.dismiss('lodas-export');
+        return;
+      }
+      toast.success(`DATEV LODAS gespeichert: ${result.bookings} Buchungen`, { id: 'lodas-export' });
+      if (result.warnings.length > 0) {
+        toast.warning(result.warnings.join('\n'), { duration: 10000 });
+      }
+    } catch (error) {
+      console.error('LODAS Export Error:', error);
+      toast.error(
+        error instanceof Error ? error.message : String(error),
+        { id: 'lodas-export' }
+      );
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
   if (!currentUser) {
     return (
       <div className="min-h-screen bg-gray-100 dark:bg-gray-900 flex items-center justify-center">
@@ -219,6 +359,39 @@ export function ReportsPage() {
                 )}
                 DATEV Export
               </Button>
+              {isTauri() && (
+                <Button
+                  onClick={handleExportTimesheet}
+                  variant="secondary"
+                  disabled={isExporting || !selectedMonth || selectedUserId === 'all'}
+                  title="Monat und Mitarbeiter auswählen"
+                >
+                  <FileText className="w-4 h-4 mr-2" />
+                  Stundenzettel (PDF)
+                </Button>
+              )}
+              {isTauri() && (
+                <Button
+                  onClick={handleExportXlsx}
+                  variant="secondary"
+                  disabled={isExporting || !selectedMonth}
+                  title="Monat auswählen"
+                >
+                  <FileSpreadsheet className="w-4 h-4 mr-2" />
+                  Monatsbericht (XLSX)
+                </Button>
+              )}
+              {isTauri() && (
+                <Button
+                  onClick={handleExportLodas}
+                  variant="secondary"
+                  disabled={isExporting || !selectedMonth}
+                  title="Monat auswählen; Einstellungen → DATEV"
+                >
+                  <Download className="w-4 h-4 mr-2" />
+                  DATEV LODAS
+                </Button>
+              )}
             </div>
           )}
         </div>
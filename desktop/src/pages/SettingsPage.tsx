@@ -1,13 +1,18 @@
 import { useState } from 'react';
-import { User, Lock, Settings as SettingsIcon, Download, Shield, RefreshCw } from 'lucide-react';
+import { User, Lock, Settings as SettingsIcon, Download, Shield, RefreshCw, Server, Calculator, Keyboard, CalendarDays } from 'lucide-react';
 import { useCurrentUser } from '../hooks';
 import PasswordChangeForm from '../components/settings/PasswordChangeForm';
 import EmailChangeForm from '../components/settings/EmailChangeForm';
 import UpdateChecker from '../components/settings/UpdateChecker';
+import ServerProfileSettings from '../components/settings/ServerProfileSettings';
+import DatevSettings from '../components/settings/DatevSettings';
+import ShortcutSettings from '../components/settings/ShortcutSettings';
+import CalendarSettings from '../components/settings/CalendarSettings';
 import { apiClient } from '../api/client';
 import { toast } from 'sonner';
+import { isTauri } from '../utils/tauri';
 
-type Tab = 'profile' | 'security' | 'updates' | 'admin';
+type Tab = 'profile' | 'security' | 'server' | 'shortcuts' | 'calendar' | 'datev' | 'updates' | 'admin';
 
 interface RecalculateResponse {
   usersProcessed: number;
@@ -25,6 +30,10 @@ export default function SettingsPage() {
   const tabs = [
     { id: 'profile' as Tab, label: 'Profil', icon: User },
     { id: 'security' as Tab, label: 'Sicherheit', icon: Lock },
+    ...(isTauri() ? [{ id: 'server' as Tab, label: 'Server', icon: Server }] : []),
+    ...(isTauri() ? [{ id: 'shortcuts' as Tab, label: 'Tastenkürzel', icon: Keyboard }] : []),
+    ...(isTauri() ? [{ id: 'calendar' as Tab, label: 'Kalender', icon: CalendarDays }] : []),
+    ...(isTauri() && isAdmin ? [{ id: 'datev' as Tab, label: 'DATEV', icon: Calculator }] : []),
     { id: 'updates' as Tab, label: 'Updates', icon: Download },
     ...(isAdmin ? [{ id: 'admin' as Tab, label: 'Admin', icon: Shield }] : []),
   ];
@@ -187,6 +196,42 @@ export default function SettingsPage() {
             </div>
           )}
 
+          {activeTab === 'server' && (
+            <div>
+              <h3 className="text-lg font-semibold text-gray-900 dark:text-white mb-4">
+                Serverprofile
+              </h3>
+              <ServerProfileSettings />
+            </div>
+          )}
+
+          {activeTab === 'shortcuts' && (
+            <div>
+              <h3 className="text-lg font-semibold text-gray-900 dark:text-white mb-4">
+                Tastenkürzel
+              </h3>
+              <ShortcutSettings />
+            </div>
+          )}
+
+          {activeTab === 'calendar' && (
+            <div>
+              <h3 className="text-lg font-semibold text-gray-900 dark:text-white mb-4">
+                Kalender
+              </h3>
+              <CalendarSettings />
+            </div>
+          )}
+
+          {activeTab === 'datev' && (
+            <div>
+              <h3 className="text-lg font-semibold text-gray-900 dark:text-white mb-4">
+                DATEV LODAS
+              </h3>
+              <DatevSettings />
+            </div>
+          )}
+
           {activeTab === 'updates' && (
             <div>
               <UpdateChecker />
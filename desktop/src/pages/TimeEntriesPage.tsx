@@ -18,7 +18,7 @@ import { Button } from '../components/ui/Button';
 import { Input } from '../components/ui/Input';
 import { Select } from '../components/ui/Select';
 import { LoadingSpinner } from '../components/ui/LoadingSpinner';
-import { Calendar, Clock, Download, TrendingUp } from 'lucide-react';
+import { Calendar, Clock, Download, TrendingUp, Upload } from 'lucide-react';
 import { useDeleteTimeEntry, useUsers } from '../hooks';
 import { useInfiniteTimeEntries } from '../hooks/useInfiniteTimeEntries';
 import InfiniteScroll from 'react-infinite-scroll-component';
@@ -26,6 +26,8 @@ import { formatDateDE, formatHours, calculateTotalHours } from '../utils';
 import type { TimeEntry } from '../types';
 import { EditTimeEntryModal } from '../components/timeEntries/EditTimeEntryModal';
 import { TimeEntryForm } from '../components/timeEntries/TimeEntryForm';
+import { TimeImportWizard } from '../components/timeEntries/TimeImportWizard';
+import { isTauri } from '../utils/tauri';
 
 // Date range presets
 type DateRangePreset = 'all-time' | 'today' | 'this-week' | 'last-week' | 'this-month' | 'last-month' | 'this-year' | 'custom';
@@ -187,6 +189,7 @@ export function TimeEntriesPage() {
 
   // Create Modal State
   const [createModalOpen, setCreateModalOpen] = useState(false);
+  const [importOpen, setImportOpen] = useState(false);
 
   // Edit Modal State
   const [editingEntry, setEditingEntry] = useState<TimeEntry | null>(null);
@@ -323,12 +326,20 @@ export function TimeEntriesPage() {
                 : 'Übersicht aller erfassten Arbeitszeiten'}
             </p>
           </div>
-          <Button
-            variant="primary"
-            onClick={() => setCreateModalOpen(true)}
-          >
-            + Zeit erfassen
-          </Button>
+          <div className="flex gap-2">
+            {isTauri() && (
+              <Button variant="secondary" onClick={() => setImportOpen(true)}>
+                <Upload className="w-4 h-4 mr-2" />
+                Importieren
+              </Button>
+            )}
+            <Button
+              variant="primary"
+              onClick={() => setCreateModalOpen(true)}
+            >
+              + Zeit erfassen
+            </Button>
+          </div>
         </div>
 
         {/* Statistics Cards */}
@@ -663,6 +674,9 @@ export function TimeEntriesPage() {
         </Card>
       </main>
 
+      {/* Import Wizard (desktop) */}
+      {isTauri() && <TimeImportWizard isOpen={importOpen} onClose={() => setImportOpen(false)} />}
+
       {/* Create Modal */}
       <TimeEntryForm
         isOpen={createModalOpen}
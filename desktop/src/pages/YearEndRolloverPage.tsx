@@ -15,6 +15,8 @@ import { Calendar, Play, History, AlertTriangle, CheckCircle, XCircle } from 'lu
 import { apiClient } from '../api/client';
 import { Button } from '../components/ui/Button';
 import { LoadingSpinner } from '../components/ui/LoadingSpinner';
+import { RolloverDryRun } from '../components/yearEnd/RolloverDryRun';
+import { isTauri } from '../utils/tauri';
 
 interface YearEndPreview {
   year: number;
@@ -313,6 +315,8 @@ export default function YearEndRolloverPage() {
           )}
         </div>
       </div>
+
+      {isTauri() && <RolloverDryRun />}
     </div>
   );
 }
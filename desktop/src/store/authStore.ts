@@ -1,6 +1,33 @@
 import { create } from 'zustand';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { apiClient } from '../api/client';
 import type { User } from '../types';
+import { isTauri } from '../utils/tauri';
+
+/** Result of the Rust `auth_login` command (the JWT stays in Rust) */
+interface DesktopLoginResult {
+  session: { user: User; apiBaseUrl: string; loggedInAt: string; expiresAt: string | null };
+  forcePasswordChange: boolean;
+}
+
+/** Where versions before the Rust session store kept the JWT */
+const LEGACY_TOKEN_KEY = 'timetracking_jwt_token';
+
+/**
+ * Desktop: hands a JWT left in localStorage by an older version to the Rust
+ * session store and deletes it, whether or not the server still accepts it
+ */
+async function migrateLegacyToken(): Promise<void> {
+  const token = localStorage.getItem(LEGACY_TOKEN_KEY);
+  if (!token) return;
+  localStorage.removeItem(LEGACY_TOKEN_KEY);
+  try {
+    await invoke('auth_import_token', { token });
+  } catch (error) {
+    console.warn('Failed to migrate stored login:', error);
+  }
+}
 
 interface AuthState {
   user: User | null;
@@ -28,6 +55,23 @@ export const useAuthStore = create<AuthState>((set) => ({
     set({ isLoading: true, error: null });
 
     try {
+      if (isTauri()) {
+        // Desktop: Rust logs in against the active server profile and keeps
+        // the JWT in its encrypted session store
+        const result = await invoke<DesktopLoginResult>('auth_login', {
+          username,
+          password,
+        });
+        set({
+          user: result.session.user,
+          isAuthenticated: true,
+          isLoading: false,
+          error: null,
+          forcePasswordChange: result.forcePasswordChange,
+        });
+        return true;
+      }
+
       // Backend returns: { success: true, data: User, message: "...", forcePasswordChange?: boolean }
       // NOT: { success: true, data: { user: User } }
       const response = await apiClient.post<User>('/auth/login', {
@@ -46,12 +90,6 @@ export const useAuthStore = create<AuthState>((set) => ({
           console.log('⚠️ Force password change required');
         }
 
-        // Store JWT token if provided
-        if (response.token) {
-          console.log('🔑 Storing JWT token');
-          apiClient.setToken(response.token);
-        }
-
         set({
           user: response.data, // Direct access, not response.data.user!
           isAuthenticated: true,
@@ -72,7 +110,11 @@ export const useAuthStore = create<AuthState>((set) => ({
         return false;
       }
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : 'Netzwerkfehler';
+      // Errors of Tauri commands are plain objects with a message (NativeApiError)
+      const errorMessage =
+        typeof error === 'object' && error !== null && 'message' in error
+          ? String((error as { message: unknown }).message)
+          : 'Netzwerkfehler';
       console.error('❌ Login error:', error);
       set({
         error: errorMessage,
@@ -88,14 +130,14 @@ export const useAuthStore = create<AuthState>((set) => ({
     set({ isLoading: true });
 
     try {
-      // 1. Call logout endpoint to destroy session on server
-      await apiClient.post('/auth/logout');
-
-      // 2. Clear JWT token from localStorage
-      console.log('🔑 Clearing JWT token');
-      apiClient.clearToken();
+      // 1. Destroy session on server (desktop: Rust also deletes the stored JWT)
+      if (isTauri()) {
+        await invoke('auth_logout');
+      } else {
+        await apiClient.post('/auth/logout');
+      }
 
-      // 3. Clear local state IMMEDIATELY
+      // 2. Clear local state IMMEDIATELY
       // This ensures UI updates even if server call fails
       set({
         user: null,
@@ -105,7 +147,7 @@ export const useAuthStore = create<AuthState>((set) => ({
         forcePasswordChange: false,
       });
 
-      // 4. Force reload to clear any cached cookies in Tauri HTTP Plugin
+      // 3. Force reload to clear any cached cookies in Tauri HTTP Plugin
       // IMPORTANT: Tauri HTTP Plugin caches cookies, window reload clears them
       // This prevents stale cookie issues on re-login
       if (typeof window !== 'undefined') {
@@ -117,9 +159,6 @@ export const useAuthStore = create<AuthState>((set) => ({
     } catch (error) {
       console.error('Logout error:', error);
 
-      // Clear JWT token anyway
-      apiClient.clearToken();
-
       // Clear state anyway (network errors shouldn't prevent logout)
       set({
         user: null,
@@ -142,6 +181,10 @@ export const useAuthStore = create<AuthState>((set) => ({
     set({ isLoading: true });
 
     try {
+      if (isTauri()) {
+        await migrateLegacyToken();
+      }
+
       const response = await apiClient.get<{ user: User }>('/auth/me');
 
       if (response.success && response.data) {
@@ -174,3 +217,14 @@ export const useAuthStore = create<AuthState>((set) => ({
     set({ forcePasswordChange: false });
   },
 }));
+
+// Desktop: the Rust API client got a 401 and dropped the session
+if (isTauri()) {
+  listen('auth:expired', () => {
+    useAuthStore.setState({
+      user: null,
+      isAuthenticated: false,
+      error: 'Ihre Anmeldung ist abgelaufen. Bitte melden Sie sich erneut an.',
+    });
+  }).catch(() => undefined);
+}
@@ -17,6 +17,8 @@ interface UIStore {
   setCurrentView: (view: ViewType) => void;
   calendarFilters: CalendarFilters;
   setCalendarFilters: (filters: CalendarFilters) => void;
+  absenceFormRequested: boolean;  // Open the request form on the absences page
+  setAbsenceFormRequested: (requested: boolean) => void;
 }
 
 export type { ViewType, CalendarFilters };
@@ -28,4 +30,6 @@ export const useUIStore = create<UIStore>((set) => ({
     selectedUserIds: [],  // Default: empty = all users visible
   },
   setCalendarFilters: (filters) => set({ calendarFilters: filters }),
+  absenceFormRequested: false,
+  setAbsenceFormRequested: (requested) => set({ absenceFormRequested: requested }),
 }));